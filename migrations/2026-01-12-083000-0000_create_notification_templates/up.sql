@@ -0,0 +1,66 @@
+-- ============================================================================
+-- Notification Templates Table
+-- ============================================================================
+-- Stores message templates rendered into notifications. Rows with a NULL
+-- user_id are built-in system templates; users override one by creating a
+-- template with the same key.
+CREATE TABLE notification_templates (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    template_key VARCHAR(100) NOT NULL,
+    description TEXT,
+    title_template TEXT,
+    body_template TEXT NOT NULL,
+    -- Format-specific variants: {"markdown": {"title": "...", "body": "..."}, "embed": {...}}
+    variants JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_notification_templates_user_key
+    ON notification_templates(user_id, template_key) WHERE user_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_notification_templates_system_key
+    ON notification_templates(template_key) WHERE user_id IS NULL;
+
+-- Add updated_at trigger
+SELECT diesel_manage_updated_at('notification_templates');
+
+-- ============================================================================
+-- Built-in Templates
+-- ============================================================================
+INSERT INTO notification_templates (user_id, template_key, description, title_template, body_template, variants)
+VALUES
+(
+    NULL,
+    'live_started',
+    'Sent when a followed anchor goes live',
+    '{{ anchor_name }} is live',
+    '{{ anchor_name }} started streaming on {{ platform }}: {{ room_title }}',
+    '{
+        "markdown": {
+            "title": "{{ anchor_name }} is live",
+            "body": "**{{ anchor_name }}** started streaming on {{ platform }}\n\n[{{ room_title }}]({{ room_url }})"
+        },
+        "embed": {
+            "title": "{{ anchor_name }} is live on {{ platform }}",
+            "body": "{{ room_title }}"
+        }
+    }'
+),
+(
+    NULL,
+    'job_failed',
+    'Sent when a scheduled job exhausts its retries',
+    'Job {{ job_name }} failed',
+    'Job {{ job_name }} failed after {{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}',
+    '{
+        "markdown": {
+            "title": "Job {{ job_name }} failed",
+            "body": "**Job `{{ job_name }}` failed** after {{ attempts }} attempt(s) in {{ duration_ms }}ms\n\n```\n{{ error }}\n```"
+        },
+        "embed": {
+            "title": "Job {{ job_name }} failed",
+            "body": "Failed after {{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}"
+        }
+    }'
+);
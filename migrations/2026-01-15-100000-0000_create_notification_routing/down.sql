@@ -0,0 +1,9 @@
+DELETE FROM scheduled_jobs WHERE job_name = 'deliver_deferred_notifications';
+
+DROP TABLE IF EXISTS deferred_notifications;
+DROP TABLE IF EXISTS notification_quiet_hours;
+DROP TABLE IF EXISTS notification_routing_rules;
+
+DROP TYPE IF EXISTS quiet_hours_action;
+DROP TYPE IF EXISTS routing_mode;
+DROP TYPE IF EXISTS notification_severity;
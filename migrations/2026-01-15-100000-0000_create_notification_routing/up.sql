@@ -0,0 +1,86 @@
+-- ============================================================================
+-- Create PostgreSQL ENUM types
+-- ============================================================================
+CREATE TYPE notification_severity AS ENUM ('low', 'normal', 'high', 'critical');
+CREATE TYPE routing_mode AS ENUM ('all', 'escalation');
+CREATE TYPE quiet_hours_action AS ENUM ('defer', 'drop');
+
+-- ============================================================================
+-- Notification Routing Rules Table
+-- ============================================================================
+-- Per-user rules deciding which channels receive a dispatched message.
+-- Rules are evaluated by priority (highest first); the first match wins.
+CREATE TABLE notification_routing_rules (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT true,
+
+    -- Match conditions (all non-empty conditions must hold)
+    match_tags TEXT[] NOT NULL DEFAULT '{}',
+    match_metadata JSONB NOT NULL DEFAULT '{}',
+    min_severity notification_severity,
+
+    -- Targets
+    channel_ids INTEGER[] NOT NULL,
+    mode routing_mode NOT NULL DEFAULT 'all',
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT unique_user_routing_rule_name UNIQUE (user_id, name)
+);
+
+CREATE INDEX idx_notification_routing_rules_user_priority
+    ON notification_routing_rules(user_id, priority DESC);
+
+SELECT diesel_manage_updated_at('notification_routing_rules');
+
+-- ============================================================================
+-- Notification Quiet Hours Table
+-- ============================================================================
+-- One quiet window per user, expressed in the user's timezone. The window may
+-- wrap past midnight (e.g., 22:00 - 07:00).
+CREATE TABLE notification_quiet_hours (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    start_time TIME NOT NULL,
+    end_time TIME NOT NULL,
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    bypass_severity notification_severity NOT NULL DEFAULT 'high',
+    action quiet_hours_action NOT NULL DEFAULT 'defer',
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT quiet_hours_non_empty CHECK (start_time <> end_time)
+);
+
+SELECT diesel_manage_updated_at('notification_quiet_hours');
+
+-- ============================================================================
+-- Deferred Notifications Table
+-- ============================================================================
+-- Messages held back by quiet hours, delivered by the
+-- deferred_notifications job once deliver_at has passed.
+CREATE TABLE deferred_notifications (
+    id BIGSERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    message JSONB NOT NULL,
+    deliver_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_deferred_notifications_deliver_at ON deferred_notifications(deliver_at);
+
+-- Deliver deferred notifications every minute
+INSERT INTO scheduled_jobs (job_name, job_type, cron_expression, description, created_by)
+VALUES (
+    'deliver_deferred_notifications',
+    'deferred_notifications',
+    '0 * * * * * *',
+    'Deliver notifications deferred by quiet hours',
+    'system'
+)
+ON CONFLICT (job_name) DO NOTHING;
@@ -0,0 +1,20 @@
+DELETE FROM scheduled_jobs WHERE job_name = 'flush_notification_digests';
+
+DROP INDEX IF EXISTS idx_notification_logs_dedup;
+ALTER TABLE notification_logs DROP COLUMN IF EXISTS dedup_key;
+
+ALTER TABLE notification_channels
+    DROP COLUMN IF EXISTS digest_enabled,
+    DROP COLUMN IF EXISTS dedup_window_seconds,
+    DROP COLUMN IF EXISTS rate_limit_window_seconds,
+    DROP COLUMN IF EXISTS rate_limit_count;
+
+-- PostgreSQL cannot drop enum values; rebuild the type without them
+UPDATE notification_logs SET status = 'sent' WHERE status IN ('batched', 'digested');
+UPDATE notification_logs SET status = 'failed' WHERE status = 'suppressed';
+
+ALTER TYPE notification_status RENAME TO notification_status_old;
+CREATE TYPE notification_status AS ENUM ('pending', 'sent', 'failed', 'retrying');
+ALTER TABLE notification_logs
+    ALTER COLUMN status TYPE notification_status USING status::text::notification_status;
+DROP TYPE notification_status_old;
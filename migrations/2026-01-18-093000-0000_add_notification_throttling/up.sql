@@ -0,0 +1,38 @@
+-- ============================================================================
+-- New notification statuses
+-- ============================================================================
+-- suppressed: skipped by deduplication or rate limiting
+-- batched:    held for the channel's next digest
+-- digested:   included in a digest that has been flushed
+ALTER TYPE notification_status ADD VALUE IF NOT EXISTS 'suppressed';
+ALTER TYPE notification_status ADD VALUE IF NOT EXISTS 'batched';
+ALTER TYPE notification_status ADD VALUE IF NOT EXISTS 'digested';
+
+-- ============================================================================
+-- Per-channel throttling settings
+-- ============================================================================
+ALTER TABLE notification_channels
+    ADD COLUMN rate_limit_count INTEGER CHECK (rate_limit_count IS NULL OR rate_limit_count > 0),
+    ADD COLUMN rate_limit_window_seconds INTEGER NOT NULL DEFAULT 60 CHECK (rate_limit_window_seconds > 0),
+    ADD COLUMN dedup_window_seconds INTEGER NOT NULL DEFAULT 300 CHECK (dedup_window_seconds >= 0),
+    ADD COLUMN digest_enabled BOOLEAN NOT NULL DEFAULT false;
+
+-- ============================================================================
+-- Dedup keys on logs
+-- ============================================================================
+ALTER TABLE notification_logs ADD COLUMN dedup_key VARCHAR(255);
+
+CREATE INDEX idx_notification_logs_dedup
+    ON notification_logs(channel_id, dedup_key, sent_at DESC)
+    WHERE dedup_key IS NOT NULL;
+
+-- Flush digests every hour
+INSERT INTO scheduled_jobs (job_name, job_type, cron_expression, description, created_by)
+VALUES (
+    'flush_notification_digests',
+    'notification_digest',
+    '0 0 * * * * *',
+    'Send accumulated digest notifications as one summary per channel',
+    'system'
+)
+ON CONFLICT (job_name) DO NOTHING;
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_notification_logs_channel_sent_at;
+
+ALTER TABLE notification_logs
+    DROP COLUMN IF EXISTS duration_ms,
+    DROP COLUMN IF EXISTS response_body,
+    DROP COLUMN IF EXISTS response_status,
+    DROP COLUMN IF EXISTS request_meta;
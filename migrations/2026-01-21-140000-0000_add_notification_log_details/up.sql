@@ -0,0 +1,16 @@
+-- ============================================================================
+-- Provider request/response capture on notification logs
+-- ============================================================================
+-- request_meta:    method, redacted URL and header names of the provider request
+-- response_status: HTTP (or provider-specific) status code
+-- response_body:   response body, truncated to 4 KiB
+-- duration_ms:     time spent in the provider call
+ALTER TABLE notification_logs
+    ADD COLUMN request_meta JSONB,
+    ADD COLUMN response_status INTEGER,
+    ADD COLUMN response_body TEXT,
+    ADD COLUMN duration_ms BIGINT;
+
+-- Log search filters by channel and time range
+CREATE INDEX idx_notification_logs_channel_sent_at
+    ON notification_logs(channel_id, sent_at DESC);
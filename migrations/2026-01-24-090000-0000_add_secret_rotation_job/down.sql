@@ -0,0 +1 @@
+DELETE FROM scheduled_jobs WHERE job_name = 'rotate_channel_secrets';
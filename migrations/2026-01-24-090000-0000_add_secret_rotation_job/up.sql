@@ -0,0 +1,11 @@
+-- Encrypt plaintext channel secrets and re-wrap secrets under retired keys
+-- once a day; the job does nothing until encryption.key is configured
+INSERT INTO scheduled_jobs (job_name, job_type, cron_expression, description, created_by)
+VALUES (
+    'rotate_channel_secrets',
+    'secret_rotation',
+    '0 30 3 * * * *',
+    'Encrypt notification channel secrets with the current encryption key',
+    'system'
+)
+ON CONFLICT (job_name) DO NOTHING;
@@ -0,0 +1,10 @@
+ALTER TABLE notification_channels
+    DROP COLUMN disabled_at,
+    DROP COLUMN disabled_reason,
+    DROP COLUMN last_error,
+    DROP COLUMN last_failure_at,
+    DROP COLUMN last_success_at,
+    DROP COLUMN failure_count,
+    DROP COLUMN success_count,
+    DROP COLUMN consecutive_failures,
+    DROP COLUMN failure_threshold;
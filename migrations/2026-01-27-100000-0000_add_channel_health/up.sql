@@ -0,0 +1,17 @@
+-- ============================================================================
+-- Channel health tracking
+-- ============================================================================
+-- failure_threshold:    consecutive failures before the channel is disabled
+--                       automatically (0 = never)
+-- consecutive_failures: failed deliveries since the last success
+-- disabled_reason/at:   set when the channel was disabled automatically
+ALTER TABLE notification_channels
+    ADD COLUMN failure_threshold INTEGER NOT NULL DEFAULT 5 CHECK (failure_threshold >= 0),
+    ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN success_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN failure_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN last_success_at TIMESTAMP,
+    ADD COLUMN last_failure_at TIMESTAMP,
+    ADD COLUMN last_error TEXT,
+    ADD COLUMN disabled_reason TEXT,
+    ADD COLUMN disabled_at TIMESTAMP;
@@ -0,0 +1,11 @@
+DELETE FROM scheduled_jobs WHERE job_name = 'deliver_notification_broadcasts';
+
+DROP TABLE IF EXISTS notification_broadcast_recipients;
+DROP TABLE IF EXISTS notification_broadcasts;
+DROP TYPE IF EXISTS broadcast_recipient_status;
+DROP TYPE IF EXISTS broadcast_status;
+
+DROP TABLE IF EXISTS user_group_members;
+DROP TABLE IF EXISTS user_groups;
+
+ALTER TABLE users DROP COLUMN is_admin;
@@ -0,0 +1,82 @@
+-- ============================================================================
+-- Administrators
+-- ============================================================================
+-- Only administrators may manage user groups and send broadcasts.
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT false;
+
+-- ============================================================================
+-- User Groups
+-- ============================================================================
+CREATE TABLE user_groups (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+SELECT diesel_manage_updated_at('user_groups');
+
+CREATE TABLE user_group_members (
+    group_id INTEGER NOT NULL REFERENCES user_groups(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    added_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (group_id, user_id)
+);
+
+CREATE INDEX idx_user_group_members_user ON user_group_members(user_id);
+
+-- ============================================================================
+-- Broadcasts
+-- ============================================================================
+CREATE TYPE broadcast_status AS ENUM ('pending', 'running', 'completed');
+CREATE TYPE broadcast_recipient_status AS ENUM (
+    'pending', 'sending', 'delivered', 'deferred', 'dropped', 'failed'
+);
+
+-- Recipients are resolved when the broadcast is created; group_id only
+-- records the audience that was selected (NULL = all users).
+CREATE TABLE notification_broadcasts (
+    id SERIAL PRIMARY KEY,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    group_id INTEGER REFERENCES user_groups(id) ON DELETE SET NULL,
+    message JSONB NOT NULL,
+    status broadcast_status NOT NULL DEFAULT 'pending',
+    total_recipients INTEGER NOT NULL DEFAULT 0,
+    delivered_count INTEGER NOT NULL DEFAULT 0,
+    deferred_count INTEGER NOT NULL DEFAULT 0,
+    dropped_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TIMESTAMP,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX idx_notification_broadcasts_created_at
+    ON notification_broadcasts(created_at DESC);
+
+-- Delivery queue: one row per recipient
+CREATE TABLE notification_broadcast_recipients (
+    id BIGSERIAL PRIMARY KEY,
+    broadcast_id INTEGER NOT NULL REFERENCES notification_broadcasts(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status broadcast_recipient_status NOT NULL DEFAULT 'pending',
+    error_message TEXT,
+    processed_at TIMESTAMP,
+    CONSTRAINT unique_broadcast_recipient UNIQUE (broadcast_id, user_id)
+);
+
+CREATE INDEX idx_broadcast_recipients_pending
+    ON notification_broadcast_recipients(id)
+    WHERE status = 'pending';
+
+-- Drain the broadcast queue every 30 seconds
+INSERT INTO scheduled_jobs (job_name, job_type, cron_expression, description, created_by)
+VALUES (
+    'deliver_notification_broadcasts',
+    'notification_broadcast',
+    '*/30 * * * * * *',
+    'Deliver queued broadcast notifications to their recipients',
+    'system'
+)
+ON CONFLICT (job_name) DO NOTHING;
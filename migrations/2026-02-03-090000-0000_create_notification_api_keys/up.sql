@@ -0,0 +1,29 @@
+-- ============================================================================
+-- Notification API Keys
+-- ============================================================================
+-- Keys let external systems send notifications on behalf of a user. A key
+-- only authorizes POST /api/notifications/send.
+--
+-- The full key is shown once at creation; only its argon2 hash is stored.
+-- key_prefix is the public part of the key and is used to look it up.
+CREATE TABLE notification_api_keys (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    key_prefix VARCHAR(32) NOT NULL UNIQUE,
+    key_hash VARCHAR(255) NOT NULL,
+    -- IP addresses or CIDR ranges allowed to use the key (empty = any)
+    allowed_ips TEXT[] NOT NULL DEFAULT '{}',
+    -- Requests allowed per window (NULL = unlimited)
+    rate_limit_count INTEGER CHECK (rate_limit_count IS NULL OR rate_limit_count > 0),
+    rate_limit_window_seconds INTEGER NOT NULL DEFAULT 60 CHECK (rate_limit_window_seconds > 0),
+    -- Fixed-window request counter
+    window_started_at TIMESTAMP,
+    window_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_user_api_key_name UNIQUE (user_id, name)
+);
+
+CREATE INDEX idx_notification_api_keys_user ON notification_api_keys(user_id);
@@ -0,0 +1,3 @@
+DELETE FROM scheduled_jobs WHERE job_name = 'purge_notification_logs';
+
+DROP TABLE IF EXISTS notification_log_stats;
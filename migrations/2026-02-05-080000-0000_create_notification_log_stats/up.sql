@@ -0,0 +1,32 @@
+-- ============================================================================
+-- Notification Log Stats Table
+-- ============================================================================
+-- Daily per-channel delivery counts. Logs are rolled up here before the
+-- retention job deletes them, so totals survive the purge.
+CREATE TABLE notification_log_stats (
+    day DATE NOT NULL,
+    channel_id INTEGER NOT NULL REFERENCES notification_channels(id) ON DELETE CASCADE,
+    status notification_status NOT NULL,
+    message_count BIGINT NOT NULL DEFAULT 0,
+    retry_count BIGINT NOT NULL DEFAULT 0,
+    -- Sum and number of logs with a recorded duration, for averages
+    total_duration_ms BIGINT NOT NULL DEFAULT 0,
+    timed_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (day, channel_id, status)
+);
+
+CREATE INDEX idx_notification_log_stats_channel ON notification_log_stats(channel_id);
+
+-- Purge notification logs older than 90 days once a day. Set "archive_dir"
+-- in the payload to keep a gzipped JSONL copy of purged logs.
+INSERT INTO scheduled_jobs (job_name, job_type, cron_expression, payload, description, created_by)
+VALUES (
+    'purge_notification_logs',
+    'notification_log_retention',
+    '0 0 4 * * * *',
+    '{"retention_days": 90}',
+    'Purge notification logs older than the retention period',
+    'system'
+)
+ON CONFLICT (job_name) DO NOTHING;
@@ -0,0 +1,3 @@
+-- Self-hosted push targets
+ALTER TYPE channel_type ADD VALUE IF NOT EXISTS 'gotify';
+ALTER TYPE channel_type ADD VALUE IF NOT EXISTS 'ntfy';
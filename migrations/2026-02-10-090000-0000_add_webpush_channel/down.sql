@@ -0,0 +1,8 @@
+-- PostgreSQL cannot drop enum values, so the type is rebuilt without it
+DELETE FROM notification_channels WHERE channel_type::text = 'webpush';
+
+ALTER TYPE channel_type RENAME TO channel_type_old;
+CREATE TYPE channel_type AS ENUM ('webhook', 'email', 'sms', 'discord', 'slack', 'bark', 'gotify', 'ntfy');
+ALTER TABLE notification_channels
+    ALTER COLUMN channel_type TYPE channel_type USING channel_type::text::channel_type;
+DROP TYPE channel_type_old;
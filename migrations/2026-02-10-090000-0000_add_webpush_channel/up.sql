@@ -0,0 +1,2 @@
+-- Browser push subscriptions (VAPID)
+ALTER TYPE channel_type ADD VALUE IF NOT EXISTS 'webpush';
@@ -0,0 +1 @@
+ALTER TABLE scheduled_jobs DROP COLUMN last_fired_at;
@@ -0,0 +1,3 @@
+-- Cron fire time most recently claimed by a replica; a conditional update
+-- on this column lets exactly one replica run each scheduled fire
+ALTER TABLE scheduled_jobs ADD COLUMN last_fired_at TIMESTAMP;
@@ -0,0 +1,2 @@
+-- IANA timezone whose wall clock a job's cron expression follows
+ALTER TABLE scheduled_jobs ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS job_workflow_step_runs;
+DROP TABLE IF EXISTS job_workflow_runs;
+DROP TABLE IF EXISTS job_workflow_steps;
+DROP TABLE IF EXISTS job_workflows;
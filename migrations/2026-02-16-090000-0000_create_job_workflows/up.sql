@@ -0,0 +1,69 @@
+-- ============================================================================
+-- Job Workflows Table
+-- ============================================================================
+-- A workflow is a DAG of steps, each running an existing scheduled job once
+-- all of the steps it depends on have succeeded.
+CREATE TABLE job_workflows (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+SELECT diesel_manage_updated_at('job_workflows');
+
+-- ============================================================================
+-- Job Workflow Steps Table
+-- ============================================================================
+-- Jobs referenced by a workflow cannot be deleted until the step is removed.
+CREATE TABLE job_workflow_steps (
+    id SERIAL PRIMARY KEY,
+    workflow_id INTEGER NOT NULL REFERENCES job_workflows(id) ON DELETE CASCADE,
+    step_name VARCHAR(100) NOT NULL,
+    job_id INTEGER NOT NULL REFERENCES scheduled_jobs(id),
+    -- Names of the steps that must succeed before this one starts
+    depends_on TEXT[] NOT NULL DEFAULT '{}',
+
+    UNIQUE (workflow_id, step_name)
+);
+
+CREATE INDEX idx_job_workflow_steps_job_id ON job_workflow_steps(job_id);
+
+-- ============================================================================
+-- Job Workflow Runs Table
+-- ============================================================================
+-- Runs created by a scheduled `workflow` job start out pending and are
+-- claimed by a scheduler; runs started through the API start out running.
+CREATE TABLE job_workflow_runs (
+    id BIGSERIAL PRIMARY KEY,
+    workflow_id INTEGER NOT NULL REFERENCES job_workflows(id) ON DELETE CASCADE,
+    run_id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid(),
+    status job_status NOT NULL,
+    started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX idx_job_workflow_runs_workflow_id ON job_workflow_runs(workflow_id, started_at);
+CREATE INDEX idx_job_workflow_runs_pending ON job_workflow_runs(status) WHERE status = 'pending';
+
+-- ============================================================================
+-- Job Workflow Step Runs Table
+-- ============================================================================
+-- Snapshot of the workflow's steps taken when a run is created, so a run's
+-- graph is unaffected by later edits. `execution_id` points at the latest
+-- attempt in job_executions, whose `result` is passed to downstream steps.
+CREATE TABLE job_workflow_step_runs (
+    id BIGSERIAL PRIMARY KEY,
+    workflow_run_id BIGINT NOT NULL REFERENCES job_workflow_runs(id) ON DELETE CASCADE,
+    step_name VARCHAR(100) NOT NULL,
+    job_id INTEGER NOT NULL,
+    depends_on TEXT[] NOT NULL,
+    status job_status NOT NULL DEFAULT 'pending',
+    execution_id UUID,
+    started_at TIMESTAMP,
+    completed_at TIMESTAMP,
+    error_message TEXT,
+
+    UNIQUE (workflow_run_id, step_name)
+);
@@ -0,0 +1,34 @@
+CREATE TYPE queued_task_status AS ENUM ('queued', 'running', 'succeeded', 'dead');
+
+-- ============================================================================
+-- Job Queue Table
+-- ============================================================================
+-- One-shot tasks of any registered job type. Workers claim due tasks by
+-- priority; a claimed task is invisible to other workers until
+-- `locked_until`, after which it is picked up again as a new attempt.
+-- Tasks that run out of attempts are kept as dead letters.
+CREATE TABLE job_queue (
+    id BIGSERIAL PRIMARY KEY,
+    job_type VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+    priority INTEGER NOT NULL DEFAULT 0,
+    run_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    status queued_task_status NOT NULL DEFAULT 'queued',
+
+    -- Retry and visibility timeout
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 3 CHECK (max_attempts > 0),
+    retry_delay_seconds INTEGER NOT NULL DEFAULT 60,
+    visibility_timeout_seconds INTEGER NOT NULL DEFAULT 300 CHECK (visibility_timeout_seconds > 0),
+    locked_until TIMESTAMP,
+    last_error TEXT,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX idx_job_queue_due ON job_queue(priority DESC, run_at) WHERE status IN ('queued', 'running');
+CREATE INDEX idx_job_queue_status ON job_queue(status, created_at);
+
+SELECT diesel_manage_updated_at('job_queue');
@@ -0,0 +1,10 @@
+DROP TABLE IF EXISTS job_execution_logs;
+
+ALTER TABLE job_queue DROP COLUMN IF EXISTS result;
+
+ALTER TABLE job_executions
+    DROP CONSTRAINT IF EXISTS uq_job_executions_execution_id,
+    DROP COLUMN IF EXISTS progress_message,
+    DROP COLUMN IF EXISTS progress_percent;
+
+DROP TYPE IF EXISTS job_log_level;
@@ -0,0 +1,26 @@
+CREATE TYPE job_log_level AS ENUM ('debug', 'info', 'warn', 'error');
+
+-- Progress reported by a running task
+ALTER TABLE job_executions
+    ADD COLUMN progress_percent SMALLINT CHECK (progress_percent BETWEEN 0 AND 100),
+    ADD COLUMN progress_message TEXT,
+    ADD CONSTRAINT uq_job_executions_execution_id UNIQUE (execution_id);
+
+-- Result returned by a queued task
+ALTER TABLE job_queue ADD COLUMN result JSONB;
+
+-- ============================================================================
+-- Job Execution Logs Table
+-- ============================================================================
+-- Structured log lines appended by a task while it runs. Removed together
+-- with their execution.
+CREATE TABLE job_execution_logs (
+    id BIGSERIAL PRIMARY KEY,
+    execution_id UUID NOT NULL REFERENCES job_executions(execution_id) ON DELETE CASCADE,
+    level job_log_level NOT NULL,
+    message TEXT NOT NULL,
+    fields JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_job_execution_logs_execution ON job_execution_logs(execution_id, id);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS job_alert_rules;
+
+ALTER TABLE scheduled_jobs DROP COLUMN IF EXISTS consecutive_failures;
+
+DROP TYPE IF EXISTS job_alert_event;
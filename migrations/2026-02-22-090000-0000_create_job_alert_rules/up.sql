@@ -0,0 +1,21 @@
+CREATE TYPE job_alert_event AS ENUM ('failure', 'timeout', 'consecutive_failures', 'recovery');
+
+-- Unsuccessful runs since the last successful one
+ALTER TABLE scheduled_jobs ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0;
+
+-- ============================================================================
+-- Job Alert Rules Table
+-- ============================================================================
+-- Notification channels alerted when a run of the job ends with `event`.
+-- `threshold` is the streak length for `consecutive_failures` rules.
+CREATE TABLE job_alert_rules (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES scheduled_jobs(id) ON DELETE CASCADE,
+    event job_alert_event NOT NULL,
+    threshold INTEGER CHECK (threshold > 1),
+    channel_ids INTEGER[] NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((event = 'consecutive_failures') = (threshold IS NOT NULL))
+);
+
+CREATE INDEX idx_job_alert_rules_job_id ON job_alert_rules(job_id);
@@ -0,0 +1,5 @@
+ALTER TABLE scheduled_jobs DROP COLUMN IF EXISTS retry_on_timeout;
+ALTER TABLE scheduled_jobs DROP COLUMN IF EXISTS retry_jitter;
+ALTER TABLE scheduled_jobs DROP COLUMN IF EXISTS retry_max_delay_seconds;
+
+DROP TYPE IF EXISTS retry_jitter;
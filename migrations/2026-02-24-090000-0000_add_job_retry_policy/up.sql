@@ -0,0 +1,9 @@
+CREATE TYPE retry_jitter AS ENUM ('none', 'full', 'decorrelated');
+
+-- Upper bound of the delay between two attempts
+ALTER TABLE scheduled_jobs ADD COLUMN retry_max_delay_seconds INTEGER NOT NULL DEFAULT 3600
+    CHECK (retry_max_delay_seconds >= 0);
+-- Randomization applied to retry delays
+ALTER TABLE scheduled_jobs ADD COLUMN retry_jitter retry_jitter NOT NULL DEFAULT 'none';
+-- Whether a timed-out attempt is retried like a failed one
+ALTER TABLE scheduled_jobs ADD COLUMN retry_on_timeout BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+-- Cancellation requested by a replica that does not own the run. The owning
+-- replica polls this flag and cancels the attempt.
+ALTER TABLE job_executions ADD COLUMN cancel_requested BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_job_workflow_runs_lease;
+
+ALTER TABLE job_workflow_runs
+    DROP COLUMN IF EXISTS locked_by,
+    DROP COLUMN IF EXISTS locked_until;
@@ -0,0 +1,8 @@
+-- Lease of the replica driving a running workflow run. The owner extends
+-- it while the run makes progress; a run whose lease expired is claimed
+-- again by another replica.
+ALTER TABLE job_workflow_runs
+    ADD COLUMN locked_until TIMESTAMP,
+    ADD COLUMN locked_by UUID;
+
+CREATE INDEX idx_job_workflow_runs_lease ON job_workflow_runs(locked_until) WHERE status = 'running';
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS job_queue_logs;
+
+ALTER TABLE job_queue
+    DROP COLUMN IF EXISTS progress_message,
+    DROP COLUMN IF EXISTS progress_percent;
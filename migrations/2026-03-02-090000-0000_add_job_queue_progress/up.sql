@@ -0,0 +1,21 @@
+-- Progress reported by the running attempt of a queued task
+ALTER TABLE job_queue
+    ADD COLUMN progress_percent SMALLINT CHECK (progress_percent BETWEEN 0 AND 100),
+    ADD COLUMN progress_message TEXT;
+
+-- ============================================================================
+-- Job Queue Logs Table
+-- ============================================================================
+-- Structured log lines appended by a queued task while it runs. Removed
+-- together with their task.
+CREATE TABLE job_queue_logs (
+    id BIGSERIAL PRIMARY KEY,
+    task_id BIGINT NOT NULL REFERENCES job_queue(id) ON DELETE CASCADE,
+    attempt INTEGER NOT NULL,
+    level job_log_level NOT NULL,
+    message TEXT NOT NULL,
+    fields JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_job_queue_logs_task ON job_queue_logs(task_id, id);
@@ -0,0 +1,3 @@
+DELETE FROM notification_templates
+WHERE user_id IS NULL
+  AND template_key IN ('job_timed_out', 'job_failure_streak', 'job_recovered');
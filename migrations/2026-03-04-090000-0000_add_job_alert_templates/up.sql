@@ -0,0 +1,59 @@
+-- ============================================================================
+-- Job Alert Templates
+-- ============================================================================
+-- System templates of the job alert events other than a plain failure,
+-- which uses the existing job_failed template. Variables: job_name, event,
+-- error, attempts, duration_ms and streak.
+INSERT INTO notification_templates (user_id, template_key, description, title_template, body_template, variants)
+VALUES
+(
+    NULL,
+    'job_timed_out',
+    'Sent when a scheduled job times out',
+    'Job {{ job_name }} timed out',
+    'Job {{ job_name }} timed out after {{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}',
+    '{
+        "markdown": {
+            "title": "Job {{ job_name }} timed out",
+            "body": "**Job `{{ job_name }}` timed out** after {{ attempts }} attempt(s) in {{ duration_ms }}ms\n\n```\n{{ error }}\n```"
+        },
+        "embed": {
+            "title": "Job {{ job_name }} timed out",
+            "body": "Timed out after {{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}"
+        }
+    }'
+),
+(
+    NULL,
+    'job_failure_streak',
+    'Sent when a scheduled job fails a number of runs in a row',
+    'Job {{ job_name }} failed {{ streak }} times in a row',
+    'Job {{ job_name }} failed {{ streak }} runs in a row; the last one after {{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}',
+    '{
+        "markdown": {
+            "title": "Job {{ job_name }} failed {{ streak }} times in a row",
+            "body": "**Job `{{ job_name }}` failed {{ streak }} runs in a row**, the last one after {{ attempts }} attempt(s) in {{ duration_ms }}ms\n\n```\n{{ error }}\n```"
+        },
+        "embed": {
+            "title": "Job {{ job_name }} failed {{ streak }} times in a row",
+            "body": "Last run failed after {{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}"
+        }
+    }'
+),
+(
+    NULL,
+    'job_recovered',
+    'Sent when a scheduled job succeeds after failing',
+    'Job {{ job_name }} recovered',
+    'Job {{ job_name }} succeeded after {{ attempts }} attempt(s) in {{ duration_ms }}ms',
+    '{
+        "markdown": {
+            "title": "Job {{ job_name }} recovered",
+            "body": "**Job `{{ job_name }}` recovered** after {{ attempts }} attempt(s) in {{ duration_ms }}ms"
+        },
+        "embed": {
+            "title": "Job {{ job_name }} recovered",
+            "body": "Succeeded after {{ attempts }} attempt(s) in {{ duration_ms }}ms"
+        }
+    }'
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_job_alert_rules_job_user;
+ALTER TABLE job_alert_rules DROP COLUMN IF EXISTS user_id;
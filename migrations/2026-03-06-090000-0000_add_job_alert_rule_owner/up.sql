@@ -0,0 +1,15 @@
+-- Alert rules belong to the user who set them, so replacing one user's rules
+-- leaves other users' rules on the same job alone
+ALTER TABLE job_alert_rules ADD COLUMN user_id INTEGER REFERENCES users(id) ON DELETE CASCADE;
+
+-- Existing rules belong to the owner of their channels
+UPDATE job_alert_rules r
+SET user_id = c.user_id
+FROM notification_channels c
+WHERE c.id = r.channel_ids[1];
+
+DELETE FROM job_alert_rules WHERE user_id IS NULL;
+
+ALTER TABLE job_alert_rules ALTER COLUMN user_id SET NOT NULL;
+
+CREATE INDEX idx_job_alert_rules_job_user ON job_alert_rules(job_id, user_id);
@@ -0,0 +1,3 @@
+-- Execution ID of the current or latest attempt of a queued task, passed to
+-- the task as its JobContext::execution_id; a new one is drawn per claim
+ALTER TABLE job_queue ADD COLUMN execution_id UUID;
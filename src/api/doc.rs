@@ -1,4 +1,4 @@
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
 pub const USER_TAG: &str = "User";
@@ -46,7 +46,14 @@ impl Modify for SecurityAddon {
                         .description(Some("JWT Bearer Token Authentication"))
                         .build(),
                 ),
-            )
+            );
+            components.add_security_scheme(
+                "apiKeyAuth",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                    "X-API-Key",
+                    "Notification API key (only for POST /api/notifications/send)",
+                ))),
+            );
         }
     }
 }
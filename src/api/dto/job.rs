@@ -1,10 +1,16 @@
 //! Job-related DTOs for API requests and responses.
 
-use crate::jobs::models::{JobExecution, NewScheduledJob, ScheduledJob, UpdateScheduledJob};
-use crate::jobs::types::JobStatus;
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{
+    JobAlertRule, JobExecution, JobExecutionLog, NewJobAlertRule, NewScheduledJob, ScheduledJob,
+    UpdateScheduledJob,
+};
+use crate::jobs::types::{JobAlertEvent, JobLogLevel, JobStatus, RetryJitter};
+use jiff::Timestamp;
+use jiff::tz::TimeZone;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 // ============================================================================
@@ -76,6 +82,31 @@ pub struct CreateJobRequest {
 
     #[schema(example = "Clean up old job execution records")]
     pub description: Option<String>,
+
+    /// IANA timezone whose wall clock the cron expression follows
+    #[serde(default = "default_timezone")]
+    #[validate(length(max = 64, message = "Timezone must be at most 64 characters"))]
+    #[schema(example = "Asia/Shanghai")]
+    pub timezone: String,
+
+    /// Upper bound of the delay between two attempts
+    #[serde(default = "default_retry_max_delay")]
+    #[validate(range(
+        min = 0,
+        max = 604_800,
+        message = "Max retry delay must be between 0 and 604800 seconds"
+    ))]
+    #[schema(example = 3600)]
+    pub retry_max_delay_seconds: i32,
+
+    /// Randomization of the delay between two attempts
+    #[serde(default = "default_retry_jitter")]
+    pub retry_jitter: RetryJitter,
+
+    /// Retry timed-out attempts like failed ones
+    #[serde(default)]
+    #[schema(example = false)]
+    pub retry_on_timeout: bool,
 }
 
 fn default_true() -> bool {
@@ -90,6 +121,18 @@ fn default_timeout() -> i32 {
     300
 }
 
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_retry_max_delay() -> i32 {
+    3600
+}
+
+fn default_retry_jitter() -> RetryJitter {
+    RetryJitter::None
+}
+
 impl CreateJobRequest {
     pub fn into_new_job(self) -> NewScheduledJob {
         NewScheduledJob {
@@ -106,6 +149,11 @@ impl CreateJobRequest {
             payload: self.payload,
             description: self.description,
             created_by: None,
+            next_run_at: None,
+            timezone: self.timezone,
+            retry_max_delay_seconds: self.retry_max_delay_seconds,
+            retry_jitter: self.retry_jitter,
+            retry_on_timeout: self.retry_on_timeout,
         }
     }
 }
@@ -125,6 +173,14 @@ pub struct UpdateJobRequest {
     pub timeout_seconds: Option<i32>,
     pub payload: Option<JsonValue>,
     pub description: Option<String>,
+
+    #[validate(length(max = 64))]
+    pub timezone: Option<String>,
+
+    #[validate(range(min = 0, max = 604_800))]
+    pub retry_max_delay_seconds: Option<i32>,
+    pub retry_jitter: Option<RetryJitter>,
+    pub retry_on_timeout: Option<bool>,
 }
 
 impl UpdateJobRequest {
@@ -143,10 +199,111 @@ impl UpdateJobRequest {
             timeout_seconds: self.timeout_seconds,
             payload: self.payload,
             description: self.description,
+            timezone: self.timezone,
+            retry_max_delay_seconds: self.retry_max_delay_seconds,
+            retry_jitter: self.retry_jitter,
+            retry_on_timeout: self.retry_on_timeout,
         }
     }
 }
 
+/// Request body for running a job immediately.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[schema(example = json!({
+    "payload": {
+        "retention_days": 7
+    }
+}))]
+pub struct RunJobRequest {
+    /// Payload for this run only; the job's stored payload when omitted
+    #[serde(default)]
+    pub payload: Option<JsonValue>,
+}
+
+/// Query parameters for previewing a job's upcoming fire times.
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct JobScheduleParams {
+    /// Number of fire times to return (max 100)
+    #[serde(default = "default_schedule_count")]
+    #[validate(range(min = 1, max = 100, message = "Count must be between 1 and 100"))]
+    #[param(minimum = 1, maximum = 100, example = 10)]
+    pub count: u32,
+
+    /// IANA timezone to display fire times in (defaults to the job's timezone)
+    #[param(example = "Asia/Shanghai")]
+    pub timezone: Option<String>,
+}
+
+fn default_schedule_count() -> u32 {
+    10
+}
+
+/// An alert rule in a request body.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct JobAlertRuleRequest {
+    pub event: JobAlertEvent,
+
+    /// Unsuccessful runs in a row that trigger the alert, 2 to 1000;
+    /// required for `consecutive_failures` only
+    #[schema(example = 3)]
+    pub threshold: Option<i32>,
+
+    /// Notification channels to alert, owned by the caller
+    #[schema(example = json!([1]))]
+    pub channel_ids: Vec<i32>,
+}
+
+/// Request body for replacing a job's alert rules.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example = json!({
+    "rules": [
+        {"event": "failure", "channel_ids": [1]},
+        {"event": "consecutive_failures", "threshold": 3, "channel_ids": [1, 2]},
+        {"event": "recovery", "channel_ids": [1]}
+    ]
+}))]
+pub struct SetJobAlertRulesRequest {
+    /// New rules; an empty list removes all rules
+    pub rules: Vec<JobAlertRuleRequest>,
+}
+
+impl SetJobAlertRulesRequest {
+    /// Converts the request into a user's rules on the given job
+    ///
+    /// # Errors
+    /// - `Validation` if a `consecutive_failures` rule has no threshold or
+    ///   one out of range, or another rule has one
+    pub fn into_rules(self, job_id: i32, user_id: i32) -> AppResult<Vec<NewJobAlertRule>> {
+        self.rules
+            .into_iter()
+            .map(|rule| {
+                let needs_threshold = rule.event == JobAlertEvent::ConsecutiveFailures;
+                if needs_threshold != rule.threshold.is_some() {
+                    return Err(AppError::Validation {
+                        field: "threshold".to_string(),
+                        reason: "A threshold is required for consecutive_failures rules \
+                                 and not allowed for other events"
+                            .to_string(),
+                    });
+                }
+                if rule.threshold.is_some_and(|n| !(2..=1000).contains(&n)) {
+                    return Err(AppError::Validation {
+                        field: "threshold".to_string(),
+                        reason: "Threshold must be between 2 and 1000".to_string(),
+                    });
+                }
+                Ok(NewJobAlertRule {
+                    job_id,
+                    event: rule.event,
+                    threshold: rule.threshold,
+                    channel_ids: rule.channel_ids,
+                    user_id,
+                })
+            })
+            .collect()
+    }
+}
+
 // ============================================================================
 // Response DTOs
 // ============================================================================
@@ -158,12 +315,16 @@ pub struct JobResponse {
     pub job_name: String,
     pub job_type: String,
     pub cron_expression: String,
+    pub timezone: String,
     pub enabled: bool,
     pub allow_concurrent: bool,
     pub max_concurrent: Option<i32>,
     pub max_retries: i32,
     pub retry_delay_seconds: i32,
     pub retry_backoff_multiplier: String,
+    pub retry_max_delay_seconds: i32,
+    pub retry_jitter: RetryJitter,
+    pub retry_on_timeout: bool,
     pub timeout_seconds: i32,
     pub payload: Option<JsonValue>,
     pub description: Option<String>,
@@ -173,6 +334,8 @@ pub struct JobResponse {
     pub created_at: String,
     pub updated_at: String,
     pub created_by: Option<String>,
+    /// Failed or timed-out runs in a row, reset by a successful run
+    pub consecutive_failures: i32,
 }
 
 impl From<ScheduledJob> for JobResponse {
@@ -182,12 +345,16 @@ impl From<ScheduledJob> for JobResponse {
             job_name: job.job_name,
             job_type: job.job_type,
             cron_expression: job.cron_expression,
+            timezone: job.timezone,
             enabled: job.enabled,
             allow_concurrent: job.allow_concurrent,
             max_concurrent: job.max_concurrent,
             max_retries: job.max_retries,
             retry_delay_seconds: job.retry_delay_seconds,
             retry_backoff_multiplier: job.retry_backoff_multiplier.to_string(),
+            retry_max_delay_seconds: job.retry_max_delay_seconds,
+            retry_jitter: job.retry_jitter,
+            retry_on_timeout: job.retry_on_timeout,
             timeout_seconds: job.timeout_seconds,
             payload: job.payload,
             description: job.description,
@@ -197,6 +364,68 @@ impl From<ScheduledJob> for JobResponse {
             created_at: job.created_at.to_jiff().to_string(),
             updated_at: job.updated_at.to_jiff().to_string(),
             created_by: job.created_by,
+            consecutive_failures: job.consecutive_failures,
+        }
+    }
+}
+
+/// Response body for a job alert rule.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobAlertRuleResponse {
+    pub id: i32,
+    pub event: JobAlertEvent,
+    pub threshold: Option<i32>,
+    pub channel_ids: Vec<i32>,
+    pub created_at: String,
+}
+
+impl From<JobAlertRule> for JobAlertRuleResponse {
+    fn from(rule: JobAlertRule) -> Self {
+        Self {
+            id: rule.id,
+            event: rule.event,
+            threshold: rule.threshold,
+            channel_ids: rule.channel_ids,
+            created_at: rule.created_at.to_jiff().to_string(),
+        }
+    }
+}
+
+/// Response body for a triggered job run.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RunJobResponse {
+    pub job_id: i32,
+    /// Execution id of the first attempt, usable to cancel the run
+    pub execution_id: String,
+}
+
+/// Response body for a job's upcoming fire times.
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example = json!({
+    "job_id": 1,
+    "cron_expression": "0 0 2 * * * *",
+    "timezone": "Asia/Shanghai",
+    "next_runs": ["2024-01-21T10:00:00+08:00", "2024-01-22T10:00:00+08:00"]
+}))]
+pub struct JobScheduleResponse {
+    pub job_id: i32,
+    pub cron_expression: String,
+    pub timezone: String,
+    /// Fire times as RFC 3339 timestamps with the timezone's offset
+    pub next_runs: Vec<String>,
+}
+
+impl JobScheduleResponse {
+    /// Builds the response, rendering fire times in `tz`
+    pub fn new(job: ScheduledJob, tz_name: String, tz: &TimeZone, runs: &[Timestamp]) -> Self {
+        Self {
+            job_id: job.id,
+            cron_expression: job.cron_expression,
+            timezone: tz_name,
+            next_runs: runs
+                .iter()
+                .map(|ts| ts.display_with_offset(tz.to_offset(*ts)).to_string())
+                .collect(),
         }
     }
 }
@@ -215,7 +444,11 @@ pub struct JobExecutionResponse {
     pub retry_attempt: i32,
     pub error_message: Option<String>,
     pub error_details: Option<JsonValue>,
+    /// Result returned by the task
     pub result: Option<JsonValue>,
+    /// Last progress reported by the task, 0 to 100
+    pub progress_percent: Option<i16>,
+    pub progress_message: Option<String>,
 }
 
 impl From<JobExecution> for JobExecutionResponse {
@@ -233,6 +466,50 @@ impl From<JobExecution> for JobExecutionResponse {
             error_message: exec.error_message,
             error_details: exec.error_details,
             result: exec.result,
+            progress_percent: exec.progress_percent,
+            progress_message: exec.progress_message,
+        }
+    }
+}
+
+/// A log line appended by a task.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobExecutionLogResponse {
+    pub level: JobLogLevel,
+    pub message: String,
+    #[schema(value_type = Option<Object>)]
+    pub fields: Option<JsonValue>,
+    pub created_at: String,
+}
+
+impl From<JobExecutionLog> for JobExecutionLogResponse {
+    fn from(line: JobExecutionLog) -> Self {
+        Self {
+            level: line.level,
+            message: line.message,
+            fields: line.fields,
+            created_at: line.created_at.to_jiff().to_string(),
+        }
+    }
+}
+
+/// Response body for one execution with its logs.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobExecutionDetailResponse {
+    #[serde(flatten)]
+    pub execution: JobExecutionResponse,
+    /// Log lines in the order they were appended
+    pub logs: Vec<JobExecutionLogResponse>,
+}
+
+impl JobExecutionDetailResponse {
+    pub fn new(execution: JobExecution, logs: Vec<JobExecutionLog>) -> Self {
+        Self {
+            execution: JobExecutionResponse::from(execution),
+            logs: logs
+                .into_iter()
+                .map(JobExecutionLogResponse::from)
+                .collect(),
         }
     }
 }
@@ -255,12 +532,20 @@ mod tests {
             timeout_seconds: 300,
             payload: Some(json!({"key": "value"})),
             description: Some("Test job".to_string()),
+            timezone: "Asia/Shanghai".to_string(),
+            retry_max_delay_seconds: 600,
+            retry_jitter: RetryJitter::Full,
+            retry_on_timeout: true,
         };
 
         let new_job = req.into_new_job();
         assert_eq!(new_job.job_name, "test_job");
         assert_eq!(new_job.job_type, "test_type");
+        assert_eq!(new_job.timezone, "Asia/Shanghai");
         assert_eq!(new_job.retry_delay_seconds, 60);
+        assert_eq!(new_job.retry_max_delay_seconds, 600);
+        assert_eq!(new_job.retry_jitter, RetryJitter::Full);
+        assert!(new_job.retry_on_timeout);
         assert_eq!(
             new_job.retry_backoff_multiplier,
             bigdecimal::BigDecimal::from(2)
@@ -280,6 +565,10 @@ mod tests {
             timeout_seconds: Some(600),
             payload: None,
             description: Some("Updated".to_string()),
+            timezone: None,
+            retry_max_delay_seconds: None,
+            retry_jitter: Some(RetryJitter::Decorrelated),
+            retry_on_timeout: None,
         };
 
         let update = req.into_update_job();
@@ -287,14 +576,74 @@ mod tests {
         assert_eq!(update.enabled, Some(false));
         assert_eq!(update.max_concurrent, Some(Some(2)));
         assert_eq!(update.max_retries, Some(5));
+        assert_eq!(update.retry_jitter, Some(RetryJitter::Decorrelated));
     }
 
     #[test]
-    fn test_job_response_from_scheduled_job() {
+    fn test_run_job_request_payload_is_optional() {
+        let req: RunJobRequest = serde_json::from_value(json!({})).unwrap();
+        assert!(req.payload.is_none());
+
+        let req: RunJobRequest =
+            serde_json::from_value(json!({"payload": {"retention_days": 7}})).unwrap();
+        assert_eq!(req.payload, Some(json!({"retention_days": 7})));
+    }
+
+    #[test]
+    fn test_set_alert_rules_request_into_rules() {
+        let req: SetJobAlertRulesRequest = serde_json::from_value(json!({
+            "rules": [
+                {"event": "failure", "channel_ids": [1]},
+                {"event": "consecutive_failures", "threshold": 3, "channel_ids": [1, 2]}
+            ]
+        }))
+        .unwrap();
+        assert!(req.validate().is_ok());
+
+        let rules = req.into_rules(7, 3).unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].job_id, 7);
+        assert_eq!(rules[0].user_id, 3);
+        assert_eq!(rules[1].event, JobAlertEvent::ConsecutiveFailures);
+        assert_eq!(rules[1].threshold, Some(3));
+        assert_eq!(rules[1].channel_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_set_alert_rules_request_checks_threshold() {
+        let missing: SetJobAlertRulesRequest = serde_json::from_value(json!({
+            "rules": [{"event": "consecutive_failures", "channel_ids": [1]}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            missing.into_rules(1, 1),
+            Err(AppError::Validation { .. })
+        ));
+
+        let unexpected: SetJobAlertRulesRequest = serde_json::from_value(json!({
+            "rules": [{"event": "recovery", "threshold": 2, "channel_ids": [1]}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            unexpected.into_rules(1, 1),
+            Err(AppError::Validation { .. })
+        ));
+
+        let too_low: SetJobAlertRulesRequest = serde_json::from_value(json!({
+            "rules": [{"event": "consecutive_failures", "threshold": 1, "channel_ids": [1]}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            too_low.into_rules(1, 1),
+            Err(AppError::Validation { .. })
+        ));
+    }
+
+    fn sample_job() -> ScheduledJob {
         use jiff_diesel::{DateTime, NullableDateTime};
 
         let dt = DateTime::from(jiff::civil::DateTime::constant(1970, 1, 1, 0, 0, 0, 0));
-        let job = ScheduledJob {
+        ScheduledJob {
             id: 1,
             job_name: "test".to_string(),
             job_type: "test_type".to_string(),
@@ -314,11 +663,70 @@ mod tests {
             created_at: dt,
             updated_at: dt,
             created_by: None,
-        };
+            last_fired_at: NullableDateTime::from(None),
+            timezone: "UTC".to_string(),
+            consecutive_failures: 0,
+            retry_max_delay_seconds: 3600,
+            retry_jitter: RetryJitter::None,
+            retry_on_timeout: false,
+        }
+    }
 
-        let response = JobResponse::from(job);
+    #[test]
+    fn test_job_response_from_scheduled_job() {
+        let response = JobResponse::from(sample_job());
         assert_eq!(response.id, 1);
         assert_eq!(response.job_name, "test");
         assert_eq!(response.retry_backoff_multiplier, "2");
     }
+
+    #[test]
+    fn test_job_schedule_response_uses_timezone_offset() {
+        let tz = TimeZone::get("Asia/Shanghai").unwrap();
+        let runs: Vec<Timestamp> = vec!["2024-01-21T02:00:00Z".parse().unwrap()];
+
+        let response =
+            JobScheduleResponse::new(sample_job(), "Asia/Shanghai".to_string(), &tz, &runs);
+        assert_eq!(response.job_id, 1);
+        assert_eq!(response.next_runs, vec!["2024-01-21T10:00:00+08:00"]);
+    }
+
+    #[test]
+    fn test_job_execution_detail_response() {
+        use jiff_diesel::{DateTime, NullableDateTime};
+
+        let dt = DateTime::from(jiff::civil::DateTime::constant(2026, 2, 20, 9, 0, 0, 0));
+        let execution = JobExecution {
+            id: 1,
+            job_id: 1,
+            job_name: "cleanup".to_string(),
+            execution_id: uuid::Uuid::nil(),
+            started_at: dt,
+            completed_at: NullableDateTime::from(None),
+            duration_ms: None,
+            status: JobStatus::Running,
+            retry_attempt: 0,
+            error_message: None,
+            error_details: None,
+            result: None,
+            progress_percent: Some(50),
+            progress_message: Some("Deleted 3 executions".to_string()),
+            cancel_requested: false,
+        };
+        let line = JobExecutionLog {
+            id: 1,
+            execution_id: uuid::Uuid::nil(),
+            level: JobLogLevel::Info,
+            message: "Batch done".to_string(),
+            fields: Some(json!({"batch": 1})),
+            created_at: dt,
+        };
+
+        let value =
+            serde_json::to_value(JobExecutionDetailResponse::new(execution, vec![line])).unwrap();
+        assert_eq!(value["status"], "running");
+        assert_eq!(value["progress_percent"], 50);
+        assert_eq!(value["logs"][0]["level"], "info");
+        assert_eq!(value["logs"][0]["fields"], json!({"batch": 1}));
+    }
 }
@@ -5,6 +5,8 @@
 //! - `user` - User-related request/response DTOs
 //! - `notification` - Notification-related request/response DTOs
 //! - `job` - Job scheduling-related request/response DTOs
+//! - `workflow` - Job workflow-related request/response DTOs
+//! - `queue` - Job queue-related request/response DTOs
 //! - `health` - Health check-related response DTOs
 //! - `live` - Live platform integration DTOs
 //! - `error` - Common error response DTOs
@@ -17,7 +19,9 @@ mod job;
 mod live;
 mod notification;
 mod pagination;
+mod queue;
 mod user;
+mod workflow;
 
 pub use auth::{
     LoginRequest, LoginResponse, RefreshTokenRequest, RefreshTokenResponse, RegisterRequest,
@@ -25,14 +29,34 @@ pub use auth::{
 };
 pub use error::ErrorResponse;
 pub use health::{ComponentHealth, HealthResponse, HealthStatus};
-pub use job::{CreateJobRequest, JobExecutionResponse, JobResponse, UpdateJobRequest};
+pub use job::{
+    CreateJobRequest, JobAlertRuleRequest, JobAlertRuleResponse, JobExecutionDetailResponse,
+    JobExecutionLogResponse, JobExecutionResponse, JobResponse, JobScheduleParams,
+    JobScheduleResponse, RunJobRequest, RunJobResponse, SetJobAlertRulesRequest, UpdateJobRequest,
+};
 pub use live::{
     LiveAnchorResponse, LiveRoomResponse, LiveRoomStatusResponse, LiveStatusBatchRequest,
     LiveStatusResponse,
 };
 pub use notification::{
-    ChannelResponse, CreateChannelRequest, LogResponse, SendNotificationRequest, SendToUserRequest,
-    UpdateChannelRequest,
+    ApiKeyResponse, BroadcastResponse, ChannelResponse, ChannelStatsResponse, ChannelUrlResponse,
+    CreateApiKeyRequest, CreateBroadcastRequest, CreateChannelRequest, CreateGroupRequest,
+    CreateRoutingRuleRequest, CreateTemplateRequest, CreatedApiKeyResponse, DeliveryStatsParams,
+    DeliveryStatsResponse, DispatchNotificationRequest, DispatchResponse, DispatchStatus,
+    GroupResponse, LogResponse, LogSearchParams, PreviewTemplateRequest, PreviewTemplateResponse,
+    PushSubscriptionKeys, QuietHoursRequest, QuietHoursResponse, RegisterPushSubscriptionRequest,
+    RoutingRuleResponse, SendNotificationRequest, SendToUserRequest, SetGroupMembersRequest,
+    TemplateResponse, UnregisterPushSubscriptionRequest, UpdateChannelRequest,
+    UpdateRoutingRuleRequest, UpdateTemplateRequest, WebPushKeyResponse,
 };
 pub use pagination::{PagedResponse, PaginationParams};
+pub use queue::{
+    EnqueueTaskRequest, QueueListParams, QueuedTaskDetailResponse, QueuedTaskLogResponse,
+    QueuedTaskResponse,
+};
 pub use user::{CreateUserRequest, UpdateUserRequest, UserResponse};
+pub use workflow::{
+    CreateWorkflowRequest, UpdateWorkflowRequest, WorkflowEdge, WorkflowResponse,
+    WorkflowRunGraphResponse, WorkflowRunResponse, WorkflowStepRequest, WorkflowStepResponse,
+    WorkflowStepRunResponse,
+};
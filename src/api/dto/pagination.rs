@@ -46,11 +46,11 @@ impl PaginationParams {
     }
 }
 
-fn default_page() -> u32 {
+pub(super) fn default_page() -> u32 {
     1
 }
 
-fn default_page_size() -> u32 {
+pub(super) fn default_page_size() -> u32 {
     20
 }
 
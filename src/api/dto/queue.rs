@@ -0,0 +1,360 @@
+//! Job queue-related DTOs for API requests and responses.
+
+use super::pagination::{PaginationParams, default_page, default_page_size};
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{NewQueuedTask, QueuedTask, QueuedTaskLog};
+use crate::jobs::types::{JobLogLevel, QueuedTaskStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+// ============================================================================
+// Request DTOs
+// ============================================================================
+
+fn default_payload() -> JsonValue {
+    serde_json::json!({})
+}
+
+fn default_max_attempts() -> i32 {
+    3
+}
+
+fn default_retry_delay_seconds() -> i32 {
+    60
+}
+
+fn default_visibility_timeout_seconds() -> i32 {
+    300
+}
+
+/// Request body for enqueuing a one-shot task.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example = json!({
+    "job_type": "data_cleanup",
+    "payload": {"retention_days": 7},
+    "delay_seconds": 600,
+    "priority": 10
+}))]
+pub struct EnqueueTaskRequest {
+    /// Registered job type to run
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Job type must be between 1 and 100 characters"
+    ))]
+    #[schema(example = "data_cleanup")]
+    pub job_type: String,
+
+    #[serde(default = "default_payload")]
+    #[schema(value_type = Object, example = json!({"retention_days": 7}))]
+    pub payload: JsonValue,
+
+    /// Earliest time to run the task (RFC 3339); defaults to now
+    #[schema(value_type = Option<String>, example = "2026-02-18T09:00:00Z")]
+    pub run_at: Option<jiff::Timestamp>,
+
+    /// Run the task this many seconds from now, instead of `run_at`
+    #[validate(range(
+        min = 0,
+        max = 31_536_000,
+        message = "Delay must be between 0 and 31536000 seconds"
+    ))]
+    pub delay_seconds: Option<i64>,
+
+    /// Tasks with a higher priority run first
+    #[serde(default)]
+    pub priority: i32,
+
+    #[serde(default = "default_max_attempts")]
+    #[validate(range(min = 1, max = 20, message = "Max attempts must be between 1 and 20"))]
+    #[schema(example = 3)]
+    pub max_attempts: i32,
+
+    /// Delay before the first retry; doubles with every further attempt
+    #[serde(default = "default_retry_delay_seconds")]
+    #[validate(range(
+        min = 0,
+        max = 86_400,
+        message = "Retry delay must be between 0 and 86400 seconds"
+    ))]
+    #[schema(example = 60)]
+    pub retry_delay_seconds: i32,
+
+    /// How long a claimed task stays hidden from other workers; an attempt
+    /// is timed out shortly before this ends
+    #[serde(default = "default_visibility_timeout_seconds")]
+    #[validate(range(
+        min = 1,
+        max = 86_400,
+        message = "Visibility timeout must be between 1 and 86400 seconds"
+    ))]
+    #[schema(example = 300)]
+    pub visibility_timeout_seconds: i32,
+}
+
+impl EnqueueTaskRequest {
+    /// Converts the request into a task due at `run_at`, `delay_seconds`
+    /// from `now`, or `now`
+    ///
+    /// # Errors
+    /// - `Validation` if both `run_at` and `delay_seconds` are set
+    pub fn into_new_task(self, now: jiff::Timestamp) -> AppResult<NewQueuedTask> {
+        let run_at = match (self.run_at, self.delay_seconds) {
+            (Some(_), Some(_)) => {
+                return Err(AppError::Validation {
+                    field: "run_at".to_string(),
+                    reason: "Set either run_at or delay_seconds, not both".to_string(),
+                });
+            }
+            (Some(run_at), None) => run_at,
+            (None, Some(delay)) => now + jiff::SignedDuration::from_secs(delay),
+            (None, None) => now,
+        };
+
+        Ok(NewQueuedTask {
+            job_type: self.job_type,
+            payload: self.payload,
+            priority: self.priority,
+            run_at: jiff_diesel::DateTime::from(
+                run_at.to_zoned(jiff::tz::TimeZone::UTC).datetime(),
+            ),
+            max_attempts: self.max_attempts,
+            retry_delay_seconds: self.retry_delay_seconds,
+            visibility_timeout_seconds: self.visibility_timeout_seconds,
+        })
+    }
+}
+
+/// Query parameters for listing queued tasks
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct QueueListParams {
+    /// Page number (1-based)
+    #[serde(default = "default_page")]
+    #[validate(range(min = 1, message = "Page must be at least 1"))]
+    #[param(minimum = 1, example = 1)]
+    pub page: u32,
+
+    /// Number of items per page (max 100)
+    #[serde(default = "default_page_size")]
+    #[validate(range(min = 1, max = 100, message = "Page size must be between 1 and 100"))]
+    #[param(minimum = 1, maximum = 100, example = 20)]
+    pub page_size: u32,
+
+    /// Only tasks with this status
+    pub status: Option<QueuedTaskStatus>,
+}
+
+impl QueueListParams {
+    /// Returns the pagination part of the query
+    pub fn pagination(&self) -> PaginationParams {
+        PaginationParams {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+// ============================================================================
+// Response DTOs
+// ============================================================================
+
+/// Response body for a queued task.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QueuedTaskResponse {
+    pub id: i64,
+    pub job_type: String,
+    #[schema(value_type = Object)]
+    pub payload: JsonValue,
+    pub priority: i32,
+    /// When the task is due, or due again after a failed attempt
+    pub run_at: String,
+    pub status: QueuedTaskStatus,
+    /// Attempts started so far
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub retry_delay_seconds: i32,
+    pub visibility_timeout_seconds: i32,
+    /// When a running attempt is considered abandoned
+    pub locked_until: Option<String>,
+    /// Error of the latest failed attempt
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub completed_at: Option<String>,
+    /// Result returned by the task when it succeeded
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<JsonValue>,
+    /// Last progress reported by the current or latest attempt, 0 to 100
+    pub progress_percent: Option<i16>,
+    pub progress_message: Option<String>,
+    /// Execution ID of the current or latest attempt, as seen by the task
+    pub execution_id: Option<String>,
+}
+
+impl From<QueuedTask> for QueuedTaskResponse {
+    fn from(task: QueuedTask) -> Self {
+        Self {
+            id: task.id,
+            job_type: task.job_type,
+            payload: task.payload,
+            priority: task.priority,
+            run_at: task.run_at.to_jiff().to_string(),
+            status: task.status,
+            attempts: task.attempts,
+            max_attempts: task.max_attempts,
+            retry_delay_seconds: task.retry_delay_seconds,
+            visibility_timeout_seconds: task.visibility_timeout_seconds,
+            locked_until: task.locked_until.to_jiff().map(|dt| dt.to_string()),
+            last_error: task.last_error,
+            created_at: task.created_at.to_jiff().to_string(),
+            updated_at: task.updated_at.to_jiff().to_string(),
+            completed_at: task.completed_at.to_jiff().map(|dt| dt.to_string()),
+            result: task.result,
+            progress_percent: task.progress_percent,
+            progress_message: task.progress_message,
+            execution_id: task.execution_id.map(|id| id.to_string()),
+        }
+    }
+}
+
+/// A log line appended by a queued task.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QueuedTaskLogResponse {
+    /// Attempt that appended the line
+    pub attempt: i32,
+    pub level: JobLogLevel,
+    pub message: String,
+    #[schema(value_type = Option<Object>)]
+    pub fields: Option<JsonValue>,
+    pub created_at: String,
+}
+
+impl From<QueuedTaskLog> for QueuedTaskLogResponse {
+    fn from(line: QueuedTaskLog) -> Self {
+        Self {
+            attempt: line.attempt,
+            level: line.level,
+            message: line.message,
+            fields: line.fields,
+            created_at: line.created_at.to_jiff().to_string(),
+        }
+    }
+}
+
+/// Response body for one queued task with its logs.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QueuedTaskDetailResponse {
+    #[serde(flatten)]
+    pub task: QueuedTaskResponse,
+    /// Log lines of all attempts in the order they were appended
+    pub logs: Vec<QueuedTaskLogResponse>,
+}
+
+impl QueuedTaskDetailResponse {
+    pub fn new(task: QueuedTask, logs: Vec<QueuedTaskLog>) -> Self {
+        Self {
+            task: QueuedTaskResponse::from(task),
+            logs: logs.into_iter().map(QueuedTaskLogResponse::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn now() -> jiff::Timestamp {
+        "2026-02-18T09:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_enqueue_request_defaults() {
+        let req: EnqueueTaskRequest =
+            serde_json::from_value(json!({"job_type": "data_cleanup"})).unwrap();
+
+        let task = req.into_new_task(now()).unwrap();
+        assert_eq!(task.payload, json!({}));
+        assert_eq!(task.priority, 0);
+        assert_eq!(task.max_attempts, 3);
+        assert_eq!(task.visibility_timeout_seconds, 300);
+        assert_eq!(task.run_at.to_jiff().to_string(), "2026-02-18T09:00:00");
+    }
+
+    #[test]
+    fn test_enqueue_request_run_time() {
+        let req: EnqueueTaskRequest =
+            serde_json::from_value(json!({"job_type": "x", "delay_seconds": 90})).unwrap();
+        let task = req.into_new_task(now()).unwrap();
+        assert_eq!(task.run_at.to_jiff().to_string(), "2026-02-18T09:01:30");
+
+        let req: EnqueueTaskRequest = serde_json::from_value(json!({
+            "job_type": "x",
+            "run_at": "2026-02-18T12:00:00+02:00"
+        }))
+        .unwrap();
+        let task = req.into_new_task(now()).unwrap();
+        assert_eq!(task.run_at.to_jiff().to_string(), "2026-02-18T10:00:00");
+
+        let req: EnqueueTaskRequest = serde_json::from_value(json!({
+            "job_type": "x",
+            "run_at": "2026-02-18T12:00:00Z",
+            "delay_seconds": 90
+        }))
+        .unwrap();
+        assert!(matches!(
+            req.into_new_task(now()),
+            Err(AppError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_queued_task_detail_response() {
+        use jiff_diesel::{DateTime, NullableDateTime};
+
+        let civil = jiff::civil::DateTime::constant(2026, 2, 20, 9, 0, 0, 0);
+        let dt = DateTime::from(civil);
+        let task = QueuedTask {
+            id: 7,
+            job_type: "data_cleanup".to_string(),
+            payload: json!({}),
+            priority: 0,
+            run_at: dt,
+            status: QueuedTaskStatus::Running,
+            attempts: 2,
+            max_attempts: 3,
+            retry_delay_seconds: 60,
+            visibility_timeout_seconds: 300,
+            locked_until: NullableDateTime::from(Some(civil)),
+            last_error: Some("connection reset".to_string()),
+            created_at: dt,
+            updated_at: dt,
+            completed_at: NullableDateTime::from(None),
+            result: None,
+            progress_percent: Some(40),
+            progress_message: Some("Deleted 2 batches".to_string()),
+            execution_id: Some(uuid::Uuid::nil()),
+        };
+        let line = QueuedTaskLog {
+            id: 1,
+            task_id: 7,
+            attempt: 2,
+            level: JobLogLevel::Info,
+            message: "Batch done".to_string(),
+            fields: Some(json!({"batch": 2})),
+            created_at: dt,
+        };
+
+        let value = serde_json::to_value(QueuedTaskDetailResponse::new(task, vec![line])).unwrap();
+        assert_eq!(value["status"], "running");
+        assert_eq!(value["progress_percent"], 40);
+        assert_eq!(
+            value["execution_id"],
+            "00000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(value["logs"][0]["attempt"], 2);
+        assert_eq!(value["logs"][0]["fields"], json!({"batch": 2}));
+    }
+}
@@ -93,6 +93,8 @@ pub struct UserResponse {
     pub username: String,
     #[schema(example = "john@example.com")]
     pub email: String,
+    #[schema(example = false)]
+    pub is_admin: bool,
     #[schema(example = "2024-01-15T10:30:00.000Z")]
     pub created_at: String,
     #[schema(example = "2024-01-20T14:45:30.000Z")]
@@ -105,6 +107,7 @@ impl From<User> for UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
+            is_admin: user.is_admin,
             created_at: user.created_at.to_jiff().to_string(),
             updated_at: user.updated_at.to_jiff().to_string(),
         }
@@ -0,0 +1,314 @@
+//! Workflow-related DTOs for API requests and responses.
+
+use crate::jobs::models::{
+    JobWorkflow, NewJobWorkflow, WorkflowRun, WorkflowStep, WorkflowStepRun, WorkflowStepSpec,
+};
+use crate::jobs::types::JobStatus;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+// ============================================================================
+// Request DTOs
+// ============================================================================
+
+/// A step of a workflow.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct WorkflowStepRequest {
+    /// Name of the step, unique within the workflow
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Step name must be between 1 and 100 characters"
+    ))]
+    #[schema(example = "compute_stats")]
+    pub step_name: String,
+
+    /// Scheduled job run by this step, with its stored payload
+    #[schema(example = 2)]
+    pub job_id: i32,
+
+    /// Names of the steps that must succeed before this one starts
+    #[serde(default)]
+    #[schema(example = json!(["sync_anchors"]))]
+    pub depends_on: Vec<String>,
+}
+
+impl From<WorkflowStepRequest> for WorkflowStepSpec {
+    fn from(step: WorkflowStepRequest) -> Self {
+        Self {
+            step_name: step.step_name,
+            job_id: step.job_id,
+            depends_on: step.depends_on,
+        }
+    }
+}
+
+/// Request body for creating a workflow.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example = json!({
+    "name": "nightly_pipeline",
+    "description": "Sync anchors, compute stats, then send the digest",
+    "steps": [
+        {"step_name": "sync_anchors", "job_id": 1},
+        {"step_name": "compute_stats", "job_id": 2, "depends_on": ["sync_anchors"]},
+        {"step_name": "send_digest", "job_id": 3, "depends_on": ["compute_stats"]}
+    ]
+}))]
+pub struct CreateWorkflowRequest {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Workflow name must be between 1 and 255 characters"
+    ))]
+    #[schema(example = "nightly_pipeline")]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    #[validate(nested)]
+    pub steps: Vec<WorkflowStepRequest>,
+}
+
+impl CreateWorkflowRequest {
+    pub fn into_parts(self) -> (NewJobWorkflow, Vec<WorkflowStepSpec>) {
+        let workflow = NewJobWorkflow {
+            name: self.name,
+            description: self.description,
+        };
+        let steps = self.steps.into_iter().map(WorkflowStepSpec::from).collect();
+        (workflow, steps)
+    }
+}
+
+/// Request body for updating a workflow.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct UpdateWorkflowRequest {
+    pub description: Option<String>,
+
+    /// Replaces all steps when present; runs already started are unaffected
+    #[validate(nested)]
+    pub steps: Option<Vec<WorkflowStepRequest>>,
+}
+
+impl UpdateWorkflowRequest {
+    pub fn into_parts(self) -> (Option<String>, Option<Vec<WorkflowStepSpec>>) {
+        let steps = self
+            .steps
+            .map(|steps| steps.into_iter().map(WorkflowStepSpec::from).collect());
+        (self.description, steps)
+    }
+}
+
+// ============================================================================
+// Response DTOs
+// ============================================================================
+
+/// A step of a workflow.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowStepResponse {
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+}
+
+impl From<WorkflowStep> for WorkflowStepResponse {
+    fn from(step: WorkflowStep) -> Self {
+        Self {
+            step_name: step.step_name,
+            job_id: step.job_id,
+            depends_on: step.depends_on,
+        }
+    }
+}
+
+/// Response body for workflow data.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowResponse {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub steps: Vec<WorkflowStepResponse>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl WorkflowResponse {
+    pub fn new(workflow: JobWorkflow, steps: Vec<WorkflowStep>) -> Self {
+        Self {
+            id: workflow.id,
+            name: workflow.name,
+            description: workflow.description,
+            steps: steps.into_iter().map(WorkflowStepResponse::from).collect(),
+            created_at: workflow.created_at.to_jiff().to_string(),
+            updated_at: workflow.updated_at.to_jiff().to_string(),
+        }
+    }
+}
+
+/// Response body for a workflow run, without its steps.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowRunResponse {
+    pub workflow_id: i32,
+    pub run_id: String,
+    pub status: JobStatus,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl From<WorkflowRun> for WorkflowRunResponse {
+    fn from(run: WorkflowRun) -> Self {
+        Self {
+            workflow_id: run.workflow_id,
+            run_id: run.run_id.to_string(),
+            status: run.status,
+            started_at: run.started_at.to_jiff().to_string(),
+            completed_at: run.completed_at.to_jiff().map(|dt| dt.to_string()),
+        }
+    }
+}
+
+/// A step of a workflow run.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowStepRunResponse {
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+    /// `pending` until started; `cancelled` without an execution if an
+    /// upstream step did not succeed
+    pub status: JobStatus,
+    /// Execution id of the latest attempt, see the job's executions
+    pub execution_id: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub error_message: Option<String>,
+}
+
+impl From<WorkflowStepRun> for WorkflowStepRunResponse {
+    fn from(step: WorkflowStepRun) -> Self {
+        Self {
+            step_name: step.step_name,
+            job_id: step.job_id,
+            depends_on: step.depends_on,
+            status: step.status,
+            execution_id: step.execution_id.map(|id| id.to_string()),
+            started_at: step.started_at.to_jiff().map(|dt| dt.to_string()),
+            completed_at: step.completed_at.to_jiff().map(|dt| dt.to_string()),
+            error_message: step.error_message,
+        }
+    }
+}
+
+/// Dependency between two steps of a run graph.
+#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WorkflowEdge {
+    /// Upstream step
+    pub from: String,
+    /// Step waiting on `from`
+    pub to: String,
+}
+
+/// Response body for a workflow run graph.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowRunGraphResponse {
+    #[serde(flatten)]
+    pub run: WorkflowRunResponse,
+    pub steps: Vec<WorkflowStepRunResponse>,
+    pub edges: Vec<WorkflowEdge>,
+}
+
+impl WorkflowRunGraphResponse {
+    pub fn new(run: WorkflowRun, steps: Vec<WorkflowStepRun>) -> Self {
+        let edges = steps
+            .iter()
+            .flat_map(|step| {
+                step.depends_on.iter().map(|upstream| WorkflowEdge {
+                    from: upstream.clone(),
+                    to: step.step_name.clone(),
+                })
+            })
+            .collect();
+
+        Self {
+            run: WorkflowRunResponse::from(run),
+            steps: steps
+                .into_iter()
+                .map(WorkflowStepRunResponse::from)
+                .collect(),
+            edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff_diesel::{DateTime, NullableDateTime};
+    use serde_json::json;
+
+    #[test]
+    fn test_create_workflow_request_into_parts() {
+        let req: CreateWorkflowRequest = serde_json::from_value(json!({
+            "name": "nightly",
+            "steps": [
+                {"step_name": "sync", "job_id": 1},
+                {"step_name": "stats", "job_id": 2, "depends_on": ["sync"]}
+            ]
+        }))
+        .unwrap();
+
+        let (workflow, steps) = req.into_parts();
+        assert_eq!(workflow.name, "nightly");
+        assert!(steps[0].depends_on.is_empty());
+        assert_eq!(steps[1].depends_on, vec!["sync"]);
+    }
+
+    #[test]
+    fn test_run_graph_edges() {
+        let dt = DateTime::from(jiff::civil::DateTime::constant(2026, 2, 16, 2, 0, 0, 0));
+        let run = WorkflowRun {
+            id: 1,
+            workflow_id: 1,
+            run_id: uuid::Uuid::nil(),
+            status: JobStatus::Running,
+            started_at: dt,
+            completed_at: NullableDateTime::from(None),
+            locked_until: NullableDateTime::from(None),
+            locked_by: None,
+        };
+        let step = |name: &str, depends_on: &[&str]| WorkflowStepRun {
+            id: 0,
+            workflow_run_id: 1,
+            step_name: name.to_string(),
+            job_id: 1,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            status: JobStatus::Pending,
+            execution_id: None,
+            started_at: NullableDateTime::from(None),
+            completed_at: NullableDateTime::from(None),
+            error_message: None,
+        };
+
+        let graph = WorkflowRunGraphResponse::new(
+            run,
+            vec![step("a", &[]), step("b", &[]), step("c", &["a", "b"])],
+        );
+        assert_eq!(
+            graph.edges,
+            vec![
+                WorkflowEdge {
+                    from: "a".to_string(),
+                    to: "c".to_string()
+                },
+                WorkflowEdge {
+                    from: "b".to_string(),
+                    to: "c".to_string()
+                },
+            ]
+        );
+
+        let value = serde_json::to_value(&graph).unwrap();
+        assert_eq!(value["status"], "running");
+        assert_eq!(value["steps"][2]["depends_on"], json!(["a", "b"]));
+    }
+}
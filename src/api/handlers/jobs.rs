@@ -2,18 +2,24 @@
 
 use crate::api::doc::JOB_TAG;
 use crate::api::dto::{
-    CreateJobRequest, JobExecutionResponse, JobResponse, PaginationParams, UpdateJobRequest,
+    CreateJobRequest, JobAlertRuleResponse, JobExecutionDetailResponse, JobExecutionResponse,
+    JobResponse, JobScheduleParams, JobScheduleResponse, PaginationParams, RunJobRequest,
+    RunJobResponse, SetJobAlertRulesRequest, UpdateJobRequest,
 };
-use crate::error::AppResult;
+use crate::api::middleware::AuthUser;
+use crate::error::{AppError, AppResult};
+use crate::jobs::JobScheduler;
 use crate::state::AppState;
+use crate::utils::timezone::parse_timezone;
 use crate::utils::validate::{ValidatedJson, ValidatedQuery};
 use axum::{
-    Json,
+    Extension, Json,
     extract::{Path, State},
     http::StatusCode,
 };
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_axum::routes;
+use uuid::Uuid;
 
 /// Creates job-related routes.
 pub fn job_routes() -> OpenApiRouter<AppState> {
@@ -25,7 +31,23 @@ pub fn job_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(delete_job))
         .routes(routes!(pause_job))
         .routes(routes!(resume_job))
+        .routes(routes!(get_job_schedule))
+        .routes(routes!(get_job_alerts))
+        .routes(routes!(set_job_alerts))
+        .routes(routes!(run_job))
         .routes(routes!(list_job_executions))
+        .routes(routes!(get_job_execution))
+        .routes(routes!(cancel_job_execution))
+}
+
+/// Returns the running scheduler, or an error when jobs are disabled.
+pub(super) fn require_scheduler(state: &AppState) -> AppResult<&JobScheduler> {
+    state
+        .scheduler
+        .as_deref()
+        .ok_or_else(|| AppError::UnprocessableContent {
+            message: "Job scheduler is not enabled".to_string(),
+        })
 }
 
 /// GET /api/jobs - List all scheduled jobs
@@ -218,6 +240,214 @@ async fn resume_job(
     Ok(Json(JobResponse::from(job)))
 }
 
+/// GET /api/jobs/:id/schedule - Preview the next fire times of a job
+#[utoipa::path(
+    get,
+    path = "/{id}/schedule",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Job ID"),
+        JobScheduleParams
+    ),
+    responses(
+        (status = 200, description = "Upcoming fire times", body = JobScheduleResponse),
+        (status = 400, description = "Invalid count or unknown timezone"),
+        (status = 404, description = "Job not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn get_job_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    ValidatedQuery(params): ValidatedQuery<JobScheduleParams>,
+) -> AppResult<Json<JobScheduleResponse>> {
+    let (job, runs) = state
+        .services
+        .jobs
+        .upcoming_runs(id, params.count as usize)
+        .await?;
+    let tz_name = params.timezone.unwrap_or_else(|| job.timezone.clone());
+    let tz = parse_timezone(&tz_name)?;
+
+    Ok(Json(JobScheduleResponse::new(job, tz_name, &tz, &runs)))
+}
+
+/// GET /api/jobs/:id/alerts - List the caller's alert rules on a job
+#[utoipa::path(
+    get,
+    path = "/{id}/alerts",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Job ID")
+    ),
+    responses(
+        (status = 200, description = "Caller's alert rules on the job", body = Vec<JobAlertRuleResponse>),
+        (status = 404, description = "Job not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn get_job_alerts(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<JobAlertRuleResponse>>> {
+    let rules = state
+        .services
+        .jobs
+        .get_alert_rules(id, auth_user.user_id)
+        .await?;
+    Ok(Json(
+        rules.into_iter().map(JobAlertRuleResponse::from).collect(),
+    ))
+}
+
+/// PUT /api/jobs/:id/alerts - Replace the alert rules of a job
+///
+/// Each rule sends a message to its channels when a run, after all its
+/// retries, fails, times out, fails for the `threshold`-th time in a row,
+/// or succeeds after failing. Channels must belong to the caller. Only the
+/// caller's rules are replaced; other users' rules on the job are kept.
+#[utoipa::path(
+    put,
+    path = "/{id}/alerts",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Job ID")
+    ),
+    request_body = SetJobAlertRulesRequest,
+    responses(
+        (status = 200, description = "Alert rules replaced", body = Vec<JobAlertRuleResponse>),
+        (status = 400, description = "Invalid rule or channel"),
+        (status = 404, description = "Job not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn set_job_alerts(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<SetJobAlertRulesRequest>,
+) -> AppResult<Json<Vec<JobAlertRuleResponse>>> {
+    let rules = req.into_rules(id, auth_user.user_id)?;
+    let rules = state
+        .services
+        .jobs
+        .set_alert_rules(id, auth_user.user_id, rules)
+        .await?;
+    Ok(Json(
+        rules.into_iter().map(JobAlertRuleResponse::from).collect(),
+    ))
+}
+
+/// POST /api/jobs/:id/run - Run a job immediately
+///
+/// The run starts in the background; the returned execution id can be
+/// used to follow or cancel it.
+#[utoipa::path(
+    post,
+    path = "/{id}/run",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Job ID")
+    ),
+    request_body(content = Option<RunJobRequest>, description = "Optional payload override"),
+    responses(
+        (status = 202, description = "Job run started", body = RunJobResponse),
+        (status = 404, description = "Job not found"),
+        (status = 422, description = "Scheduler disabled or concurrency limit reached")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn run_job(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    body: Option<Json<RunJobRequest>>,
+) -> AppResult<(StatusCode, Json<RunJobResponse>)> {
+    let scheduler = require_scheduler(&state)?;
+    let Json(req) = body.unwrap_or_default();
+    let execution_id = scheduler.run_now(id, req.payload).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(RunJobResponse {
+            job_id: id,
+            execution_id: execution_id.to_string(),
+        }),
+    ))
+}
+
+/// GET /api/jobs/executions/:execution_id - Get an execution with its logs
+///
+/// While the execution runs, it shows the progress last reported by the
+/// task; once finished, its result or error details.
+#[utoipa::path(
+    get,
+    path = "/executions/{execution_id}",
+    tag = JOB_TAG,
+    params(
+        ("execution_id" = String, Path, description = "Execution ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Execution found", body = JobExecutionDetailResponse),
+        (status = 404, description = "Execution not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn get_job_execution(
+    State(state): State<AppState>,
+    Path(execution_id): Path<Uuid>,
+) -> AppResult<Json<JobExecutionDetailResponse>> {
+    let (execution, logs) = state.services.jobs.get_job_execution(execution_id).await?;
+    Ok(Json(JobExecutionDetailResponse::new(execution, logs)))
+}
+
+/// POST /api/jobs/executions/:execution_id/cancel - Cancel a running execution
+///
+/// An execution running on another replica is flagged and stopped by that
+/// replica at its next check.
+#[utoipa::path(
+    post,
+    path = "/executions/{execution_id}/cancel",
+    tag = JOB_TAG,
+    params(
+        ("execution_id" = String, Path, description = "Execution ID (UUID)")
+    ),
+    responses(
+        (status = 202, description = "Cancellation requested"),
+        (status = 404, description = "No running execution with this ID"),
+        (status = 422, description = "Scheduler disabled")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn cancel_job_execution(
+    State(state): State<AppState>,
+    Path(execution_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let scheduler = require_scheduler(&state)?;
+
+    if scheduler.cancel_execution(execution_id).await? {
+        Ok(StatusCode::ACCEPTED)
+    } else {
+        Err(AppError::NotFound {
+            entity: "Running execution".to_string(),
+            field: "execution_id".to_string(),
+            value: execution_id.to_string(),
+        })
+    }
+}
+
 /// GET /api/jobs/:id/executions - List execution history for a job
 #[utoipa::path(
     get,
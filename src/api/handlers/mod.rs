@@ -8,4 +8,6 @@ pub mod jobs;
 pub mod live;
 pub mod me;
 pub mod notifications;
+pub mod queue;
 pub mod users;
+pub mod workflows;
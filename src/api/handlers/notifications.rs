@@ -4,13 +4,27 @@
 
 use crate::api::doc::NOTIFICATION_TAG;
 use crate::api::dto::{
-    ChannelResponse, CreateChannelRequest, LogResponse, PagedResponse, PaginationParams,
-    SendNotificationRequest, SendToUserRequest, UpdateChannelRequest,
+    ApiKeyResponse, BroadcastResponse, ChannelResponse, ChannelStatsResponse, ChannelUrlResponse,
+    CreateApiKeyRequest, CreateBroadcastRequest, CreateChannelRequest, CreateGroupRequest,
+    CreateRoutingRuleRequest, CreateTemplateRequest, CreatedApiKeyResponse, DeliveryStatsParams,
+    DeliveryStatsResponse, DispatchNotificationRequest, DispatchResponse, DispatchStatus,
+    GroupResponse, LogResponse, LogSearchParams, PagedResponse, PaginationParams,
+    PreviewTemplateRequest, PreviewTemplateResponse, QuietHoursRequest, QuietHoursResponse,
+    RegisterPushSubscriptionRequest, RoutingRuleResponse, SendNotificationRequest,
+    SendToUserRequest, SetGroupMembersRequest, TemplateResponse, UnregisterPushSubscriptionRequest,
+    UpdateChannelRequest, UpdateRoutingRuleRequest, UpdateTemplateRequest, WebPushKeyResponse,
 };
 use crate::api::middleware::AuthUser;
 use crate::error::{AppError, AppResult};
-use crate::models::{NewNotificationChannel, UpdateNotificationChannel};
-use crate::services::notifications::NotificationMessage;
+use crate::models::{
+    NewNotificationChannel, NewNotificationQuietHours, NewNotificationRoutingRule,
+    NewNotificationTemplate, NewUserGroup, TemplateVariants, UpdateNotificationChannel,
+    UpdateNotificationRoutingRule, UpdateNotificationTemplate, WebPushKeys, WebPushSubscription,
+};
+use crate::repositories::NotificationLogFilter;
+use crate::services::notifications::{
+    DispatchOutcome, MessageTemplate, NotificationMessage, parse_channel_url,
+};
 use crate::state::AppState;
 use crate::utils::validate::{ValidatedJson, ValidatedQuery};
 use axum::{
@@ -27,21 +41,89 @@ use utoipa_axum::routes;
 /// - GET /channels         - List user's channels
 /// - POST /channels        - Create channel
 /// - GET /channels/:id     - Get channel by ID
+/// - GET /channels/:id/url - Export channel as a notification URL
+/// - GET /channels/:id/stats - All-time delivery counts of a channel
 /// - PUT /channels/:id     - Update channel
 /// - DELETE /channels/:id  - Delete channel
+/// - GET /webpush/vapid-public-key - Server key for browser subscriptions
+/// - POST /webpush/subscriptions - Register a browser push subscription
+/// - DELETE /webpush/subscriptions - Remove a browser push subscription
 /// - POST /channels/:id/send - Send via channel
 /// - POST /send            - Send to user's channels
-/// - GET /logs             - List logs
+/// - POST /dispatch        - Send through routing rules and quiet hours
+/// - GET /stats            - Delivery reliability per provider over time
+/// - GET /logs             - Search logs
+/// - GET /logs/:id         - Get log entry
+/// - POST /logs/:id/resend - Resend a logged notification
+/// - GET /templates        - List templates (own + system)
+/// - POST /templates       - Create template
+/// - GET /templates/:id    - Get template by ID
+/// - PUT /templates/:id    - Update template
+/// - DELETE /templates/:id - Delete template
+/// - POST /templates/preview - Render a template without sending
+/// - GET /routing/rules    - List routing rules
+/// - POST /routing/rules   - Create routing rule
+/// - GET /routing/rules/:id - Get routing rule
+/// - PUT /routing/rules/:id - Update routing rule
+/// - DELETE /routing/rules/:id - Delete routing rule
+/// - GET /routing/quiet-hours - Get quiet hours
+/// - PUT /routing/quiet-hours - Set quiet hours
+/// - DELETE /routing/quiet-hours - Remove quiet hours
+/// - GET /groups           - List user groups (admin)
+/// - POST /groups          - Create user group (admin)
+/// - GET /groups/:id       - Get user group (admin)
+/// - PUT /groups/:id/members - Replace group members (admin)
+/// - DELETE /groups/:id    - Delete user group (admin)
+/// - GET /broadcasts       - List broadcasts (admin)
+/// - POST /broadcasts      - Broadcast to a group or all users (admin)
+/// - GET /broadcasts/:id   - Get broadcast delivery stats (admin)
+/// - GET /api-keys         - List API keys
+/// - POST /api-keys        - Create API key
+/// - DELETE /api-keys/:id  - Revoke API key
 pub fn notification_routes() -> OpenApiRouter<AppState> {
     OpenApiRouter::new()
         .routes(routes!(list_channels))
         .routes(routes!(create_channel))
         .routes(routes!(get_channel))
+        .routes(routes!(get_channel_url))
+        .routes(routes!(get_channel_stats))
         .routes(routes!(update_channel))
         .routes(routes!(delete_channel))
+        .routes(routes!(get_vapid_public_key))
+        .routes(routes!(register_push_subscription))
+        .routes(routes!(unregister_push_subscription))
         .routes(routes!(send_to_channel))
         .routes(routes!(send_to_user))
+        .routes(routes!(dispatch_notification))
+        .routes(routes!(get_delivery_stats))
         .routes(routes!(list_logs))
+        .routes(routes!(get_log))
+        .routes(routes!(resend_log))
+        .routes(routes!(list_templates))
+        .routes(routes!(create_template))
+        .routes(routes!(get_template))
+        .routes(routes!(update_template))
+        .routes(routes!(delete_template))
+        .routes(routes!(preview_template))
+        .routes(routes!(list_routing_rules))
+        .routes(routes!(create_routing_rule))
+        .routes(routes!(get_routing_rule))
+        .routes(routes!(update_routing_rule))
+        .routes(routes!(delete_routing_rule))
+        .routes(routes!(get_quiet_hours))
+        .routes(routes!(set_quiet_hours))
+        .routes(routes!(delete_quiet_hours))
+        .routes(routes!(list_groups))
+        .routes(routes!(create_group))
+        .routes(routes!(get_group))
+        .routes(routes!(set_group_members))
+        .routes(routes!(delete_group))
+        .routes(routes!(list_broadcasts))
+        .routes(routes!(create_broadcast))
+        .routes(routes!(get_broadcast))
+        .routes(routes!(list_api_keys))
+        .routes(routes!(create_api_key))
+        .routes(routes!(delete_api_key))
 }
 
 // ============================================================================
@@ -102,13 +184,29 @@ async fn create_channel(
     Extension(auth_user): Extension<AuthUser>,
     ValidatedJson(payload): ValidatedJson<CreateChannelRequest>,
 ) -> AppResult<(StatusCode, Json<ChannelResponse>)> {
+    let (channel_type, config) = match (payload.url, payload.channel_type, payload.config) {
+        (Some(url), None, None) => parse_channel_url(&url)?,
+        (None, Some(channel_type), Some(config)) => (channel_type, config),
+        _ => {
+            return Err(AppError::Validation {
+                field: "url".to_string(),
+                reason: "Provide either url, or channel_type and config".to_string(),
+            });
+        }
+    };
+
     let new_channel = NewNotificationChannel {
         user_id: auth_user.user_id,
-        channel_type: payload.channel_type,
+        channel_type,
         name: payload.name,
-        config: payload.config,
+        config,
         enabled: payload.enabled,
         priority: payload.priority,
+        rate_limit_count: payload.rate_limit_count,
+        rate_limit_window_seconds: payload.rate_limit_window_seconds,
+        dedup_window_seconds: payload.dedup_window_seconds,
+        digest_enabled: payload.digest_enabled,
+        failure_threshold: payload.failure_threshold,
     };
 
     let channel = state
@@ -154,6 +252,81 @@ async fn get_channel(
     Ok(Json(ChannelResponse::from(channel)))
 }
 
+/// GET /api/notifications/channels/:id/url - Export channel as URL
+///
+/// Exports a channel as an Apprise-style notification URL that can be
+/// used to create the channel again. The URL includes the channel's
+/// secrets. Only the channel owner can export it.
+#[utoipa::path(
+    get,
+    path = "/channels/{id}/url",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Channel ID")
+    ),
+    responses(
+        (status = 200, description = "Channel URL", body = ChannelUrlResponse),
+        (status = 400, description = "Channel config cannot be expressed as a URL"),
+        (status = 404, description = "Channel not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_channel_url(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<ChannelUrlResponse>> {
+    let channel = state.services.notifications.get_channel(id).await?;
+
+    // Verify ownership
+    if channel.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let url = state.services.notifications.export_channel_url(&channel)?;
+    Ok(Json(ChannelUrlResponse { url }))
+}
+
+/// GET /api/notifications/channels/:id/stats - Get channel delivery stats
+///
+/// Returns message counts per status over the channel's whole history,
+/// including logs already removed by the retention job.
+/// Only the channel owner can access them.
+#[utoipa::path(
+    get,
+    path = "/channels/{id}/stats",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Channel ID")
+    ),
+    responses(
+        (status = 200, description = "Channel stats", body = ChannelStatsResponse),
+        (status = 404, description = "Channel not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_channel_stats(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<ChannelStatsResponse>> {
+    let channel = state.services.notifications.get_channel(id).await?;
+
+    // Verify ownership
+    if channel.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let totals = state.services.notifications.get_channel_stats(id).await?;
+    Ok(Json(ChannelStatsResponse::new(id, totals)))
+}
+
 /// PUT /api/notifications/channels/:id - Update channel
 ///
 /// Updates an existing notification channel.
@@ -193,6 +366,12 @@ async fn update_channel(
         config: payload.config,
         enabled: payload.enabled,
         priority: payload.priority,
+        rate_limit_count: payload.rate_limit_count,
+        rate_limit_window_seconds: payload.rate_limit_window_seconds,
+        dedup_window_seconds: payload.dedup_window_seconds,
+        digest_enabled: payload.digest_enabled,
+        failure_threshold: payload.failure_threshold,
+        ..Default::default()
     };
 
     let updated = state
@@ -247,6 +426,120 @@ async fn delete_channel(
     }
 }
 
+// ============================================================================
+// Web Push Handlers
+// ============================================================================
+
+/// GET /api/notifications/webpush/vapid-public-key - Get the VAPID public key
+///
+/// Returns the key browsers pass as `applicationServerKey` when subscribing.
+#[utoipa::path(
+    get,
+    path = "/webpush/vapid-public-key",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 200, description = "VAPID public key", body = WebPushKeyResponse),
+        (status = 404, description = "Web Push is not configured")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_vapid_public_key() -> AppResult<Json<WebPushKeyResponse>> {
+    let vapid = crate::utils::webpush::vapid().ok_or_else(|| AppError::NotFound {
+        entity: "webpush".to_string(),
+        field: "vapid_public_key".to_string(),
+        value: "not configured".to_string(),
+    })?;
+
+    Ok(Json(WebPushKeyResponse {
+        public_key: vapid.public_key().to_string(),
+    }))
+}
+
+/// POST /api/notifications/webpush/subscriptions - Register a push subscription
+///
+/// Creates a Web Push channel for the browser subscription. Registering an
+/// already known endpoint updates its channel with the new keys.
+#[utoipa::path(
+    post,
+    path = "/webpush/subscriptions",
+    tag = NOTIFICATION_TAG,
+    request_body = RegisterPushSubscriptionRequest,
+    responses(
+        (status = 201, description = "Channel created", body = ChannelResponse),
+        (status = 200, description = "Existing channel updated", body = ChannelResponse),
+        (status = 400, description = "Invalid subscription or Web Push not configured")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn register_push_subscription(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<RegisterPushSubscriptionRequest>,
+) -> AppResult<(StatusCode, Json<ChannelResponse>)> {
+    let subscription = WebPushSubscription {
+        endpoint: payload.endpoint,
+        keys: WebPushKeys {
+            p256dh: payload.keys.p256dh,
+            auth: payload.keys.auth,
+        },
+    };
+
+    let (channel, created) = state
+        .services
+        .notifications
+        .register_push_subscription(
+            auth_user.user_id,
+            payload.name,
+            payload.priority,
+            subscription,
+        )
+        .await?;
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(ChannelResponse::from(channel))))
+}
+
+/// DELETE /api/notifications/webpush/subscriptions - Remove a push subscription
+///
+/// Deletes the authenticated user's Web Push channel for the endpoint,
+/// e.g. after the browser unsubscribed.
+#[utoipa::path(
+    delete,
+    path = "/webpush/subscriptions",
+    tag = NOTIFICATION_TAG,
+    request_body = UnregisterPushSubscriptionRequest,
+    responses(
+        (status = 204, description = "Subscription removed"),
+        (status = 404, description = "Subscription not found")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn unregister_push_subscription(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<UnregisterPushSubscriptionRequest>,
+) -> AppResult<StatusCode> {
+    let deleted = state
+        .services
+        .notifications
+        .remove_push_subscription(auth_user.user_id, &payload.endpoint)
+        .await?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound {
+            entity: "push_subscription".to_string(),
+            field: "endpoint".to_string(),
+            value: payload.endpoint,
+        })
+    }
+}
+
 // ============================================================================
 // Message Sending Handlers
 // ============================================================================
@@ -289,6 +582,13 @@ async fn send_to_channel(
         title: payload.title,
         body: payload.body,
         metadata: payload.metadata,
+        severity: payload.severity,
+        tags: payload.tags,
+        dedup_key: payload.dedup_key,
+        image_url: payload.image_url,
+        click_url: payload.click_url,
+        attachments: payload.attachments,
+        ..Default::default()
     };
 
     let log = state
@@ -302,16 +602,19 @@ async fn send_to_channel(
 /// POST /api/notifications/send - Send to user's channels
 ///
 /// Sends a notification to all enabled channels of a specific type
-/// for the authenticated user, in priority order.
+/// for the authenticated user, in priority order. This is the only
+/// endpoint that accepts notification API keys.
 #[utoipa::path(
     post,
     path = "/send",
     tag = NOTIFICATION_TAG,
     request_body = SendToUserRequest,
     responses(
-        (status = 200, description = "Notifications sent", body = Vec<LogResponse>)
+        (status = 200, description = "Notifications sent", body = Vec<LogResponse>),
+        (status = 403, description = "API key not allowed from this address"),
+        (status = 429, description = "API key rate limit exceeded")
     ),
-    security(("bearerAuth" = []))
+    security(("bearerAuth" = []), ("apiKeyAuth" = []))
 )]
 async fn send_to_user(
     State(state): State<AppState>,
@@ -322,6 +625,13 @@ async fn send_to_user(
         title: payload.title,
         body: payload.body,
         metadata: payload.metadata,
+        severity: payload.severity,
+        tags: payload.tags,
+        dedup_key: payload.dedup_key,
+        image_url: payload.image_url,
+        click_url: payload.click_url,
+        attachments: payload.attachments,
+        ..Default::default()
     };
 
     let logs = state
@@ -334,18 +644,141 @@ async fn send_to_user(
     Ok(Json(responses))
 }
 
+/// POST /api/notifications/dispatch - Dispatch through routing rules
+///
+/// Sends a notification using the authenticated user's routing rules and
+/// quiet hours. Low-severity messages may be deferred or dropped during
+/// quiet hours; otherwise the first matching rule picks the channels.
+#[utoipa::path(
+    post,
+    path = "/dispatch",
+    tag = NOTIFICATION_TAG,
+    request_body = DispatchNotificationRequest,
+    responses(
+        (status = 200, description = "Notification dispatched", body = DispatchResponse),
+        (status = 404, description = "No enabled channels")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn dispatch_notification(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<DispatchNotificationRequest>,
+) -> AppResult<Json<DispatchResponse>> {
+    let message = NotificationMessage {
+        title: payload.title,
+        body: payload.body,
+        metadata: payload.metadata,
+        severity: payload.severity,
+        tags: payload.tags,
+        dedup_key: payload.dedup_key,
+        image_url: payload.image_url,
+        click_url: payload.click_url,
+        attachments: payload.attachments,
+        ..Default::default()
+    };
+
+    let outcome = state
+        .services
+        .notifications
+        .dispatch(auth_user.user_id, message)
+        .await?;
+
+    let response = match outcome {
+        DispatchOutcome::Delivered { rule_id, logs } => DispatchResponse {
+            status: DispatchStatus::Delivered,
+            rule_id,
+            deliver_at: None,
+            logs: logs.into_iter().map(LogResponse::from).collect(),
+        },
+        DispatchOutcome::Deferred { deliver_at } => DispatchResponse {
+            status: DispatchStatus::Deferred,
+            rule_id: None,
+            deliver_at: Some(deliver_at.to_string()),
+            logs: vec![],
+        },
+        DispatchOutcome::Dropped => DispatchResponse {
+            status: DispatchStatus::Dropped,
+            rule_id: None,
+            deliver_at: None,
+            logs: vec![],
+        },
+    };
+
+    Ok(Json(response))
+}
+
+// ============================================================================
+// Stats Handlers
+// ============================================================================
+
+/// GET /api/notifications/stats - Delivery reliability per provider
+///
+/// Aggregates the authenticated user's delivery logs per provider (channel
+/// type) and time bucket: sends, failures, retries, success rate and
+/// p50/p95 provider latency. Covers logs still within the retention period.
+#[utoipa::path(
+    get,
+    path = "/stats",
+    tag = NOTIFICATION_TAG,
+    params(DeliveryStatsParams),
+    responses(
+        (status = 200, description = "Delivery stats", body = DeliveryStatsResponse),
+        (status = 400, description = "Invalid time range"),
+        (status = 404, description = "Channel not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_delivery_stats(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(params): ValidatedQuery<DeliveryStatsParams>,
+) -> AppResult<Json<DeliveryStatsResponse>> {
+    if let Some(channel_id) = params.channel_id {
+        let channel = state.services.notifications.get_channel(channel_id).await?;
+
+        // Verify ownership
+        if channel.user_id != auth_user.user_id {
+            return Err(AppError::Forbidden {
+                message: "Access denied".to_string(),
+            });
+        }
+    }
+
+    let (from, to, rows) = state
+        .services
+        .notifications
+        .get_delivery_stats(
+            auth_user.user_id,
+            params.channel_id,
+            params.from,
+            params.to,
+            params.bucket,
+        )
+        .await?;
+
+    Ok(Json(DeliveryStatsResponse::new(
+        from,
+        to,
+        params.bucket,
+        rows,
+    )))
+}
+
 // ============================================================================
 // Log Handlers
 // ============================================================================
 
-/// GET /api/notifications/logs - List notification logs
+/// GET /api/notifications/logs - Search notification logs
 ///
-/// Lists notification send history for the authenticated user's channels.
+/// Lists notification send history for the authenticated user's channels,
+/// newest first, optionally filtered by channel, status, time range and text.
 #[utoipa::path(
     get,
     path = "/logs",
     tag = NOTIFICATION_TAG,
-    params(PaginationParams),
+    params(LogSearchParams),
     responses(
         (status = 200, description = "List of logs", body = PagedResponse<LogResponse>)
     ),
@@ -354,34 +787,1085 @@ async fn send_to_user(
 async fn list_logs(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-    ValidatedQuery(params): ValidatedQuery<PaginationParams>,
+    ValidatedQuery(params): ValidatedQuery<LogSearchParams>,
 ) -> AppResult<Json<PagedResponse<LogResponse>>> {
-    let params = params.normalize();
+    let pagination = params.pagination().normalize();
+
+    let filter = NotificationLogFilter {
+        channel_id: params.channel_id,
+        status: params.status,
+        from: params.from.map(to_db_datetime),
+        to: params.to.map(to_db_datetime),
+        text: params.q,
+    };
 
-    // Get user's channels to filter logs
-    let user_channels = state
+    let (logs, total_count) = state
         .services
         .notifications
-        .list_user_channels(auth_user.user_id)
+        .search_logs(
+            auth_user.user_id,
+            &filter,
+            pagination.offset() as i64,
+            pagination.limit() as i64,
+        )
         .await?;
 
-    // For simplicity, get logs from first channel
-    // In production, you'd aggregate logs from all channels
-    if let Some(first_channel) = user_channels.first() {
-        let (logs, total_count) = state
-            .services
-            .notifications
-            .get_channel_logs(
-                first_channel.id,
-                params.offset() as i64,
-                params.limit() as i64,
-            )
-            .await?;
-
-        let responses: Vec<LogResponse> = logs.into_iter().map(LogResponse::from).collect();
-        let paged_response = PagedResponse::new(responses, &params, total_count as u64);
-        Ok(Json(paged_response))
-    } else {
-        Ok(Json(PagedResponse::new(vec![], &params, 0)))
+    let responses: Vec<LogResponse> = logs.into_iter().map(LogResponse::from).collect();
+    Ok(Json(PagedResponse::new(
+        responses,
+        &pagination,
+        total_count as u64,
+    )))
+}
+
+/// GET /api/notifications/logs/{id} - Get log entry
+///
+/// Returns a log entry with the captured provider request and response.
+/// Only the owner of the log's channel can view it.
+#[utoipa::path(
+    get,
+    path = "/logs/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i64, Path, description = "Log ID")
+    ),
+    responses(
+        (status = 200, description = "Log details", body = LogResponse),
+        (status = 404, description = "Log not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_log(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> AppResult<Json<LogResponse>> {
+    let log = state.services.notifications.get_log(id).await?;
+    let channel = state
+        .services
+        .notifications
+        .get_channel(log.channel_id)
+        .await?;
+
+    // Verify ownership
+    if channel.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    Ok(Json(LogResponse::from(log)))
+}
+
+/// POST /api/notifications/logs/{id}/resend - Resend a logged notification
+///
+/// Sends the logged message through its channel again, bypassing dedup,
+/// rate limits and digests. Only the owner of the log's channel can resend.
+#[utoipa::path(
+    post,
+    path = "/logs/{id}/resend",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i64, Path, description = "Log ID")
+    ),
+    responses(
+        (status = 200, description = "Notification resent", body = LogResponse),
+        (status = 400, description = "Channel disabled or message unreadable"),
+        (status = 404, description = "Log not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn resend_log(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> AppResult<Json<LogResponse>> {
+    let log = state.services.notifications.get_log(id).await?;
+    let channel = state
+        .services
+        .notifications
+        .get_channel(log.channel_id)
+        .await?;
+
+    // Verify ownership
+    if channel.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
     }
+
+    let resent = state.services.notifications.resend_log(id).await?;
+    Ok(Json(LogResponse::from(resent)))
+}
+
+// ============================================================================
+// Template Handlers
+// ============================================================================
+
+/// GET /api/notifications/templates - List templates
+///
+/// Returns the authenticated user's templates together with the built-in
+/// system templates.
+#[utoipa::path(
+    get,
+    path = "/templates",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 200, description = "List of templates", body = Vec<TemplateResponse>)
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn list_templates(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<TemplateResponse>>> {
+    let templates = state
+        .services
+        .notifications
+        .list_templates(auth_user.user_id)
+        .await?;
+
+    let responses: Vec<TemplateResponse> =
+        templates.into_iter().map(TemplateResponse::from).collect();
+    Ok(Json(responses))
+}
+
+/// POST /api/notifications/templates - Create template
+///
+/// Creates a template owned by the authenticated user. A user template
+/// overrides the system template with the same key.
+#[utoipa::path(
+    post,
+    path = "/templates",
+    tag = NOTIFICATION_TAG,
+    request_body = CreateTemplateRequest,
+    responses(
+        (status = 201, description = "Template created", body = TemplateResponse),
+        (status = 400, description = "Invalid template"),
+        (status = 409, description = "Template key already exists")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<CreateTemplateRequest>,
+) -> AppResult<(StatusCode, Json<TemplateResponse>)> {
+    let new_template = NewNotificationTemplate {
+        user_id: Some(auth_user.user_id),
+        template_key: payload.template_key,
+        description: payload.description,
+        title_template: payload.title_template,
+        body_template: payload.body_template,
+        variants: variants_to_json(&payload.variants)?,
+    };
+
+    let template = state
+        .services
+        .notifications
+        .create_template(new_template)
+        .await?;
+    Ok((StatusCode::CREATED, Json(TemplateResponse::from(template))))
+}
+
+/// GET /api/notifications/templates/:id - Get template by ID
+///
+/// Retrieves a template owned by the user or a system template.
+#[utoipa::path(
+    get,
+    path = "/templates/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Template ID")
+    ),
+    responses(
+        (status = 200, description = "Template found", body = TemplateResponse),
+        (status = 404, description = "Template not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<TemplateResponse>> {
+    let template = state.services.notifications.get_template(id).await?;
+
+    // System templates are visible to everyone
+    if template
+        .user_id
+        .is_some_and(|owner| owner != auth_user.user_id)
+    {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    Ok(Json(TemplateResponse::from(template)))
+}
+
+/// PUT /api/notifications/templates/:id - Update template
+///
+/// Updates a template owned by the authenticated user.
+/// System templates are read-only.
+#[utoipa::path(
+    put,
+    path = "/templates/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Template ID")
+    ),
+    request_body = UpdateTemplateRequest,
+    responses(
+        (status = 200, description = "Template updated", body = TemplateResponse),
+        (status = 400, description = "Invalid template"),
+        (status = 404, description = "Template not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn update_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateTemplateRequest>,
+) -> AppResult<Json<TemplateResponse>> {
+    let template = state.services.notifications.get_template(id).await?;
+
+    // Verify ownership (system templates are rejected by the service)
+    if template
+        .user_id
+        .is_some_and(|owner| owner != auth_user.user_id)
+    {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let update_data = UpdateNotificationTemplate {
+        description: payload.description,
+        title_template: payload.title_template,
+        body_template: payload.body_template,
+        variants: payload
+            .variants
+            .as_ref()
+            .map(variants_to_json)
+            .transpose()?,
+    };
+
+    let updated = state
+        .services
+        .notifications
+        .update_template(id, update_data)
+        .await?;
+    Ok(Json(TemplateResponse::from(updated)))
+}
+
+/// DELETE /api/notifications/templates/:id - Delete template
+///
+/// Deletes a template owned by the authenticated user.
+#[utoipa::path(
+    delete,
+    path = "/templates/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Template ID")
+    ),
+    responses(
+        (status = 204, description = "Template deleted"),
+        (status = 404, description = "Template not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    let template = state.services.notifications.get_template(id).await?;
+
+    // Verify ownership; system templates cannot be deleted
+    if template.user_id != Some(auth_user.user_id) {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let deleted = state.services.notifications.delete_template(id).await?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound {
+            entity: "notification_template".to_string(),
+            field: "id".to_string(),
+            value: id.to_string(),
+        })
+    }
+}
+
+/// POST /api/notifications/templates/preview - Preview template
+///
+/// Renders a stored template (by key) or an inline draft with the given
+/// variables, without sending anything. When `channel_type` is set, the
+/// variant that channel would receive is returned.
+#[utoipa::path(
+    post,
+    path = "/templates/preview",
+    tag = NOTIFICATION_TAG,
+    request_body = PreviewTemplateRequest,
+    responses(
+        (status = 200, description = "Rendered preview", body = PreviewTemplateResponse),
+        (status = 400, description = "Invalid template"),
+        (status = 404, description = "Template not found")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn preview_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<PreviewTemplateRequest>,
+) -> AppResult<Json<PreviewTemplateResponse>> {
+    let template = match (payload.template_key, payload.body_template) {
+        (Some(key), _) => {
+            let stored = state
+                .services
+                .notifications
+                .find_template_by_key(auth_user.user_id, &key)
+                .await?;
+            MessageTemplate::from_model(&stored)?
+        }
+        (None, Some(body)) => {
+            MessageTemplate::compile(payload.title_template.as_deref(), &body, &payload.variants)?
+        }
+        (None, None) => {
+            return Err(AppError::BadRequest {
+                message: "Either template_key or body_template is required".to_string(),
+            });
+        }
+    };
+
+    let missing_variables = template.missing_variables(&payload.variables);
+    let mut message = template.render(&payload.variables);
+    if let Some(channel_type) = payload.channel_type {
+        message = message.for_channel(channel_type);
+    }
+
+    Ok(Json(PreviewTemplateResponse {
+        title: message.title,
+        body: message.body,
+        format: message.format,
+        missing_variables,
+    }))
+}
+
+/// Serializes typed template variants for the JSONB column
+fn variants_to_json(variants: &TemplateVariants) -> AppResult<serde_json::Value> {
+    serde_json::to_value(variants).map_err(|e| AppError::Internal {
+        source: anyhow::Error::from(e),
+    })
+}
+
+// ============================================================================
+// Routing Handlers
+// ============================================================================
+
+/// GET /api/notifications/routing/rules - List routing rules
+///
+/// Returns the authenticated user's routing rules in evaluation order.
+#[utoipa::path(
+    get,
+    path = "/routing/rules",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 200, description = "List of routing rules", body = Vec<RoutingRuleResponse>)
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn list_routing_rules(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<RoutingRuleResponse>>> {
+    let rules = state
+        .services
+        .notifications
+        .list_routing_rules(auth_user.user_id)
+        .await?;
+
+    let responses: Vec<RoutingRuleResponse> =
+        rules.into_iter().map(RoutingRuleResponse::from).collect();
+    Ok(Json(responses))
+}
+
+/// POST /api/notifications/routing/rules - Create routing rule
+///
+/// Creates a routing rule for the authenticated user. Target channels
+/// must belong to the user.
+#[utoipa::path(
+    post,
+    path = "/routing/rules",
+    tag = NOTIFICATION_TAG,
+    request_body = CreateRoutingRuleRequest,
+    responses(
+        (status = 201, description = "Routing rule created", body = RoutingRuleResponse),
+        (status = 400, description = "Invalid request")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_routing_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<CreateRoutingRuleRequest>,
+) -> AppResult<(StatusCode, Json<RoutingRuleResponse>)> {
+    let new_rule = NewNotificationRoutingRule {
+        user_id: auth_user.user_id,
+        name: payload.name,
+        priority: payload.priority,
+        enabled: payload.enabled,
+        match_tags: payload.match_tags,
+        match_metadata: metadata_to_json(payload.match_metadata),
+        min_severity: payload.min_severity,
+        channel_ids: payload.channel_ids,
+        mode: payload.mode,
+    };
+
+    let rule = state
+        .services
+        .notifications
+        .create_routing_rule(new_rule)
+        .await?;
+    Ok((StatusCode::CREATED, Json(RoutingRuleResponse::from(rule))))
+}
+
+/// GET /api/notifications/routing/rules/:id - Get routing rule
+///
+/// Only the rule owner can access it.
+#[utoipa::path(
+    get,
+    path = "/routing/rules/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Routing rule ID")
+    ),
+    responses(
+        (status = 200, description = "Routing rule found", body = RoutingRuleResponse),
+        (status = 404, description = "Routing rule not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_routing_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<RoutingRuleResponse>> {
+    let rule = state.services.notifications.get_routing_rule(id).await?;
+
+    // Verify ownership
+    if rule.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    Ok(Json(RoutingRuleResponse::from(rule)))
+}
+
+/// PUT /api/notifications/routing/rules/:id - Update routing rule
+///
+/// Only the rule owner can update it.
+#[utoipa::path(
+    put,
+    path = "/routing/rules/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Routing rule ID")
+    ),
+    request_body = UpdateRoutingRuleRequest,
+    responses(
+        (status = 200, description = "Routing rule updated", body = RoutingRuleResponse),
+        (status = 404, description = "Routing rule not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn update_routing_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateRoutingRuleRequest>,
+) -> AppResult<Json<RoutingRuleResponse>> {
+    let rule = state.services.notifications.get_routing_rule(id).await?;
+
+    // Verify ownership
+    if rule.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let update_data = UpdateNotificationRoutingRule {
+        name: payload.name,
+        priority: payload.priority,
+        enabled: payload.enabled,
+        match_tags: payload.match_tags,
+        match_metadata: payload.match_metadata.map(metadata_to_json),
+        min_severity: payload.min_severity,
+        channel_ids: payload.channel_ids,
+        mode: payload.mode,
+    };
+
+    let updated = state
+        .services
+        .notifications
+        .update_routing_rule(id, update_data)
+        .await?;
+    Ok(Json(RoutingRuleResponse::from(updated)))
+}
+
+/// DELETE /api/notifications/routing/rules/:id - Delete routing rule
+///
+/// Only the rule owner can delete it.
+#[utoipa::path(
+    delete,
+    path = "/routing/rules/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Routing rule ID")
+    ),
+    responses(
+        (status = 204, description = "Routing rule deleted"),
+        (status = 404, description = "Routing rule not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_routing_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    let rule = state.services.notifications.get_routing_rule(id).await?;
+
+    // Verify ownership
+    if rule.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    let deleted = state.services.notifications.delete_routing_rule(id).await?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound {
+            entity: "notification_routing_rule".to_string(),
+            field: "id".to_string(),
+            value: id.to_string(),
+        })
+    }
+}
+
+/// GET /api/notifications/routing/quiet-hours - Get quiet hours
+///
+/// Returns the authenticated user's quiet hours.
+#[utoipa::path(
+    get,
+    path = "/routing/quiet-hours",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 200, description = "Quiet hours", body = QuietHoursResponse),
+        (status = 404, description = "Quiet hours not configured")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_quiet_hours(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> AppResult<Json<QuietHoursResponse>> {
+    let quiet_hours = state
+        .services
+        .notifications
+        .get_quiet_hours(auth_user.user_id)
+        .await?;
+    Ok(Json(QuietHoursResponse::from(quiet_hours)))
+}
+
+/// PUT /api/notifications/routing/quiet-hours - Set quiet hours
+///
+/// Creates or replaces the authenticated user's quiet hours.
+#[utoipa::path(
+    put,
+    path = "/routing/quiet-hours",
+    tag = NOTIFICATION_TAG,
+    request_body = QuietHoursRequest,
+    responses(
+        (status = 200, description = "Quiet hours saved", body = QuietHoursResponse),
+        (status = 400, description = "Invalid time or timezone")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn set_quiet_hours(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<QuietHoursRequest>,
+) -> AppResult<Json<QuietHoursResponse>> {
+    let quiet_hours = NewNotificationQuietHours {
+        user_id: auth_user.user_id,
+        start_time: parse_time_of_day("start_time", &payload.start_time)?.into(),
+        end_time: parse_time_of_day("end_time", &payload.end_time)?.into(),
+        timezone: payload.timezone,
+        bypass_severity: payload.bypass_severity,
+        action: payload.action,
+        enabled: payload.enabled,
+    };
+
+    let saved = state
+        .services
+        .notifications
+        .set_quiet_hours(quiet_hours)
+        .await?;
+    Ok(Json(QuietHoursResponse::from(saved)))
+}
+
+/// DELETE /api/notifications/routing/quiet-hours - Remove quiet hours
+#[utoipa::path(
+    delete,
+    path = "/routing/quiet-hours",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 204, description = "Quiet hours removed"),
+        (status = 404, description = "Quiet hours not configured")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_quiet_hours(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> AppResult<StatusCode> {
+    let deleted = state
+        .services
+        .notifications
+        .delete_quiet_hours(auth_user.user_id)
+        .await?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound {
+            entity: "notification_quiet_hours".to_string(),
+            field: "user_id".to_string(),
+            value: auth_user.user_id.to_string(),
+        })
+    }
+}
+
+// ============================================================================
+// Group and Broadcast Handlers (administrators only)
+// ============================================================================
+
+/// GET /api/notifications/groups - List user groups
+#[utoipa::path(
+    get,
+    path = "/groups",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 200, description = "List of groups", body = Vec<GroupResponse>),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn list_groups(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<GroupResponse>>> {
+    require_admin(&state, &auth_user).await?;
+
+    let notifications = &state.services.notifications;
+    let mut responses = Vec::new();
+    for group in notifications.list_groups().await? {
+        let members = notifications.get_group_members(group.id).await?;
+        responses.push(GroupResponse::new(group, members));
+    }
+    Ok(Json(responses))
+}
+
+/// POST /api/notifications/groups - Create user group
+#[utoipa::path(
+    post,
+    path = "/groups",
+    tag = NOTIFICATION_TAG,
+    request_body = CreateGroupRequest,
+    responses(
+        (status = 201, description = "Group created", body = GroupResponse),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_group(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<CreateGroupRequest>,
+) -> AppResult<(StatusCode, Json<GroupResponse>)> {
+    require_admin(&state, &auth_user).await?;
+
+    let new_group = NewUserGroup {
+        name: payload.name,
+        description: payload.description,
+    };
+
+    let notifications = &state.services.notifications;
+    let group = notifications
+        .create_group(new_group, &payload.user_ids)
+        .await?;
+    let members = notifications.get_group_members(group.id).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(GroupResponse::new(group, members)),
+    ))
+}
+
+/// GET /api/notifications/groups/:id - Get user group
+#[utoipa::path(
+    get,
+    path = "/groups/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Group ID")
+    ),
+    responses(
+        (status = 200, description = "Group found", body = GroupResponse),
+        (status = 404, description = "Group not found"),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_group(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<GroupResponse>> {
+    require_admin(&state, &auth_user).await?;
+
+    let notifications = &state.services.notifications;
+    let group = notifications.get_group(id).await?;
+    let members = notifications.get_group_members(id).await?;
+    Ok(Json(GroupResponse::new(group, members)))
+}
+
+/// PUT /api/notifications/groups/:id/members - Replace group members
+#[utoipa::path(
+    put,
+    path = "/groups/{id}/members",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Group ID")
+    ),
+    request_body = SetGroupMembersRequest,
+    responses(
+        (status = 200, description = "Members updated", body = GroupResponse),
+        (status = 404, description = "Group not found"),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn set_group_members(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<SetGroupMembersRequest>,
+) -> AppResult<Json<GroupResponse>> {
+    require_admin(&state, &auth_user).await?;
+
+    let notifications = &state.services.notifications;
+    notifications
+        .set_group_members(id, &payload.user_ids)
+        .await?;
+    let group = notifications.get_group(id).await?;
+    let members = notifications.get_group_members(id).await?;
+    Ok(Json(GroupResponse::new(group, members)))
+}
+
+/// DELETE /api/notifications/groups/:id - Delete user group
+#[utoipa::path(
+    delete,
+    path = "/groups/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Group ID")
+    ),
+    responses(
+        (status = 204, description = "Group deleted"),
+        (status = 404, description = "Group not found"),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_group(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    require_admin(&state, &auth_user).await?;
+
+    let deleted = state.services.notifications.delete_group(id).await?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound {
+            entity: "user_group".to_string(),
+            field: "id".to_string(),
+            value: id.to_string(),
+        })
+    }
+}
+
+/// GET /api/notifications/broadcasts - List broadcasts
+#[utoipa::path(
+    get,
+    path = "/broadcasts",
+    tag = NOTIFICATION_TAG,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Paginated list of broadcasts", body = PagedResponse<BroadcastResponse>),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn list_broadcasts(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(params): ValidatedQuery<PaginationParams>,
+) -> AppResult<Json<PagedResponse<BroadcastResponse>>> {
+    require_admin(&state, &auth_user).await?;
+    let params = params.normalize();
+
+    let (broadcasts, total_count) = state
+        .services
+        .notifications
+        .list_broadcasts(params.offset() as i64, params.limit() as i64)
+        .await?;
+
+    let responses: Vec<BroadcastResponse> = broadcasts
+        .into_iter()
+        .map(BroadcastResponse::from)
+        .collect();
+    Ok(Json(PagedResponse::new(
+        responses,
+        &params,
+        total_count as u64,
+    )))
+}
+
+/// POST /api/notifications/broadcasts - Broadcast a notification
+///
+/// Queues the message for every member of a group, or for every user when
+/// no group is given. Each recipient receives it through their own routing
+/// rules and quiet hours; delivery progress is reported on the broadcast.
+#[utoipa::path(
+    post,
+    path = "/broadcasts",
+    tag = NOTIFICATION_TAG,
+    request_body = CreateBroadcastRequest,
+    responses(
+        (status = 202, description = "Broadcast queued", body = BroadcastResponse),
+        (status = 404, description = "Group not found"),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_broadcast(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<CreateBroadcastRequest>,
+) -> AppResult<(StatusCode, Json<BroadcastResponse>)> {
+    require_admin(&state, &auth_user).await?;
+
+    let message = NotificationMessage {
+        title: payload.title,
+        body: payload.body,
+        metadata: payload.metadata,
+        severity: payload.severity,
+        tags: payload.tags,
+        image_url: payload.image_url,
+        click_url: payload.click_url,
+        attachments: payload.attachments,
+        ..Default::default()
+    };
+
+    let broadcast = state
+        .services
+        .notifications
+        .create_broadcast(auth_user.user_id, payload.group_id, message)
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(BroadcastResponse::from(broadcast)),
+    ))
+}
+
+/// GET /api/notifications/broadcasts/:id - Get broadcast with delivery stats
+#[utoipa::path(
+    get,
+    path = "/broadcasts/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "Broadcast ID")
+    ),
+    responses(
+        (status = 200, description = "Broadcast found", body = BroadcastResponse),
+        (status = 404, description = "Broadcast not found"),
+        (status = 403, description = "Administrator access required")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_broadcast(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<BroadcastResponse>> {
+    require_admin(&state, &auth_user).await?;
+
+    let broadcast = state.services.notifications.get_broadcast(id).await?;
+    Ok(Json(BroadcastResponse::from(broadcast)))
+}
+
+// ============================================================================
+// API Key Handlers
+// ============================================================================
+
+/// GET /api/notifications/api-keys - List API keys
+#[utoipa::path(
+    get,
+    path = "/api-keys",
+    tag = NOTIFICATION_TAG,
+    responses(
+        (status = 200, description = "List of API keys", body = Vec<ApiKeyResponse>)
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn list_api_keys(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<ApiKeyResponse>>> {
+    let keys = state
+        .services
+        .notifications
+        .list_api_keys(auth_user.user_id)
+        .await?;
+
+    let responses: Vec<ApiKeyResponse> = keys.into_iter().map(ApiKeyResponse::from).collect();
+    Ok(Json(responses))
+}
+
+/// POST /api/notifications/api-keys - Create API key
+///
+/// Creates a key that external systems can send in the `X-API-Key` header
+/// to call `POST /api/notifications/send` as the authenticated user. The
+/// full key is only returned in this response.
+#[utoipa::path(
+    post,
+    path = "/api-keys",
+    tag = NOTIFICATION_TAG,
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "API key created", body = CreatedApiKeyResponse),
+        (status = 400, description = "Invalid allow-list entry"),
+        (status = 409, description = "A key with this name already exists")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(payload): ValidatedJson<CreateApiKeyRequest>,
+) -> AppResult<(StatusCode, Json<CreatedApiKeyResponse>)> {
+    let (api_key, key) = state
+        .services
+        .notifications
+        .create_api_key(
+            auth_user.user_id,
+            payload.name,
+            payload.allowed_ips,
+            payload.rate_limit_count,
+            payload.rate_limit_window_seconds,
+            payload.expires_at.map(to_db_datetime),
+        )
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedApiKeyResponse {
+            key,
+            api_key: ApiKeyResponse::from(api_key),
+        }),
+    ))
+}
+
+/// DELETE /api/notifications/api-keys/:id - Revoke API key
+#[utoipa::path(
+    delete,
+    path = "/api-keys/{id}",
+    tag = NOTIFICATION_TAG,
+    params(
+        ("id" = i32, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 404, description = "API key not found"),
+        (status = 403, description = "Access denied")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_api_key(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    let notifications = &state.services.notifications;
+    let api_key = notifications.get_api_key(id).await?;
+    if api_key.user_id != auth_user.user_id {
+        return Err(AppError::Forbidden {
+            message: "Access denied".to_string(),
+        });
+    }
+
+    notifications.delete_api_key(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Rejects callers that are not administrators
+async fn require_admin(state: &AppState, auth_user: &AuthUser) -> AppResult<()> {
+    let user = state.services.users.get_user(auth_user.user_id).await?;
+    if !user.is_admin {
+        return Err(AppError::Forbidden {
+            message: "Administrator access required".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Converts metadata match conditions into the JSONB column value
+fn metadata_to_json(metadata: std::collections::HashMap<String, String>) -> serde_json::Value {
+    serde_json::Value::Object(
+        metadata
+            .into_iter()
+            .map(|(k, v)| (k, serde_json::Value::String(v)))
+            .collect(),
+    )
+}
+
+/// Parses a wall-clock time such as "22:00" or "07:30:00"
+fn parse_time_of_day(field: &str, value: &str) -> AppResult<jiff::civil::Time> {
+    value.parse().map_err(|e| AppError::Validation {
+        field: field.to_string(),
+        reason: format!("Invalid time '{}': {}", value, e),
+    })
+}
+
+/// Converts an instant into the UTC datetime stored in TIMESTAMP columns
+fn to_db_datetime(ts: jiff::Timestamp) -> jiff_diesel::DateTime {
+    jiff_diesel::DateTime::from(ts.to_zoned(jiff::tz::TimeZone::UTC).datetime())
 }
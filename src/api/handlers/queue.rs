@@ -0,0 +1,173 @@
+//! Job queue request handlers.
+
+use crate::api::doc::JOB_TAG;
+use crate::api::dto::{
+    EnqueueTaskRequest, QueueListParams, QueuedTaskDetailResponse, QueuedTaskResponse,
+};
+use crate::api::handlers::jobs::require_scheduler;
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_axum::routes;
+
+/// Creates job queue-related routes.
+pub fn queue_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_queued_tasks))
+        .routes(routes!(enqueue_task))
+        .routes(routes!(get_queued_task))
+        .routes(routes!(delete_queued_task))
+        .routes(routes!(requeue_task))
+}
+
+/// GET /api/jobs/queue - List queued tasks
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = JOB_TAG,
+    params(QueueListParams),
+    responses(
+        (status = 200, description = "List queued tasks by page, newest first", body = Vec<QueuedTaskResponse>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn list_queued_tasks(
+    State(state): State<AppState>,
+    ValidatedQuery(params): ValidatedQuery<QueueListParams>,
+) -> AppResult<Json<Vec<QueuedTaskResponse>>> {
+    let pagination = params.pagination().normalize();
+    let tasks = state
+        .services
+        .jobs
+        .list_queued_tasks(
+            params.status,
+            pagination.limit() as i64,
+            pagination.offset() as i64,
+        )
+        .await?;
+
+    let responses: Vec<QueuedTaskResponse> =
+        tasks.into_iter().map(QueuedTaskResponse::from).collect();
+
+    Ok(Json(responses))
+}
+
+/// POST /api/jobs/queue - Enqueue a one-shot task
+///
+/// The task runs once, as soon as possible or at the requested time, on
+/// whichever replica claims it first. Failed attempts are retried with
+/// exponential backoff until `max_attempts`, after which the task is kept
+/// as a dead letter.
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = JOB_TAG,
+    request_body = EnqueueTaskRequest,
+    responses(
+        (status = 201, description = "Task enqueued", body = QueuedTaskResponse),
+        (status = 400, description = "Unknown job type, invalid payload or run time"),
+        (status = 422, description = "Scheduler disabled")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn enqueue_task(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<EnqueueTaskRequest>,
+) -> AppResult<(StatusCode, Json<QueuedTaskResponse>)> {
+    let scheduler = require_scheduler(&state)?;
+    let task = req.into_new_task(jiff::Timestamp::now())?;
+    let task = scheduler.enqueue(task).await?;
+
+    Ok((StatusCode::CREATED, Json(QueuedTaskResponse::from(task))))
+}
+
+/// GET /api/jobs/queue/:id - Get queued task by ID
+///
+/// Includes the task's progress and the log lines of all its attempts.
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = JOB_TAG,
+    params(
+        ("id" = i64, Path, description = "Queued task ID")
+    ),
+    responses(
+        (status = 200, description = "Queued task found", body = QueuedTaskDetailResponse),
+        (status = 404, description = "Queued task not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn get_queued_task(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> AppResult<Json<QueuedTaskDetailResponse>> {
+    let (task, logs) = state.services.jobs.get_queued_task(id).await?;
+    Ok(Json(QueuedTaskDetailResponse::new(task, logs)))
+}
+
+/// DELETE /api/jobs/queue/:id - Delete a queued task
+///
+/// Removes a pending, finished or dead task. Running tasks cannot be
+/// deleted.
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = JOB_TAG,
+    params(
+        ("id" = i64, Path, description = "Queued task ID")
+    ),
+    responses(
+        (status = 204, description = "Queued task deleted"),
+        (status = 404, description = "Queued task not found"),
+        (status = 422, description = "Task is running")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn delete_queued_task(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> AppResult<StatusCode> {
+    state.services.jobs.delete_queued_task(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/jobs/queue/:id/requeue - Requeue a dead task
+///
+/// Resets the attempt count and makes the task due immediately.
+#[utoipa::path(
+    post,
+    path = "/{id}/requeue",
+    tag = JOB_TAG,
+    params(
+        ("id" = i64, Path, description = "Queued task ID")
+    ),
+    responses(
+        (status = 200, description = "Task requeued", body = QueuedTaskResponse),
+        (status = 404, description = "Queued task not found"),
+        (status = 422, description = "Task is not dead")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn requeue_task(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> AppResult<Json<QueuedTaskResponse>> {
+    let task = state.services.jobs.requeue_task(id).await?;
+    Ok(Json(QueuedTaskResponse::from(task)))
+}
@@ -0,0 +1,261 @@
+//! Job workflow request handlers.
+
+use crate::api::doc::JOB_TAG;
+use crate::api::dto::{
+    CreateWorkflowRequest, PaginationParams, UpdateWorkflowRequest, WorkflowResponse,
+    WorkflowRunGraphResponse, WorkflowRunResponse,
+};
+use crate::api::handlers::jobs::require_scheduler;
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_axum::routes;
+use uuid::Uuid;
+
+/// Creates workflow-related routes.
+pub fn workflow_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_workflows))
+        .routes(routes!(create_workflow))
+        .routes(routes!(get_workflow))
+        .routes(routes!(update_workflow))
+        .routes(routes!(delete_workflow))
+        .routes(routes!(run_workflow))
+        .routes(routes!(list_workflow_runs))
+        .routes(routes!(get_workflow_run))
+}
+
+/// GET /api/jobs/workflows - List workflows
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = JOB_TAG,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "List workflows by page", body = Vec<WorkflowResponse>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn list_workflows(
+    State(state): State<AppState>,
+    ValidatedQuery(params): ValidatedQuery<PaginationParams>,
+) -> AppResult<Json<Vec<WorkflowResponse>>> {
+    let params = params.normalize();
+    let workflows = state
+        .services
+        .jobs
+        .list_workflows(params.limit() as i64, params.offset() as i64)
+        .await?;
+
+    let responses: Vec<WorkflowResponse> = workflows
+        .into_iter()
+        .map(|(workflow, steps)| WorkflowResponse::new(workflow, steps))
+        .collect();
+
+    Ok(Json(responses))
+}
+
+/// POST /api/jobs/workflows - Create a workflow
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = JOB_TAG,
+    request_body = CreateWorkflowRequest,
+    responses(
+        (status = 201, description = "Workflow created successfully", body = WorkflowResponse),
+        (status = 400, description = "Invalid steps or unknown job"),
+        (status = 409, description = "Workflow name already exists")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn create_workflow(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<CreateWorkflowRequest>,
+) -> AppResult<(StatusCode, Json<WorkflowResponse>)> {
+    let (workflow, steps) = req.into_parts();
+    let (workflow, steps) = state.services.jobs.create_workflow(workflow, steps).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(WorkflowResponse::new(workflow, steps)),
+    ))
+}
+
+/// GET /api/jobs/workflows/:id - Get workflow by ID
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Workflow ID")
+    ),
+    responses(
+        (status = 200, description = "Workflow found", body = WorkflowResponse),
+        (status = 404, description = "Workflow not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn get_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<WorkflowResponse>> {
+    let (workflow, steps) = state.services.jobs.get_workflow(id).await?;
+    Ok(Json(WorkflowResponse::new(workflow, steps)))
+}
+
+/// PUT /api/jobs/workflows/:id - Update workflow by ID
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Workflow ID")
+    ),
+    request_body = UpdateWorkflowRequest,
+    responses(
+        (status = 200, description = "Workflow updated successfully", body = WorkflowResponse),
+        (status = 400, description = "Invalid steps or unknown job"),
+        (status = 404, description = "Workflow not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn update_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<UpdateWorkflowRequest>,
+) -> AppResult<Json<WorkflowResponse>> {
+    let (description, steps) = req.into_parts();
+    let (workflow, steps) = state
+        .services
+        .jobs
+        .update_workflow(id, description, steps)
+        .await?;
+
+    Ok(Json(WorkflowResponse::new(workflow, steps)))
+}
+
+/// DELETE /api/jobs/workflows/:id - Delete workflow by ID
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Workflow ID")
+    ),
+    responses(
+        (status = 204, description = "Workflow deleted successfully"),
+        (status = 404, description = "Workflow not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn delete_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    state.services.jobs.delete_workflow(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/jobs/workflows/:id/run - Run a workflow immediately
+///
+/// Steps start in the background; the returned run id can be used to
+/// follow the run graph.
+#[utoipa::path(
+    post,
+    path = "/{id}/run",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Workflow ID")
+    ),
+    responses(
+        (status = 202, description = "Workflow run started", body = WorkflowRunResponse),
+        (status = 404, description = "Workflow not found"),
+        (status = 422, description = "Scheduler disabled")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn run_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<(StatusCode, Json<WorkflowRunResponse>)> {
+    let scheduler = require_scheduler(&state)?;
+    let run = scheduler.run_workflow(id).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(WorkflowRunResponse::from(run))))
+}
+
+/// GET /api/jobs/workflows/:id/runs - List runs of a workflow
+#[utoipa::path(
+    get,
+    path = "/{id}/runs",
+    tag = JOB_TAG,
+    params(
+        ("id" = i32, Path, description = "Workflow ID"),
+        PaginationParams
+    ),
+    responses(
+        (status = 200, description = "List workflow runs, newest first", body = Vec<WorkflowRunResponse>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn list_workflow_runs(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    ValidatedQuery(params): ValidatedQuery<PaginationParams>,
+) -> AppResult<Json<Vec<WorkflowRunResponse>>> {
+    let params = params.normalize();
+    let runs = state
+        .services
+        .jobs
+        .list_workflow_runs(id, params.limit() as i64, params.offset() as i64)
+        .await?;
+
+    let responses: Vec<WorkflowRunResponse> =
+        runs.into_iter().map(WorkflowRunResponse::from).collect();
+
+    Ok(Json(responses))
+}
+
+/// GET /api/jobs/workflows/runs/:run_id - Get the step graph of a workflow run
+#[utoipa::path(
+    get,
+    path = "/runs/{run_id}",
+    tag = JOB_TAG,
+    params(
+        ("run_id" = String, Path, description = "Workflow run ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Run with the state of each step", body = WorkflowRunGraphResponse),
+        (status = 404, description = "Workflow run not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+async fn get_workflow_run(
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<WorkflowRunGraphResponse>> {
+    let (run, steps) = state.services.jobs.get_workflow_run(run_id).await?;
+    Ok(Json(WorkflowRunGraphResponse::new(run, steps)))
+}
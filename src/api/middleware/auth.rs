@@ -1,10 +1,14 @@
 //! JWT authentication middleware.
 //!
 //! Provides middleware for validating JWT tokens and extracting user claims.
+//! Notification API keys are accepted as well, but only for sending
+//! notifications.
+
+use std::net::{IpAddr, SocketAddr};
 
 use axum::{
-    extract::{Request, State},
-    http::header,
+    extract::{ConnectInfo, OriginalUri, Request, State},
+    http::{Method, header},
     middleware::Next,
     response::Response,
 };
@@ -38,13 +42,32 @@ impl From<Claims> for AuthUser {
     }
 }
 
+/// Header carrying a notification API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// The only endpoint notification API keys may call
+const API_KEY_ENDPOINT: &str = "/api/notifications/send";
+
+/// Checks whether a request may be authenticated with an API key
+///
+/// # Arguments
+/// * `method` - The request method
+/// * `path` - The full request path
+pub fn api_key_allowed(method: &Method, path: &str) -> bool {
+    method == Method::POST && path.trim_end_matches('/') == API_KEY_ENDPOINT
+}
+
 /// JWT authentication middleware
 ///
 /// Validates the JWT token from the Authorization header and adds
 /// the authenticated user information to request extensions.
 ///
+/// Requests carrying an `X-API-Key` header are authenticated with the
+/// notification API key instead; such keys may only call
+/// `POST /api/notifications/send`.
+///
 /// # Headers
-/// Expects: `Authorization: Bearer <token>`
+/// Expects: `Authorization: Bearer <token>` or `X-API-Key: <key>`
 ///
 /// # Errors
 /// Returns 401 Unauthorized if:
@@ -52,6 +75,11 @@ impl From<Claims> for AuthUser {
 /// - Token format is invalid
 /// - Token validation fails
 /// - Token has expired
+/// - The API key is unknown or expired
+///
+/// Returns 403 Forbidden if an API key is used for another endpoint or
+/// from an address outside its allow-list, and 429 Too Many Requests if
+/// the key's rate limit is exceeded.
 ///
 /// # Example
 /// ```ignore
@@ -64,6 +92,33 @@ pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
+    if let Some(api_key) = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_owned)
+    {
+        // Routers are nested, so the request URI no longer holds the full path
+        let path = request
+            .extensions()
+            .get::<OriginalUri>()
+            .map_or_else(|| request.uri().path(), |uri| uri.path());
+        if !api_key_allowed(request.method(), path) {
+            return Err(AppError::Forbidden {
+                message: format!("API keys may only be used for POST {}", API_KEY_ENDPOINT),
+            });
+        }
+
+        let client_ip = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        let auth_user = authenticate_api_key(&state, &api_key, client_ip).await?;
+        request.extensions_mut().insert(auth_user);
+        return Ok(next.run(request).await);
+    }
+
     // Extract Authorization header
     let auth_header = request
         .headers()
@@ -91,6 +146,26 @@ pub async fn auth_middleware(
     Ok(next.run(request).await)
 }
 
+/// Authenticates a request made with a notification API key
+async fn authenticate_api_key(
+    state: &AppState,
+    api_key: &str,
+    client_ip: Option<IpAddr>,
+) -> Result<AuthUser, AppError> {
+    let key = state
+        .services
+        .notifications
+        .authenticate_api_key(api_key, client_ip)
+        .await?;
+    let user = state.services.users.get_user(key.user_id).await?;
+
+    Ok(AuthUser {
+        user_id: user.id,
+        email: user.email,
+        username: user.username,
+    })
+}
+
 /// Optional JWT authentication middleware
 ///
 /// Similar to `auth_middleware` but doesn't fail if the token is missing.
@@ -170,6 +245,22 @@ mod tests {
         assert_eq!(auth_user.user_id, 0); // Falls back to 0 on parse error
     }
 
+    #[test]
+    fn test_api_keys_only_allowed_for_send() {
+        assert!(api_key_allowed(&Method::POST, "/api/notifications/send"));
+        assert!(api_key_allowed(&Method::POST, "/api/notifications/send/"));
+        assert!(!api_key_allowed(&Method::GET, "/api/notifications/send"));
+        assert!(!api_key_allowed(
+            &Method::POST,
+            "/api/notifications/dispatch"
+        ));
+        assert!(!api_key_allowed(
+            &Method::POST,
+            "/api/notifications/channels/1/send"
+        ));
+        assert!(!api_key_allowed(&Method::DELETE, "/api/users/1"));
+    }
+
     #[test]
     fn test_generate_valid_token() {
         let config = create_test_jwt_config();
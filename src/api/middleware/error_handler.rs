@@ -26,10 +26,12 @@ impl IntoResponse for AppError {
     /// - UnprocessableContent → 422 UNPROCESSABLE_ENTITY
     /// - Unauthorized → 401 UNAUTHORIZED
     /// - Forbidden → 403 FORBIDDEN
+    /// - TooManyRequests → 429 TOO_MANY_REQUESTS
     /// - Database → 500 INTERNAL_SERVER_ERROR
     /// - Configuration → 500 INTERNAL_SERVER_ERROR
     /// - ConnectionPool → 503 SERVICE_UNAVAILABLE
     /// - Internal → 500 INTERNAL_SERVER_ERROR
+    /// - Permanent → same as the wrapped error
     ///
     /// # Requirements
     /// - 4.1-4.10: Convert AppError variants to appropriate HTTP status codes
@@ -37,6 +39,10 @@ impl IntoResponse for AppError {
     /// - 6.4: Sanitize error messages for external responses
     /// - 7.3-7.4: Include request ID when available for correlation
     fn into_response(self) -> Response {
+        if let AppError::Permanent { source } = self {
+            return source.into_response();
+        }
+
         let (status, error_response) = match &self {
             AppError::NotFound {
                 entity,
@@ -96,6 +102,10 @@ impl IntoResponse for AppError {
                 StatusCode::FORBIDDEN,
                 ErrorResponse::new("FORBIDDEN", message),
             ),
+            AppError::TooManyRequests { message } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorResponse::new("TOO_MANY_REQUESTS", message),
+            ),
             AppError::Database { operation, source } => {
                 // Log 500 error with full details including error chain
                 // Note: Set RUST_BACKTRACE=1 or RUST_LIB_BACKTRACE=1 to capture backtraces
@@ -178,6 +188,7 @@ impl IntoResponse for AppError {
                     ErrorResponse::external_api_error(platform, message),
                 )
             }
+            AppError::Permanent { .. } => unreachable!("permanent errors are unwrapped above"),
         };
 
         (status, Json(error_response)).into_response()
@@ -344,11 +355,13 @@ pub fn error_to_status_code(error: &AppError) -> StatusCode {
         AppError::UnprocessableContent { .. } => StatusCode::UNPROCESSABLE_ENTITY,
         AppError::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
         AppError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        AppError::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
         AppError::Database { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         AppError::Configuration { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         AppError::ConnectionPool { .. } => StatusCode::SERVICE_UNAVAILABLE,
         AppError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         AppError::ExternalApi { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        AppError::Permanent { source } => error_to_status_code(source),
     }
 }
 
@@ -366,11 +379,13 @@ pub fn error_to_code(error: &AppError) -> &'static str {
         AppError::UnprocessableContent { .. } => "UNPROCESSABLE_CONTENT",
         AppError::Unauthorized { .. } => "UNAUTHORIZED",
         AppError::Forbidden { .. } => "FORBIDDEN",
+        AppError::TooManyRequests { .. } => "TOO_MANY_REQUESTS",
         AppError::Database { .. } => "DATABASE_ERROR",
         AppError::Configuration { .. } => "CONFIGURATION_ERROR",
         AppError::ConnectionPool { .. } => "SERVICE_UNAVAILABLE",
         AppError::Internal { .. } => "INTERNAL_ERROR",
         AppError::ExternalApi { .. } => "EXTERNAL_API_ERROR",
+        AppError::Permanent { source } => error_to_code(source),
     }
 }
 
@@ -449,6 +464,15 @@ mod tests {
         assert_eq!(error_to_code(&error), "FORBIDDEN");
     }
 
+    #[test]
+    fn test_too_many_requests_status_code() {
+        let error = AppError::TooManyRequests {
+            message: "Rate limit exceeded".to_string(),
+        };
+        assert_eq!(error_to_status_code(&error), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error_to_code(&error), "TOO_MANY_REQUESTS");
+    }
+
     #[test]
     fn test_database_status_code() {
         let error = AppError::Database {
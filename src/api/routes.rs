@@ -58,6 +58,8 @@ pub fn create_router(state: AppState) -> Router {
             handlers::notifications::notification_routes(),
         )
         .nest("/jobs", handlers::jobs::job_routes())
+        .nest("/jobs/workflows", handlers::workflows::workflow_routes())
+        .nest("/jobs/queue", handlers::queue::queue_routes())
         .nest("/live", handlers::live::live_routes())
         .layer(middleware::from_fn_with_state(
             state.clone(),
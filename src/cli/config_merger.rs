@@ -164,6 +164,12 @@ impl ConfigurationMerger {
             } => {
                 // Migration commands don't override server configuration
             }
+            Commands::VapidKeys => {
+                // Key generation doesn't use the configuration
+            }
+            Commands::Admin { .. } => {
+                // Access changes only use the database configuration
+            }
         }
 
         Ok(())
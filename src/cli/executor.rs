@@ -3,7 +3,9 @@
 //! This module provides the main entry point for executing CLI commands
 //! after parsing and configuration loading.
 
-use super::handlers::{MigrateCommandHandler, ServeCommandHandler};
+use super::handlers::{
+    AdminCommandHandler, MigrateCommandHandler, ServeCommandHandler, VapidKeysCommandHandler,
+};
 use super::parser::{Cli, Commands};
 use crate::config::settings::Settings;
 use crate::error::AppResult;
@@ -40,6 +42,10 @@ pub async fn execute_command(cli: &Cli, settings: Settings) -> AppResult<()> {
                 .execute(*dry_run, *rollback)
                 .await
         }
+        Some(Commands::VapidKeys) => VapidKeysCommandHandler::new().execute(),
+        Some(Commands::Admin { action }) => {
+            AdminCommandHandler::new(settings).execute(action).await
+        }
     }
 }
 
@@ -74,6 +80,7 @@ fn validate_command_args(cli: &Cli, _settings: &Settings) -> AppResult<()> {
             } => {
                 validate_migrate_args(*rollback)?;
             }
+            Commands::VapidKeys | Commands::Admin { .. } => {}
         }
     }
 
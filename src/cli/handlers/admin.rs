@@ -0,0 +1,54 @@
+//! Admin command handler
+//!
+//! Grants and revokes administrator access, including for the first
+//! administrator, who cannot be appointed through the API.
+
+use crate::cli::parser::AdminAction;
+use crate::config::settings::Settings;
+use crate::db::establish_async_connection_pool;
+use crate::error::AppResult;
+use crate::repositories::UserRepository;
+use crate::services::UserService;
+
+/// Handler for the admin command
+pub struct AdminCommandHandler {
+    config: Settings,
+}
+
+impl AdminCommandHandler {
+    /// Create a new admin command handler
+    pub fn new(config: Settings) -> Self {
+        Self { config }
+    }
+
+    /// Execute the admin command
+    ///
+    /// # Arguments
+    /// * `action` - Access change to apply
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success, or AppError on failure
+    ///
+    /// # Errors
+    /// - Database connection errors
+    /// - `NotFound` if no user has the given email
+    pub async fn execute(&self, action: &AdminAction) -> AppResult<()> {
+        self.config.database.validate()?;
+
+        let (email, admin) = match action {
+            AdminAction::Grant { email } => (email, true),
+            AdminAction::Revoke { email } => (email, false),
+        };
+
+        let pool = establish_async_connection_pool(&self.config.database).await?;
+        let users = UserService::new(UserRepository::new(pool));
+        let user = users.set_admin(email, admin).await?;
+
+        if user.is_admin {
+            println!("{} is now an administrator", user.email);
+        } else {
+            println!("{} is no longer an administrator", user.email);
+        }
+        Ok(())
+    }
+}
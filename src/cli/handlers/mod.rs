@@ -3,8 +3,12 @@
 //! This module contains handlers for different CLI commands,
 //! separating command execution logic from parsing and validation.
 
+pub mod admin;
 pub mod migrate;
 pub mod serve;
+pub mod vapid_keys;
 
+pub use admin::AdminCommandHandler;
 pub use migrate::MigrateCommandHandler;
 pub use serve::ServeCommandHandler;
+pub use vapid_keys::VapidKeysCommandHandler;
@@ -0,0 +1,58 @@
+//! VAPID key generation command handler
+//!
+//! Generates the key pair that identifies this server to Web Push services.
+
+use crate::error::AppResult;
+use crate::utils::webpush::{VapidKeys, generate_vapid_keys};
+
+/// Handler for the vapid-keys command
+#[derive(Default)]
+pub struct VapidKeysCommandHandler;
+
+impl VapidKeysCommandHandler {
+    /// Create a new vapid-keys command handler
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate a key pair and print it as a configuration section
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success, or AppError on failure
+    ///
+    /// # Errors
+    /// - Key generation errors
+    pub fn execute(&self) -> AppResult<()> {
+        let keys = generate_vapid_keys()?;
+        print!("{}", Self::render(&keys));
+        Ok(())
+    }
+
+    /// Render keys as a `[webpush]` TOML section
+    fn render(keys: &VapidKeys) -> String {
+        format!(
+            "[webpush]\npublic_key = \"{}\"\nprivate_key = \"{}\"\nsubject = \"mailto:admin@example.com\"\n",
+            keys.public_key, keys.private_key
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::WebPushConfig;
+
+    #[test]
+    fn test_rendered_keys_are_valid_config() {
+        let keys = generate_vapid_keys().unwrap();
+        let rendered = VapidKeysCommandHandler::render(&keys);
+
+        #[derive(serde::Deserialize)]
+        struct File {
+            webpush: WebPushConfig,
+        }
+        let file: File = toml::from_str(&rendered).unwrap();
+        assert_eq!(file.webpush.public_key, keys.public_key);
+        assert!(file.webpush.validate().is_ok());
+    }
+}
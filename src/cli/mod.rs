@@ -15,7 +15,7 @@ pub mod validation;
 // Re-export public types for convenience
 pub use config_merger::ConfigurationMerger;
 pub use executor::execute_command;
-pub use parser::{Cli, Commands, Environment, LogLevel};
+pub use parser::{AdminAction, Cli, Commands, Environment, LogLevel};
 
 use crate::config::settings::Settings;
 use crate::logger::init_logger;
@@ -163,6 +163,44 @@ pub enum Commands {
         #[arg(long, value_name = "STEPS", conflicts_with = "dry_run", value_parser = super::validation::validate_rollback_steps)]
         rollback: Option<u32>,
     },
+    /// Generate a VAPID key pair for Web Push
+    ///
+    /// Prints a new key pair as a `[webpush]` configuration section. Browsers
+    /// bind their subscriptions to the public key, so replacing the keys
+    /// invalidates all existing Web Push channels.
+    ///
+    /// Examples:
+    ///   fusion-rs vapid-keys                 # Print a new key pair
+    ///   fusion-rs vapid-keys >> config/local.toml
+    VapidKeys,
+    /// Grant or revoke administrator access
+    ///
+    /// Administrators manage user groups and broadcasts. Use this to set up
+    /// the first administrator, or to change access without going through
+    /// the database.
+    ///
+    /// Examples:
+    ///   fusion-rs admin grant alice@example.com
+    ///   fusion-rs admin revoke alice@example.com
+    Admin {
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+}
+
+/// Administrator access changes
+#[derive(Subcommand, Debug, Clone)]
+pub enum AdminAction {
+    /// Make a user an administrator
+    Grant {
+        /// Email address of the user
+        email: String,
+    },
+    /// Remove a user's administrator access
+    Revoke {
+        /// Email address of the user
+        email: String,
+    },
 }
 
 /// Environment options
@@ -224,6 +262,7 @@ impl Cli {
                         return Err("Cannot use --dry-run and --rollback together".to_string());
                     }
                 }
+                Commands::VapidKeys | Commands::Admin { .. } => {}
             }
         }
 
@@ -355,6 +394,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_vapid_keys_command() {
+        let cli = Cli::try_parse_from(["fusion-rs", "vapid-keys"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::VapidKeys)));
+    }
+
+    #[test]
+    fn test_admin_command() {
+        let cli =
+            Cli::try_parse_from(["fusion-rs", "admin", "grant", "alice@example.com"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Admin { action: AdminAction::Grant { ref email } }) if email == "alice@example.com"
+        ));
+
+        let cli =
+            Cli::try_parse_from(["fusion-rs", "admin", "revoke", "alice@example.com"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Admin {
+                action: AdminAction::Revoke { .. }
+            })
+        ));
+
+        assert!(Cli::try_parse_from(["fusion-rs", "admin", "grant"]).is_err());
+    }
+
     #[test]
     fn test_verbose_flag() {
         let cli = Cli::try_parse_from(["fusion-rs", "--verbose"]).unwrap();
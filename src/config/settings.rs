@@ -3,6 +3,7 @@
 //! This module defines all configuration structures that can be loaded from
 //! TOML files and environment variables.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -92,6 +93,14 @@ fn default_refresh_token_expiration() -> i64 {
     168 // 7 days (168 hours)
 }
 
+fn default_encryption_key_id() -> String {
+    "primary".to_string()
+}
+
+fn default_webpush_ttl_seconds() -> u32 {
+    86400 // 1 day
+}
+
 // ============================================================================
 // Application Configuration
 // ============================================================================
@@ -274,6 +283,82 @@ impl JwtConfig {
     }
 }
 
+// ============================================================================
+// Encryption Configuration
+// ============================================================================
+
+/// Encryption of secrets stored in the database (e.g. channel credentials)
+///
+/// Secrets are encrypted with a random data key per value, and the data key
+/// is encrypted with the master key identified by `key_id`. Rotating the
+/// master key only re-encrypts the data keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptionConfig {
+    /// Base64-encoded 32-byte master key
+    /// Secrets are stored unencrypted when empty (use environment variables)
+    #[serde(default)]
+    pub key: String,
+
+    /// Identifier of the master key, recorded with every encrypted value
+    #[serde(default = "default_encryption_key_id")]
+    pub key_id: String,
+
+    /// Retired master keys by identifier, kept to decrypt values written
+    /// before a rotation
+    #[serde(default)]
+    pub previous_keys: HashMap<String, String>,
+}
+
+impl Default for EncryptionConfig {
+    fn default() -> Self {
+        Self {
+            key: String::new(),
+            key_id: default_encryption_key_id(),
+            previous_keys: HashMap::new(),
+        }
+    }
+}
+
+// ============================================================================
+// Web Push Configuration
+// ============================================================================
+
+/// Web Push (VAPID) configuration for browser notifications
+///
+/// The key pair identifies this server to browser push services. Keys are
+/// in the format used by browsers and common Web Push libraries and can be
+/// generated with `fusion-rs vapid-keys`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebPushConfig {
+    /// Base64url-encoded uncompressed P-256 public key (65 bytes)
+    /// Web Push is disabled when empty
+    #[serde(default)]
+    pub public_key: String,
+
+    /// Base64url-encoded P-256 private key (32 bytes)
+    #[serde(default)]
+    pub private_key: String,
+
+    /// Contact URI sent to push services (`mailto:` or `https:`)
+    #[serde(default)]
+    pub subject: String,
+
+    /// How long push services keep undelivered messages, in seconds
+    #[serde(default = "default_webpush_ttl_seconds")]
+    pub ttl_seconds: u32,
+}
+
+impl Default for WebPushConfig {
+    fn default() -> Self {
+        Self {
+            public_key: String::new(),
+            private_key: String::new(),
+            subject: String::new(),
+            ttl_seconds: default_webpush_ttl_seconds(),
+        }
+    }
+}
+
 // ============================================================================
 // Logger Settings (compatible with existing LoggerConfig)
 // ============================================================================
@@ -726,6 +811,14 @@ pub struct Settings {
     #[serde(default)]
     pub jwt: JwtConfig,
 
+    /// Secret encryption configuration
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
+
+    /// Web Push configuration
+    #[serde(default)]
+    pub webpush: WebPushConfig,
+
     /// Logger configuration
     #[serde(default)]
     pub logger: LoggerSettings,
@@ -926,22 +1019,40 @@ mod tests {
         Just(CacheConfig::default())
     }
 
+    fn arb_encryption_config() -> impl Strategy<Value = EncryptionConfig> {
+        (
+            prop_oneof![
+                Just(String::new()),
+                Just("MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=".to_string()),
+            ],
+            "[a-z][a-z0-9-]{0,10}", // key_id
+        )
+            .prop_map(|(key, key_id)| EncryptionConfig {
+                key,
+                key_id,
+                previous_keys: HashMap::new(),
+            })
+    }
+
     fn arb_settings() -> impl Strategy<Value = Settings> {
         (
             arb_application_config(),
             arb_server_config(),
             arb_database_config(),
             arb_jwt_config(),
+            arb_encryption_config(),
             arb_logger_settings(),
             arb_jobs_config(),
             arb_cache_config(),
         )
             .prop_map(
-                |(application, server, database, jwt, logger, jobs, cache)| Settings {
+                |(application, server, database, jwt, encryption, logger, jobs, cache)| Settings {
                     application,
                     server,
                     database,
                     jwt,
+                    encryption,
+                    webpush: WebPushConfig::default(),
                     logger,
                     jobs,
                     cache,
@@ -5,8 +5,11 @@
 
 use crate::config::error::ConfigError;
 use crate::config::settings::{
-    DatabaseConfig, FileSettings, LoggerSettings, ServerConfig, Settings,
+    DatabaseConfig, EncryptionConfig, FileSettings, LoggerSettings, ServerConfig, Settings,
+    WebPushConfig,
 };
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
 
 /// Valid log levels
 const VALID_LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
@@ -192,6 +195,104 @@ impl LoggerSettings {
     }
 }
 
+impl EncryptionConfig {
+    /// Validate encryption configuration
+    ///
+    /// # Validation Rules
+    /// - Key, if set, must be base64 encoding exactly 32 bytes
+    /// - Key ID must be non-empty and must not contain ':'
+    /// - Previous keys must follow the same rules and must not reuse the active key ID
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        validate_key_id("encryption.key_id", &self.key_id)?;
+
+        if !self.key.is_empty() {
+            validate_master_key("encryption.key", &self.key)?;
+        }
+
+        for (id, key) in &self.previous_keys {
+            let field = format!("encryption.previous_keys.{}", id);
+            validate_key_id(&field, id)?;
+            validate_master_key(&field, key)?;
+
+            if *id == self.key_id {
+                return Err(ConfigError::ValidationError {
+                    field,
+                    message: "Previous key ID must differ from the active key ID.".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that a master key ID can be embedded in encrypted values
+fn validate_key_id(field: &str, id: &str) -> Result<(), ConfigError> {
+    if id.is_empty() || id.contains(':') {
+        return Err(ConfigError::ValidationError {
+            field: field.to_string(),
+            message: "Key ID must be non-empty and must not contain ':'.".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a master key is base64 encoding 32 bytes
+fn validate_master_key(field: &str, key: &str) -> Result<(), ConfigError> {
+    match STANDARD.decode(key) {
+        Ok(bytes) if bytes.len() == 32 => Ok(()),
+        _ => Err(ConfigError::ValidationError {
+            field: field.to_string(),
+            message: "Encryption key must be base64 encoding exactly 32 bytes.".to_string(),
+        }),
+    }
+}
+
+impl WebPushConfig {
+    /// Validate Web Push configuration
+    ///
+    /// # Validation Rules
+    /// - Public and private keys must both be set or both be empty
+    /// - Public key must be base64url encoding an uncompressed 65-byte P-256 key
+    /// - Private key must be base64url encoding 32 bytes
+    /// - Subject must be a `mailto:` or `https:` URI when keys are set
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.public_key.is_empty() && self.private_key.is_empty() {
+            return Ok(());
+        }
+
+        match URL_SAFE_NO_PAD.decode(&self.public_key) {
+            Ok(bytes) if bytes.len() == 65 && bytes[0] == 0x04 => {}
+            _ => {
+                return Err(ConfigError::ValidationError {
+                    field: "webpush.public_key".to_string(),
+                    message: "Public key must be base64url encoding an uncompressed P-256 key."
+                        .to_string(),
+                });
+            }
+        }
+
+        match URL_SAFE_NO_PAD.decode(&self.private_key) {
+            Ok(bytes) if bytes.len() == 32 => {}
+            _ => {
+                return Err(ConfigError::ValidationError {
+                    field: "webpush.private_key".to_string(),
+                    message: "Private key must be base64url encoding exactly 32 bytes.".to_string(),
+                });
+            }
+        }
+
+        if !self.subject.starts_with("mailto:") && !self.subject.starts_with("https://") {
+            return Err(ConfigError::ValidationError {
+                field: "webpush.subject".to_string(),
+                message: "Subject must be a mailto: or https: URI.".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl Settings {
     /// Validate all configuration settings
     ///
@@ -200,6 +301,8 @@ impl Settings {
     pub fn validate(&self) -> Result<(), ConfigError> {
         self.server.validate()?;
         self.database.validate()?;
+        self.encryption.validate()?;
+        self.webpush.validate()?;
         self.logger.validate()?;
         Ok(())
     }
@@ -487,6 +590,113 @@ mod tests {
         );
     }
 
+    // ========================================================================
+    // EncryptionConfig validation tests
+    // ========================================================================
+
+    const TEST_KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+
+    #[test]
+    fn test_encryption_config_default_valid() {
+        assert!(EncryptionConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_encryption_config_valid_key() {
+        let config = EncryptionConfig {
+            key: TEST_KEY.to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_encryption_config_invalid_key_length() {
+        let config = EncryptionConfig {
+            key: "c2hvcnQ=".to_string(),
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::ValidationError { field, .. } if field == "encryption.key")
+        );
+    }
+
+    #[test]
+    fn test_encryption_config_invalid_key_id() {
+        let config = EncryptionConfig {
+            key_id: "a:b".to_string(),
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::ValidationError { field, .. } if field == "encryption.key_id")
+        );
+    }
+
+    #[test]
+    fn test_encryption_config_previous_key_reuses_id() {
+        let mut config = EncryptionConfig {
+            key: TEST_KEY.to_string(),
+            ..Default::default()
+        };
+        config
+            .previous_keys
+            .insert("primary".to_string(), TEST_KEY.to_string());
+        assert!(config.validate().is_err());
+    }
+
+    // ========================================================================
+    // WebPushConfig validation tests
+    // ========================================================================
+
+    const TEST_VAPID_PUBLIC_KEY: &str =
+        "BP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A8";
+    const TEST_VAPID_PRIVATE_KEY: &str = "yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw";
+
+    fn webpush_config() -> WebPushConfig {
+        WebPushConfig {
+            public_key: TEST_VAPID_PUBLIC_KEY.to_string(),
+            private_key: TEST_VAPID_PRIVATE_KEY.to_string(),
+            subject: "mailto:ops@example.com".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_webpush_config_default_valid() {
+        assert!(WebPushConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_webpush_config_valid_keys() {
+        assert!(webpush_config().validate().is_ok());
+    }
+
+    #[test]
+    fn test_webpush_config_missing_private_key() {
+        let config = WebPushConfig {
+            private_key: String::new(),
+            ..webpush_config()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::ValidationError { field, .. } if field == "webpush.private_key")
+        );
+    }
+
+    #[test]
+    fn test_webpush_config_invalid_subject() {
+        let config = WebPushConfig {
+            subject: "ops@example.com".to_string(),
+            ..webpush_config()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::ValidationError { field, .. } if field == "webpush.subject")
+        );
+    }
+
     // ========================================================================
     // Settings validation tests
     // ========================================================================
@@ -57,6 +57,10 @@ pub enum AppError {
     #[error("Forbidden: {message}")]
     Forbidden { message: String },
 
+    /// Rate limit exceeded error with descriptive message
+    #[error("Too many requests: {message}")]
+    TooManyRequests { message: String },
+
     /// Database operation error with operation context
     #[error("Database operation failed: {operation}")]
     Database {
@@ -95,6 +99,10 @@ pub enum AppError {
         #[source]
         source: Option<anyhow::Error>,
     },
+
+    /// Error that retrying cannot fix, shown and mapped like the wrapped error
+    #[error(transparent)]
+    Permanent { source: Box<AppError> },
 }
 
 impl From<anyhow::Error> for AppError {
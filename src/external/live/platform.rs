@@ -22,3 +22,25 @@ impl fmt::Display for LivePlatform {
         }
     }
 }
+
+impl LivePlatform {
+    /// Returns the web page of a live room, used as the deep link in alerts
+    ///
+    /// # Arguments
+    /// * `room_id` - The room ID as returned in `RoomInfo`
+    pub fn room_url(&self, room_id: &str) -> String {
+        match self {
+            LivePlatform::Bilibili => format!("https://live.bilibili.com/{}", room_id),
+            // RoomInfo carries Douyin's internal room ID, which only the
+            // reflow page accepts
+            LivePlatform::Douyin => {
+                format!(
+                    "https://webcast.amemv.com/douyin/webcast/reflow/{}",
+                    room_id
+                )
+            }
+            LivePlatform::Douyu => format!("https://www.douyu.com/{}", room_id),
+            LivePlatform::Huya => format!("https://www.huya.com/{}", room_id),
+        }
+    }
+}
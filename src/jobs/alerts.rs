@@ -0,0 +1,168 @@
+//! Job failure alerting.
+//!
+//! Each job may have alert rules that send a message to notification
+//! channels when a run ends in a given way. Rules are evaluated once per
+//! run, after all its retries, against the run's status and the job's
+//! streak of unsuccessful runs.
+
+use std::time::Duration;
+
+use crate::db::AsyncDbPool;
+use crate::jobs::models::{JobAlertRule, ScheduledJob};
+use crate::jobs::types::{JobAlertEvent, JobStatus};
+use crate::repositories::JobAlertRepository;
+use crate::services::notifications::{NotificationService, job_alert_vars};
+
+/// Maximum number of alert rules per job
+pub const MAX_ALERT_RULES: usize = 20;
+
+/// Whether a finished run triggers a rule
+///
+/// # Arguments
+/// * `rule` - Rule to evaluate
+/// * `status` - Final status of the run
+/// * `previous_streak` - Unsuccessful runs in a row before this run
+pub fn triggered(rule: &JobAlertRule, status: JobStatus, previous_streak: i32) -> bool {
+    let streak = status.next_failure_streak(previous_streak);
+    match rule.event {
+        JobAlertEvent::Failure => status == JobStatus::Failed,
+        JobAlertEvent::Timeout => status == JobStatus::Timeout,
+        // Only the run that reaches the threshold alerts, not every run after
+        JobAlertEvent::ConsecutiveFailures => {
+            streak != previous_streak && Some(streak) == rule.threshold
+        }
+        JobAlertEvent::Recovery => status == JobStatus::Success && previous_streak > 0,
+    }
+}
+
+/// How a run ended, as reported in alerts
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub status: JobStatus,
+    /// Error of the last attempt, if the run did not succeed
+    pub error: Option<String>,
+    /// Attempts made, including the first
+    pub attempts: usize,
+    /// Time from the first attempt to the end of the last one
+    pub duration: Duration,
+    /// Unsuccessful runs in a row before this run
+    pub previous_streak: i32,
+}
+
+/// Sends the alerts of finished runs
+pub struct JobAlerter {
+    alert_repo: JobAlertRepository,
+    notifications: NotificationService,
+}
+
+impl JobAlerter {
+    pub fn new(db_pool: AsyncDbPool, notifications: NotificationService) -> Self {
+        Self {
+            alert_repo: JobAlertRepository::new(db_pool),
+            notifications,
+        }
+    }
+
+    /// Alert the channels of every rule the run triggers
+    ///
+    /// A channel targeted by several triggered rules is alerted once:
+    /// with a `consecutive_failures` rule if one triggered, since it tells
+    /// more than a plain failure, otherwise with the first rule. The alert
+    /// is rendered with the rule event's template, see
+    /// [`job_alert_template`](crate::services::notifications::job_alert_template).
+    /// Failures are logged; alerting never fails a run.
+    pub async fn notify(&self, job: &ScheduledJob, report: &RunReport) {
+        let rules = match self.alert_repo.list_by_job(job.id).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::error!(job_name = %job.job_name, error = %e, "Failed to load job alert rules");
+                return;
+            }
+        };
+
+        let mut triggered_rules: Vec<&JobAlertRule> = rules
+            .iter()
+            .filter(|rule| triggered(rule, report.status, report.previous_streak))
+            .collect();
+        triggered_rules.sort_by_key(|rule| rule.event != JobAlertEvent::ConsecutiveFailures);
+
+        let streak = report.status.next_failure_streak(report.previous_streak);
+        let mut alerted = Vec::new();
+        for rule in triggered_rules {
+            let vars = job_alert_vars(
+                rule.event,
+                &job.job_name,
+                report.error.as_deref(),
+                report.attempts,
+                report.duration,
+                streak,
+            );
+
+            for &channel_id in &rule.channel_ids {
+                if alerted.contains(&channel_id) {
+                    continue;
+                }
+                alerted.push(channel_id);
+                if let Err(e) = self
+                    .notifications
+                    .send_job_alert(channel_id, rule.event, &job.job_name, &vars)
+                    .await
+                {
+                    tracing::warn!(job_name = %job.job_name, channel_id, error = %e, "Failed to send job alert");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(event: JobAlertEvent, threshold: Option<i32>) -> JobAlertRule {
+        JobAlertRule {
+            id: 1,
+            job_id: 1,
+            event,
+            threshold,
+            channel_ids: vec![1],
+            created_at: jiff_diesel::DateTime::from(jiff::civil::DateTime::constant(
+                2026, 2, 22, 9, 0, 0, 0,
+            )),
+            user_id: 1,
+        }
+    }
+
+    #[test]
+    fn test_failure_and_timeout_rules() {
+        let failure = rule(JobAlertEvent::Failure, None);
+        let timeout = rule(JobAlertEvent::Timeout, None);
+
+        assert!(triggered(&failure, JobStatus::Failed, 0));
+        assert!(!triggered(&failure, JobStatus::Timeout, 0));
+        assert!(triggered(&timeout, JobStatus::Timeout, 0));
+        assert!(!triggered(&timeout, JobStatus::Success, 0));
+        assert!(!triggered(&failure, JobStatus::Cancelled, 0));
+    }
+
+    #[test]
+    fn test_consecutive_failures_rule_fires_once_at_threshold() {
+        let rule = rule(JobAlertEvent::ConsecutiveFailures, Some(3));
+
+        assert!(!triggered(&rule, JobStatus::Failed, 1));
+        assert!(triggered(&rule, JobStatus::Failed, 2));
+        assert!(triggered(&rule, JobStatus::Timeout, 2));
+        assert!(!triggered(&rule, JobStatus::Failed, 3));
+        // A cancelled run neither extends nor re-announces the streak
+        assert!(!triggered(&rule, JobStatus::Cancelled, 3));
+    }
+
+    #[test]
+    fn test_recovery_rule() {
+        let rule = rule(JobAlertEvent::Recovery, None);
+
+        assert!(triggered(&rule, JobStatus::Success, 2));
+        assert!(!triggered(&rule, JobStatus::Success, 0));
+        assert!(!triggered(&rule, JobStatus::Failed, 2));
+    }
+}
@@ -0,0 +1,304 @@
+//! Cron expression parsing and fire time calculation.
+//!
+//! Expressions require a seconds field and accept an optional year. The
+//! scheduler arms every job on [`next_after`], so the fire times reported
+//! here are the ones it fires at.
+//!
+//! Schedules are evaluated on the wall clock of a job's timezone. A wall
+//! time skipped by a forward offset transition does not fire; a wall time
+//! repeated by a backward transition fires only the first time.
+
+use chrono::{DateTime, FixedOffset};
+use croner::Cron;
+use croner::parser::{CronParser, Seconds};
+use jiff::tz::{TimeZone, TimeZoneTransition};
+use jiff::{SignedDuration, Timestamp};
+
+use crate::error::{AppError, AppResult};
+
+/// How far [`last_at_or_before`] searches back, a little over a leap year
+const MAX_LOOKBACK: SignedDuration = SignedDuration::from_hours(24 * 367);
+
+/// Parses a cron expression
+///
+/// # Arguments
+/// * `expression` - Cron expression with a seconds field (e.g., "0 0 2 * * *")
+///
+/// # Errors
+/// - `Validation` on the `cron_expression` field if the expression is invalid
+pub fn parse(expression: &str) -> AppResult<Cron> {
+    CronParser::builder()
+        .seconds(Seconds::Required)
+        .dom_and_dow(true)
+        .build()
+        .parse(expression)
+        .map_err(|e| AppError::Validation {
+            field: "cron_expression".to_string(),
+            reason: format!("Invalid cron expression '{}': {}", expression, e),
+        })
+}
+
+/// Returns the UTC offset of `tz` at `at`, in seconds
+fn offset_seconds(tz: &TimeZone, at: Timestamp) -> i32 {
+    tz.to_offset(at).seconds()
+}
+
+/// Returns the first fire time strictly after `after`
+///
+/// # Arguments
+/// * `cron` - Parsed schedule
+/// * `tz` - Timezone whose wall clock the schedule follows
+/// * `after` - Exclusive lower bound
+///
+/// # Returns
+/// `None` if the schedule never fires again (e.g., a past year)
+pub fn next_after(cron: &Cron, tz: &TimeZone, after: Timestamp) -> Option<Timestamp> {
+    let mut from = after;
+    let mut offset_since = after;
+    let mut offset = offset_seconds(tz, after);
+
+    // `after` may fall in wall-clock times repeated by the last transition
+    let at_or_before = after + SignedDuration::from_nanos(1);
+    if let Some(transition) = tz.preceding(at_or_before).next() {
+        from = from.max(repeated_until(tz, &transition) - SignedDuration::from_secs(1));
+    }
+
+    loop {
+        let next = next_with_offset(cron, offset, from)?;
+        match tz.following(offset_since).next() {
+            // The offset changes before `next`; continue on the new wall clock
+            Some(transition) if transition.timestamp() <= next => {
+                offset_since = transition.timestamp();
+                offset = transition.offset().seconds();
+                from = repeated_until(tz, &transition) - SignedDuration::from_secs(1);
+            }
+            _ => return Some(next),
+        }
+    }
+}
+
+/// End of the wall-clock times a transition repeats
+///
+/// After a backward transition the wall clock shows times it already
+/// showed before it, until it reaches the time of the transition on the old
+/// offset. A forward transition repeats nothing and ends at the transition.
+fn repeated_until(tz: &TimeZone, transition: &TimeZoneTransition) -> Timestamp {
+    let at = transition.timestamp();
+    let before = offset_seconds(tz, at - SignedDuration::from_secs(1));
+    let shift = (before - transition.offset().seconds()).max(0);
+    at + SignedDuration::from_secs(i64::from(shift))
+}
+
+/// Returns the latest fire time at or before `at`
+///
+/// Follows the same sequence as [`next_after`], so every caller maps a
+/// moment shortly after a fire time to that fire time, whatever the
+/// fraction of a second or the offset transitions around it.
+///
+/// # Arguments
+/// * `cron` - Parsed schedule
+/// * `tz` - Timezone whose wall clock the schedule follows
+/// * `at` - Inclusive upper bound
+///
+/// # Returns
+/// `None` if the schedule has not fired in the year before `at`
+pub fn last_at_or_before(cron: &Cron, tz: &TimeZone, at: Timestamp) -> Option<Timestamp> {
+    let mut lookback = SignedDuration::from_secs(2);
+    loop {
+        let mut from = at - lookback;
+        let mut last = None;
+        while let Some(next) = next_after(cron, tz, from).filter(|next| *next <= at) {
+            last = Some(next);
+            from = next;
+        }
+        if last.is_some() || lookback >= MAX_LOOKBACK {
+            return last;
+        }
+        lookback = (lookback * 2).min(MAX_LOOKBACK);
+    }
+}
+
+/// Returns the first fire time after `after` as a `next_run_at` value
+pub fn next_run_at(cron: &Cron, tz: &TimeZone, after: Timestamp) -> Option<jiff_diesel::DateTime> {
+    next_after(cron, tz, after)
+        .map(|ts| jiff_diesel::DateTime::from(ts.to_zoned(TimeZone::UTC).datetime()))
+}
+
+/// Returns the next `count` fire times strictly after `after`
+pub fn upcoming(cron: &Cron, tz: &TimeZone, after: Timestamp, count: usize) -> Vec<Timestamp> {
+    let mut runs = Vec::with_capacity(count);
+    let mut from = after;
+    while runs.len() < count {
+        let Some(next) = next_after(cron, tz, from) else {
+            break;
+        };
+        runs.push(next);
+        from = next;
+    }
+    runs
+}
+
+/// First fire time strictly after `after` on a fixed-offset wall clock
+fn next_with_offset(cron: &Cron, offset: i32, after: Timestamp) -> Option<Timestamp> {
+    let offset = FixedOffset::east_opt(offset)?;
+    // Fire times are whole seconds, so truncation keeps the bound exclusive
+    let start = DateTime::from_timestamp(after.as_second(), 0)?.with_timezone(&offset);
+    let next = cron.find_next_occurrence(&start, false).ok()?;
+    Timestamp::from_second(next.timestamp()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    fn tz(name: &str) -> TimeZone {
+        TimeZone::get(name).unwrap()
+    }
+
+    #[test]
+    fn test_parse_accepts_scheduler_formats() {
+        assert!(parse("0 0 2 * * *").is_ok());
+        assert!(parse("0 30 3 * * * *").is_ok());
+        assert!(parse("*/30 * * * * * *").is_ok());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expression() {
+        let err = parse("0 0 25 * * *").unwrap_err();
+        assert!(
+            matches!(err, AppError::Validation { ref field, .. } if field == "cron_expression")
+        );
+        // Five-field expressions lack the seconds field the scheduler requires
+        assert!(parse("0 2 * * *").is_err());
+    }
+
+    #[test]
+    fn test_next_after_is_strictly_after() {
+        let cron = parse("0 0 * * * *").unwrap();
+        assert_eq!(
+            next_after(&cron, &TimeZone::UTC, ts("2026-02-12T09:00:00Z")),
+            Some(ts("2026-02-12T10:00:00Z"))
+        );
+        assert_eq!(
+            next_after(&cron, &TimeZone::UTC, ts("2026-02-12T09:59:59.5Z")),
+            Some(ts("2026-02-12T10:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_last_at_or_before_ignores_fractions_of_a_second() {
+        let cron = parse("0 0 * * * *").unwrap();
+        let tz = tz("Europe/Berlin");
+        // Two ticks of the 09:00:00 fire, on either side of a second boundary
+        let early = last_at_or_before(&cron, &tz, ts("2026-02-12T09:00:00.9Z"));
+        let late = last_at_or_before(&cron, &tz, ts("2026-02-12T09:00:01.2Z"));
+        assert_eq!(early, Some(ts("2026-02-12T09:00:00Z")));
+        assert_eq!(late, early);
+        assert_eq!(
+            last_at_or_before(&cron, &tz, ts("2026-02-12T09:00:00Z")),
+            Some(ts("2026-02-12T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_last_at_or_before_sparse_schedule() {
+        let cron = parse("0 0 0 1 1 *").unwrap();
+        assert_eq!(
+            last_at_or_before(&cron, &TimeZone::UTC, ts("2026-12-31T23:59:59Z")),
+            Some(ts("2026-01-01T00:00:00Z"))
+        );
+        let past = parse("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(
+            last_at_or_before(&past, &TimeZone::UTC, ts("2026-02-12T09:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_after_in_timezone() {
+        let cron = parse("0 0 8 * * *").unwrap();
+        assert_eq!(
+            next_after(&cron, &tz("Asia/Shanghai"), ts("2026-02-12T09:00:00Z")),
+            Some(ts("2026-02-13T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_upcoming_across_dst() {
+        // New York springs forward on 2026-03-08 and falls back on 2026-11-01
+        let cron = parse("0 0 8 * * *").unwrap();
+        let new_york = tz("America/New_York");
+
+        let spring = upcoming(&cron, &new_york, ts("2026-03-06T12:00:00Z"), 3);
+        assert_eq!(
+            spring,
+            vec![
+                ts("2026-03-06T13:00:00Z"),
+                ts("2026-03-07T13:00:00Z"),
+                ts("2026-03-08T12:00:00Z"),
+            ]
+        );
+
+        let fall = upcoming(&cron, &new_york, ts("2026-10-31T00:00:00Z"), 2);
+        assert_eq!(
+            fall,
+            vec![ts("2026-10-31T12:00:00Z"), ts("2026-11-01T13:00:00Z")]
+        );
+    }
+
+    #[test]
+    fn test_repeated_wall_time_fires_once() {
+        // Berlin falls back from 03:00 CEST to 02:00 CET on 2026-10-25
+        let cron = parse("0 30 2 * * *").unwrap();
+        let berlin = tz("Europe/Berlin");
+
+        let fires = upcoming(&cron, &berlin, ts("2026-10-24T12:00:00Z"), 2);
+        assert_eq!(
+            fires,
+            vec![ts("2026-10-25T00:30:00Z"), ts("2026-10-26T01:30:00Z")]
+        );
+        // Starting inside the repeated hour does not fire its times again
+        assert_eq!(
+            next_after(&cron, &berlin, ts("2026-10-25T01:10:00Z")),
+            Some(ts("2026-10-26T01:30:00Z"))
+        );
+        assert_eq!(
+            last_at_or_before(&cron, &berlin, ts("2026-10-25T01:30:00.5Z")),
+            Some(ts("2026-10-25T00:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_next_run_at_is_utc() {
+        let cron = parse("0 0 * * * *").unwrap();
+        let expected = jiff_diesel::DateTime::from(jiff::civil::date(2026, 2, 12).at(10, 0, 0, 0));
+        assert_eq!(
+            next_run_at(&cron, &TimeZone::UTC, ts("2026-02-12T09:00:00Z")),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_upcoming() {
+        let cron = parse("0 30 3 * * *").unwrap();
+        let times = upcoming(&cron, &TimeZone::UTC, ts("2026-02-12T09:00:00Z"), 3);
+        assert_eq!(
+            times,
+            vec![
+                ts("2026-02-13T03:30:00Z"),
+                ts("2026-02-14T03:30:00Z"),
+                ts("2026-02-15T03:30:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_upcoming_past_year_is_empty() {
+        let cron = parse("0 0 0 1 1 * 2020").unwrap();
+        assert!(upcoming(&cron, &TimeZone::UTC, ts("2026-02-12T09:00:00Z"), 3).is_empty());
+    }
+}
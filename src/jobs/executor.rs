@@ -2,15 +2,41 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use serde_json::Value as JsonValue;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use crate::db::AsyncDbPool;
 use crate::error::{AppError, AppResult};
-use crate::jobs::models::{NewJobExecution, ScheduledJob};
-use crate::jobs::types::{JobContext, JobStatus, JobTask};
+use crate::jobs::alerts::{JobAlerter, RunReport};
+use crate::jobs::models::{JobExecution, NewJobExecution, ScheduledJob};
+use crate::jobs::retry::{RetryPolicy, should_retry};
+use crate::jobs::types::{ContextOwner, JobContext, JobStatus, JobTask};
 use crate::repositories::{JobExecutionRepository, JobRepository};
+use crate::services::notifications::NotificationService;
+
+/// How often a running attempt checks whether another replica requested its
+/// cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How a run ended, once all its attempts are over
+struct RunOutcome {
+    status: JobStatus,
+    /// Error of the last attempt, if the run did not succeed
+    error: Option<String>,
+}
+
+impl RunOutcome {
+    fn cancelled() -> Self {
+        Self {
+            status: JobStatus::Cancelled,
+            error: Some("Cancelled by operator".to_string()),
+        }
+    }
+}
 
 /// Tracks concurrent job executions in memory
 #[derive(Clone, Default)]
@@ -56,82 +82,386 @@ impl ConcurrencyTracker {
     }
 }
 
+/// Maximum running executions allowed for a job, `None` if unlimited
+fn concurrency_limit(job: &ScheduledJob) -> Option<i64> {
+    if job.allow_concurrent {
+        job.max_concurrent.map(i64::from)
+    } else {
+        Some(1)
+    }
+}
+
+fn concurrency_limit_reached(job: &ScheduledJob) -> AppError {
+    AppError::UnprocessableContent {
+        message: format!("Concurrency limit reached for job: {}", job.job_name),
+    }
+}
+
+/// Structured form of a task error, stored as an execution's
+/// `error_details`
+///
+/// Besides the message, it lists the chain of underlying causes, which
+/// the message of wrapping errors such as `Internal` leaves out.
+fn error_details(error: &AppError) -> JsonValue {
+    let mut causes = Vec::new();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        causes.push(cause.to_string());
+        source = cause.source();
+    }
+    serde_json::json!({
+        "message": error.to_string(),
+        "causes": causes,
+    })
+}
+
 /// Executes jobs with retry, timeout, and concurrency control
 pub struct JobExecutor {
     db_pool: AsyncDbPool,
     job_repo: JobRepository,
     execution_repo: JobExecutionRepository,
+    alerter: JobAlerter,
     concurrency: ConcurrencyTracker,
+    /// Cancellation token of every in-flight run, keyed by execution id.
+    /// All attempts of one run share a token.
+    active: RwLock<HashMap<Uuid, CancellationToken>>,
 }
 
 impl JobExecutor {
-    pub fn new(db_pool: AsyncDbPool) -> Self {
+    pub fn new(db_pool: AsyncDbPool, notifications: NotificationService) -> Self {
         Self {
             job_repo: JobRepository::new(db_pool.clone()),
             execution_repo: JobExecutionRepository::new(db_pool.clone()),
+            alerter: JobAlerter::new(db_pool.clone(), notifications),
             db_pool,
             concurrency: ConcurrencyTracker::new(),
+            active: RwLock::new(HashMap::new()),
         }
     }
 
     pub async fn execute_job(&self, job: ScheduledJob, task: Box<dyn JobTask>) -> AppResult<()> {
-        if !self.concurrency.can_execute(&job).await {
-            return Err(AppError::UnprocessableContent {
-                message: format!("Concurrency limit reached for job: {}", job.job_name),
-            });
+        let (first, token) = self.begin(&job).await?;
+        let (result, _) = self
+            .execute_with_retry(&job, task, first, token, HashMap::new())
+            .await;
+        self.concurrency.decrement(&job.job_name).await;
+
+        result
+    }
+
+    /// Start a run in the background
+    ///
+    /// Concurrency limits are checked before returning, so a rejected
+    /// trigger fails immediately instead of inside the spawned run.
+    ///
+    /// # Arguments
+    /// * `job` - Job to run
+    /// * `task` - Task built from the job type and payload
+    ///
+    /// # Returns
+    /// Execution id of the first attempt, usable with [`Self::cancel`]
+    pub async fn trigger(
+        self: &Arc<Self>,
+        job: ScheduledJob,
+        task: Box<dyn JobTask>,
+    ) -> AppResult<Uuid> {
+        let (execution_id, _) = self.spawn(job, task, HashMap::new()).await?;
+        Ok(execution_id)
+    }
+
+    /// Start a run in the background and return a handle to its outcome
+    ///
+    /// # Arguments
+    /// * `job` - Job to run
+    /// * `task` - Task built from the job type and payload
+    /// * `upstream_results` - Results handed to the task through its context
+    ///
+    /// # Returns
+    /// Execution id of the first attempt, and a handle resolving to the
+    /// execution id of the last attempt once the run has finished
+    ///
+    /// # Errors
+    /// - `UnprocessableContent` if the job is at its concurrency limit
+    pub async fn spawn(
+        self: &Arc<Self>,
+        job: ScheduledJob,
+        task: Box<dyn JobTask>,
+        upstream_results: HashMap<String, JsonValue>,
+    ) -> AppResult<(Uuid, JoinHandle<Uuid>)> {
+        let limit_reached = concurrency_limit_reached(&job);
+        self.try_spawn(job, task, upstream_results)
+            .await?
+            .ok_or(limit_reached)
+    }
+
+    /// Start a run in the background unless the job is at its concurrency
+    /// limit
+    ///
+    /// # Returns
+    /// Like [`Self::spawn`], or None if the job is at its concurrency limit
+    pub async fn try_spawn(
+        self: &Arc<Self>,
+        job: ScheduledJob,
+        task: Box<dyn JobTask>,
+        upstream_results: HashMap<String, JsonValue>,
+    ) -> AppResult<Option<(Uuid, JoinHandle<Uuid>)>> {
+        let Some((first, token)) = self.try_begin(&job).await? else {
+            return Ok(None);
+        };
+
+        let execution_id = first.execution_id;
+        let executor = Arc::clone(self);
+        let handle = tokio::spawn(async move {
+            let (result, last) = executor
+                .execute_with_retry(&job, task, first, token, upstream_results)
+                .await;
+            if let Err(e) = result {
+                tracing::error!(job_name = %job.job_name, error = %e, "Job execution failed");
+            }
+            executor.concurrency.decrement(&job.job_name).await;
+            last
+        });
+
+        Ok(Some((execution_id, handle)))
+    }
+
+    /// Request cancellation of an in-flight run
+    ///
+    /// A run owned by this instance is signalled directly. Otherwise the
+    /// running execution is flagged, and the replica that owns it cancels
+    /// it within [`CANCEL_POLL_INTERVAL`].
+    ///
+    /// # Arguments
+    /// * `execution_id` - Execution id of any attempt of a run owned by this
+    ///   instance, or of the running attempt of a run owned by another one
+    ///
+    /// # Returns
+    /// `true` if the run was still in flight and has been signalled
+    pub async fn cancel(&self, execution_id: Uuid) -> AppResult<bool> {
+        if let Some(token) = self.active.read().await.get(&execution_id) {
+            token.cancel();
+            return Ok(true);
+        }
+
+        self.execution_repo.request_cancel(execution_id).await
+    }
+
+    /// Resolves once cancellation of the execution has been requested
+    /// through another replica
+    async fn cancel_requested(&self, execution_id: Uuid) {
+        loop {
+            tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+            match self.execution_repo.is_cancel_requested(execution_id).await {
+                Ok(true) => return,
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!(%execution_id, error = %e, "Failed to check for a cancellation request");
+                }
+            }
         }
+    }
+
+    /// Reserve a concurrency slot for the job and record its first attempt
+    ///
+    /// # Errors
+    /// - `UnprocessableContent` if the job is at its concurrency limit
+    async fn begin(&self, job: &ScheduledJob) -> AppResult<(JobExecution, CancellationToken)> {
+        self.try_begin(job)
+            .await?
+            .ok_or_else(|| concurrency_limit_reached(job))
+    }
 
+    /// Reserve a concurrency slot for the job and record its first attempt,
+    /// unless the job is at its concurrency limit
+    ///
+    /// The in-memory tracker rejects local overlaps without a round trip;
+    /// the execution insert then enforces the limit across replicas. The
+    /// run's cancellation token is registered before this returns.
+    ///
+    /// # Returns
+    /// None if the job is at its concurrency limit
+    async fn try_begin(
+        &self,
+        job: &ScheduledJob,
+    ) -> AppResult<Option<(JobExecution, CancellationToken)>> {
+        if !self.concurrency.can_execute(job).await {
+            return Ok(None);
+        }
         self.concurrency.increment(&job.job_name).await;
-        let result = self.execute_with_retry(&job, task).await;
-        self.concurrency.decrement(&job.job_name).await;
 
-        result
+        let exec = NewJobExecution {
+            job_id: job.id,
+            job_name: job.job_name.clone(),
+            execution_id: Uuid::new_v4(),
+            status: JobStatus::Running,
+            retry_attempt: 0,
+        };
+        let created = self
+            .execution_repo
+            .create_within_limit(exec, concurrency_limit(job), job.timeout_seconds as i64)
+            .await;
+
+        match created {
+            Ok(Some(execution)) => {
+                let token = CancellationToken::new();
+                self.active
+                    .write()
+                    .await
+                    .insert(execution.execution_id, token.clone());
+                Ok(Some((execution, token)))
+            }
+            Ok(None) => {
+                self.concurrency.decrement(&job.job_name).await;
+                Ok(None)
+            }
+            Err(e) => {
+                self.concurrency.decrement(&job.job_name).await;
+                Err(e)
+            }
+        }
     }
 
+    /// Run all attempts of a job
+    ///
+    /// # Returns
+    /// The outcome of the run and the execution id of its last attempt
     async fn execute_with_retry(
         &self,
         job: &ScheduledJob,
         task: Box<dyn JobTask>,
-    ) -> AppResult<()> {
-        let mut last_error = None;
+        first: JobExecution,
+        token: CancellationToken,
+        upstream_results: HashMap<String, JsonValue>,
+    ) -> (AppResult<()>, Uuid) {
+        let first_id = first.execution_id;
+        let mut execution_ids = Vec::new();
+        let run_start = Instant::now();
+
+        let outcome = self
+            .run_attempts(
+                job,
+                task,
+                first,
+                &token,
+                &upstream_results,
+                &mut execution_ids,
+            )
+            .await;
+
+        {
+            let mut active = self.active.write().await;
+            for execution_id in &execution_ids {
+                active.remove(execution_id);
+            }
+        }
+
+        let result = match outcome {
+            Ok(outcome) => {
+                self.finish(job, outcome, execution_ids.len(), run_start.elapsed())
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let last = execution_ids.last().copied().unwrap_or(first_id);
+        (result, last)
+    }
+
+    /// Run attempts until one succeeds, the retries are exhausted, an
+    /// attempt times out or the run is cancelled
+    ///
+    /// # Errors
+    /// Only if an execution could not be recorded
+    async fn run_attempts(
+        &self,
+        job: &ScheduledJob,
+        task: Box<dyn JobTask>,
+        first: JobExecution,
+        token: &CancellationToken,
+        upstream_results: &HashMap<String, JsonValue>,
+        execution_ids: &mut Vec<Uuid>,
+    ) -> AppResult<RunOutcome> {
+        let policy = RetryPolicy::from_job(job);
+        let mut previous_delay = None;
+        let mut last_failure = None;
+        let mut first = Some(first);
 
         for attempt in 0..=job.max_retries {
-            let execution_id = Uuid::new_v4();
             let start_time = Instant::now();
 
-            let exec = NewJobExecution {
-                job_id: job.id,
-                job_name: job.job_name.clone(),
-                execution_id,
-                status: JobStatus::Running,
-                retry_attempt: attempt,
+            let execution = match first.take() {
+                Some(execution) => execution,
+                None => {
+                    let exec = NewJobExecution {
+                        job_id: job.id,
+                        job_name: job.job_name.clone(),
+                        execution_id: Uuid::new_v4(),
+                        status: JobStatus::Running,
+                        retry_attempt: attempt,
+                    };
+                    self.execution_repo.create(exec).await?
+                }
             };
-
-            let execution = self.execution_repo.create(exec).await?;
+            let execution_id = execution.execution_id;
+            execution_ids.push(execution_id);
+            self.active
+                .write()
+                .await
+                .insert(execution_id, token.clone());
 
             let ctx = JobContext {
                 execution_id,
-                job_id: job.id,
+                owner: ContextOwner::Job(job.id),
                 job_name: job.job_name.clone(),
                 retry_attempt: attempt as u32,
                 db_pool: self.db_pool.clone(),
-                cancellation_token: tokio_util::sync::CancellationToken::new(),
+                cancellation_token: token.clone(),
+                upstream_results: upstream_results.clone(),
             };
 
             let timeout_duration = Duration::from_secs(job.timeout_seconds as u64);
-            let result = tokio::time::timeout(timeout_duration, task.execute(ctx)).await;
+            // Dropping the task future aborts it at its next await point
+            let result = tokio::select! {
+                _ = token.cancelled() => None,
+                _ = self.cancel_requested(execution_id) => {
+                    token.cancel();
+                    None
+                }
+                result = tokio::time::timeout(timeout_duration, task.execute(ctx)) => Some(result),
+            };
 
             let duration_ms = start_time.elapsed().as_millis() as i64;
 
+            let Some(result) = result else {
+                self.execution_repo
+                    .complete(
+                        execution.id,
+                        JobStatus::Cancelled,
+                        duration_ms,
+                        Some("Cancelled by operator".to_string()),
+                        None,
+                        None,
+                    )
+                    .await?;
+                return Ok(RunOutcome::cancelled());
+            };
+
             match result {
-                Ok(Ok(())) => {
+                Ok(Ok(output)) => {
                     self.execution_repo
-                        .complete(execution.id, JobStatus::Success, duration_ms, None, None)
-                        .await?;
-                    self.job_repo
-                        .update_last_run(job.id, JobStatus::Success)
+                        .complete(
+                            execution.id,
+                            JobStatus::Success,
+                            duration_ms,
+                            None,
+                            None,
+                            output,
+                        )
                         .await?;
-                    return Ok(());
+                    return Ok(RunOutcome {
+                        status: JobStatus::Success,
+                        error: None,
+                    });
                 }
                 Ok(Err(e)) => {
                     let error_msg = e.to_string();
@@ -141,14 +471,16 @@ impl JobExecutor {
                             JobStatus::Failed,
                             duration_ms,
                             Some(error_msg.clone()),
+                            Some(error_details(&e)),
                             None,
                         )
                         .await?;
-                    last_error = Some(error_msg);
-
-                    if attempt < job.max_retries {
-                        let delay = self.calculate_retry_delay(job, attempt as u32);
-                        tokio::time::sleep(delay).await;
+                    last_failure = Some((JobStatus::Failed, error_msg));
+                    if !should_retry(task.as_ref(), &e) {
+                        if attempt < job.max_retries {
+                            tracing::info!(job_name = %job.job_name, error = %e, "Job error is not retryable, giving up");
+                        }
+                        break;
                     }
                 }
                 Err(_) => {
@@ -159,39 +491,133 @@ impl JobExecutor {
                             JobStatus::Timeout,
                             duration_ms,
                             Some(error_msg.clone()),
+                            Some(serde_json::json!({ "timeout_seconds": job.timeout_seconds })),
                             None,
                         )
                         .await?;
-                    self.job_repo
-                        .update_last_run(job.id, JobStatus::Timeout)
-                        .await?;
-                    return Err(AppError::Internal {
-                        source: anyhow::anyhow!("Job timeout after {}s", job.timeout_seconds),
-                    });
+                    last_failure = Some((JobStatus::Timeout, error_msg));
+                    if !policy.retry_on_timeout {
+                        break;
+                    }
                 }
             }
+
+            if attempt < job.max_retries {
+                let delay = policy.delay(attempt as u32, previous_delay, &mut rand::rng());
+                previous_delay = Some(delay);
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        // The run's newest row must show the cancellation,
+                        // not the failure of the attempt before it
+                        let exec = NewJobExecution {
+                            job_id: job.id,
+                            job_name: job.job_name.clone(),
+                            execution_id: Uuid::new_v4(),
+                            status: JobStatus::Running,
+                            retry_attempt: attempt + 1,
+                        };
+                        let execution = self.execution_repo.create(exec).await?;
+                        execution_ids.push(execution.execution_id);
+                        self.execution_repo
+                            .complete(
+                                execution.id,
+                                JobStatus::Cancelled,
+                                0,
+                                Some("Cancelled by operator".to_string()),
+                                None,
+                                None,
+                            )
+                            .await?;
+                        return Ok(RunOutcome::cancelled());
+                    }
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+        }
+
+        let (status, error) =
+            last_failure.unwrap_or((JobStatus::Failed, "Unknown error".to_string()));
+        Ok(RunOutcome {
+            status,
+            error: Some(error),
+        })
+    }
+
+    /// Record how a run ended on the job and send its alerts
+    ///
+    /// # Arguments
+    /// * `outcome` - How the run ended
+    /// * `attempts` - Attempts made, including the first
+    /// * `duration` - Time from the first attempt to the end of the last one
+    ///
+    /// # Errors
+    /// `Internal` if the run failed or timed out
+    async fn finish(
+        &self,
+        job: &ScheduledJob,
+        outcome: RunOutcome,
+        attempts: usize,
+        duration: Duration,
+    ) -> AppResult<()> {
+        if outcome.status == JobStatus::Cancelled {
+            tracing::info!(job_name = %job.job_name, "Job execution cancelled");
         }
 
-        self.job_repo
-            .update_last_run(job.id, JobStatus::Failed)
+        let previous_streak = self
+            .job_repo
+            .update_last_run(job.id, outcome.status)
             .await?;
+        let report = RunReport {
+            status: outcome.status,
+            error: outcome.error,
+            attempts,
+            duration,
+            previous_streak,
+        };
+        self.alerter.notify(job, &report).await;
 
-        Err(AppError::Internal {
-            source: anyhow::anyhow!(
-                "Job execution failed: {}",
-                last_error.unwrap_or_else(|| "Unknown error".to_string())
-            ),
-        })
+        match report.status {
+            JobStatus::Failed => Err(AppError::Internal {
+                source: anyhow::anyhow!(
+                    "Job execution failed: {}",
+                    report.error.unwrap_or_default()
+                ),
+            }),
+            JobStatus::Timeout => Err(AppError::Internal {
+                source: anyhow::anyhow!("Job timeout after {}s", job.timeout_seconds),
+            }),
+            _ => Ok(()),
+        }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_details_include_causes() {
+        let error = AppError::Internal {
+            source: anyhow::anyhow!("connection reset").context("fetching anchors"),
+        };
+
+        let details = error_details(&error);
+        assert_eq!(details["message"], "Internal error");
+        assert_eq!(details["causes"][0], "fetching anchors");
+        assert_eq!(details["causes"][1], "connection reset");
+    }
+
+    #[test]
+    fn test_error_details_of_permanent_errors() {
+        let error = crate::jobs::retry::permanent(AppError::Database {
+            operation: "select".to_string(),
+            source: anyhow::anyhow!("relation does not exist"),
+        });
 
-    fn calculate_retry_delay(&self, job: &ScheduledJob, attempt: u32) -> Duration {
-        let base_delay = job.retry_delay_seconds as f64;
-        let multiplier = job
-            .retry_backoff_multiplier
-            .to_string()
-            .parse::<f64>()
-            .unwrap_or(2.0);
-        let delay_secs = base_delay * multiplier.powi(attempt as i32);
-        Duration::from_secs(delay_secs as u64)
+        // This is the execution's error_message
+        assert_eq!(error.to_string(), "Database operation failed: select");
+        let details = error_details(&error);
+        assert_eq!(details["message"], "Database operation failed: select");
+        assert_eq!(details["causes"][0], "relation does not exist");
     }
 }
@@ -1,15 +1,27 @@
+pub mod alerts;
+pub mod cron;
 pub mod error;
 pub mod executor;
 pub mod models;
+pub mod queue;
 pub mod registry;
+pub mod retry;
 pub mod scheduler;
 pub mod tasks;
 pub mod types;
+pub mod workflow;
 
 pub use executor::{ConcurrencyTracker, JobExecutor};
 pub use models::{
-    JobExecution, NewJobExecution, NewScheduledJob, ScheduledJob, UpdateScheduledJob,
+    JobExecution, JobExecutionLog, JobWorkflow, NewJobExecution, NewQueuedTask, NewQueuedTaskLog,
+    NewScheduledJob, QueuedTask, QueuedTaskLog, ScheduledJob, UpdateScheduledJob, WorkflowRun,
+    WorkflowStep, WorkflowStepRun,
 };
+pub use queue::QueueWorker;
 pub use registry::JobRegistry;
+pub use retry::RetryPolicy;
 pub use scheduler::JobScheduler;
-pub use types::{JobContext, JobStatus, JobTask};
+pub use types::{
+    ContextOwner, JobContext, JobLogLevel, JobStatus, JobTask, QueuedTaskStatus, RetryJitter,
+};
+pub use workflow::WorkflowRunner;
@@ -2,8 +2,11 @@ use diesel::prelude::*;
 use jiff_diesel::{DateTime, NullableDateTime};
 use serde_json::Value as JsonValue;
 
-use crate::jobs::types::JobStatus;
-use crate::schema::{job_executions, scheduled_jobs};
+use crate::jobs::types::{JobAlertEvent, JobLogLevel, JobStatus, QueuedTaskStatus, RetryJitter};
+use crate::schema::{
+    job_alert_rules, job_execution_logs, job_executions, job_queue, job_queue_logs,
+    job_workflow_runs, job_workflow_step_runs, job_workflow_steps, job_workflows, scheduled_jobs,
+};
 
 // ============================================================================
 // ScheduledJob Models
@@ -31,6 +34,12 @@ pub struct ScheduledJob {
     pub created_at: DateTime,
     pub updated_at: DateTime,
     pub created_by: Option<String>,
+    pub last_fired_at: NullableDateTime,
+    pub timezone: String,
+    pub consecutive_failures: i32,
+    pub retry_max_delay_seconds: i32,
+    pub retry_jitter: RetryJitter,
+    pub retry_on_timeout: bool,
 }
 
 #[derive(Debug, Insertable)]
@@ -49,6 +58,11 @@ pub struct NewScheduledJob {
     pub payload: Option<JsonValue>,
     pub description: Option<String>,
     pub created_by: Option<String>,
+    pub next_run_at: Option<DateTime>,
+    pub timezone: String,
+    pub retry_max_delay_seconds: i32,
+    pub retry_jitter: RetryJitter,
+    pub retry_on_timeout: bool,
 }
 
 #[derive(Debug, Default, AsChangeset)]
@@ -64,6 +78,36 @@ pub struct UpdateScheduledJob {
     pub timeout_seconds: Option<i32>,
     pub payload: Option<JsonValue>,
     pub description: Option<String>,
+    pub timezone: Option<String>,
+    pub retry_max_delay_seconds: Option<i32>,
+    pub retry_jitter: Option<RetryJitter>,
+    pub retry_on_timeout: Option<bool>,
+}
+
+// ============================================================================
+// JobAlertRule Models
+// ============================================================================
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_alert_rules)]
+pub struct JobAlertRule {
+    pub id: i32,
+    pub job_id: i32,
+    pub event: JobAlertEvent,
+    pub threshold: Option<i32>,
+    pub channel_ids: Vec<i32>,
+    pub created_at: DateTime,
+    pub user_id: i32,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_alert_rules)]
+pub struct NewJobAlertRule {
+    pub job_id: i32,
+    pub event: JobAlertEvent,
+    pub threshold: Option<i32>,
+    pub channel_ids: Vec<i32>,
+    pub user_id: i32,
 }
 
 // ============================================================================
@@ -85,6 +129,9 @@ pub struct JobExecution {
     pub error_message: Option<String>,
     pub error_details: Option<JsonValue>,
     pub result: Option<JsonValue>,
+    pub progress_percent: Option<i16>,
+    pub progress_message: Option<String>,
+    pub cancel_requested: bool,
 }
 
 #[derive(Debug, Insertable)]
@@ -96,3 +143,182 @@ pub struct NewJobExecution {
     pub status: JobStatus,
     pub retry_attempt: i32,
 }
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_execution_logs)]
+pub struct JobExecutionLog {
+    pub id: i64,
+    pub execution_id: uuid::Uuid,
+    pub level: JobLogLevel,
+    pub message: String,
+    pub fields: Option<JsonValue>,
+    pub created_at: DateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_execution_logs)]
+pub struct NewJobExecutionLog {
+    pub execution_id: uuid::Uuid,
+    pub level: JobLogLevel,
+    pub message: String,
+    pub fields: Option<JsonValue>,
+}
+
+// ============================================================================
+// Workflow Models
+// ============================================================================
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_workflows)]
+pub struct JobWorkflow {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_workflows)]
+pub struct NewJobWorkflow {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_workflow_steps)]
+pub struct WorkflowStep {
+    pub id: i32,
+    pub workflow_id: i32,
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+}
+
+/// A workflow step before it is attached to a workflow
+#[derive(Debug, Clone)]
+pub struct WorkflowStepSpec {
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_workflow_steps)]
+pub struct NewWorkflowStep {
+    pub workflow_id: i32,
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_workflow_runs)]
+pub struct WorkflowRun {
+    pub id: i64,
+    pub workflow_id: i32,
+    pub run_id: uuid::Uuid,
+    pub status: JobStatus,
+    pub started_at: DateTime,
+    pub completed_at: NullableDateTime,
+    /// End of the driving replica's lease while running
+    pub locked_until: NullableDateTime,
+    /// Claim of the replica driving the run
+    pub locked_by: Option<uuid::Uuid>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_workflow_runs)]
+pub struct NewWorkflowRun {
+    pub workflow_id: i32,
+    pub run_id: uuid::Uuid,
+    pub status: JobStatus,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_workflow_step_runs)]
+pub struct WorkflowStepRun {
+    pub id: i64,
+    pub workflow_run_id: i64,
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+    pub status: JobStatus,
+    pub execution_id: Option<uuid::Uuid>,
+    pub started_at: NullableDateTime,
+    pub completed_at: NullableDateTime,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_workflow_step_runs)]
+pub struct NewWorkflowStepRun {
+    pub workflow_run_id: i64,
+    pub step_name: String,
+    pub job_id: i32,
+    pub depends_on: Vec<String>,
+    pub status: JobStatus,
+}
+
+// ============================================================================
+// Job Queue Models
+// ============================================================================
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_queue)]
+pub struct QueuedTask {
+    pub id: i64,
+    pub job_type: String,
+    pub payload: JsonValue,
+    pub priority: i32,
+    pub run_at: DateTime,
+    pub status: QueuedTaskStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub retry_delay_seconds: i32,
+    pub visibility_timeout_seconds: i32,
+    pub locked_until: NullableDateTime,
+    pub last_error: Option<String>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub completed_at: NullableDateTime,
+    pub result: Option<JsonValue>,
+    pub progress_percent: Option<i16>,
+    pub progress_message: Option<String>,
+    /// Execution ID of the current or latest attempt
+    pub execution_id: Option<uuid::Uuid>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_queue)]
+pub struct NewQueuedTask {
+    pub job_type: String,
+    pub payload: JsonValue,
+    pub priority: i32,
+    pub run_at: DateTime,
+    pub max_attempts: i32,
+    pub retry_delay_seconds: i32,
+    pub visibility_timeout_seconds: i32,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = job_queue_logs)]
+pub struct QueuedTaskLog {
+    pub id: i64,
+    pub task_id: i64,
+    pub attempt: i32,
+    pub level: JobLogLevel,
+    pub message: String,
+    pub fields: Option<JsonValue>,
+    pub created_at: DateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_queue_logs)]
+pub struct NewQueuedTaskLog {
+    pub task_id: i64,
+    pub attempt: i32,
+    pub level: JobLogLevel,
+    pub message: String,
+    pub fields: Option<JsonValue>,
+}
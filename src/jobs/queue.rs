@@ -0,0 +1,238 @@
+//! Ad-hoc task queue.
+//!
+//! One-shot tasks are enqueued with a job type and payload, optionally
+//! delayed, and run once by any replica. A worker claims due tasks with a
+//! visibility timeout: if it does not report back in time the task becomes
+//! visible again and counts as a failed attempt. An attempt is timed out a
+//! little before its visibility timeout ends, so a live worker records the
+//! outcome before another one can claim the task. Tasks that run out of
+//! attempts are kept as dead letters until requeued or deleted.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{NewQueuedTask, QueuedTask};
+use crate::jobs::registry::JobRegistry;
+use crate::jobs::retry::should_retry;
+use crate::jobs::types::{ContextOwner, JobContext};
+use crate::repositories::JobQueueRepository;
+
+/// Maximum number of queued tasks running at once on a replica
+pub const QUEUE_CONCURRENCY: usize = 10;
+
+/// Upper bound of the delay between two attempts of a queued task
+const MAX_RETRY_DELAY_SECONDS: i64 = 86_400;
+
+/// Upper bound of the time kept between an attempt's execution timeout and
+/// the end of its visibility timeout
+const MAX_VISIBILITY_MARGIN: Duration = Duration::from_secs(30);
+
+/// How long an attempt may run before it is timed out
+///
+/// A tenth of the visibility timeout, at most [`MAX_VISIBILITY_MARGIN`], is
+/// left for recording the outcome while the task is still claimed.
+///
+/// # Arguments
+/// * `visibility_timeout_seconds` - Visibility timeout of the task
+pub fn execution_timeout(visibility_timeout_seconds: i32) -> Duration {
+    let visibility = Duration::from_secs(visibility_timeout_seconds.max(0) as u64);
+    visibility - (visibility / 10).min(MAX_VISIBILITY_MARGIN)
+}
+
+/// Delay before retrying a task after a failed attempt
+///
+/// The delay doubles with every attempt, starting at `base_seconds`.
+///
+/// # Arguments
+/// * `base_seconds` - Delay after the first attempt
+/// * `attempt` - Number of the attempt that failed, starting at 1
+pub fn retry_delay_seconds(base_seconds: i32, attempt: i32) -> i64 {
+    let exponent = attempt.saturating_sub(1).clamp(0, 32) as u32;
+    (base_seconds.max(0) as i64)
+        .saturating_mul(2_i64.saturating_pow(exponent))
+        .min(MAX_RETRY_DELAY_SECONDS)
+}
+
+/// Runs due queued tasks on this replica
+pub struct QueueWorker {
+    db_pool: AsyncDbPool,
+    registry: Arc<JobRegistry>,
+    queue_repo: JobQueueRepository,
+    permits: Arc<Semaphore>,
+}
+
+impl QueueWorker {
+    pub fn new(db_pool: AsyncDbPool, registry: Arc<JobRegistry>) -> Self {
+        Self {
+            queue_repo: JobQueueRepository::new(db_pool.clone()),
+            db_pool,
+            registry,
+            permits: Arc::new(Semaphore::new(QUEUE_CONCURRENCY)),
+        }
+    }
+
+    /// Add a task to the queue
+    ///
+    /// The task is built once from its job type and payload so that a bad
+    /// request is rejected now rather than dead-lettered later.
+    ///
+    /// # Errors
+    /// - `Validation` if the job type is unknown or the payload does not
+    ///   fit it
+    pub async fn enqueue(&self, task: NewQueuedTask) -> AppResult<QueuedTask> {
+        match self
+            .registry
+            .create_task(&task.job_type, task.payload.clone())
+        {
+            Ok(_) => {}
+            Err(AppError::NotFound { .. }) => {
+                return Err(AppError::Validation {
+                    field: "job_type".to_string(),
+                    reason: format!("Unknown job type: {}", task.job_type),
+                });
+            }
+            Err(AppError::Internal { source }) => {
+                return Err(AppError::Validation {
+                    field: "payload".to_string(),
+                    reason: source.to_string(),
+                });
+            }
+            Err(e) => return Err(e),
+        }
+
+        self.queue_repo.enqueue(task).await
+    }
+
+    /// Dead-letter abandoned tasks, then claim as many due tasks as there
+    /// are free slots and run them in the background
+    pub async fn poll(self: &Arc<Self>) -> AppResult<()> {
+        let dead = self.queue_repo.dead_letter_expired().await?;
+        if dead > 0 {
+            tracing::warn!(
+                count = dead,
+                "Dead-lettered queued tasks after visibility timeout"
+            );
+        }
+
+        let free = self.permits.available_permits();
+        if free == 0 {
+            return Ok(());
+        }
+
+        for task in self.queue_repo.claim_due(free as i64).await? {
+            let Ok(permit) = Arc::clone(&self.permits).try_acquire_owned() else {
+                // Unreachable while only this loop acquires permits; the
+                // task becomes visible again after its timeout
+                break;
+            };
+            let worker = Arc::clone(self);
+            tokio::spawn(async move {
+                worker.run(task).await;
+                drop(permit);
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Run one attempt of a claimed task and record its outcome
+    async fn run(&self, task: QueuedTask) {
+        let outcome = match self
+            .registry
+            .create_task(&task.job_type, task.payload.clone())
+        {
+            Ok(job_task) => {
+                let ctx = JobContext {
+                    // Set by every claim; a fresh one only for rows claimed
+                    // before the column existed
+                    execution_id: task.execution_id.unwrap_or_else(Uuid::new_v4),
+                    owner: ContextOwner::QueuedTask {
+                        id: task.id,
+                        attempt: task.attempts,
+                    },
+                    job_name: format!("queue#{}", task.id),
+                    retry_attempt: task.attempts.saturating_sub(1) as u32,
+                    db_pool: self.db_pool.clone(),
+                    cancellation_token: CancellationToken::new(),
+                    upstream_results: HashMap::new(),
+                };
+                let timeout = execution_timeout(task.visibility_timeout_seconds);
+                match tokio::time::timeout(timeout, job_task.execute(ctx)).await {
+                    Ok(Ok(output)) => Ok(output),
+                    Ok(Err(e)) => Err((e.to_string(), should_retry(job_task.as_ref(), &e))),
+                    Err(_) => Err((
+                        format!("Timed out after {:.1} seconds", timeout.as_secs_f64()),
+                        true,
+                    )),
+                }
+            }
+            // Neither the job type nor the payload will get any better on a
+            // later attempt
+            Err(AppError::Internal { source }) => {
+                Err((format!("Invalid payload: {}", source), false))
+            }
+            Err(e) => Err((e.to_string(), false)),
+        };
+
+        let recorded = match outcome {
+            Ok(output) => {
+                tracing::info!(task_id = task.id, job_type = %task.job_type, "Queued task succeeded");
+                self.queue_repo
+                    .complete(task.id, task.attempts, output)
+                    .await
+            }
+            Err((error, retryable)) => {
+                let retry_at = (retryable && task.attempts < task.max_attempts).then(|| {
+                    let delay = retry_delay_seconds(task.retry_delay_seconds, task.attempts);
+                    let at = jiff::Timestamp::now() + jiff::SignedDuration::from_secs(delay);
+                    jiff_diesel::DateTime::from(at.to_zoned(jiff::tz::TimeZone::UTC).datetime())
+                });
+                if retry_at.is_some() {
+                    tracing::warn!(task_id = task.id, attempt = task.attempts, error = %error, "Queued task failed, retrying");
+                } else {
+                    tracing::error!(task_id = task.id, attempt = task.attempts, error = %error, "Queued task failed, moved to dead letters");
+                }
+                self.queue_repo
+                    .fail(task.id, task.attempts, error, retry_at)
+                    .await
+            }
+        };
+
+        if let Err(e) = recorded {
+            tracing::error!(task_id = task.id, error = %e, "Failed to record queued task outcome");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_timeout_ends_before_visibility_timeout() {
+        assert_eq!(execution_timeout(300), Duration::from_secs(270));
+        assert_eq!(execution_timeout(86_400), Duration::from_secs(86_370));
+        assert_eq!(execution_timeout(1), Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_per_attempt() {
+        assert_eq!(retry_delay_seconds(60, 1), 60);
+        assert_eq!(retry_delay_seconds(60, 2), 120);
+        assert_eq!(retry_delay_seconds(60, 3), 240);
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        assert_eq!(retry_delay_seconds(60, 20), MAX_RETRY_DELAY_SECONDS);
+        assert_eq!(retry_delay_seconds(i32::MAX, 100), MAX_RETRY_DELAY_SECONDS);
+        assert_eq!(retry_delay_seconds(0, 5), 0);
+    }
+}
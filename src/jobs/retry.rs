@@ -0,0 +1,237 @@
+//! Retry policy of scheduled jobs.
+//!
+//! A failed attempt is retried after an exponential backoff, capped at a
+//! maximum delay and optionally randomized so that jobs failing together do
+//! not retry in lockstep. Errors that cannot get better on a later attempt
+//! are not retried: errors a task marks [`permanent`], and errors the task
+//! does not consider retryable, by default client errors such as a failed
+//! validation.
+
+use std::time::Duration;
+
+use bigdecimal::ToPrimitive;
+use rand::Rng;
+
+use crate::error::AppError;
+use crate::jobs::models::ScheduledJob;
+use crate::jobs::types::{JobTask, RetryJitter};
+
+/// Backoff multiplier used when the stored one is not a usable number
+const DEFAULT_MULTIPLIER: f64 = 2.0;
+
+/// Marks a task error as permanent, failing the run without retries
+///
+/// The error keeps its message and causes, so the execution records it
+/// as if it had been returned unmarked.
+pub fn permanent(error: AppError) -> AppError {
+    AppError::Permanent {
+        source: Box::new(error),
+    }
+}
+
+/// Whether the error was marked with [`permanent`]
+pub fn is_permanent(error: &AppError) -> bool {
+    matches!(error, AppError::Permanent { .. })
+}
+
+/// Default retryable-error predicate of tasks
+///
+/// Infrastructure and upstream failures are transient. Errors caused by the
+/// job's own input or state (validation, missing or duplicate entities,
+/// refused access, bad configuration) are not, nor are permanent errors.
+pub fn is_transient(error: &AppError) -> bool {
+    match error {
+        AppError::NotFound { .. }
+        | AppError::Duplicate { .. }
+        | AppError::Validation { .. }
+        | AppError::ValidationErrors { .. }
+        | AppError::BadRequest { .. }
+        | AppError::UnprocessableContent { .. }
+        | AppError::Unauthorized { .. }
+        | AppError::Forbidden { .. }
+        | AppError::Configuration { .. } => false,
+        AppError::Permanent { .. } => false,
+        AppError::TooManyRequests { .. }
+        | AppError::Internal { .. }
+        | AppError::Database { .. }
+        | AppError::ConnectionPool { .. }
+        | AppError::ExternalApi { .. } => true,
+    }
+}
+
+/// Whether a task's failed attempt should be retried
+///
+/// Permanent errors are never retried; other errors are left to the task's
+/// [`JobTask::is_retryable`].
+pub fn should_retry(task: &dyn JobTask, error: &AppError) -> bool {
+    !is_permanent(error) && task.is_retryable(error)
+}
+
+/// How long to wait between the attempts of a job run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Delay after the first failed attempt
+    pub base_delay: Duration,
+    /// Factor applied to the delay after every further attempt
+    pub multiplier: f64,
+    /// Upper bound of any delay
+    pub max_delay: Duration,
+    pub jitter: RetryJitter,
+    /// Whether a timed-out attempt is retried like a failed one
+    pub retry_on_timeout: bool,
+}
+
+impl RetryPolicy {
+    /// Reads the retry settings of a job
+    pub fn from_job(job: &ScheduledJob) -> Self {
+        let multiplier = job
+            .retry_backoff_multiplier
+            .to_f64()
+            .filter(|m| m.is_finite() && *m >= 0.0)
+            .unwrap_or(DEFAULT_MULTIPLIER);
+
+        Self {
+            base_delay: Duration::from_secs(job.retry_delay_seconds.max(0) as u64),
+            multiplier,
+            max_delay: Duration::from_secs(job.retry_max_delay_seconds.max(0) as u64),
+            jitter: job.retry_jitter,
+            retry_on_timeout: job.retry_on_timeout,
+        }
+    }
+
+    /// Exponential backoff after a failed attempt, without jitter
+    ///
+    /// # Arguments
+    /// * `attempt` - The attempt that failed, starting at 0
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.min(i32::MAX as u32) as i32;
+        let secs = self.base_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        if secs.is_finite() && secs < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max_delay
+        }
+    }
+
+    /// Delay before retrying a failed attempt
+    ///
+    /// # Arguments
+    /// * `attempt` - The attempt that failed, starting at 0
+    /// * `previous` - Delay waited before that attempt, `None` for the
+    ///   first one
+    /// * `rng` - Source of the jitter
+    pub fn delay(&self, attempt: u32, previous: Option<Duration>, rng: &mut impl Rng) -> Duration {
+        match self.jitter {
+            RetryJitter::None => self.backoff(attempt),
+            RetryJitter::Full => random_between(Duration::ZERO, self.backoff(attempt), rng),
+            RetryJitter::Decorrelated => {
+                let base = self.base_delay.min(self.max_delay);
+                let upper = previous
+                    .unwrap_or(base)
+                    .saturating_mul(3)
+                    .min(self.max_delay);
+                random_between(base, upper, rng)
+            }
+        }
+    }
+}
+
+/// Uniformly random duration in `[low, high]`, or `low` if the range is
+/// empty
+fn random_between(low: Duration, high: Duration, rng: &mut impl Rng) -> Duration {
+    if high <= low {
+        return low;
+    }
+    Duration::from_secs_f64(rng.random_range(low.as_secs_f64()..=high.as_secs_f64()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(jitter: RetryJitter) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(300),
+            jitter,
+            retry_on_timeout: false,
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_until_max_delay() {
+        let policy = policy(RetryJitter::None);
+        assert_eq!(policy.backoff(0), Duration::from_secs(10));
+        assert_eq!(policy.backoff(1), Duration::from_secs(20));
+        assert_eq!(policy.backoff(4), Duration::from_secs(160));
+        assert_eq!(policy.backoff(5), Duration::from_secs(300));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_fractional_multiplier() {
+        let policy = RetryPolicy {
+            multiplier: 1.5,
+            ..policy(RetryJitter::None)
+        };
+        assert_eq!(policy.backoff(2), Duration::from_secs_f64(22.5));
+    }
+
+    #[test]
+    fn test_full_jitter_stays_below_backoff() {
+        let policy = policy(RetryJitter::Full);
+        let mut rng = rand::rng();
+        for attempt in 0..8 {
+            let delay = policy.delay(attempt, None, &mut rng);
+            assert!(delay <= policy.backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_bounds() {
+        let policy = policy(RetryJitter::Decorrelated);
+        let mut rng = rand::rng();
+
+        let first = policy.delay(0, None, &mut rng);
+        assert!((Duration::from_secs(10)..=Duration::from_secs(30)).contains(&first));
+
+        for _ in 0..20 {
+            let delay = policy.delay(3, Some(Duration::from_secs(200)), &mut rng);
+            assert!((Duration::from_secs(10)..=Duration::from_secs(300)).contains(&delay));
+        }
+    }
+
+    #[test]
+    fn test_client_errors_are_not_transient() {
+        let validation = AppError::Validation {
+            field: "payload".to_string(),
+            reason: "missing workflow_id".to_string(),
+        };
+        let database = AppError::Database {
+            operation: "select".to_string(),
+            source: anyhow::anyhow!("connection reset"),
+        };
+
+        assert!(!is_transient(&validation));
+        assert!(is_transient(&database));
+        assert!(is_transient(&AppError::Internal {
+            source: anyhow::anyhow!("boom"),
+        }));
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_retried() {
+        let error = permanent(AppError::Database {
+            operation: "select".to_string(),
+            source: anyhow::anyhow!("relation does not exist"),
+        });
+
+        assert!(is_permanent(&error));
+        assert!(!is_transient(&error));
+        assert_eq!(error.to_string(), "Database operation failed: select");
+        assert!(!is_permanent(&AppError::Internal {
+            source: anyhow::anyhow!("boom"),
+        }));
+    }
+}
@@ -1,35 +1,278 @@
+use croner::Cron;
+use jiff::tz::TimeZone;
+use jiff_diesel::DateTime;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tokio_cron_scheduler::{Job, JobScheduler as TokioCronScheduler};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
 use crate::db::AsyncDbPool;
 use crate::error::{AppError, AppResult};
+use crate::jobs::cron;
 use crate::jobs::executor::JobExecutor;
-use crate::jobs::models::ScheduledJob;
+use crate::jobs::models::{NewQueuedTask, QueuedTask, ScheduledJob, WorkflowRun};
+use crate::jobs::queue::QueueWorker;
 use crate::jobs::registry::JobRegistry;
-use crate::repositories::JobRepository;
+use crate::jobs::workflow::WorkflowRunner;
+use crate::repositories::{JobExecutionRepository, JobRepository, JobWorkflowRepository};
+use crate::services::notifications::NotificationService;
+use crate::utils::timezone::parse_timezone;
+
+/// How often pending workflow runs are claimed
+const WORKFLOW_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often due queued tasks are claimed
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a job's cron entry is registered with
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Registration {
+    cron_expression: String,
+    timezone: String,
+}
+
+impl Registration {
+    /// Registration of a job's schedule
+    ///
+    /// # Errors
+    /// - `Validation` if the cron expression is invalid or the timezone is
+    ///   unknown
+    fn new(cron_expression: &str, timezone: &str) -> AppResult<Self> {
+        cron::parse(cron_expression)?;
+        parse_timezone(timezone)?;
+        Ok(Self {
+            cron_expression: cron_expression.to_string(),
+            timezone: timezone.to_string(),
+        })
+    }
+
+    /// Fire times of the registered schedule, starting at `now`
+    fn clock(&self, now: jiff::Timestamp) -> AppResult<EntryClock> {
+        let schedule = Arc::new(cron::parse(&self.cron_expression)?);
+        let tz = parse_timezone(&self.timezone)?;
+        Ok(EntryClock::new(schedule, tz, now))
+    }
+}
+
+/// Registrations of the given enabled jobs, keyed by job id
+///
+/// A job whose schedule cannot be registered is logged and left out, so
+/// one bad row does not keep the other jobs from being scheduled.
+fn desired_registrations(jobs: &[ScheduledJob]) -> HashMap<i32, Registration> {
+    let mut desired = HashMap::new();
+    for job in jobs {
+        match Registration::new(&job.cron_expression, &job.timezone) {
+            Ok(registration) => {
+                desired.insert(job.id, registration);
+            }
+            Err(e) => {
+                tracing::warn!(job_id = job.id, error = %e, "Skipping job with an invalid schedule");
+            }
+        }
+    }
+    desired
+}
+
+/// A cron entry registered with tokio-cron-scheduler for a scheduled job
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CronEntry {
+    /// tokio-cron-scheduler id of the job's next fire
+    uuid: Uuid,
+    registration: Registration,
+}
+
+/// Changes needed to bring the registered cron entries in line with the database
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ReconcilePlan {
+    /// Jobs without a cron entry yet
+    add: Vec<i32>,
+    /// Jobs whose schedule or timezone changed
+    replace: Vec<i32>,
+    /// Jobs that were deleted or disabled
+    remove: Vec<i32>,
+}
+
+impl ReconcilePlan {
+    /// Diffs registered entries against the desired registrations
+    ///
+    /// # Arguments
+    /// * `entries` - Cron entries currently registered, keyed by job id
+    /// * `desired` - Registration of every enabled job, keyed by job id
+    fn diff(entries: &HashMap<i32, CronEntry>, desired: &HashMap<i32, Registration>) -> Self {
+        let mut plan = Self::default();
+
+        for (&id, registration) in desired {
+            match entries.get(&id) {
+                None => plan.add.push(id),
+                Some(entry) if entry.registration != *registration => plan.replace.push(id),
+                Some(_) => {}
+            }
+        }
+        plan.remove = entries
+            .keys()
+            .filter(|id| !desired.contains_key(id))
+            .copied()
+            .collect();
+
+        plan.add.sort_unstable();
+        plan.replace.sort_unstable();
+        plan.remove.sort_unstable();
+        plan
+    }
+}
+
+/// Fire times of one cron entry
+///
+/// An entry is registered for [`Self::next`], then claims the occurrence
+/// returned by [`Self::wake`] and registers the one after it.
+#[derive(Clone)]
+struct EntryClock {
+    schedule: Arc<Cron>,
+    tz: TimeZone,
+    /// Last fired occurrence, or when the entry was registered
+    last: jiff::Timestamp,
+}
+
+impl EntryClock {
+    fn new(schedule: Arc<Cron>, tz: TimeZone, now: jiff::Timestamp) -> Self {
+        Self {
+            schedule,
+            tz,
+            last: now,
+        }
+    }
+
+    /// Fire time to register, `None` once the schedule ends
+    fn next(&self) -> Option<jiff::Timestamp> {
+        cron::next_after(&self.schedule, &self.tz, self.last)
+    }
+
+    /// Occurrence to fire for a wake at `now`
+    ///
+    /// A late wake (e.g., after the host was suspended) fires the latest
+    /// occurrence once rather than every one it missed.
+    ///
+    /// # Arguments
+    /// * `armed` - Fire time the entry was registered for
+    /// * `now` - When the entry fired, at or after `armed`
+    fn wake(&mut self, armed: jiff::Timestamp, now: jiff::Timestamp) -> jiff::Timestamp {
+        let fire = fire_time(&self.schedule, &self.tz, now)
+            .filter(|fire| *fire >= armed)
+            .unwrap_or(armed);
+        self.last = fire;
+        fire
+    }
+}
+
+/// Scheduled occurrence a wake at `now` belongs to, used as the
+/// cluster-wide claim key
+///
+/// Entries wake shortly after the occurrence, at a slightly different
+/// moment on every replica, but all of them map back to the same occurrence.
+fn fire_time(schedule: &Cron, tz: &TimeZone, now: jiff::Timestamp) -> Option<jiff::Timestamp> {
+    cron::last_at_or_before(schedule, tz, now)
+}
+
+/// Instant at which tokio-cron-scheduler fires an occurrence at `at`
+///
+/// One-shot jobs fire on whole seconds with their delay truncated;
+/// rounding the delay up keeps the fire from landing before `at`.
+fn fire_instant(at: jiff::Timestamp, now: jiff::Timestamp) -> Instant {
+    let delay = Duration::try_from(at.duration_since(now)).unwrap_or(Duration::ZERO);
+    Instant::now() + delay + Duration::from_secs(1)
+}
+
+/// Add a job to tokio-cron-scheduler
+///
+/// # Returns
+/// The tokio-cron-scheduler id of the job
+async fn register(scheduler: &TokioCronScheduler, job: Job) -> AppResult<Uuid> {
+    scheduler.add(job).await.map_err(|e| AppError::Internal {
+        source: anyhow::Error::from(e),
+    })
+}
+
+/// Remove a job's pending fire from tokio-cron-scheduler
+///
+/// A failure is only logged: once the entry is gone from the scheduler's
+/// entries, ticks of the fire are dropped anyway.
+async fn unschedule(scheduler: &TokioCronScheduler, job_id: i32, uuid: Uuid) {
+    if let Err(e) = scheduler.remove(&uuid).await {
+        tracing::warn!(job_id, error = %e, "Failed to remove cron entry");
+    }
+}
 
 /// Wrapper around tokio-cron-scheduler with dynamic job management
+///
+/// Each enabled [`ScheduledJob`](crate::jobs::ScheduledJob) owns exactly one cron entry. The entry only
+/// carries the job id; the row is re-read on every tick so that payload,
+/// retry and timeout changes apply without re-registering the entry.
+///
+/// Replicas sharing a database may all run a scheduler: each fire is
+/// claimed in the database (see [`JobRepository::claim_fire`]) and
+/// concurrency limits are enforced when executions are recorded.
+///
+/// tokio-cron-scheduler evaluates cron jobs on a fixed UTC offset, which
+/// breaks across DST changes. Fire times are therefore computed on the wall
+/// clock of the job's timezone (see [`cron::next_after`]) and each one is
+/// registered as a one-shot job; firing it registers the next one.
+///
+/// Workflow runs created by scheduled `workflow` jobs are claimed and
+/// driven by whichever replica polls for them first, and so are tasks in
+/// the ad-hoc queue (see [`QueueWorker`]).
+#[derive(Clone)]
 pub struct JobScheduler {
     scheduler: Arc<Mutex<TokioCronScheduler>>,
     executor: Arc<JobExecutor>,
     registry: Arc<JobRegistry>,
+    workflows: Arc<WorkflowRunner>,
+    queue: Arc<QueueWorker>,
     job_repo: JobRepository,
+    entries: Arc<Mutex<HashMap<i32, CronEntry>>>,
+    shutdown: CancellationToken,
 }
 
 impl JobScheduler {
-    pub async fn new(db_pool: AsyncDbPool, registry: JobRegistry) -> AppResult<Self> {
+    /// Create a scheduler for the registered tasks
+    ///
+    /// # Arguments
+    /// * `db_pool` - Database connection pool
+    /// * `registry` - Tasks that jobs can run
+    /// * `notifications` - Service sending job alerts
+    pub async fn new(
+        db_pool: AsyncDbPool,
+        registry: JobRegistry,
+        notifications: NotificationService,
+    ) -> AppResult<Self> {
         let scheduler = TokioCronScheduler::new()
             .await
             .map_err(|e| AppError::Internal {
                 source: anyhow::Error::from(e),
             })?;
 
+        let executor = Arc::new(JobExecutor::new(db_pool.clone(), notifications));
+        let registry = Arc::new(registry);
+        let workflows = Arc::new(WorkflowRunner::new(
+            Arc::clone(&executor),
+            Arc::clone(&registry),
+            JobRepository::new(db_pool.clone()),
+            JobExecutionRepository::new(db_pool.clone()),
+            JobWorkflowRepository::new(db_pool.clone()),
+        ));
+        let queue = Arc::new(QueueWorker::new(db_pool.clone(), Arc::clone(&registry)));
+
         Ok(Self {
             scheduler: Arc::new(Mutex::new(scheduler)),
-            executor: Arc::new(JobExecutor::new(db_pool.clone())),
-            registry: Arc::new(registry),
+            executor,
+            registry,
+            workflows,
+            queue,
             job_repo: JobRepository::new(db_pool),
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            shutdown: CancellationToken::new(),
         })
     }
 
@@ -44,11 +287,37 @@ impl JobScheduler {
             .map_err(|e| AppError::Internal {
                 source: anyhow::Error::from(e),
             })?;
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut workflows = tokio::time::interval(WORKFLOW_POLL_INTERVAL);
+            workflows.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut queue = tokio::time::interval(QUEUE_POLL_INTERVAL);
+            queue.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = this.shutdown.cancelled() => break,
+                    _ = workflows.tick() => {
+                        if let Err(e) = this.workflows.start_pending().await {
+                            tracing::error!(error = %e, "Failed to start pending workflow runs");
+                        }
+                    }
+                    _ = queue.tick() => {
+                        if let Err(e) = this.queue.poll().await {
+                            tracing::error!(error = %e, "Failed to poll the job queue");
+                        }
+                    }
+                }
+            }
+        });
+
         Ok(())
     }
 
     /// Stop the scheduler gracefully
     pub async fn stop(&self) -> AppResult<()> {
+        self.shutdown.cancel();
+        self.entries.lock().await.clear();
         self.scheduler
             .lock()
             .await
@@ -60,56 +329,504 @@ impl JobScheduler {
         Ok(())
     }
 
-    /// Reload all enabled jobs from database
+    /// Reconcile cron entries with the enabled jobs in the database
+    ///
+    /// Adds entries for new or resumed jobs, removes entries of deleted or
+    /// paused jobs and replaces entries whose schedule or timezone changed.
+    /// Unchanged jobs keep their entry, so calling this after every job
+    /// mutation never registers a job twice. Jobs that cannot be scheduled
+    /// are logged and skipped.
+    ///
+    /// # Errors
+    /// - `Database` if the enabled jobs could not be loaded
     pub async fn reload_jobs(&self) -> AppResult<()> {
         let jobs = self.job_repo.get_enabled_jobs().await?;
+        let desired = desired_registrations(&jobs);
 
-        for job in jobs {
-            self.schedule_job(job).await?;
-        }
-
+        // Held for the whole pass so concurrent reloads cannot interleave
+        let mut entries = self.entries.lock().await;
+        self.apply(&mut entries, desired).await;
         Ok(())
     }
 
-    /// Schedule a single job
-    async fn schedule_job(&self, job: ScheduledJob) -> AppResult<()> {
-        let executor = Arc::clone(&self.executor);
-        let registry = Arc::clone(&self.registry);
-        let job_clone = job.clone();
+    /// Apply the changes between registered and desired entries
+    ///
+    /// A job's new entry is registered before its current one is removed,
+    /// so a schedule that fails to register leaves the current entry in
+    /// place. Failures are logged and the other jobs are still reconciled.
+    async fn apply(
+        &self,
+        entries: &mut HashMap<i32, CronEntry>,
+        mut desired: HashMap<i32, Registration>,
+    ) {
+        let plan = ReconcilePlan::diff(entries, &desired);
+        let scheduler = self.scheduler.lock().await;
 
-        let cron_job = Job::new_async(job.cron_expression.as_str(), move |_uuid, _lock| {
-            let executor = Arc::clone(&executor);
-            let registry = Arc::clone(&registry);
-            let job = job_clone.clone();
+        for id in &plan.remove {
+            if let Some(entry) = entries.remove(id) {
+                unschedule(&scheduler, *id, entry.uuid).await;
+                tracing::debug!(job_id = id, "Removed cron entry");
+            }
+        }
 
-            Box::pin(async move {
-                let payload = job.payload.clone().unwrap_or(serde_json::json!({}));
+        let now = jiff::Timestamp::now();
+        for id in plan.add.iter().chain(&plan.replace) {
+            let Some(registration) = desired.remove(id) else {
+                continue;
+            };
+            let clock = match registration.clock(now) {
+                Ok(clock) => clock,
+                Err(e) => {
+                    tracing::warn!(job_id = id, error = %e, "Skipping job with an invalid schedule");
+                    continue;
+                }
+            };
 
-                match registry.create_task(&job.job_type, payload) {
-                    Ok(task) => {
-                        if let Err(e) = executor.execute_job(job, task).await {
-                            tracing::error!(error = %e, "Job execution failed");
+            let next = clock.next();
+            match next {
+                Some(next) => {
+                    let registered = match self.fire_job(*id, clock, next) {
+                        Ok(cron_job) => register(&scheduler, cron_job).await,
+                        Err(e) => Err(e),
+                    };
+                    let uuid = match registered {
+                        Ok(uuid) => uuid,
+                        Err(e) => {
+                            tracing::error!(job_id = id, error = %e, "Failed to schedule cron entry");
+                            continue;
                         }
+                    };
+                    tracing::debug!(
+                        job_id = id,
+                        timezone = %registration.timezone,
+                        "Scheduled cron entry"
+                    );
+                    if let Some(replaced) = entries.insert(*id, CronEntry { uuid, registration }) {
+                        unschedule(&scheduler, *id, replaced.uuid).await;
                     }
-                    Err(e) => {
-                        tracing::error!(error = %e, "Failed to create task");
+                }
+                None => {
+                    tracing::debug!(job_id = id, "Cron schedule has no further fire times");
+                    if let Some(replaced) = entries.remove(id) {
+                        unschedule(&scheduler, *id, replaced.uuid).await;
                     }
                 }
-            })
+            }
+
+            if let Err(e) = self
+                .job_repo
+                .set_next_run(*id, next.map(utc_datetime))
+                .await
+            {
+                tracing::error!(job_id = id, error = %e, "Failed to store next run time");
+            }
+        }
+    }
+
+    /// Run a job immediately, outside its cron schedule
+    ///
+    /// Disabled jobs can be run as well, which helps when debugging a task.
+    ///
+    /// # Arguments
+    /// * `job_id` - Job to run
+    /// * `payload` - Payload to use instead of the job's stored payload
+    ///
+    /// # Returns
+    /// Execution id of the first attempt
+    ///
+    /// # Errors
+    /// - `NotFound` if the job or its job type does not exist
+    /// - `UnprocessableContent` if the job's concurrency limit is reached
+    pub async fn run_now(
+        &self,
+        job_id: i32,
+        payload: Option<serde_json::Value>,
+    ) -> AppResult<Uuid> {
+        let job = self.job_repo.get_by_id(job_id).await?;
+        let payload = payload
+            .or_else(|| job.payload.clone())
+            .unwrap_or(serde_json::json!({}));
+        let task = self.registry.create_task(&job.job_type, payload)?;
+
+        self.executor.trigger(job, task).await
+    }
+
+    /// Run a workflow immediately
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Workflow to run
+    ///
+    /// # Returns
+    /// The new run; its steps start in the background
+    ///
+    /// # Errors
+    /// - `NotFound` if the workflow does not exist
+    pub async fn run_workflow(&self, workflow_id: i32) -> AppResult<WorkflowRun> {
+        self.workflows.start(workflow_id).await
+    }
+
+    /// Add a one-shot task to the job queue
+    ///
+    /// # Returns
+    /// The queued task; it runs once `run_at` has passed
+    ///
+    /// # Errors
+    /// - `Validation` if the job type is unknown or the payload does not
+    ///   fit it
+    pub async fn enqueue(&self, task: NewQueuedTask) -> AppResult<QueuedTask> {
+        self.queue.enqueue(task).await
+    }
+
+    /// Cancel an in-flight execution
+    ///
+    /// # Arguments
+    /// * `execution_id` - Execution id of the run, see [`JobExecutor::cancel`]
+    ///
+    /// # Returns
+    /// `true` if the execution was running and has been signalled
+    ///
+    /// # Errors
+    /// - `Database` if a run owned by another replica could not be flagged
+    pub async fn cancel_execution(&self, execution_id: Uuid) -> AppResult<bool> {
+        self.executor.cancel(execution_id).await
+    }
+
+    /// One-shot tokio-cron-scheduler job that ticks a job's entry
+    ///
+    /// # Arguments
+    /// * `job_id` - Job to fire
+    /// * `clock` - Fire times of the job's entry
+    /// * `at` - Fire time to tick at, from [`EntryClock::next`]
+    fn fire_job(&self, job_id: i32, clock: EntryClock, at: jiff::Timestamp) -> AppResult<Job> {
+        let this = self.clone();
+        let instant = fire_instant(at, jiff::Timestamp::now());
+        Job::new_one_shot_at_instant_async(instant, move |uuid, scheduler| {
+            let this = this.clone();
+            let clock = clock.clone();
+            Box::pin(async move { this.on_tick(uuid, scheduler, job_id, clock, at).await })
+        })
+        .map_err(|e| AppError::Internal {
+            source: anyhow::Error::from(e),
         })
-        .map_err(|e| AppError::BadRequest {
-            message: format!("Invalid cron expression: {}", e),
-        })?;
+    }
 
-        self.scheduler
-            .lock()
-            .await
-            .add(cron_job)
-            .await
-            .map_err(|e| AppError::Internal {
-                source: anyhow::Error::from(e),
-            })?;
+    /// Register a job's next fire, then fire the current one
+    ///
+    /// Ticks of an entry that was replaced or removed in the meantime are
+    /// dropped.
+    ///
+    /// # Arguments
+    /// * `uuid` - tokio-cron-scheduler id of the entry that ticked
+    /// * `scheduler` - Scheduler the entry was registered with
+    /// * `job_id` - Job to fire
+    /// * `clock` - Fire times of the job's entry
+    /// * `armed` - Fire time the entry was registered for
+    async fn on_tick(
+        &self,
+        uuid: Uuid,
+        scheduler: TokioCronScheduler,
+        job_id: i32,
+        mut clock: EntryClock,
+        armed: jiff::Timestamp,
+    ) {
+        let fire = clock.wake(armed, jiff::Timestamp::now());
+        let next_run_at = clock.next();
 
-        Ok(())
+        {
+            let mut entries = self.entries.lock().await;
+            if entries.get(&job_id).map(|entry| entry.uuid) != Some(uuid) {
+                tracing::debug!(job_id, "Skipping tick of a replaced cron entry");
+                return;
+            }
+            let registered = match next_run_at {
+                Some(next) => match self.fire_job(job_id, clock, next) {
+                    Ok(cron_job) => register(&scheduler, cron_job).await.map(Some),
+                    Err(e) => Err(e),
+                },
+                None => Ok(None),
+            };
+            match registered {
+                Ok(Some(next_uuid)) => {
+                    if let Some(entry) = entries.get_mut(&job_id) {
+                        entry.uuid = next_uuid;
+                    }
+                }
+                Ok(None) => {
+                    entries.remove(&job_id);
+                    tracing::debug!(job_id, "Cron schedule has no further fire times");
+                }
+                Err(e) => {
+                    // Registered again on the next reload
+                    entries.remove(&job_id);
+                    tracing::error!(job_id, error = %e, "Failed to schedule the next job fire");
+                }
+            }
+        }
+
+        self.fire(job_id, fire, next_run_at).await;
+    }
+
+    /// Claim an occurrence of a job and run it if this replica won the claim
+    ///
+    /// Every replica fires the same entries; only the one that claims the
+    /// occurrence runs it, against the latest job row.
+    async fn fire(&self, job_id: i32, fire: jiff::Timestamp, next_run_at: Option<jiff::Timestamp>) {
+        let claimed = self
+            .job_repo
+            .claim_fire(job_id, utc_datetime(fire), next_run_at.map(utc_datetime))
+            .await;
+        let job = match claimed {
+            Ok(Some(job)) => job,
+            Ok(None) => {
+                tracing::debug!(
+                    job_id,
+                    "Skipping fire claimed elsewhere or for a disabled job"
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::error!(job_id, error = %e, "Failed to claim job fire");
+                return;
+            }
+        };
+        let payload = job.payload.clone().unwrap_or(serde_json::json!({}));
+
+        match self.registry.create_task(&job.job_type, payload) {
+            Ok(task) => {
+                if let Err(e) = self.executor.execute_job(job, task).await {
+                    tracing::error!(error = %e, "Job execution failed");
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to create task");
+            }
+        }
+    }
+}
+
+/// A fire time as stored in the database, in UTC
+fn utc_datetime(ts: jiff::Timestamp) -> DateTime {
+    DateTime::from(ts.to_zoned(TimeZone::UTC).datetime())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registration(cron: &str) -> Registration {
+        Registration {
+            cron_expression: cron.to_string(),
+            timezone: "UTC".to_string(),
+        }
+    }
+
+    fn entry(cron: &str) -> CronEntry {
+        CronEntry {
+            uuid: Uuid::new_v4(),
+            registration: registration(cron),
+        }
+    }
+
+    fn ts(s: &str) -> jiff::Timestamp {
+        s.parse().unwrap()
+    }
+
+    /// Fires of an entry armed at `start` that wakes `lag` after each fire
+    /// time, with the `next_run_at` stored alongside each claim
+    fn fires(
+        expression: &str,
+        timezone: &str,
+        start: jiff::Timestamp,
+        lag: jiff::SignedDuration,
+        count: usize,
+    ) -> Vec<(jiff::Timestamp, Option<jiff::Timestamp>)> {
+        let schedule = Arc::new(cron::parse(expression).unwrap());
+        let mut clock = EntryClock::new(schedule, TimeZone::get(timezone).unwrap(), start);
+        let mut fires = Vec::new();
+        while fires.len() < count {
+            let Some(next) = clock.next() else {
+                break;
+            };
+            let fire = clock.wake(next, next + lag);
+            fires.push((fire, clock.next()));
+        }
+        fires
+    }
+
+    #[test]
+    fn test_fire_time_is_the_scheduled_occurrence() {
+        let schedule = cron::parse("0 0 9 * * *").unwrap();
+        let tz = TimeZone::get("Europe/Berlin").unwrap();
+        let expected = ts("2026-02-12T08:00:00Z");
+
+        // Replicas claiming the same fire a fraction of a second apart,
+        // across a second boundary
+        let first = ts("2026-02-12T08:00:00.900Z");
+        let second = ts("2026-02-12T08:00:01.200Z");
+        assert_eq!(fire_time(&schedule, &tz, first), Some(expected));
+        assert_eq!(fire_time(&schedule, &tz, second), Some(expected));
+    }
+
+    #[test]
+    fn test_entry_fires_across_dst() {
+        let lag = jiff::SignedDuration::from_millis(300);
+
+        // Berlin springs forward from 02:00 CET to 03:00 CEST on 2026-03-29;
+        // 03:00 that day is the first instant of summer time
+        let spring = fires(
+            "0 0 3 * * *",
+            "Europe/Berlin",
+            ts("2026-03-28T12:00:00Z"),
+            lag,
+            2,
+        );
+        assert_eq!(
+            spring,
+            vec![
+                (ts("2026-03-29T01:00:00Z"), Some(ts("2026-03-30T01:00:00Z"))),
+                (ts("2026-03-30T01:00:00Z"), Some(ts("2026-03-31T01:00:00Z"))),
+            ]
+        );
+
+        // It falls back from 03:00 CEST to 02:00 CET on 2026-10-25; the
+        // repeated 02:30 fires once
+        let fall = fires(
+            "0 30 2 * * *",
+            "Europe/Berlin",
+            ts("2026-10-24T12:00:00Z"),
+            lag,
+            2,
+        );
+        assert_eq!(
+            fall,
+            vec![
+                (ts("2026-10-25T00:30:00Z"), Some(ts("2026-10-26T01:30:00Z"))),
+                (ts("2026-10-26T01:30:00Z"), Some(ts("2026-10-27T01:30:00Z"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_late_wake_fires_latest_occurrence_once() {
+        let schedule = Arc::new(cron::parse("0 0 * * * *").unwrap());
+        let mut clock = EntryClock::new(schedule, TimeZone::UTC, ts("2026-02-12T08:30:00Z"));
+
+        let armed = clock.next().unwrap();
+        assert_eq!(armed, ts("2026-02-12T09:00:00Z"));
+        assert_eq!(
+            clock.wake(armed, ts("2026-02-12T11:15:00Z")),
+            ts("2026-02-12T11:00:00Z")
+        );
+        assert_eq!(clock.next(), Some(ts("2026-02-12T12:00:00Z")));
+    }
+
+    #[test]
+    fn test_registration_rejects_invalid_schedule() {
+        assert!(Registration::new("0 0 8 * * *", "Europe/Berlin").is_ok());
+        assert!(Registration::new("0 0 8 * * *", "Mars/Base").is_err());
+        assert!(Registration::new("0 0 25 * * *", "UTC").is_err());
+    }
+
+    fn job(id: i32, cron_expression: &str, timezone: &str) -> ScheduledJob {
+        use jiff_diesel::NullableDateTime;
+
+        let dt = DateTime::from(jiff::civil::DateTime::constant(2026, 2, 12, 9, 0, 0, 0));
+        ScheduledJob {
+            id,
+            job_name: format!("job{}", id),
+            job_type: "data_cleanup".to_string(),
+            cron_expression: cron_expression.to_string(),
+            enabled: true,
+            allow_concurrent: false,
+            max_concurrent: None,
+            max_retries: 3,
+            retry_delay_seconds: 60,
+            retry_backoff_multiplier: bigdecimal::BigDecimal::from(2),
+            timeout_seconds: 300,
+            payload: None,
+            description: None,
+            last_run_at: NullableDateTime::from(None),
+            last_run_status: None,
+            next_run_at: NullableDateTime::from(None),
+            created_at: dt,
+            updated_at: dt,
+            created_by: None,
+            last_fired_at: NullableDateTime::from(None),
+            timezone: timezone.to_string(),
+            consecutive_failures: 0,
+            retry_max_delay_seconds: 3600,
+            retry_jitter: crate::jobs::types::RetryJitter::None,
+            retry_on_timeout: false,
+        }
+    }
+
+    #[test]
+    fn test_desired_registrations_skip_invalid_jobs() {
+        let jobs = vec![
+            job(1, "0 0 8 * * *", "Europe/Berlin"),
+            job(2, "0 0 8 * * *", "Mars/Base"),
+            job(3, "0 0 25 * * *", "UTC"),
+            job(4, "0 * * * * *", "UTC"),
+        ];
+
+        let desired = desired_registrations(&jobs);
+        let mut ids: Vec<i32> = desired.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 4]);
+    }
+
+    #[test]
+    fn test_diff_adds_new_jobs() {
+        let entries = HashMap::new();
+        let desired = HashMap::from([
+            (1, registration("0 * * * * *")),
+            (2, registration("0 0 * * * *")),
+        ]);
+
+        let plan = ReconcilePlan::diff(&entries, &desired);
+        assert_eq!(plan.add, vec![1, 2]);
+        assert!(plan.replace.is_empty());
+        assert!(plan.remove.is_empty());
+    }
+
+    #[test]
+    fn test_diff_keeps_unchanged_jobs() {
+        let entries = HashMap::from([(1, entry("0 * * * * *"))]);
+        let desired = HashMap::from([(1, registration("0 * * * * *"))]);
+
+        assert_eq!(
+            ReconcilePlan::diff(&entries, &desired),
+            ReconcilePlan::default()
+        );
+    }
+
+    #[test]
+    fn test_diff_replaces_and_removes() {
+        let entries = HashMap::from([
+            (1, entry("0 * * * * *")),
+            (2, entry("0 0 * * * *")),
+            (3, entry("0 0 0 * * *")),
+        ]);
+        // Job 1 was rescheduled, job 2 paused or deleted, job 4 created
+        let desired = HashMap::from([
+            (1, registration("30 * * * * *")),
+            (3, registration("0 0 0 * * *")),
+            (4, registration("0 * * * * *")),
+        ]);
+
+        let plan = ReconcilePlan::diff(&entries, &desired);
+        assert_eq!(plan.add, vec![4]);
+        assert_eq!(plan.replace, vec![1]);
+        assert_eq!(plan.remove, vec![2]);
+    }
+
+    #[test]
+    fn test_diff_replaces_on_timezone_change() {
+        let entries = HashMap::from([(1, entry("0 0 8 * * *"))]);
+        let mut moved = registration("0 0 8 * * *");
+        moved.timezone = "Europe/Berlin".to_string();
+        let desired = HashMap::from([(1, moved)]);
+
+        assert_eq!(ReconcilePlan::diff(&entries, &desired).replace, vec![1]);
     }
 }
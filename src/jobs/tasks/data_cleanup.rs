@@ -1,9 +1,10 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
 
 use crate::error::AppResult;
 use crate::jobs::types::{JobContext, JobTask};
-use crate::repositories::JobExecutionRepository;
+use crate::repositories::{JobExecutionRepository, JobQueueRepository};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataCleanupTask {
@@ -24,24 +25,35 @@ impl JobTask for DataCleanupTask {
         "data_cleanup"
     }
 
-    async fn execute(&self, ctx: JobContext) -> AppResult<()> {
-        let execution_repo = JobExecutionRepository::new(ctx.db_pool);
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        let execution_repo = JobExecutionRepository::new(ctx.db_pool.clone());
         let deleted = execution_repo
             .cleanup_old_executions(self.retention_days)
             .await?;
+        ctx.report_progress(50, format!("Deleted {} executions", deleted))
+            .await;
+
+        let queue_repo = JobQueueRepository::new(ctx.db_pool.clone());
+        let deleted_tasks = queue_repo.cleanup_succeeded(self.retention_days).await?;
+        ctx.report_progress(100, format!("Deleted {} queued tasks", deleted_tasks))
+            .await;
 
         tracing::info!(
             deleted_count = deleted,
+            deleted_tasks,
             retention_days = self.retention_days,
             "Data cleanup completed"
         );
 
-        Ok(())
+        Ok(Some(json!({
+            "deleted_count": deleted,
+            "deleted_tasks": deleted_tasks,
+        })))
     }
 
     fn description(&self) -> Option<String> {
         Some(format!(
-            "Clean up job execution history older than {} days",
+            "Clean up job execution history and succeeded queued tasks older than {} days",
             self.retention_days
         ))
     }
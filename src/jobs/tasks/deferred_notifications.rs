@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+use crate::error::AppResult;
+use crate::jobs::types::{JobContext, JobTask};
+use crate::repositories::Repositories;
+use crate::services::Services;
+
+/// Delivers notifications deferred by quiet hours once their window ends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeferredNotificationTask {
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+}
+
+fn default_batch_size() -> i64 {
+    100
+}
+
+#[async_trait]
+impl JobTask for DeferredNotificationTask {
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        "deferred_notifications"
+    }
+
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        let services = Services::new(Repositories::new(ctx.db_pool));
+        let delivered = services
+            .notifications
+            .deliver_deferred(self.batch_size)
+            .await?;
+
+        tracing::info!(
+            delivered_count = delivered,
+            batch_size = self.batch_size,
+            "Deferred notifications delivered"
+        );
+
+        Ok(Some(json!({ "delivered_count": delivered })))
+    }
+
+    fn description(&self) -> Option<String> {
+        Some(format!(
+            "Deliver up to {} notifications deferred by quiet hours",
+            self.batch_size
+        ))
+    }
+}
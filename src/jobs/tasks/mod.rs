@@ -1,3 +1,15 @@
 pub mod data_cleanup;
+pub mod deferred_notifications;
+pub mod notification_broadcast;
+pub mod notification_digest;
+pub mod notification_log_retention;
+pub mod secret_rotation;
+pub mod workflow;
 
 pub use data_cleanup::DataCleanupTask;
+pub use deferred_notifications::DeferredNotificationTask;
+pub use notification_broadcast::NotificationBroadcastTask;
+pub use notification_digest::NotificationDigestTask;
+pub use notification_log_retention::NotificationLogRetentionTask;
+pub use secret_rotation::SecretRotationTask;
+pub use workflow::WorkflowTask;
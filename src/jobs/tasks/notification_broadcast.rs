@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+use crate::error::AppResult;
+use crate::jobs::types::{JobContext, JobTask};
+use crate::repositories::Repositories;
+use crate::services::Services;
+
+/// Delivers queued broadcast notifications to their recipients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationBroadcastTask {
+    /// Maximum number of recipients processed per run
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+    /// Maximum number of recipients dispatched in parallel
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+}
+
+fn default_batch_size() -> i64 {
+    500
+}
+
+fn default_concurrency() -> usize {
+    10
+}
+
+#[async_trait]
+impl JobTask for NotificationBroadcastTask {
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        "notification_broadcast"
+    }
+
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        let services = Services::new(Repositories::new(ctx.db_pool));
+        let processed = services
+            .notifications
+            .process_broadcasts(self.batch_size, self.concurrency)
+            .await?;
+
+        if processed > 0 {
+            tracing::info!(
+                processed_count = processed,
+                concurrency = self.concurrency,
+                "Broadcast recipients processed"
+            );
+        }
+
+        Ok(Some(json!({ "processed_count": processed })))
+    }
+
+    fn description(&self) -> Option<String> {
+        Some(format!(
+            "Deliver up to {} queued broadcast notifications, {} at a time",
+            self.batch_size, self.concurrency
+        ))
+    }
+}
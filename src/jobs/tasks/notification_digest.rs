@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+use crate::error::AppResult;
+use crate::jobs::types::{JobContext, JobTask};
+use crate::repositories::Repositories;
+use crate::services::Services;
+
+/// Flushes batched messages of digest-mode channels as one summary each
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationDigestTask {}
+
+#[async_trait]
+impl JobTask for NotificationDigestTask {
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        "notification_digest"
+    }
+
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        let services = Services::new(Repositories::new(ctx.db_pool));
+        let flushed = services.notifications.flush_digests().await?;
+
+        tracing::info!(digest_count = flushed, "Notification digests flushed");
+
+        Ok(Some(json!({ "digest_count": flushed })))
+    }
+
+    fn description(&self) -> Option<String> {
+        Some("Send batched notifications as one digest per channel".to_string())
+    }
+}
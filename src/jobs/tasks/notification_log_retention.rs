@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+use std::path::Path;
+
+use crate::error::AppResult;
+use crate::jobs::types::{JobContext, JobTask};
+use crate::repositories::Repositories;
+use crate::services::Services;
+
+/// Purges old notification logs, keeping their counts as daily stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationLogRetentionTask {
+    #[serde(default = "default_retention_days")]
+    pub retention_days: i64,
+    /// Number of logs deleted per batch
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+    /// Directory for gzipped JSONL archives of purged logs (none = delete only)
+    #[serde(default)]
+    pub archive_dir: Option<String>,
+}
+
+fn default_retention_days() -> i64 {
+    90
+}
+
+fn default_batch_size() -> i64 {
+    1000
+}
+
+#[async_trait]
+impl JobTask for NotificationLogRetentionTask {
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        "notification_log_retention"
+    }
+
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        let services = Services::new(Repositories::new(ctx.db_pool));
+        let purged = services
+            .notifications
+            .purge_logs(
+                self.retention_days,
+                self.batch_size,
+                self.archive_dir.as_deref().map(Path::new),
+            )
+            .await?;
+
+        tracing::info!(
+            purged_count = purged,
+            retention_days = self.retention_days,
+            archive_dir = self.archive_dir.as_deref(),
+            "Notification log retention completed"
+        );
+
+        Ok(Some(json!({ "purged_count": purged })))
+    }
+
+    fn description(&self) -> Option<String> {
+        let action = if self.archive_dir.is_some() {
+            "Archive and delete"
+        } else {
+            "Delete"
+        };
+        Some(format!(
+            "{} notification logs older than {} days",
+            action, self.retention_days
+        ))
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+use crate::error::AppResult;
+use crate::jobs::types::{JobContext, JobTask};
+use crate::repositories::Repositories;
+use crate::services::Services;
+use crate::utils::secret::secret_cipher;
+
+/// Re-encrypts notification channel secrets under the active master key
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecretRotationTask {}
+
+#[async_trait]
+impl JobTask for SecretRotationTask {
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        "secret_rotation"
+    }
+
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        if secret_cipher().is_none() {
+            tracing::debug!("No encryption key configured, skipping secret rotation");
+            return Ok(None);
+        }
+
+        let services = Services::new(Repositories::new(ctx.db_pool));
+        let rotated = services.notifications.rotate_channel_secrets().await?;
+
+        tracing::info!(channel_count = rotated, "Channel secrets rotated");
+
+        Ok(Some(json!({ "channel_count": rotated })))
+    }
+
+    fn description(&self) -> Option<String> {
+        Some("Encrypt channel secrets with the current encryption key".to_string())
+    }
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+use crate::error::AppResult;
+use crate::jobs::types::{JobContext, JobTask};
+use crate::repositories::JobWorkflowRepository;
+
+/// Starts a run of a workflow on the job's schedule
+///
+/// The run is only queued here; a scheduler claims and drives it, so this
+/// execution finishes as soon as the run exists. Follow the run itself
+/// for the outcome of its steps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTask {
+    pub workflow_id: i32,
+}
+
+#[async_trait]
+impl JobTask for WorkflowTask {
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        "workflow"
+    }
+
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>> {
+        let run = JobWorkflowRepository::new(ctx.db_pool)
+            .create_run(self.workflow_id)
+            .await?;
+
+        tracing::info!(
+            workflow_id = self.workflow_id,
+            run_id = %run.run_id,
+            "Workflow run queued"
+        );
+
+        Ok(Some(json!({ "run_id": run.run_id })))
+    }
+
+    fn description(&self) -> Option<String> {
+        Some(format!("Run workflow {}", self.workflow_id))
+    }
+}
@@ -1,22 +1,116 @@
 use async_trait::async_trait;
 use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use tokio_util::sync::CancellationToken;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::db::AsyncDbPool;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{NewJobExecutionLog, NewQueuedTaskLog};
+use crate::jobs::retry;
+use crate::repositories::{JobExecutionRepository, JobQueueRepository};
+
+/// What a [`JobContext`] runs on behalf of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextOwner {
+    /// A run of a scheduled job, recorded as a job execution
+    Job(i32),
+    /// One attempt of a task in the job queue
+    QueuedTask { id: i64, attempt: i32 },
+}
 
 /// Job execution context passed to tasks
 #[derive(Clone)]
 pub struct JobContext {
+    /// Execution record of a scheduled job run, or the ID a queued task's
+    /// attempt was claimed with
     pub execution_id: Uuid,
-    pub job_id: i32,
+    pub owner: ContextOwner,
     pub job_name: String,
     pub retry_attempt: u32,
     pub db_pool: AsyncDbPool,
     pub cancellation_token: CancellationToken,
+    /// Results of the upstream steps when run as a workflow step, keyed by
+    /// step name; empty otherwise
+    pub upstream_results: HashMap<String, JsonValue>,
+}
+
+impl JobContext {
+    /// Report how far the execution has progressed
+    ///
+    /// Shown on the execution, or on the queued task, while it runs;
+    /// best-effort, a failure to record it does not fail the task.
+    ///
+    /// # Arguments
+    /// * `percent` - Completion, capped at 100
+    /// * `message` - What the task is doing
+    pub async fn report_progress(&self, percent: u8, message: impl Into<String>) {
+        let percent = percent.min(100);
+        let message = message.into();
+        let recorded = match self.owner {
+            ContextOwner::Job(_) => {
+                JobExecutionRepository::new(self.db_pool.clone())
+                    .update_progress(self.execution_id, percent, message)
+                    .await
+            }
+            ContextOwner::QueuedTask { id, attempt } => {
+                JobQueueRepository::new(self.db_pool.clone())
+                    .update_progress(id, attempt, percent, message)
+                    .await
+            }
+        };
+        if let Err(e) = recorded {
+            tracing::warn!(owner = ?self.owner, error = %e, "Failed to record job progress");
+        }
+    }
+
+    /// Append a structured log line to the execution, or to the queued task
+    ///
+    /// Best-effort like [`Self::report_progress`].
+    ///
+    /// # Arguments
+    /// * `level` - Severity of the line
+    /// * `message` - Log message
+    /// * `fields` - Optional structured data attached to the line
+    pub async fn log(
+        &self,
+        level: JobLogLevel,
+        message: impl Into<String>,
+        fields: Option<JsonValue>,
+    ) {
+        let message = message.into();
+        let recorded = match self.owner {
+            ContextOwner::Job(_) => {
+                let line = NewJobExecutionLog {
+                    execution_id: self.execution_id,
+                    level,
+                    message,
+                    fields,
+                };
+                JobExecutionRepository::new(self.db_pool.clone())
+                    .append_log(line)
+                    .await
+            }
+            ContextOwner::QueuedTask { id, attempt } => {
+                let line = NewQueuedTaskLog {
+                    task_id: id,
+                    attempt,
+                    level,
+                    message,
+                    fields,
+                };
+                JobQueueRepository::new(self.db_pool.clone())
+                    .append_log(line)
+                    .await
+            }
+        };
+        if let Err(e) = recorded {
+            tracing::warn!(owner = ?self.owner, error = %e, "Failed to record job log");
+        }
+    }
 }
 
 /// Job execution status
@@ -32,6 +126,23 @@ pub enum JobStatus {
     Cancelled,
 }
 
+impl JobStatus {
+    /// Streak of unsuccessful runs after a run ends with this status
+    ///
+    /// Failed and timed-out runs extend the streak, a successful run ends it,
+    /// and cancelled runs leave it unchanged.
+    ///
+    /// # Arguments
+    /// * `previous` - Unsuccessful runs in a row before this run
+    pub fn next_failure_streak(self, previous: i32) -> i32 {
+        match self {
+            JobStatus::Success => 0,
+            JobStatus::Failed | JobStatus::Timeout => previous.saturating_add(1),
+            _ => previous,
+        }
+    }
+}
+
 impl std::fmt::Display for JobStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -45,6 +156,70 @@ impl std::fmt::Display for JobStatus {
     }
 }
 
+/// Status of a one-shot task in the job queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum, ToSchema)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::QueuedTaskStatus")]
+#[serde(rename_all = "lowercase")]
+pub enum QueuedTaskStatus {
+    /// Waiting for `run_at`, or for a retry
+    Queued,
+    /// Claimed by a worker until its visibility timeout
+    Running,
+    Succeeded,
+    /// Out of attempts; kept until requeued or deleted
+    Dead,
+}
+
+impl std::fmt::Display for QueuedTaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueuedTaskStatus::Queued => write!(f, "queued"),
+            QueuedTaskStatus::Running => write!(f, "running"),
+            QueuedTaskStatus::Succeeded => write!(f, "succeeded"),
+            QueuedTaskStatus::Dead => write!(f, "dead"),
+        }
+    }
+}
+
+/// Outcome of a run that triggers a job alert rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum, ToSchema)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::JobAlertEvent")]
+#[serde(rename_all = "snake_case")]
+pub enum JobAlertEvent {
+    /// The run failed after all its retries
+    Failure,
+    /// The run timed out
+    Timeout,
+    /// The run was the `threshold`-th unsuccessful run in a row
+    ConsecutiveFailures,
+    /// The run succeeded after one or more unsuccessful runs
+    Recovery,
+}
+
+/// Severity of a job execution log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum, ToSchema)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::JobLogLevel")]
+#[serde(rename_all = "lowercase")]
+pub enum JobLogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Randomization of the delay between two attempts of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum, ToSchema)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::RetryJitter")]
+#[serde(rename_all = "lowercase")]
+pub enum RetryJitter {
+    /// Exact exponential backoff
+    None,
+    /// Uniformly random between zero and the backoff
+    Full,
+    /// Random between the base delay and three times the previous delay
+    Decorrelated,
+}
+
 /// Trait that all job tasks must implement
 #[async_trait]
 pub trait JobTask: Send + Sync + std::fmt::Debug {
@@ -54,12 +229,24 @@ pub trait JobTask: Send + Sync + std::fmt::Debug {
         Self: Sized;
 
     /// Execute the task
-    async fn execute(&self, ctx: JobContext) -> AppResult<()>;
+    ///
+    /// # Returns
+    /// An optional result stored on the execution, and handed to
+    /// downstream steps when run in a workflow
+    async fn execute(&self, ctx: JobContext) -> AppResult<Option<JsonValue>>;
 
     /// Optional description
     fn description(&self) -> Option<String> {
         None
     }
+
+    /// Whether a failed attempt may succeed if tried again
+    ///
+    /// Errors marked with [`retry::permanent`] are never retried, whatever
+    /// this returns. Defaults to [`retry::is_transient`].
+    fn is_retryable(&self, error: &AppError) -> bool {
+        retry::is_transient(error)
+    }
 }
 
 #[cfg(test)]
@@ -86,9 +273,32 @@ mod tests {
         assert_eq!(deserialized, JobStatus::Success);
     }
 
+    #[test]
+    fn test_queued_task_status_serialization() {
+        let json = serde_json::to_string(&QueuedTaskStatus::Dead).unwrap();
+        assert_eq!(json, "\"dead\"");
+
+        let status: QueuedTaskStatus = serde_json::from_str("\"queued\"").unwrap();
+        assert_eq!(status, QueuedTaskStatus::Queued);
+    }
+
+    #[test]
+    fn test_job_log_level_serialization() {
+        let json = serde_json::to_string(&JobLogLevel::Warn).unwrap();
+        assert_eq!(json, "\"warn\"");
+    }
+
     #[test]
     fn test_job_status_equality() {
         assert_eq!(JobStatus::Pending, JobStatus::Pending);
         assert_ne!(JobStatus::Pending, JobStatus::Running);
     }
+
+    #[test]
+    fn test_next_failure_streak() {
+        assert_eq!(JobStatus::Failed.next_failure_streak(2), 3);
+        assert_eq!(JobStatus::Timeout.next_failure_streak(2), 3);
+        assert_eq!(JobStatus::Success.next_failure_streak(2), 0);
+        assert_eq!(JobStatus::Cancelled.next_failure_streak(2), 2);
+    }
 }
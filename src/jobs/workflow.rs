@@ -0,0 +1,583 @@
+//! Workflow orchestration.
+//!
+//! A workflow is a DAG of steps, each running an existing scheduled job.
+//! A step starts once every step it depends on has succeeded, so
+//! independent steps fan out and a step with several parents fans in.
+//! Steps whose parents did not all succeed are cancelled without running.
+//!
+//! Each step runs through the [`JobExecutor`] like any other run, so it
+//! keeps its job's retries, timeout and concurrency limit; a step whose job
+//! is at its limit stays pending and is started once a slot frees up. Its
+//! execution's
+//! `result` is handed to downstream steps through
+//! [`JobContext::upstream_results`](crate::jobs::JobContext::upstream_results).
+//!
+//! A run is driven by the replica that claimed it, under a lease it renews
+//! while the run makes progress. If that replica stops, another one claims
+//! the run once the lease expires and starts the steps that were left
+//! running again.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::jobs::executor::JobExecutor;
+use crate::jobs::models::{WorkflowRun, WorkflowStepRun};
+use crate::jobs::registry::JobRegistry;
+use crate::jobs::types::JobStatus;
+use crate::repositories::{JobExecutionRepository, JobRepository, JobWorkflowRepository};
+
+/// Maximum number of steps in a workflow
+pub const MAX_WORKFLOW_STEPS: usize = 50;
+
+/// How long a claimed run stays leased to its replica without a heartbeat
+const RUN_LEASE_SECONDS: i32 = 60;
+
+/// How often the replica driving a run renews its lease
+const RUN_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(20);
+
+/// How long a step whose job is at its concurrency limit waits before it is
+/// started again
+const STEP_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+fn invalid_steps(reason: impl Into<String>) -> AppError {
+    AppError::Validation {
+        field: "steps".to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Dependency graph of a workflow's steps
+#[derive(Debug, Clone)]
+pub struct WorkflowGraph {
+    /// Step names with their dependencies, in definition order
+    steps: Vec<(String, Vec<String>)>,
+}
+
+impl WorkflowGraph {
+    /// Builds a graph, checking that it is a valid DAG
+    ///
+    /// # Arguments
+    /// * `steps` - Step names with the names of the steps they depend on
+    ///
+    /// # Errors
+    /// - `Validation` if there are no or too many steps, a name is empty or
+    ///   repeated, a dependency is unknown, or the dependencies form a cycle
+    pub fn new(steps: Vec<(String, Vec<String>)>) -> AppResult<Self> {
+        if steps.is_empty() {
+            return Err(invalid_steps("A workflow needs at least one step"));
+        }
+        if steps.len() > MAX_WORKFLOW_STEPS {
+            return Err(invalid_steps(format!(
+                "A workflow can have at most {} steps",
+                MAX_WORKFLOW_STEPS
+            )));
+        }
+
+        let mut names = HashSet::new();
+        for (name, _) in &steps {
+            if name.trim().is_empty() {
+                return Err(invalid_steps("Step names must not be empty"));
+            }
+            if !names.insert(name.as_str()) {
+                return Err(invalid_steps(format!("Duplicate step name: {}", name)));
+            }
+        }
+        for (name, depends_on) in &steps {
+            for dependency in depends_on {
+                if dependency == name {
+                    return Err(invalid_steps(format!("Step {} depends on itself", name)));
+                }
+                if !names.contains(dependency.as_str()) {
+                    return Err(invalid_steps(format!(
+                        "Step {} depends on unknown step {}",
+                        name, dependency
+                    )));
+                }
+            }
+        }
+
+        let graph = Self { steps };
+        graph.check_acyclic()?;
+        Ok(graph)
+    }
+
+    /// Rejects dependency cycles by repeatedly removing steps whose
+    /// dependencies have all been removed
+    fn check_acyclic(&self) -> AppResult<()> {
+        let mut done: HashSet<&str> = HashSet::new();
+        loop {
+            let before = done.len();
+            for (name, depends_on) in &self.steps {
+                if depends_on.iter().all(|d| done.contains(d.as_str())) {
+                    done.insert(name.as_str());
+                }
+            }
+            if done.len() == self.steps.len() {
+                return Ok(());
+            }
+            if done.len() == before {
+                let mut cyclic: Vec<&str> = self
+                    .steps
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .filter(|name| !done.contains(name))
+                    .collect();
+                cyclic.sort_unstable();
+                return Err(invalid_steps(format!(
+                    "Steps form a dependency cycle: {}",
+                    cyclic.join(", ")
+                )));
+            }
+        }
+    }
+
+    /// Pending steps whose dependencies have all succeeded
+    pub fn ready(&self, statuses: &HashMap<String, JobStatus>) -> Vec<String> {
+        self.steps
+            .iter()
+            .filter(|(name, _)| statuses.get(name) == Some(&JobStatus::Pending))
+            .filter(|(_, depends_on)| {
+                depends_on
+                    .iter()
+                    .all(|d| statuses.get(d) == Some(&JobStatus::Success))
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Pending steps with a dependency that finished without succeeding
+    pub fn blocked(&self, statuses: &HashMap<String, JobStatus>) -> Vec<String> {
+        let mut blocked = Vec::new();
+        for (name, depends_on) in &self.steps {
+            let pending = statuses.get(name) == Some(&JobStatus::Pending);
+            let failed_dependency = depends_on.iter().any(|d| {
+                matches!(
+                    statuses.get(d),
+                    Some(JobStatus::Failed | JobStatus::Timeout | JobStatus::Cancelled)
+                )
+            });
+            if pending && failed_dependency {
+                blocked.push(name.clone());
+            }
+        }
+        blocked
+    }
+}
+
+/// Overall status of a finished run from the statuses of its steps
+///
+/// A run succeeds when every step did; it failed if any step failed or
+/// timed out, and was cancelled otherwise.
+pub fn run_status<'a>(statuses: impl IntoIterator<Item = &'a JobStatus>) -> JobStatus {
+    let mut status = JobStatus::Success;
+    for step in statuses {
+        match step {
+            JobStatus::Failed | JobStatus::Timeout => return JobStatus::Failed,
+            JobStatus::Success => {}
+            _ => status = JobStatus::Cancelled,
+        }
+    }
+    status
+}
+
+/// Drives workflow runs on this replica
+pub struct WorkflowRunner {
+    executor: Arc<JobExecutor>,
+    registry: Arc<JobRegistry>,
+    job_repo: JobRepository,
+    execution_repo: JobExecutionRepository,
+    workflow_repo: JobWorkflowRepository,
+}
+
+impl WorkflowRunner {
+    pub fn new(
+        executor: Arc<JobExecutor>,
+        registry: Arc<JobRegistry>,
+        job_repo: JobRepository,
+        execution_repo: JobExecutionRepository,
+        workflow_repo: JobWorkflowRepository,
+    ) -> Self {
+        Self {
+            executor,
+            registry,
+            job_repo,
+            execution_repo,
+            workflow_repo,
+        }
+    }
+
+    /// Start a run of a workflow in the background
+    ///
+    /// # Errors
+    /// - `NotFound` if the workflow does not exist
+    pub async fn start(self: &Arc<Self>, workflow_id: i32) -> AppResult<WorkflowRun> {
+        let run = self.workflow_repo.create_run(workflow_id).await?;
+        let owner = Uuid::new_v4();
+        // Another replica polling right now may claim it first and drive it
+        match self
+            .workflow_repo
+            .claim_run(run.id, owner, RUN_LEASE_SECONDS)
+            .await?
+        {
+            Some(claimed) => {
+                self.spawn(claimed.clone(), owner);
+                Ok(claimed)
+            }
+            None => Ok(run),
+        }
+    }
+
+    /// Claim pending runs, created by scheduled `workflow` jobs, and runs
+    /// abandoned by a replica that stopped, and drive them in the background
+    pub async fn start_pending(self: &Arc<Self>) -> AppResult<()> {
+        let owner = Uuid::new_v4();
+        for run in self
+            .workflow_repo
+            .claim_runs(owner, RUN_LEASE_SECONDS)
+            .await?
+        {
+            self.spawn(run, owner);
+        }
+        Ok(())
+    }
+
+    fn spawn(self: &Arc<Self>, run: WorkflowRun, owner: Uuid) {
+        let runner = Arc::clone(self);
+        tokio::spawn(async move {
+            match runner.drive(&run, owner).await {
+                Ok(status) => {
+                    tracing::info!(run_id = %run.run_id, status = %status, "Workflow run finished");
+                }
+                Err(e) => {
+                    tracing::error!(run_id = %run.run_id, error = %e, "Workflow run failed");
+                    if let Err(e) = runner
+                        .workflow_repo
+                        .finish_run(run.id, owner, JobStatus::Failed)
+                        .await
+                    {
+                        tracing::error!(run_id = %run.run_id, error = %e, "Failed to record workflow run");
+                    }
+                }
+            }
+        });
+    }
+
+    /// Run the steps of a run until none can make progress
+    ///
+    /// Steps left running by a previous owner of the run are started again.
+    ///
+    /// # Arguments
+    /// * `run` - Run claimed by this replica
+    /// * `owner` - Claim token of the run
+    ///
+    /// # Returns
+    /// The final status of the run
+    ///
+    /// # Errors
+    /// - `Internal` if another replica claimed the run after this replica's
+    ///   lease expired
+    async fn drive(&self, run: &WorkflowRun, owner: Uuid) -> AppResult<JobStatus> {
+        let step_runs = self.workflow_repo.list_step_runs(run.id).await?;
+        let graph = WorkflowGraph::new(
+            step_runs
+                .iter()
+                .map(|step| (step.step_name.clone(), step.depends_on.clone()))
+                .collect(),
+        )?;
+        let steps: HashMap<String, WorkflowStepRun> = step_runs
+            .into_iter()
+            .map(|step| (step.step_name.clone(), step))
+            .collect();
+
+        let mut statuses: HashMap<String, JobStatus> = steps
+            .iter()
+            .map(|(name, step)| {
+                let status = match step.status {
+                    JobStatus::Running => JobStatus::Pending,
+                    status => status,
+                };
+                (name.clone(), status)
+            })
+            .collect();
+        let mut results = self.upstream_results(&steps).await?;
+        let mut running = JoinSet::new();
+        let mut heartbeat = tokio::time::interval(RUN_HEARTBEAT_INTERVAL);
+        heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        heartbeat.tick().await;
+        // Ready steps whose job is at its concurrency limit
+        let mut deferred = HashSet::new();
+
+        loop {
+            // Settle every step that can be decided without waiting
+            loop {
+                let blocked = graph.blocked(&statuses);
+                let mut ready = graph.ready(&statuses);
+                ready.retain(|name| !deferred.contains(name));
+                if blocked.is_empty() && ready.is_empty() {
+                    break;
+                }
+
+                for name in blocked {
+                    self.workflow_repo
+                        .finish_step(
+                            steps[&name].id,
+                            JobStatus::Cancelled,
+                            None,
+                            Some("Skipped because an upstream step did not succeed".to_string()),
+                        )
+                        .await?;
+                    statuses.insert(name, JobStatus::Cancelled);
+                }
+
+                for name in ready {
+                    let step = &steps[&name];
+                    let upstream = step
+                        .depends_on
+                        .iter()
+                        .map(|d| (d.clone(), results.get(d).cloned().unwrap_or_default()))
+                        .collect();
+
+                    match self.start_step(step, upstream).await {
+                        Ok(Some((execution_id, handle))) => {
+                            self.workflow_repo.start_step(step.id, execution_id).await?;
+                            statuses.insert(name.clone(), JobStatus::Running);
+                            running.spawn(async move { (name, handle.await) });
+                        }
+                        Ok(None) => {
+                            tracing::debug!(run_id = %run.run_id, step = %name, "Step's job is at its concurrency limit, retrying later");
+                            deferred.insert(name);
+                        }
+                        Err(e) => {
+                            self.workflow_repo
+                                .finish_step(step.id, JobStatus::Failed, None, Some(e.to_string()))
+                                .await?;
+                            statuses.insert(name, JobStatus::Failed);
+                        }
+                    }
+                }
+            }
+
+            if running.is_empty() && deferred.is_empty() {
+                break;
+            }
+
+            let retry_at = tokio::time::Instant::now() + STEP_RETRY_INTERVAL;
+            let joined = loop {
+                tokio::select! {
+                    Some(joined) = running.join_next() => break Some(joined),
+                    _ = tokio::time::sleep_until(retry_at), if !deferred.is_empty() => break None,
+                    _ = heartbeat.tick() => {
+                        let renewed = self
+                            .workflow_repo
+                            .extend_lease(run.id, owner, RUN_LEASE_SECONDS)
+                            .await?;
+                        if !renewed {
+                            return Err(AppError::Internal {
+                                source: anyhow::anyhow!(
+                                    "Workflow run {} was claimed by another replica",
+                                    run.run_id
+                                ),
+                            });
+                        }
+                    }
+                }
+            };
+            let Some(joined) = joined else {
+                deferred.clear();
+                continue;
+            };
+            let (name, outcome) = joined.map_err(|e| AppError::Internal {
+                source: anyhow::Error::from(e),
+            })?;
+            let step = &steps[&name];
+
+            let status = match outcome {
+                Ok(execution_id) => {
+                    let execution = self
+                        .execution_repo
+                        .get_by_execution_id(execution_id)
+                        .await?;
+                    if execution.status == JobStatus::Success {
+                        results.insert(name.clone(), execution.result.unwrap_or_default());
+                    }
+                    self.workflow_repo
+                        .finish_step(
+                            step.id,
+                            execution.status,
+                            Some(execution_id),
+                            execution.error_message,
+                        )
+                        .await?;
+                    execution.status
+                }
+                Err(e) => {
+                    self.workflow_repo
+                        .finish_step(step.id, JobStatus::Failed, None, Some(e.to_string()))
+                        .await?;
+                    JobStatus::Failed
+                }
+            };
+            statuses.insert(name, status);
+        }
+
+        let status = run_status(statuses.values());
+        self.workflow_repo.finish_run(run.id, owner, status).await?;
+        Ok(status)
+    }
+
+    /// Results of the steps that already succeeded, when resuming a run
+    /// claimed from another replica
+    async fn upstream_results(
+        &self,
+        steps: &HashMap<String, WorkflowStepRun>,
+    ) -> AppResult<HashMap<String, JsonValue>> {
+        let mut results = HashMap::new();
+        for (name, step) in steps {
+            let Some(execution_id) = step.execution_id else {
+                continue;
+            };
+            if step.status != JobStatus::Success {
+                continue;
+            }
+            let execution = self
+                .execution_repo
+                .get_by_execution_id(execution_id)
+                .await?;
+            results.insert(name.clone(), execution.result.unwrap_or_default());
+        }
+        Ok(results)
+    }
+
+    /// Start the job of a step with its stored payload
+    ///
+    /// # Returns
+    /// None if the job is at its concurrency limit
+    async fn start_step(
+        &self,
+        step: &WorkflowStepRun,
+        upstream_results: HashMap<String, JsonValue>,
+    ) -> AppResult<Option<(Uuid, tokio::task::JoinHandle<Uuid>)>> {
+        let job = self.job_repo.get_by_id(step.job_id).await?;
+        let payload = job.payload.clone().unwrap_or(serde_json::json!({}));
+        let task = self.registry.create_task(&job.job_type, payload)?;
+
+        self.executor.try_spawn(job, task, upstream_results).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, depends_on: &[&str]) -> (String, Vec<String>) {
+        (
+            name.to_string(),
+            depends_on.iter().map(|d| d.to_string()).collect(),
+        )
+    }
+
+    /// sync -> (stats, anchors) -> digest
+    fn diamond() -> WorkflowGraph {
+        WorkflowGraph::new(vec![
+            step("sync", &[]),
+            step("stats", &["sync"]),
+            step("anchors", &["sync"]),
+            step("digest", &["stats", "anchors"]),
+        ])
+        .unwrap()
+    }
+
+    fn statuses(entries: &[(&str, JobStatus)]) -> HashMap<String, JobStatus> {
+        entries
+            .iter()
+            .map(|(name, status)| (name.to_string(), *status))
+            .collect()
+    }
+
+    #[test]
+    fn test_graph_rejects_invalid_steps() {
+        assert!(WorkflowGraph::new(vec![]).is_err());
+        assert!(WorkflowGraph::new(vec![step("a", &[]), step("a", &[])]).is_err());
+        assert!(WorkflowGraph::new(vec![step("a", &["missing"])]).is_err());
+        assert!(WorkflowGraph::new(vec![step("a", &["a"])]).is_err());
+    }
+
+    #[test]
+    fn test_graph_rejects_cycles() {
+        let err = WorkflowGraph::new(vec![
+            step("root", &[]),
+            step("a", &["root", "c"]),
+            step("b", &["a"]),
+            step("c", &["b"]),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("a, b, c"));
+    }
+
+    #[test]
+    fn test_ready_fans_out_and_in() {
+        let graph = diamond();
+        let pending = JobStatus::Pending;
+
+        let initial = statuses(&[
+            ("sync", pending),
+            ("stats", pending),
+            ("anchors", pending),
+            ("digest", pending),
+        ]);
+        assert_eq!(graph.ready(&initial), vec!["sync"]);
+
+        let fan_out = statuses(&[
+            ("sync", JobStatus::Success),
+            ("stats", pending),
+            ("anchors", pending),
+            ("digest", pending),
+        ]);
+        assert_eq!(graph.ready(&fan_out), vec!["stats", "anchors"]);
+
+        let half_done = statuses(&[
+            ("sync", JobStatus::Success),
+            ("stats", JobStatus::Success),
+            ("anchors", JobStatus::Running),
+            ("digest", pending),
+        ]);
+        assert!(graph.ready(&half_done).is_empty());
+
+        let fan_in = statuses(&[
+            ("sync", JobStatus::Success),
+            ("stats", JobStatus::Success),
+            ("anchors", JobStatus::Success),
+            ("digest", pending),
+        ]);
+        assert_eq!(graph.ready(&fan_in), vec!["digest"]);
+    }
+
+    #[test]
+    fn test_blocked_after_upstream_failure() {
+        let graph = diamond();
+        let current = statuses(&[
+            ("sync", JobStatus::Success),
+            ("stats", JobStatus::Failed),
+            ("anchors", JobStatus::Running),
+            ("digest", JobStatus::Pending),
+        ]);
+
+        assert_eq!(graph.blocked(&current), vec!["digest"]);
+        assert!(graph.ready(&current).is_empty());
+    }
+
+    #[test]
+    fn test_run_status() {
+        use JobStatus::*;
+
+        assert_eq!(run_status(&[Success, Success]), Success);
+        assert_eq!(run_status(&[Success, Cancelled]), Cancelled);
+        assert_eq!(run_status(&[Cancelled, Timeout, Success]), Failed);
+    }
+}
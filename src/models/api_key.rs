@@ -0,0 +1,49 @@
+//! Notification API key models for database operations.
+//!
+//! API keys let external systems send notifications on behalf of a user
+//! without a JWT.
+
+use diesel::prelude::*;
+use jiff_diesel::DateTime;
+
+// ============================================================================
+// NotificationApiKey Models (Query/Insert)
+// ============================================================================
+
+/// NotificationApiKey query model for SELECT operations
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationApiKey {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    /// Public part of the key used for lookup
+    pub key_prefix: String,
+    /// Argon2 hash of the full key
+    pub key_hash: String,
+    /// IP addresses or CIDR ranges allowed to use the key (empty = any)
+    pub allowed_ips: Vec<String>,
+    /// Requests allowed per window (None = unlimited)
+    pub rate_limit_count: Option<i32>,
+    pub rate_limit_window_seconds: i32,
+    pub window_started_at: Option<DateTime>,
+    pub window_count: i32,
+    pub expires_at: Option<DateTime>,
+    pub last_used_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+/// NewNotificationApiKey insert model for INSERT operations
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = crate::schema::notification_api_keys)]
+pub struct NewNotificationApiKey {
+    pub user_id: i32,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    pub allowed_ips: Vec<String>,
+    pub rate_limit_count: Option<i32>,
+    pub rate_limit_window_seconds: i32,
+    pub expires_at: Option<DateTime>,
+}
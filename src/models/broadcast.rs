@@ -0,0 +1,154 @@
+//! Broadcast models for database operations.
+//!
+//! This module provides data models for user groups and for broadcast
+//! notifications sent to a group or to every user.
+
+use diesel::prelude::*;
+use jiff_diesel::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+// ============================================================================
+// Enums
+// ============================================================================
+
+/// Progress of a broadcast
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    diesel_derive_enum::DbEnum,
+)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::BroadcastStatus")]
+#[serde(rename_all = "lowercase")]
+pub enum BroadcastStatus {
+    /// Queued, no recipient processed yet
+    Pending,
+    /// Recipients are being processed
+    Running,
+    /// Every recipient has been processed
+    Completed,
+}
+
+/// Delivery state of one broadcast recipient
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    diesel_derive_enum::DbEnum,
+)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::BroadcastRecipientStatus")]
+#[serde(rename_all = "lowercase")]
+pub enum BroadcastRecipientStatus {
+    /// Waiting in the queue
+    Pending,
+    /// Claimed by a delivery run
+    Sending,
+    /// Sent through at least one of the recipient's channels
+    Delivered,
+    /// Held by the recipient's quiet hours
+    Deferred,
+    /// Dropped by the recipient's quiet hours
+    Dropped,
+    /// No channel accepted the message
+    Failed,
+}
+
+// ============================================================================
+// UserGroup Models (Query/Insert/Update)
+// ============================================================================
+
+/// UserGroup query model for SELECT operations
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::user_groups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserGroup {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+/// NewUserGroup insert model for INSERT operations
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = crate::schema::user_groups)]
+pub struct NewUserGroup {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// UserGroupMember insert model linking a user to a group
+#[derive(Debug, Insertable, Clone, Copy)]
+#[diesel(table_name = crate::schema::user_group_members)]
+pub struct NewUserGroupMember {
+    pub group_id: i32,
+    pub user_id: i32,
+}
+
+// ============================================================================
+// NotificationBroadcast Models (Query/Insert)
+// ============================================================================
+
+/// NotificationBroadcast query model for SELECT operations
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_broadcasts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationBroadcast {
+    pub id: i32,
+    pub created_by: Option<i32>,
+    /// Selected audience (None = all users)
+    pub group_id: Option<i32>,
+    /// The serialized notification message
+    pub message: JsonValue,
+    pub status: BroadcastStatus,
+    pub total_recipients: i32,
+    pub delivered_count: i32,
+    pub deferred_count: i32,
+    pub dropped_count: i32,
+    pub failed_count: i32,
+    pub created_at: DateTime,
+    pub started_at: Option<DateTime>,
+    pub completed_at: Option<DateTime>,
+}
+
+/// NewNotificationBroadcast insert model for INSERT operations
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = crate::schema::notification_broadcasts)]
+pub struct NewNotificationBroadcast {
+    pub created_by: Option<i32>,
+    pub group_id: Option<i32>,
+    pub message: JsonValue,
+}
+
+/// BroadcastRecipient query model for SELECT operations
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_broadcast_recipients)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BroadcastRecipient {
+    pub id: i64,
+    pub broadcast_id: i32,
+    pub user_id: i32,
+    pub status: BroadcastRecipientStatus,
+    pub error_message: Option<String>,
+    pub processed_at: Option<DateTime>,
+}
+
+/// NewBroadcastRecipient insert model queuing one recipient
+#[derive(Debug, Insertable, Clone, Copy)]
+#[diesel(table_name = crate::schema::notification_broadcast_recipients)]
+pub struct NewBroadcastRecipient {
+    pub broadcast_id: i32,
+    pub user_id: i32,
+}
@@ -1,8 +1,26 @@
+mod api_key;
+mod broadcast;
 mod notification;
+mod notification_routing;
 mod user;
 
+pub use api_key::{NewNotificationApiKey, NotificationApiKey};
+pub use broadcast::{
+    BroadcastRecipient, BroadcastRecipientStatus, BroadcastStatus, NewBroadcastRecipient,
+    NewNotificationBroadcast, NewUserGroup, NewUserGroupMember, NotificationBroadcast, UserGroup,
+};
 pub use notification::{
-    BarkConfig, ChannelType, NewNotificationChannel, NewNotificationLog, NotificationChannel,
-    NotificationLog, NotificationStatus, UpdateNotificationChannel, WebhookConfig,
+    BarkConfig, ChannelType, EmailConfig, GotifyConfig, MASKED_SECRET, MessageFormat,
+    NewNotificationChannel, NewNotificationLog, NewNotificationLogStat, NewNotificationTemplate,
+    NotificationChannel, NotificationDeliveryStats, NotificationLog, NotificationLogStat,
+    NotificationLogTotals, NotificationSeverity, NotificationStatus, NotificationTemplate,
+    NtfyConfig, StatsBucket, TemplateVariant, TemplateVariants, UpdateNotificationChannel,
+    UpdateNotificationTemplate, WebPushKeys, WebPushSubscription, WebhookAuth, WebhookBody,
+    WebhookConfig, WebhookSigning, WebhookSuccess,
+};
+pub use notification_routing::{
+    DeferredNotification, NewDeferredNotification, NewNotificationQuietHours,
+    NewNotificationRoutingRule, NotificationQuietHours, NotificationRoutingRule, QuietHoursAction,
+    RoutingMode, UpdateNotificationRoutingRule,
 };
 pub use user::{NewUser, UpdateUser, User};
@@ -1,10 +1,10 @@
 //! Notification models for database operations.
 //!
 //! This module provides data models for the notification system including
-//! notification channels and logs.
+//! notification channels, logs, and message templates.
 
 use diesel::prelude::*;
-use jiff_diesel::DateTime;
+use jiff_diesel::{Date, DateTime};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
@@ -34,6 +34,129 @@ pub enum ChannelType {
     Discord,
     Slack,
     Bark,
+    Gotify,
+    Ntfy,
+    #[db_enum(rename = "webpush")]
+    WebPush,
+}
+
+impl ChannelType {
+    /// Returns the message format this channel type renders best
+    ///
+    /// Used to pick a template variant when a message is delivered, e.g.
+    /// plain text for Bark pushes and embeds for Discord.
+    pub fn preferred_format(&self) -> MessageFormat {
+        match self {
+            ChannelType::Webhook | ChannelType::Sms | ChannelType::Bark | ChannelType::WebPush => {
+                MessageFormat::Text
+            }
+            ChannelType::Email | ChannelType::Slack | ChannelType::Gotify | ChannelType::Ntfy => {
+                MessageFormat::Markdown
+            }
+            ChannelType::Discord => MessageFormat::Embed,
+        }
+    }
+
+    /// Returns the config fields holding secrets for this channel type
+    ///
+    /// Fields are dotted paths into the config object; `*` matches every
+    /// key of an object (e.g. all webhook header values).
+    pub fn secret_fields(&self) -> &'static [&'static str] {
+        match self {
+            ChannelType::Webhook => &["headers.*", "signing.secret", "auth.password", "auth.token"],
+            ChannelType::Bark => &["device_key"],
+            ChannelType::Gotify => &["app_token"],
+            ChannelType::Ntfy => &["token", "password"],
+            ChannelType::WebPush => &["keys.auth"],
+            ChannelType::Email => &["password"],
+            ChannelType::Sms => &["api_key", "auth_token"],
+            ChannelType::Discord | ChannelType::Slack => &["webhook_url", "token"],
+        }
+    }
+
+    /// Locates the secret string values present in a config
+    ///
+    /// # Arguments
+    /// * `config` - Channel config JSON
+    ///
+    /// # Returns
+    /// JSON pointers (RFC 6901) to each secret string value
+    pub fn secret_pointers(&self, config: &JsonValue) -> Vec<String> {
+        let mut pointers = Vec::new();
+        for field in self.secret_fields() {
+            let segments: Vec<&str> = field.split('.').collect();
+            collect_pointers(config, &segments, String::new(), &mut pointers);
+        }
+        pointers
+    }
+}
+
+/// Placeholder returned by the API instead of secret config values
+pub const MASKED_SECRET: &str = "********";
+
+/// Walks a dotted field path, recording pointers to string values
+fn collect_pointers(value: &JsonValue, segments: &[&str], prefix: String, out: &mut Vec<String>) {
+    let Some((first, rest)) = segments.split_first() else {
+        if value.is_string() {
+            out.push(prefix);
+        }
+        return;
+    };
+
+    let Some(object) = value.as_object() else {
+        return;
+    };
+
+    for (key, child) in object {
+        if *first == "*" || key == first {
+            let pointer = format!("{}/{}", prefix, key.replace('~', "~0").replace('/', "~1"));
+            collect_pointers(child, rest, pointer, out);
+        }
+    }
+}
+
+/// Output format of a rendered notification message
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageFormat {
+    /// Plain text
+    #[default]
+    Text,
+    /// Markdown-formatted text
+    Markdown,
+    /// Rich embed (title, description, and media)
+    Embed,
+}
+
+/// Severity of a notification message
+///
+/// Ordered from least to most urgent, so severities can be compared when
+/// matching routing rules and quiet-hours bypass thresholds.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    diesel_derive_enum::DbEnum,
+)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::NotificationSeverity")]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationSeverity {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Critical,
 }
 
 /// Status of a notification log entry
@@ -43,6 +166,7 @@ pub enum ChannelType {
     Copy,
     PartialEq,
     Eq,
+    Hash,
     Serialize,
     Deserialize,
     utoipa::ToSchema,
@@ -55,6 +179,12 @@ pub enum NotificationStatus {
     Sent,
     Failed,
     Retrying,
+    /// Skipped by deduplication or rate limiting
+    Suppressed,
+    /// Held for the channel's next digest
+    Batched,
+    /// Included in a digest that has been flushed
+    Digested,
 }
 
 // ============================================================================
@@ -75,6 +205,46 @@ pub struct NotificationChannel {
     pub priority: i32,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    /// Maximum sends per rate-limit window (None = unlimited)
+    pub rate_limit_count: Option<i32>,
+    pub rate_limit_window_seconds: i32,
+    /// How long a dedup key suppresses repeats (0 = no deduplication)
+    pub dedup_window_seconds: i32,
+    /// Accumulate non-urgent messages and send them as a periodic digest
+    pub digest_enabled: bool,
+    /// Consecutive failures before the channel is disabled (0 = never)
+    pub failure_threshold: i32,
+    pub consecutive_failures: i32,
+    pub success_count: i64,
+    pub failure_count: i64,
+    pub last_success_at: Option<DateTime>,
+    pub last_failure_at: Option<DateTime>,
+    pub last_error: Option<String>,
+    /// Why the channel was disabled automatically (None if disabled by the user)
+    pub disabled_reason: Option<String>,
+    pub disabled_at: Option<DateTime>,
+}
+
+impl NotificationChannel {
+    /// Returns the share of delivery attempts that succeeded
+    ///
+    /// # Returns
+    /// A ratio between 0 and 1, or None if nothing was sent yet
+    pub fn success_rate(&self) -> Option<f64> {
+        let total = self.success_count + self.failure_count;
+        (total > 0).then(|| self.success_count as f64 / total as f64)
+    }
+
+    /// Returns the config with every secret value replaced by [`MASKED_SECRET`]
+    pub fn masked_config(&self) -> JsonValue {
+        let mut config = self.config.clone();
+        for pointer in self.channel_type.secret_pointers(&self.config) {
+            if let Some(value) = config.pointer_mut(&pointer) {
+                *value = JsonValue::String(MASKED_SECRET.to_string());
+            }
+        }
+        config
+    }
 }
 
 /// NewNotificationChannel insert model for INSERT operations
@@ -87,6 +257,11 @@ pub struct NewNotificationChannel {
     pub config: JsonValue,
     pub enabled: bool,
     pub priority: i32,
+    pub rate_limit_count: Option<i32>,
+    pub rate_limit_window_seconds: i32,
+    pub dedup_window_seconds: i32,
+    pub digest_enabled: bool,
+    pub failure_threshold: i32,
 }
 
 /// UpdateNotificationChannel model for UPDATE operations
@@ -97,6 +272,15 @@ pub struct UpdateNotificationChannel {
     pub config: Option<JsonValue>,
     pub enabled: Option<bool>,
     pub priority: Option<i32>,
+    pub rate_limit_count: Option<Option<i32>>,
+    pub rate_limit_window_seconds: Option<i32>,
+    pub dedup_window_seconds: Option<i32>,
+    pub digest_enabled: Option<bool>,
+    pub failure_threshold: Option<i32>,
+    pub consecutive_failures: Option<i32>,
+    pub disabled_reason: Option<Option<String>>,
+    #[serde(skip)]
+    pub disabled_at: Option<Option<DateTime>>,
 }
 
 // ============================================================================
@@ -115,6 +299,11 @@ pub struct NotificationLog {
     pub error_message: Option<String>,
     pub retry_count: i32,
     pub sent_at: DateTime,
+    pub dedup_key: Option<String>,
+    pub request_meta: Option<JsonValue>,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub duration_ms: Option<i64>,
 }
 
 /// NewNotificationLog insert model for INSERT operations
@@ -126,8 +315,240 @@ pub struct NewNotificationLog {
     pub status: NotificationStatus,
     pub error_message: Option<String>,
     pub retry_count: i32,
+    pub dedup_key: Option<String>,
+    pub request_meta: Option<JsonValue>,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub duration_ms: Option<i64>,
 }
 
+// ============================================================================
+// NotificationLogStat Models (Query/Insert)
+// ============================================================================
+
+/// Daily delivery counts of a channel, rolled up from purged logs
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_log_stats)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationLogStat {
+    pub day: Date,
+    pub channel_id: i32,
+    pub status: NotificationStatus,
+    pub message_count: i64,
+    pub retry_count: i64,
+    pub total_duration_ms: i64,
+    pub timed_count: i64,
+    pub updated_at: DateTime,
+}
+
+/// NewNotificationLogStat insert model, added onto existing counts
+#[derive(Debug, Insertable, Clone, PartialEq, Eq)]
+#[diesel(table_name = crate::schema::notification_log_stats)]
+pub struct NewNotificationLogStat {
+    pub day: Date,
+    pub channel_id: i32,
+    pub status: NotificationStatus,
+    pub message_count: i64,
+    pub retry_count: i64,
+    pub total_duration_ms: i64,
+    pub timed_count: i64,
+}
+
+impl NewNotificationLogStat {
+    /// Sums logs into one row per day, channel and status
+    ///
+    /// # Arguments
+    /// * `logs` - The logs to roll up
+    ///
+    /// # Returns
+    /// The daily counts, ordered by day, channel and status
+    pub fn rollup(logs: &[NotificationLog]) -> Vec<Self> {
+        let mut rows: HashMap<(Date, i32, NotificationStatus), Self> = HashMap::new();
+
+        for log in logs {
+            let day = Date::from(log.sent_at.to_jiff().date());
+            let row = rows
+                .entry((day, log.channel_id, log.status))
+                .or_insert_with(|| Self {
+                    day,
+                    channel_id: log.channel_id,
+                    status: log.status,
+                    message_count: 0,
+                    retry_count: 0,
+                    total_duration_ms: 0,
+                    timed_count: 0,
+                });
+
+            row.message_count += 1;
+            row.retry_count += i64::from(log.retry_count);
+            if let Some(duration) = log.duration_ms {
+                row.total_duration_ms += duration;
+                row.timed_count += 1;
+            }
+        }
+
+        let mut rows: Vec<Self> = rows.into_values().collect();
+        rows.sort_by_key(|row| (row.day, row.channel_id, row.status as u8));
+        rows
+    }
+}
+
+/// All-time delivery counts of a channel for one status
+///
+/// Combines the rolled-up stats of purged logs with the logs still stored.
+#[derive(Debug, QueryableByName, Clone)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationLogTotals {
+    #[diesel(sql_type = crate::schema::sql_types::NotificationStatus)]
+    pub status: NotificationStatus,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub message_count: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub retry_count: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub total_duration_ms: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub timed_count: i64,
+}
+
+/// Time bucket size for delivery stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsBucket {
+    Hour,
+    #[default]
+    Day,
+}
+
+impl StatsBucket {
+    /// Returns the `date_trunc` field name of the bucket
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            StatsBucket::Hour => "hour",
+            StatsBucket::Day => "day",
+        }
+    }
+
+    /// Returns the range covered when no start time is given
+    pub fn default_range(&self) -> jiff::SignedDuration {
+        match self {
+            StatsBucket::Hour => jiff::SignedDuration::from_hours(24),
+            StatsBucket::Day => jiff::SignedDuration::from_hours(30 * 24),
+        }
+    }
+
+    /// Returns the longest range that can be queried, bounding the bucket count
+    pub fn max_range(&self) -> jiff::SignedDuration {
+        match self {
+            StatsBucket::Hour => jiff::SignedDuration::from_hours(31 * 24),
+            StatsBucket::Day => jiff::SignedDuration::from_hours(366 * 24),
+        }
+    }
+}
+
+/// Delivery counts and latency of one provider
+///
+/// Rows with a `bucket` cover one time bucket; rows without one cover the
+/// whole queried range. Only completed deliveries (sent or failed) count.
+#[derive(Debug, QueryableByName, Clone)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationDeliveryStats {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
+    pub bucket: Option<DateTime>,
+    #[diesel(sql_type = crate::schema::sql_types::ChannelType)]
+    pub channel_type: ChannelType,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub sends: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub failures: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub retries: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub p50_duration_ms: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub p95_duration_ms: Option<f64>,
+}
+
+// ============================================================================
+// NotificationTemplate Models (Query/Insert/Update)
+// ============================================================================
+
+/// NotificationTemplate query model for SELECT operations
+///
+/// Templates with `user_id = None` are built-in system templates.
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_templates)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationTemplate {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub template_key: String,
+    pub description: Option<String>,
+    pub title_template: Option<String>,
+    pub body_template: String,
+    pub variants: JsonValue,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+impl NotificationTemplate {
+    /// Returns true for built-in templates that are not owned by any user
+    pub fn is_system(&self) -> bool {
+        self.user_id.is_none()
+    }
+
+    /// Parse the JSONB variants into typed template variants
+    ///
+    /// # Returns
+    /// Result containing the variants keyed by format or deserialization error
+    pub fn parsed_variants(&self) -> Result<TemplateVariants, serde_json::Error> {
+        serde_json::from_value(self.variants.clone())
+    }
+}
+
+/// NewNotificationTemplate insert model for INSERT operations
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = crate::schema::notification_templates)]
+pub struct NewNotificationTemplate {
+    pub user_id: Option<i32>,
+    pub template_key: String,
+    pub description: Option<String>,
+    pub title_template: Option<String>,
+    pub body_template: String,
+    pub variants: JsonValue,
+}
+
+/// UpdateNotificationTemplate model for UPDATE operations
+#[derive(Debug, AsChangeset, Clone, Default)]
+#[diesel(table_name = crate::schema::notification_templates)]
+pub struct UpdateNotificationTemplate {
+    pub description: Option<String>,
+    pub title_template: Option<String>,
+    pub body_template: Option<String>,
+    pub variants: Option<JsonValue>,
+}
+
+/// Format-specific template text
+///
+/// # Example JSON
+/// ```json
+/// {
+///     "title": "{{ anchor_name }} is live",
+///     "body": "**{{ anchor_name }}** started streaming"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TemplateVariant {
+    /// Title template (falls back to the base title when omitted)
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Body template
+    pub body: String,
+}
+
+/// Template variants keyed by output format
+pub type TemplateVariants = HashMap<MessageFormat, TemplateVariant>;
+
 // ============================================================================
 // Config Type-Safe Helpers
 // ============================================================================
@@ -136,6 +557,18 @@ pub struct NewNotificationLog {
 ///
 /// This struct provides type-safe parsing and serialization of webhook
 /// configuration stored as JSONB in the database.
+///
+/// # Example JSON Config
+/// ```json
+/// {
+///     "url": "https://example.com/hook",
+///     "body": {"format": "json", "template": {"text": "{{ title }}: {{ body }}"}},
+///     "signing": {"secret": "s3cret"},
+///     "auth": {"type": "bearer", "token": "abc"},
+///     "query": {"source": "fusion"},
+///     "success": {"status_min": 200, "status_max": 299, "json_path": "ok"}
+/// }
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookConfig {
     pub url: String,
@@ -145,6 +578,21 @@ pub struct WebhookConfig {
     pub headers: HashMap<String, String>,
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
+    /// Request body format (defaults to the built-in JSON payload)
+    #[serde(default)]
+    pub body: WebhookBody,
+    /// HMAC request signing (optional)
+    #[serde(default)]
+    pub signing: Option<WebhookSigning>,
+    /// Authentication scheme (optional)
+    #[serde(default)]
+    pub auth: Option<WebhookAuth>,
+    /// Query parameters appended to the URL; values are templates
+    #[serde(default)]
+    pub query: HashMap<String, String>,
+    /// Rules deciding whether a response counts as delivered
+    #[serde(default)]
+    pub success: WebhookSuccess,
 }
 
 fn default_method() -> String {
@@ -155,6 +603,107 @@ fn default_timeout() -> u64 {
     30
 }
 
+/// Webhook request body format
+///
+/// String values in templates use the `{{ variable }}` syntax of
+/// notification templates; available variables are `title`, `body`,
+/// `metadata`, `severity`, `tags` and `timestamp`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum WebhookBody {
+    /// JSON body; without a template the default
+    /// `{"title", "body", "metadata"}` payload is sent
+    Json {
+        #[serde(default)]
+        template: Option<JsonValue>,
+    },
+    /// `application/x-www-form-urlencoded` body built from templated fields
+    Form { fields: HashMap<String, String> },
+    /// Raw text body rendered from a template
+    Raw {
+        template: String,
+        #[serde(default)]
+        content_type: Option<String>,
+    },
+}
+
+impl Default for WebhookBody {
+    fn default() -> Self {
+        Self::Json { template: None }
+    }
+}
+
+/// HMAC-SHA256 signing of webhook requests
+///
+/// The signature covers `"{timestamp}.{body}"` and is sent as
+/// `sha256=<hex digest>` alongside the Unix timestamp, so receivers can
+/// reject replayed requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSigning {
+    /// Shared secret used as the HMAC key
+    pub secret: String,
+    /// Header carrying the signature
+    #[serde(default = "default_signature_header")]
+    pub header: String,
+    /// Header carrying the signing timestamp
+    #[serde(default = "default_timestamp_header")]
+    pub timestamp_header: String,
+}
+
+fn default_signature_header() -> String {
+    "X-Signature".to_string()
+}
+
+fn default_timestamp_header() -> String {
+    "X-Timestamp".to_string()
+}
+
+/// Webhook authentication scheme
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebhookAuth {
+    /// HTTP basic authentication
+    Basic { username: String, password: String },
+    /// Bearer token in the Authorization header
+    Bearer { token: String },
+}
+
+/// Success predicate for webhook responses
+///
+/// A response succeeds when its status is within `status_min..=status_max`
+/// and, if `json_path` is set, the response body is JSON whose value at that
+/// dotted path equals `json_equals` (or is truthy when `json_equals` is unset).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSuccess {
+    #[serde(default = "default_status_min")]
+    pub status_min: u16,
+    #[serde(default = "default_status_max")]
+    pub status_max: u16,
+    #[serde(default)]
+    pub json_path: Option<String>,
+    #[serde(default)]
+    pub json_equals: Option<JsonValue>,
+}
+
+impl Default for WebhookSuccess {
+    fn default() -> Self {
+        Self {
+            status_min: default_status_min(),
+            status_max: default_status_max(),
+            json_path: None,
+            json_equals: None,
+        }
+    }
+}
+
+fn default_status_min() -> u16 {
+    200
+}
+
+fn default_status_max() -> u16 {
+    299
+}
+
 impl WebhookConfig {
     /// Parse JSONB config into typed WebhookConfig
     ///
@@ -295,3 +844,341 @@ impl BarkConfig {
         )
     }
 }
+
+// ============================================================================
+// Email Config
+// ============================================================================
+
+/// Email (SMTP) notification configuration
+///
+/// # Example JSON Config
+/// ```json
+/// {
+///     "smtp_host": "smtp.example.com",
+///     "smtp_port": 587,
+///     "username": "alerts",
+///     "password": "secret",
+///     "from": "alerts@example.com",
+///     "to": ["ops@example.com"],
+///     "tls": true
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// SMTP server host name
+    pub smtp_host: String,
+
+    /// SMTP server port
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+
+    /// SMTP login (optional)
+    #[serde(default)]
+    pub username: Option<String>,
+
+    /// SMTP password (optional)
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// Sender address
+    pub from: String,
+
+    /// Recipient addresses
+    pub to: Vec<String>,
+
+    /// Whether to use TLS (STARTTLS) for the connection
+    #[serde(default)]
+    pub tls: bool,
+}
+
+fn default_smtp_port() -> u16 {
+    25
+}
+
+impl EmailConfig {
+    /// Parse JSONB config into typed EmailConfig
+    ///
+    /// # Arguments
+    /// * `config` - The JSONB value from the database
+    ///
+    /// # Returns
+    /// Result containing the parsed config or deserialization error
+    pub fn from_json(config: &JsonValue) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(config.clone())
+    }
+
+    /// Convert to JSONB for database storage
+    ///
+    /// # Returns
+    /// Result containing the JSONB value or serialization error
+    pub fn to_json(&self) -> Result<JsonValue, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+// ============================================================================
+// Gotify Config
+// ============================================================================
+
+/// Gotify notification configuration
+///
+/// Messages are posted to a self-hosted Gotify server with an application
+/// token.
+///
+/// # Example JSON Config
+/// ```json
+/// {
+///     "server_url": "https://gotify.example.com",
+///     "app_token": "AbCdEf123456",
+///     "priority": 5
+/// }
+/// ```
+///
+/// Without `priority`, it is derived from the message severity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GotifyConfig {
+    /// Gotify server base URL
+    pub server_url: String,
+
+    /// Application token used to post messages
+    pub app_token: String,
+
+    /// Message priority (0-10, optional)
+    #[serde(default)]
+    pub priority: Option<u8>,
+}
+
+impl GotifyConfig {
+    /// Parse JSONB config into typed GotifyConfig
+    ///
+    /// # Arguments
+    /// * `config` - The JSONB value from the database
+    ///
+    /// # Returns
+    /// Result containing the parsed config or deserialization error
+    pub fn from_json(config: &JsonValue) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(config.clone())
+    }
+
+    /// Convert to JSONB for database storage
+    ///
+    /// # Returns
+    /// Result containing the JSONB value or serialization error
+    pub fn to_json(&self) -> Result<JsonValue, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    /// Builds the Gotify message endpoint URL
+    pub fn build_api_url(&self) -> String {
+        format!("{}/message", self.server_url.trim_end_matches('/'))
+    }
+}
+
+// ============================================================================
+// Ntfy Config
+// ============================================================================
+
+/// ntfy notification configuration
+///
+/// Messages are published to a topic on ntfy.sh or a self-hosted ntfy
+/// server. Protected topics use either an access token or a username and
+/// password.
+///
+/// # Example JSON Config
+/// ```json
+/// {
+///     "server_url": "https://ntfy.example.com",
+///     "topic": "alerts",
+///     "token": "tk_abc123",
+///     "priority": 4,
+///     "tags": ["warning"]
+/// }
+/// ```
+///
+/// Note: `server_url` is optional and defaults to `https://ntfy.sh`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NtfyConfig {
+    /// ntfy server base URL (optional, defaults to "https://ntfy.sh")
+    #[serde(default = "default_ntfy_server_url")]
+    pub server_url: String,
+
+    /// Topic to publish to
+    pub topic: String,
+
+    /// Access token (optional)
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Username for basic authentication (optional)
+    #[serde(default)]
+    pub username: Option<String>,
+
+    /// Password for basic authentication (optional)
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// Message priority (1-5, optional)
+    #[serde(default)]
+    pub priority: Option<u8>,
+
+    /// Tags or emoji shortcodes added to every message
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_ntfy_server_url() -> String {
+    "https://ntfy.sh".to_string()
+}
+
+impl NtfyConfig {
+    /// Parse JSONB config into typed NtfyConfig
+    ///
+    /// # Arguments
+    /// * `config` - The JSONB value from the database
+    ///
+    /// # Returns
+    /// Result containing the parsed config or deserialization error
+    pub fn from_json(config: &JsonValue) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(config.clone())
+    }
+
+    /// Convert to JSONB for database storage
+    ///
+    /// # Returns
+    /// Result containing the JSONB value or serialization error
+    pub fn to_json(&self) -> Result<JsonValue, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+// ============================================================================
+// Web Push Config
+// ============================================================================
+
+/// Web Push subscription of a browser
+///
+/// Matches the JSON produced by `PushSubscription.toJSON()` in the browser,
+/// so dashboards can register the subscription as-is.
+///
+/// # Example JSON Config
+/// ```json
+/// {
+///     "endpoint": "https://fcm.googleapis.com/fcm/send/abc123",
+///     "keys": {
+///         "p256dh": "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+///         "auth": "BTBZMqHH6r4Tts7J_aSIgg"
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebPushSubscription {
+    /// Push service endpoint of the subscription
+    pub endpoint: String,
+
+    /// Keys used to encrypt messages for the subscription
+    pub keys: WebPushKeys,
+}
+
+/// Encryption keys of a Web Push subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebPushKeys {
+    /// Base64url-encoded P-256 public key of the browser
+    pub p256dh: String,
+
+    /// Base64url-encoded 16-byte authentication secret
+    pub auth: String,
+}
+
+impl WebPushSubscription {
+    /// Parse JSONB config into typed WebPushSubscription
+    ///
+    /// # Arguments
+    /// * `config` - The JSONB value from the database
+    ///
+    /// # Returns
+    /// Result containing the parsed config or deserialization error
+    pub fn from_json(config: &JsonValue) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(config.clone())
+    }
+
+    /// Convert to JSONB for database storage
+    ///
+    /// # Returns
+    /// Result containing the JSONB value or serialization error
+    pub fn to_json(&self) -> Result<JsonValue, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(
+        channel_id: i32,
+        status: NotificationStatus,
+        day: i8,
+        duration_ms: Option<i64>,
+    ) -> NotificationLog {
+        NotificationLog {
+            id: 0,
+            channel_id,
+            message: String::new(),
+            status,
+            error_message: None,
+            retry_count: 1,
+            sent_at: DateTime::from(jiff::civil::date(2026, 1, day).at(12, 0, 0, 0)),
+            dedup_key: None,
+            request_meta: None,
+            response_status: None,
+            response_body: None,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn test_rollup_groups_by_day_channel_and_status() {
+        let stats = NewNotificationLogStat::rollup(&[
+            log(1, NotificationStatus::Sent, 1, Some(100)),
+            log(1, NotificationStatus::Sent, 1, Some(50)),
+            log(1, NotificationStatus::Sent, 1, None),
+            log(1, NotificationStatus::Failed, 1, Some(10)),
+            log(2, NotificationStatus::Sent, 1, None),
+            log(1, NotificationStatus::Sent, 2, Some(20)),
+        ]);
+
+        assert_eq!(stats.len(), 4);
+        assert_eq!(
+            stats[0],
+            NewNotificationLogStat {
+                day: Date::from(jiff::civil::date(2026, 1, 1)),
+                channel_id: 1,
+                status: NotificationStatus::Sent,
+                message_count: 3,
+                retry_count: 3,
+                total_duration_ms: 150,
+                timed_count: 2,
+            }
+        );
+        assert_eq!(stats[1].status, NotificationStatus::Failed);
+        assert_eq!(stats[2].channel_id, 2);
+        assert_eq!(stats[3].day, Date::from(jiff::civil::date(2026, 1, 2)));
+    }
+
+    #[test]
+    fn test_rollup_empty() {
+        assert!(NewNotificationLogStat::rollup(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_stats_bucket_ranges() {
+        let bucket: StatsBucket = serde_json::from_str(r#""hour""#).unwrap();
+        assert_eq!(bucket, StatsBucket::Hour);
+        assert_eq!(bucket.as_sql(), "hour");
+        assert_eq!(StatsBucket::default(), StatsBucket::Day);
+
+        for bucket in [StatsBucket::Hour, StatsBucket::Day] {
+            assert!(bucket.default_range() <= bucket.max_range());
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Notification routing models for database operations.
+//!
+//! This module provides data models for per-user routing rules, quiet hours,
+//! and notifications deferred by quiet hours.
+
+use diesel::prelude::*;
+use jiff_diesel::{DateTime, Time};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::notification::NotificationSeverity;
+
+// ============================================================================
+// Enums
+// ============================================================================
+
+/// How a routing rule delivers to its channels
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    diesel_derive_enum::DbEnum,
+)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::RoutingMode")]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingMode {
+    /// Send to every target channel in parallel
+    #[default]
+    All,
+    /// Try channels in priority order, stopping at the first success
+    Escalation,
+}
+
+/// What happens to a low-severity message during quiet hours
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    diesel_derive_enum::DbEnum,
+)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::QuietHoursAction")]
+#[serde(rename_all = "lowercase")]
+pub enum QuietHoursAction {
+    /// Hold the message until the quiet window ends
+    #[default]
+    Defer,
+    /// Discard the message
+    Drop,
+}
+
+// ============================================================================
+// NotificationRoutingRule Models (Query/Insert/Update)
+// ============================================================================
+
+/// NotificationRoutingRule query model for SELECT operations
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_routing_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationRoutingRule {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub priority: i32,
+    pub enabled: bool,
+    pub match_tags: Vec<String>,
+    pub match_metadata: JsonValue,
+    pub min_severity: Option<NotificationSeverity>,
+    pub channel_ids: Vec<i32>,
+    pub mode: RoutingMode,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+/// NewNotificationRoutingRule insert model for INSERT operations
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = crate::schema::notification_routing_rules)]
+pub struct NewNotificationRoutingRule {
+    pub user_id: i32,
+    pub name: String,
+    pub priority: i32,
+    pub enabled: bool,
+    pub match_tags: Vec<String>,
+    pub match_metadata: JsonValue,
+    pub min_severity: Option<NotificationSeverity>,
+    pub channel_ids: Vec<i32>,
+    pub mode: RoutingMode,
+}
+
+/// UpdateNotificationRoutingRule model for UPDATE operations
+#[derive(Debug, AsChangeset, Clone, Default)]
+#[diesel(table_name = crate::schema::notification_routing_rules)]
+pub struct UpdateNotificationRoutingRule {
+    pub name: Option<String>,
+    pub priority: Option<i32>,
+    pub enabled: Option<bool>,
+    pub match_tags: Option<Vec<String>>,
+    pub match_metadata: Option<JsonValue>,
+    pub min_severity: Option<Option<NotificationSeverity>>,
+    pub channel_ids: Option<Vec<i32>>,
+    pub mode: Option<RoutingMode>,
+}
+
+// ============================================================================
+// NotificationQuietHours Models (Query/Upsert)
+// ============================================================================
+
+/// NotificationQuietHours query model for SELECT operations
+///
+/// `start_time` and `end_time` are wall-clock times in `timezone`; a window
+/// whose start is after its end wraps past midnight.
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::notification_quiet_hours)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationQuietHours {
+    pub id: i32,
+    pub user_id: i32,
+    pub start_time: Time,
+    pub end_time: Time,
+    pub timezone: String,
+    pub bypass_severity: NotificationSeverity,
+    pub action: QuietHoursAction,
+    pub enabled: bool,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+/// NewNotificationQuietHours model for INSERT ... ON CONFLICT UPDATE
+#[derive(Debug, Insertable, AsChangeset, Clone)]
+#[diesel(table_name = crate::schema::notification_quiet_hours)]
+pub struct NewNotificationQuietHours {
+    pub user_id: i32,
+    pub start_time: Time,
+    pub end_time: Time,
+    pub timezone: String,
+    pub bypass_severity: NotificationSeverity,
+    pub action: QuietHoursAction,
+    pub enabled: bool,
+}
+
+// ============================================================================
+// DeferredNotification Models (Query/Insert)
+// ============================================================================
+
+/// DeferredNotification query model for SELECT operations
+///
+/// `message` holds the serialized `NotificationMessage`.
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = crate::schema::deferred_notifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeferredNotification {
+    pub id: i64,
+    pub user_id: i32,
+    pub message: JsonValue,
+    pub deliver_at: DateTime,
+    pub created_at: DateTime,
+}
+
+/// NewDeferredNotification insert model for INSERT operations
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = crate::schema::deferred_notifications)]
+pub struct NewDeferredNotification {
+    pub user_id: i32,
+    pub message: JsonValue,
+    pub deliver_at: DateTime,
+}
@@ -14,6 +14,8 @@ pub struct User {
     pub password: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    /// Administrators may manage user groups and send broadcasts
+    pub is_admin: bool,
 }
 
 /// NewUser model for inserting new records
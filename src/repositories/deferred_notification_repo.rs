@@ -0,0 +1,107 @@
+//! Deferred notification repository for async database operations.
+//!
+//! Stores messages held back by quiet hours until they are due.
+
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use jiff_diesel::DateTime;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{DeferredNotification, NewDeferredNotification};
+
+/// Deferred notification repository
+#[derive(Clone)]
+pub struct DeferredNotificationRepository {
+    pool: AsyncDbPool,
+}
+
+impl DeferredNotificationRepository {
+    /// Creates a new DeferredNotificationRepository with the given connection pool.
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a deferred notification
+    ///
+    /// # Arguments
+    /// * `new_deferred` - The deferred message and its delivery time
+    ///
+    /// # Returns
+    /// The stored deferred notification
+    pub async fn create(
+        &self,
+        new_deferred: NewDeferredNotification,
+    ) -> AppResult<DeferredNotification> {
+        use crate::schema::deferred_notifications::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(deferred_notifications)
+            .values(&new_deferred)
+            .returning(DeferredNotification::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Removes and returns notifications that are due for delivery
+    ///
+    /// Rows are locked with `SKIP LOCKED` so concurrent workers never take
+    /// the same message.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of notifications to take
+    ///
+    /// # Returns
+    /// The due notifications, oldest first
+    pub async fn take_due(&self, limit: i64) -> AppResult<Vec<DeferredNotification>> {
+        use crate::schema::deferred_notifications::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let now = DateTime::from(
+            jiff::Timestamp::now()
+                .to_zoned(jiff::tz::TimeZone::UTC)
+                .datetime(),
+        );
+
+        let mut due = conn
+            .transaction::<_, AppError, _>(|conn| {
+                async move {
+                    let due_ids: Vec<i64> = deferred_notifications
+                        .filter(deliver_at.le(now))
+                        .order(deliver_at.asc())
+                        .limit(limit)
+                        .select(id)
+                        .for_update()
+                        .skip_locked()
+                        .load(conn)
+                        .await?;
+
+                    let due = diesel::delete(deferred_notifications.filter(id.eq_any(due_ids)))
+                        .returning(DeferredNotification::as_returning())
+                        .get_results(conn)
+                        .await?;
+
+                    Ok(due)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        due.sort_by_key(|d| d.deliver_at);
+        Ok(due)
+    }
+}
@@ -0,0 +1,111 @@
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{JobAlertRule, NewJobAlertRule};
+use crate::schema::job_alert_rules;
+
+#[derive(Clone)]
+pub struct JobAlertRepository {
+    pool: AsyncDbPool,
+}
+
+impl JobAlertRepository {
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists the alert rules of a job, in creation order
+    pub async fn list_by_job(&self, job_id: i32) -> AppResult<Vec<JobAlertRule>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_alert_rules::table
+            .filter(job_alert_rules::job_id.eq(job_id))
+            .order(job_alert_rules::id.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Lists a user's alert rules on a job, in creation order
+    pub async fn list_by_job_and_user(
+        &self,
+        job_id: i32,
+        user_id: i32,
+    ) -> AppResult<Vec<JobAlertRule>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_alert_rules::table
+            .filter(job_alert_rules::job_id.eq(job_id))
+            .filter(job_alert_rules::user_id.eq(user_id))
+            .order(job_alert_rules::id.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Replaces a user's alert rules on a job
+    ///
+    /// Rules other users set on the job are left unchanged.
+    ///
+    /// # Arguments
+    /// * `job_id` - Job whose rules are replaced
+    /// * `user_id` - User whose rules are replaced
+    /// * `rules` - New rules; an empty list removes all of the user's rules
+    ///
+    /// # Returns
+    /// The user's rules on the job after the replacement
+    pub async fn replace(
+        &self,
+        job_id: i32,
+        user_id: i32,
+        rules: Vec<NewJobAlertRule>,
+    ) -> AppResult<Vec<JobAlertRule>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                diesel::delete(
+                    job_alert_rules::table
+                        .filter(job_alert_rules::job_id.eq(job_id))
+                        .filter(job_alert_rules::user_id.eq(user_id)),
+                )
+                .execute(conn)
+                .await?;
+
+                if rules.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let created = diesel::insert_into(job_alert_rules::table)
+                    .values(&rules)
+                    .get_results(conn)
+                    .await?;
+
+                Ok(created)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
@@ -1,13 +1,14 @@
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
 use jiff_diesel::DateTime;
 use serde_json::Value as JsonValue;
 
 use crate::db::AsyncDbPool;
 use crate::error::{AppError, AppResult};
-use crate::jobs::models::{JobExecution, NewJobExecution};
+use crate::jobs::models::{JobExecution, JobExecutionLog, NewJobExecution, NewJobExecutionLog};
 use crate::jobs::types::JobStatus;
-use crate::schema::job_executions;
+use crate::schema::{job_execution_logs, job_executions, scheduled_jobs};
 
 #[derive(Clone)]
 pub struct JobExecutionRepository {
@@ -35,12 +36,92 @@ impl JobExecutionRepository {
             .map_err(AppError::from)
     }
 
+    /// Records a new execution unless the job is at its concurrency limit
+    ///
+    /// The job row is locked while running executions are counted, so
+    /// replicas starting the same job are serialized and the limit holds
+    /// across the cluster. Running executions older than the job timeout
+    /// are ignored; they were left behind by a replica that went away.
+    ///
+    /// # Arguments
+    /// * `exec` - Execution to record
+    /// * `limit` - Maximum running executions, `None` for no limit
+    /// * `timeout_seconds` - The job's execution timeout
+    ///
+    /// # Returns
+    /// The recorded execution, or `None` if the limit was reached
+    pub async fn create_within_limit(
+        &self,
+        exec: NewJobExecution,
+        limit: Option<i64>,
+        timeout_seconds: i64,
+    ) -> AppResult<Option<JobExecution>> {
+        let Some(limit) = limit else {
+            return self.create(exec).await.map(Some);
+        };
+
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let stale_before_ts =
+            jiff::Timestamp::now() - jiff::SignedDuration::from_secs(timeout_seconds);
+        let stale_before =
+            DateTime::from(stale_before_ts.to_zoned(jiff::tz::TimeZone::UTC).datetime());
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                scheduled_jobs::table
+                    .find(exec.job_id)
+                    .select(scheduled_jobs::id)
+                    .for_update()
+                    .first::<i32>(conn)
+                    .await?;
+
+                let running: i64 = job_executions::table
+                    .filter(job_executions::job_id.eq(exec.job_id))
+                    .filter(job_executions::status.eq(JobStatus::Running))
+                    .filter(job_executions::started_at.gt(stale_before))
+                    .count()
+                    .get_result(conn)
+                    .await?;
+
+                if running >= limit {
+                    return Ok(None);
+                }
+
+                let execution = diesel::insert_into(job_executions::table)
+                    .values(&exec)
+                    .get_result(conn)
+                    .await?;
+
+                Ok(Some(execution))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Records the outcome of an attempt
+    ///
+    /// # Arguments
+    /// * `id` - Execution row ID
+    /// * `status` - Final status of the attempt
+    /// * `duration_ms` - How long the attempt ran
+    /// * `error` - Error message of a failed attempt
+    /// * `error_details` - Structured error of a failed attempt
+    /// * `result` - Result returned by a successful task
     pub async fn complete(
         &self,
         id: i64,
         status: JobStatus,
         duration_ms: i64,
         error: Option<String>,
+        error_details: Option<JsonValue>,
         result: Option<JsonValue>,
     ) -> AppResult<()> {
         let mut conn = self
@@ -57,6 +138,7 @@ impl JobExecutionRepository {
                 job_executions::duration_ms.eq(duration_ms),
                 job_executions::status.eq(status),
                 job_executions::error_message.eq(error),
+                job_executions::error_details.eq(error_details),
                 job_executions::result.eq(result),
             ))
             .execute(&mut conn)
@@ -66,6 +148,147 @@ impl JobExecutionRepository {
         Ok(())
     }
 
+    /// Records the progress reported by a running task
+    ///
+    /// # Arguments
+    /// * `execution_id` - Execution reporting progress
+    /// * `percent` - Completion, 0 to 100
+    /// * `message` - What the task is doing
+    pub async fn update_progress(
+        &self,
+        execution_id: uuid::Uuid,
+        percent: u8,
+        message: String,
+    ) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(job_executions::table.filter(job_executions::execution_id.eq(execution_id)))
+            .set((
+                job_executions::progress_percent.eq(i16::from(percent)),
+                job_executions::progress_message.eq(message),
+            ))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Appends a log line written by a running task
+    ///
+    /// # Arguments
+    /// * `line` - The line, carrying its execution ID
+    pub async fn append_log(&self, line: NewJobExecutionLog) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(job_execution_logs::table)
+            .values(&line)
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Lists the log lines of an execution in the order they were appended
+    pub async fn list_logs(&self, execution_id: uuid::Uuid) -> AppResult<Vec<JobExecutionLog>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_execution_logs::table
+            .filter(job_execution_logs::execution_id.eq(execution_id))
+            .order(job_execution_logs::id.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Flags a running execution for cancellation by the replica that owns it
+    ///
+    /// # Returns
+    /// `true` if the execution is still running and has been flagged
+    pub async fn request_cancel(&self, execution_id: uuid::Uuid) -> AppResult<bool> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let updated = diesel::update(
+            job_executions::table
+                .filter(job_executions::execution_id.eq(execution_id))
+                .filter(job_executions::completed_at.is_null()),
+        )
+        .set(job_executions::cancel_requested.eq(true))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(updated > 0)
+    }
+
+    /// Whether cancellation of an execution has been requested
+    pub async fn is_cancel_requested(&self, execution_id: uuid::Uuid) -> AppResult<bool> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_executions::table
+            .filter(job_executions::execution_id.eq(execution_id))
+            .select(job_executions::cancel_requested)
+            .first(&mut conn)
+            .await
+            .optional()
+            .map(|flag| flag.unwrap_or(false))
+            .map_err(AppError::from)
+    }
+
+    pub async fn get_by_execution_id(&self, execution_id: uuid::Uuid) -> AppResult<JobExecution> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_executions::table
+            .filter(job_executions::execution_id.eq(execution_id))
+            .first(&mut conn)
+            .await
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => AppError::NotFound {
+                    entity: "Execution".to_string(),
+                    field: "execution_id".to_string(),
+                    value: execution_id.to_string(),
+                },
+                _ => AppError::from(e),
+            })
+    }
+
     pub async fn list_by_job(
         &self,
         job_id: i32,
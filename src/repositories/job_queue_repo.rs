@@ -0,0 +1,448 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Timestamp, Uuid};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use jiff_diesel::DateTime;
+use serde_json::Value as JsonValue;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{NewQueuedTask, NewQueuedTaskLog, QueuedTask, QueuedTaskLog};
+use crate::jobs::types::QueuedTaskStatus;
+use crate::schema::{job_queue, job_queue_logs};
+
+fn task_not_found(id: i64) -> AppError {
+    AppError::NotFound {
+        entity: "Queued task".to_string(),
+        field: "id".to_string(),
+        value: id.to_string(),
+    }
+}
+
+#[derive(Clone)]
+pub struct JobQueueRepository {
+    pool: AsyncDbPool,
+}
+
+impl JobQueueRepository {
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn enqueue(&self, task: NewQueuedTask) -> AppResult<QueuedTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(job_queue::table)
+            .values(&task)
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    pub async fn get_by_id(&self, id: i64) -> AppResult<QueuedTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_queue::table
+            .find(id)
+            .first(&mut conn)
+            .await
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => task_not_found(id),
+                _ => AppError::from(e),
+            })
+    }
+
+    /// Lists tasks, newest first
+    ///
+    /// # Arguments
+    /// * `status` - Only tasks with this status, all tasks if `None`
+    pub async fn list(
+        &self,
+        status: Option<QueuedTaskStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<QueuedTask>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let mut query = job_queue::table.into_boxed();
+        if let Some(status) = status {
+            query = query.filter(job_queue::status.eq(status));
+        }
+
+        query
+            .order(job_queue::id.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Claims due tasks for the calling worker
+    ///
+    /// Tasks are taken by priority, then by due time. Each claimed task
+    /// counts an attempt, gets a new execution ID and stays invisible to
+    /// other workers for its visibility timeout; a running task whose timeout has passed is due
+    /// again. Rows locked by a concurrent claim are skipped.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of tasks to claim
+    pub async fn claim_due(&self, limit: i64) -> AppResult<Vec<QueuedTask>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let mut claimed = conn
+            .transaction::<_, AppError, _>(|conn| {
+                async move {
+                    let now = diesel::dsl::now;
+                    let due_ids: Vec<i64> = job_queue::table
+                        .filter(job_queue::run_at.le(now))
+                        .filter(
+                            job_queue::status
+                                .eq(QueuedTaskStatus::Queued)
+                                .or(job_queue::status
+                                    .eq(QueuedTaskStatus::Running)
+                                    .and(job_queue::locked_until.lt(now))),
+                        )
+                        .filter(job_queue::attempts.lt(job_queue::max_attempts))
+                        .order((job_queue::priority.desc(), job_queue::run_at.asc()))
+                        .limit(limit)
+                        .select(job_queue::id)
+                        .for_update()
+                        .skip_locked()
+                        .load(conn)
+                        .await?;
+
+                    let claimed = diesel::update(
+                        job_queue::table.filter(job_queue::id.eq_any(due_ids)),
+                    )
+                    .set((
+                        job_queue::status.eq(QueuedTaskStatus::Running),
+                        job_queue::attempts.eq(job_queue::attempts + 1),
+                        job_queue::locked_until.eq(sql::<Nullable<Timestamp>>(
+                            "CURRENT_TIMESTAMP + visibility_timeout_seconds * INTERVAL '1 second'",
+                        )),
+                        job_queue::progress_percent.eq(None::<i16>),
+                        job_queue::progress_message.eq(None::<String>),
+                        job_queue::execution_id.eq(sql::<Nullable<Uuid>>("gen_random_uuid()")),
+                    ))
+                    .get_results::<QueuedTask>(conn)
+                    .await?;
+
+                    Ok(claimed)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        claimed.sort_by_key(|task| (std::cmp::Reverse(task.priority), task.run_at));
+        Ok(claimed)
+    }
+
+    /// Moves running tasks that timed out on their last attempt to the
+    /// dead letters
+    ///
+    /// These were claimed by a worker that never reported back.
+    ///
+    /// # Returns
+    /// The number of tasks dead-lettered
+    pub async fn dead_letter_expired(&self) -> AppResult<usize> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            job_queue::table
+                .filter(job_queue::status.eq(QueuedTaskStatus::Running))
+                .filter(job_queue::locked_until.lt(diesel::dsl::now))
+                .filter(job_queue::attempts.ge(job_queue::max_attempts)),
+        )
+        .set((
+            job_queue::status.eq(QueuedTaskStatus::Dead),
+            job_queue::last_error.eq("Visibility timeout expired on the last attempt"),
+            job_queue::completed_at.eq(diesel::dsl::now),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// Records a successful attempt and the task's result
+    ///
+    /// Only the claim identified by `attempt` is updated, so a worker that
+    /// overran its visibility timeout cannot overwrite a later attempt.
+    pub async fn complete(
+        &self,
+        id: i64,
+        attempt: i32,
+        result: Option<JsonValue>,
+    ) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            job_queue::table
+                .find(id)
+                .filter(job_queue::attempts.eq(attempt)),
+        )
+        .set((
+            job_queue::status.eq(QueuedTaskStatus::Succeeded),
+            job_queue::locked_until.eq(None::<DateTime>),
+            job_queue::completed_at.eq(diesel::dsl::now),
+            job_queue::result.eq(result),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Records the progress reported by a running attempt
+    ///
+    /// Progress of an attempt that overran its visibility timeout is
+    /// dropped once the task has been claimed again.
+    ///
+    /// # Arguments
+    /// * `id` - Task ID
+    /// * `attempt` - Attempt reporting progress
+    /// * `percent` - Completion, 0 to 100
+    /// * `message` - What the task is doing
+    pub async fn update_progress(
+        &self,
+        id: i64,
+        attempt: i32,
+        percent: u8,
+        message: String,
+    ) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            job_queue::table
+                .find(id)
+                .filter(job_queue::attempts.eq(attempt)),
+        )
+        .set((
+            job_queue::progress_percent.eq(i16::from(percent)),
+            job_queue::progress_message.eq(message),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Appends a log line written by an attempt of a task
+    ///
+    /// # Arguments
+    /// * `line` - The line, carrying its task and attempt
+    pub async fn append_log(&self, line: NewQueuedTaskLog) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(job_queue_logs::table)
+            .values(&line)
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Lists the log lines of a task, across all attempts, in the order they
+    /// were appended
+    pub async fn list_logs(&self, task_id: i64) -> AppResult<Vec<QueuedTaskLog>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_queue_logs::table
+            .filter(job_queue_logs::task_id.eq(task_id))
+            .order(job_queue_logs::id.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Records a failed attempt
+    ///
+    /// # Arguments
+    /// * `id` - Task ID
+    /// * `attempt` - Attempt that failed, as returned by [`Self::claim_due`]
+    /// * `error` - Error message of the attempt
+    /// * `retry_at` - When to try again, `None` to dead-letter the task
+    pub async fn fail(
+        &self,
+        id: i64,
+        attempt: i32,
+        error: String,
+        retry_at: Option<DateTime>,
+    ) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let target = job_queue::table
+            .find(id)
+            .filter(job_queue::attempts.eq(attempt));
+        match retry_at {
+            Some(retry_at) => {
+                diesel::update(target)
+                    .set((
+                        job_queue::status.eq(QueuedTaskStatus::Queued),
+                        job_queue::run_at.eq(retry_at),
+                        job_queue::locked_until.eq(None::<DateTime>),
+                        job_queue::last_error.eq(error),
+                    ))
+                    .execute(&mut conn)
+                    .await
+            }
+            None => {
+                diesel::update(target)
+                    .set((
+                        job_queue::status.eq(QueuedTaskStatus::Dead),
+                        job_queue::locked_until.eq(None::<DateTime>),
+                        job_queue::last_error.eq(error),
+                        job_queue::completed_at.eq(diesel::dsl::now),
+                    ))
+                    .execute(&mut conn)
+                    .await
+            }
+        }
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Puts a dead task back in the queue with a fresh set of attempts
+    ///
+    /// # Returns
+    /// The requeued task, or `None` if it is not dead
+    pub async fn requeue(&self, id: i64) -> AppResult<Option<QueuedTask>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            job_queue::table
+                .find(id)
+                .filter(job_queue::status.eq(QueuedTaskStatus::Dead)),
+        )
+        .set((
+            job_queue::status.eq(QueuedTaskStatus::Queued),
+            job_queue::attempts.eq(0),
+            job_queue::run_at.eq(diesel::dsl::now),
+            job_queue::completed_at.eq(None::<DateTime>),
+        ))
+        .get_result(&mut conn)
+        .await
+        .optional()
+        .map_err(AppError::from)
+    }
+
+    /// Deletes a task unless a worker is running it
+    ///
+    /// # Returns
+    /// `true` if the task was deleted
+    pub async fn delete_idle(&self, id: i64) -> AppResult<bool> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let deleted = diesel::delete(
+            job_queue::table
+                .find(id)
+                .filter(job_queue::status.ne(QueuedTaskStatus::Running)),
+        )
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Deletes succeeded tasks completed more than `retention_days` ago
+    pub async fn cleanup_succeeded(&self, retention_days: i64) -> AppResult<usize> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let cutoff_ts =
+            jiff::Timestamp::now() - jiff::SignedDuration::from_hours(retention_days * 24);
+        let cutoff = DateTime::from(cutoff_ts.to_zoned(jiff::tz::TimeZone::UTC).datetime());
+
+        diesel::delete(
+            job_queue::table
+                .filter(job_queue::status.eq(QueuedTaskStatus::Succeeded))
+                .filter(job_queue::completed_at.lt(cutoff)),
+        )
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)
+    }
+}
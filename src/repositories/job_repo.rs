@@ -1,5 +1,7 @@
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use jiff_diesel::DateTime;
 
 use crate::db::AsyncDbPool;
 use crate::error::{AppError, AppResult};
@@ -143,7 +145,16 @@ impl JobRepository {
         }
     }
 
-    pub async fn update_last_run(&self, id: i32, status: JobStatus) -> AppResult<()> {
+    /// Stores the next fire time of a job
+    ///
+    /// # Arguments
+    /// * `id` - Job ID
+    /// * `next_run_at` - Next fire time in UTC, `None` if the job will not fire
+    pub async fn set_next_run(
+        &self,
+        id: i32,
+        next_run_at: Option<DateTime>,
+    ) -> AppResult<ScheduledJob> {
         let mut conn = self
             .pool
             .get()
@@ -153,14 +164,114 @@ impl JobRepository {
             })?;
 
         diesel::update(scheduled_jobs::table.find(id))
-            .set((
-                scheduled_jobs::last_run_at.eq(diesel::dsl::now),
-                scheduled_jobs::last_run_status.eq(status),
-            ))
-            .execute(&mut conn)
+            .set(scheduled_jobs::next_run_at.eq(next_run_at))
+            .get_result(&mut conn)
             .await
-            .map_err(AppError::from)?;
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => AppError::NotFound {
+                    entity: "Job".to_string(),
+                    field: "id".to_string(),
+                    value: id.to_string(),
+                },
+                _ => AppError::from(e),
+            })
+    }
+
+    /// Records how a job's latest run ended
+    ///
+    /// Also maintains the job's streak of unsuccessful runs, see
+    /// [`JobStatus::next_failure_streak`].
+    ///
+    /// # Returns
+    /// The streak before this run
+    pub async fn update_last_run(&self, id: i32, status: JobStatus) -> AppResult<i32> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let previous: i32 = scheduled_jobs::table
+                    .find(id)
+                    .select(scheduled_jobs::consecutive_failures)
+                    .for_update()
+                    .first(conn)
+                    .await
+                    .map_err(|e| match e {
+                        diesel::result::Error::NotFound => AppError::NotFound {
+                            entity: "Job".to_string(),
+                            field: "id".to_string(),
+                            value: id.to_string(),
+                        },
+                        _ => AppError::from(e),
+                    })?;
+
+                diesel::update(scheduled_jobs::table.find(id))
+                    .set((
+                        scheduled_jobs::last_run_at.eq(diesel::dsl::now),
+                        scheduled_jobs::last_run_status.eq(status),
+                        scheduled_jobs::consecutive_failures
+                            .eq(status.next_failure_streak(previous)),
+                    ))
+                    .execute(conn)
+                    .await?;
+
+                Ok(previous)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Claims a cron fire time for the calling replica
+    ///
+    /// Every replica fires the same cron entries; the conditional update
+    /// lets exactly one of them advance `last_fired_at` to `fire_time`.
+    ///
+    /// # Arguments
+    /// * `id` - Job ID
+    /// * `fire_time` - Scheduled occurrence being fired, in UTC; the same on
+    ///   every replica whatever its clock reads when the tick lands
+    /// * `next_run_at` - Following fire time, stored alongside the claim
+    ///
+    /// # Returns
+    /// The latest job row if claimed, `None` if another replica claimed
+    /// this fire or the job was disabled or deleted
+    pub async fn claim_fire(
+        &self,
+        id: i32,
+        fire_time: DateTime,
+        next_run_at: Option<DateTime>,
+    ) -> AppResult<Option<ScheduledJob>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
 
-        Ok(())
+        diesel::update(
+            scheduled_jobs::table
+                .find(id)
+                .filter(scheduled_jobs::enabled.eq(true))
+                .filter(
+                    scheduled_jobs::last_fired_at
+                        .is_null()
+                        .or(scheduled_jobs::last_fired_at.lt(fire_time)),
+                ),
+        )
+        .set((
+            scheduled_jobs::last_fired_at.eq(fire_time),
+            scheduled_jobs::next_run_at.eq(next_run_at),
+        ))
+        .get_result(&mut conn)
+        .await
+        .optional()
+        .map_err(AppError::from)
     }
 }
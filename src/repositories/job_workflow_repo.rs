@@ -0,0 +1,595 @@
+use diesel::dsl::{IntervalDsl, now};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::jobs::models::{
+    JobWorkflow, NewJobWorkflow, NewWorkflowRun, NewWorkflowStep, NewWorkflowStepRun, WorkflowRun,
+    WorkflowStep, WorkflowStepRun, WorkflowStepSpec,
+};
+use crate::jobs::types::JobStatus;
+use crate::schema::{job_workflow_runs, job_workflow_step_runs, job_workflow_steps, job_workflows};
+
+fn workflow_not_found(id: i32) -> AppError {
+    AppError::NotFound {
+        entity: "Workflow".to_string(),
+        field: "id".to_string(),
+        value: id.to_string(),
+    }
+}
+
+/// Inserts the steps of a workflow
+async fn insert_steps(
+    conn: &mut AsyncPgConnection,
+    workflow_id: i32,
+    steps: Vec<WorkflowStepSpec>,
+) -> AppResult<Vec<WorkflowStep>> {
+    let rows: Vec<NewWorkflowStep> = steps
+        .into_iter()
+        .map(|step| NewWorkflowStep {
+            workflow_id,
+            step_name: step.step_name,
+            job_id: step.job_id,
+            depends_on: step.depends_on,
+        })
+        .collect();
+
+    diesel::insert_into(job_workflow_steps::table)
+        .values(&rows)
+        .get_results(conn)
+        .await
+        .map_err(AppError::from)
+}
+
+#[derive(Clone)]
+pub struct JobWorkflowRepository {
+    pool: AsyncDbPool,
+}
+
+impl JobWorkflowRepository {
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a workflow together with its steps
+    pub async fn create(
+        &self,
+        workflow: NewJobWorkflow,
+        steps: Vec<WorkflowStepSpec>,
+    ) -> AppResult<(JobWorkflow, Vec<WorkflowStep>)> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let workflow: JobWorkflow = diesel::insert_into(job_workflows::table)
+                    .values(&workflow)
+                    .get_result(conn)
+                    .await?;
+                let steps = insert_steps(conn, workflow.id, steps).await?;
+                Ok((workflow, steps))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Updates a workflow's description and, if given, replaces its steps
+    ///
+    /// Runs already started keep their own snapshot of the steps.
+    pub async fn update(
+        &self,
+        id: i32,
+        description: Option<String>,
+        steps: Option<Vec<WorkflowStepSpec>>,
+    ) -> AppResult<(JobWorkflow, Vec<WorkflowStep>)> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let target = job_workflows::table.find(id);
+                let workflow: JobWorkflow = match description {
+                    Some(description) => {
+                        diesel::update(target)
+                            .set(job_workflows::description.eq(description))
+                            .get_result(conn)
+                            .await
+                    }
+                    None => {
+                        diesel::update(target)
+                            .set(job_workflows::updated_at.eq(diesel::dsl::now))
+                            .get_result(conn)
+                            .await
+                    }
+                }
+                .optional()?
+                .ok_or_else(|| workflow_not_found(id))?;
+
+                let steps = match steps {
+                    Some(steps) => {
+                        diesel::delete(
+                            job_workflow_steps::table
+                                .filter(job_workflow_steps::workflow_id.eq(id)),
+                        )
+                        .execute(conn)
+                        .await?;
+                        insert_steps(conn, id, steps).await?
+                    }
+                    None => {
+                        job_workflow_steps::table
+                            .filter(job_workflow_steps::workflow_id.eq(id))
+                            .order(job_workflow_steps::id.asc())
+                            .load(conn)
+                            .await?
+                    }
+                };
+
+                Ok((workflow, steps))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    pub async fn get_by_id(&self, id: i32) -> AppResult<JobWorkflow> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflows::table
+            .find(id)
+            .first(&mut conn)
+            .await
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => workflow_not_found(id),
+                _ => AppError::from(e),
+            })
+    }
+
+    pub async fn list(&self, limit: i64, offset: i64) -> AppResult<Vec<JobWorkflow>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflows::table
+            .order(job_workflows::id.asc())
+            .limit(limit)
+            .offset(offset)
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Lists the steps of a workflow in definition order
+    pub async fn list_steps(&self, workflow_id: i32) -> AppResult<Vec<WorkflowStep>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflow_steps::table
+            .filter(job_workflow_steps::workflow_id.eq(workflow_id))
+            .order(job_workflow_steps::id.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Names of the workflows with a step running the given job
+    pub async fn names_using_job(&self, job_id: i32) -> AppResult<Vec<String>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflows::table
+            .inner_join(job_workflow_steps::table)
+            .filter(job_workflow_steps::job_id.eq(job_id))
+            .select(job_workflows::name)
+            .distinct()
+            .order(job_workflows::name.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    pub async fn delete(&self, id: i32) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let deleted = diesel::delete(job_workflows::table.find(id))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        if deleted == 0 {
+            Err(workflow_not_found(id))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Creates a pending run of a workflow with a pending step run for
+    /// every step
+    ///
+    /// The run is driven by whichever replica claims it, see
+    /// [`Self::claim_runs`] and [`Self::claim_run`].
+    ///
+    /// # Errors
+    /// - `NotFound` if the workflow does not exist
+    pub async fn create_run(&self, workflow_id: i32) -> AppResult<WorkflowRun> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                job_workflows::table
+                    .find(workflow_id)
+                    .select(job_workflows::id)
+                    .first::<i32>(conn)
+                    .await
+                    .optional()?
+                    .ok_or_else(|| workflow_not_found(workflow_id))?;
+
+                let run: WorkflowRun = diesel::insert_into(job_workflow_runs::table)
+                    .values(&NewWorkflowRun {
+                        workflow_id,
+                        run_id: Uuid::new_v4(),
+                        status: JobStatus::Pending,
+                    })
+                    .get_result(conn)
+                    .await?;
+
+                let steps: Vec<WorkflowStep> = job_workflow_steps::table
+                    .filter(job_workflow_steps::workflow_id.eq(workflow_id))
+                    .order(job_workflow_steps::id.asc())
+                    .load(conn)
+                    .await?;
+                let step_runs: Vec<NewWorkflowStepRun> = steps
+                    .into_iter()
+                    .map(|step| NewWorkflowStepRun {
+                        workflow_run_id: run.id,
+                        step_name: step.step_name,
+                        job_id: step.job_id,
+                        depends_on: step.depends_on,
+                        status: JobStatus::Pending,
+                    })
+                    .collect();
+                diesel::insert_into(job_workflow_step_runs::table)
+                    .values(&step_runs)
+                    .execute(conn)
+                    .await?;
+
+                Ok(run)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Claims runs for the calling replica
+    ///
+    /// Takes every pending run, and every running run whose lease expired
+    /// because the replica driving it stopped. Each claimed run is leased
+    /// to `owner` for `lease_seconds`. Rows locked by a concurrent claim
+    /// are skipped.
+    ///
+    /// # Arguments
+    /// * `owner` - Claim token, required to extend the lease or finish
+    ///   the run
+    /// * `lease_seconds` - How long the run stays claimed without a
+    ///   heartbeat
+    pub async fn claim_runs(&self, owner: Uuid, lease_seconds: i32) -> AppResult<Vec<WorkflowRun>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let ids: Vec<i64> = job_workflow_runs::table
+                    .filter(
+                        job_workflow_runs::status.eq(JobStatus::Pending).or(
+                            job_workflow_runs::status
+                                .eq(JobStatus::Running)
+                                .and(job_workflow_runs::locked_until.lt(now)),
+                        ),
+                    )
+                    .select(job_workflow_runs::id)
+                    .for_update()
+                    .skip_locked()
+                    .load(conn)
+                    .await?;
+
+                let claimed = diesel::update(
+                    job_workflow_runs::table.filter(job_workflow_runs::id.eq_any(ids)),
+                )
+                .set((
+                    job_workflow_runs::status.eq(JobStatus::Running),
+                    job_workflow_runs::locked_until.eq((now + lease_seconds.seconds()).nullable()),
+                    job_workflow_runs::locked_by.eq(owner),
+                ))
+                .get_results(conn)
+                .await?;
+
+                Ok(claimed)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Claims one pending run for the calling replica
+    ///
+    /// # Arguments
+    /// * `id` - Run ID
+    /// * `owner` - Claim token, see [`Self::claim_runs`]
+    /// * `lease_seconds` - How long the run stays claimed without a
+    ///   heartbeat
+    ///
+    /// # Returns
+    /// The claimed run, `None` if another replica claimed it first
+    pub async fn claim_run(
+        &self,
+        id: i64,
+        owner: Uuid,
+        lease_seconds: i32,
+    ) -> AppResult<Option<WorkflowRun>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            job_workflow_runs::table
+                .find(id)
+                .filter(job_workflow_runs::status.eq(JobStatus::Pending)),
+        )
+        .set((
+            job_workflow_runs::status.eq(JobStatus::Running),
+            job_workflow_runs::locked_until.eq((now + lease_seconds.seconds()).nullable()),
+            job_workflow_runs::locked_by.eq(owner),
+        ))
+        .get_result(&mut conn)
+        .await
+        .optional()
+        .map_err(AppError::from)
+    }
+
+    /// Extends the lease of a run still claimed by `owner`
+    ///
+    /// # Returns
+    /// `false` if the run was claimed by another replica in the meantime
+    pub async fn extend_lease(&self, id: i64, owner: Uuid, lease_seconds: i32) -> AppResult<bool> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let updated = diesel::update(
+            job_workflow_runs::table
+                .find(id)
+                .filter(job_workflow_runs::status.eq(JobStatus::Running))
+                .filter(job_workflow_runs::locked_by.eq(owner)),
+        )
+        .set(job_workflow_runs::locked_until.eq((now + lease_seconds.seconds()).nullable()))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(updated > 0)
+    }
+
+    pub async fn get_run(&self, run_id: Uuid) -> AppResult<WorkflowRun> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflow_runs::table
+            .filter(job_workflow_runs::run_id.eq(run_id))
+            .first(&mut conn)
+            .await
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => AppError::NotFound {
+                    entity: "Workflow run".to_string(),
+                    field: "run_id".to_string(),
+                    value: run_id.to_string(),
+                },
+                _ => AppError::from(e),
+            })
+    }
+
+    pub async fn list_runs(
+        &self,
+        workflow_id: i32,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<WorkflowRun>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflow_runs::table
+            .filter(job_workflow_runs::workflow_id.eq(workflow_id))
+            .order(job_workflow_runs::started_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    pub async fn list_step_runs(&self, workflow_run_id: i64) -> AppResult<Vec<WorkflowStepRun>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        job_workflow_step_runs::table
+            .filter(job_workflow_step_runs::workflow_run_id.eq(workflow_run_id))
+            .order(job_workflow_step_runs::id.asc())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Marks a step run as started
+    ///
+    /// # Arguments
+    /// * `id` - Step run ID
+    /// * `execution_id` - Execution id of the step's first attempt
+    pub async fn start_step(&self, id: i64, execution_id: Uuid) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(job_workflow_step_runs::table.find(id))
+            .set((
+                job_workflow_step_runs::status.eq(JobStatus::Running),
+                job_workflow_step_runs::execution_id.eq(execution_id),
+                job_workflow_step_runs::started_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Records the outcome of a step run
+    ///
+    /// # Arguments
+    /// * `id` - Step run ID
+    /// * `status` - Final status of the step
+    /// * `execution_id` - Execution id of the step's last attempt, `None`
+    ///   to keep the current one
+    /// * `error` - Why the step did not succeed
+    pub async fn finish_step(
+        &self,
+        id: i64,
+        status: JobStatus,
+        execution_id: Option<Uuid>,
+        error: Option<String>,
+    ) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let target = job_workflow_step_runs::table.find(id);
+        let values = (
+            job_workflow_step_runs::status.eq(status),
+            job_workflow_step_runs::completed_at.eq(diesel::dsl::now),
+            job_workflow_step_runs::error_message.eq(error),
+        );
+        match execution_id {
+            Some(execution_id) => {
+                diesel::update(target)
+                    .set((
+                        values,
+                        job_workflow_step_runs::execution_id.eq(execution_id),
+                    ))
+                    .execute(&mut conn)
+                    .await
+            }
+            None => diesel::update(target).set(values).execute(&mut conn).await,
+        }
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Records the outcome of a run still claimed by `owner`
+    ///
+    /// A replica that lost its claim leaves the run to the new owner.
+    pub async fn finish_run(&self, id: i64, owner: Uuid, status: JobStatus) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            job_workflow_runs::table
+                .find(id)
+                .filter(job_workflow_runs::locked_by.eq(owner)),
+        )
+        .set((
+            job_workflow_runs::status.eq(status),
+            job_workflow_runs::completed_at.eq(now),
+            job_workflow_runs::locked_until.eq(None::<jiff_diesel::DateTime>),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
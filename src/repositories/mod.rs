@@ -2,16 +2,34 @@
 //!
 //! Provides async CRUD operations for all domain entities.
 
+mod deferred_notification_repo;
+mod job_alert_repo;
 mod job_execution_repo;
+mod job_queue_repo;
 mod job_repo;
+mod job_workflow_repo;
+mod notification_api_key_repo;
+mod notification_broadcast_repo;
 mod notification_channel_repo;
 mod notification_log_repo;
+mod notification_routing_repo;
+mod notification_template_repo;
+mod user_group_repo;
 mod user_repo;
 
+pub use deferred_notification_repo::DeferredNotificationRepository;
+pub use job_alert_repo::JobAlertRepository;
 pub use job_execution_repo::JobExecutionRepository;
+pub use job_queue_repo::JobQueueRepository;
 pub use job_repo::JobRepository;
+pub use job_workflow_repo::JobWorkflowRepository;
+pub use notification_api_key_repo::NotificationApiKeyRepository;
+pub use notification_broadcast_repo::NotificationBroadcastRepository;
 pub use notification_channel_repo::NotificationChannelRepository;
-pub use notification_log_repo::NotificationLogRepository;
+pub use notification_log_repo::{NotificationLogFilter, NotificationLogRepository};
+pub use notification_routing_repo::NotificationRoutingRepository;
+pub use notification_template_repo::NotificationTemplateRepository;
+pub use user_group_repo::UserGroupRepository;
 pub use user_repo::UserRepository;
 
 use crate::db::AsyncDbPool;
@@ -25,8 +43,17 @@ pub struct Repositories {
     pub users: UserRepository,
     pub notification_channels: NotificationChannelRepository,
     pub notification_logs: NotificationLogRepository,
+    pub notification_templates: NotificationTemplateRepository,
+    pub notification_routing: NotificationRoutingRepository,
+    pub deferred_notifications: DeferredNotificationRepository,
+    pub notification_broadcasts: NotificationBroadcastRepository,
+    pub user_groups: UserGroupRepository,
+    pub notification_api_keys: NotificationApiKeyRepository,
     pub jobs: JobRepository,
     pub executions: JobExecutionRepository,
+    pub workflows: JobWorkflowRepository,
+    pub job_queue: JobQueueRepository,
+    pub job_alerts: JobAlertRepository,
 }
 
 impl Repositories {
@@ -39,8 +66,17 @@ impl Repositories {
             users: UserRepository::new(pool.clone()),
             notification_channels: NotificationChannelRepository::new(pool.clone()),
             notification_logs: NotificationLogRepository::new(pool.clone()),
+            notification_templates: NotificationTemplateRepository::new(pool.clone()),
+            notification_routing: NotificationRoutingRepository::new(pool.clone()),
+            deferred_notifications: DeferredNotificationRepository::new(pool.clone()),
+            notification_broadcasts: NotificationBroadcastRepository::new(pool.clone()),
+            user_groups: UserGroupRepository::new(pool.clone()),
+            notification_api_keys: NotificationApiKeyRepository::new(pool.clone()),
             jobs: JobRepository::new(pool.clone()),
-            executions: JobExecutionRepository::new(pool),
+            executions: JobExecutionRepository::new(pool.clone()),
+            workflows: JobWorkflowRepository::new(pool.clone()),
+            job_queue: JobQueueRepository::new(pool.clone()),
+            job_alerts: JobAlertRepository::new(pool),
         }
     }
 }
@@ -0,0 +1,211 @@
+//! Notification API key repository for async database operations.
+//!
+//! Provides CRUD operations and request counting for the
+//! notification_api_keys table.
+
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use jiff_diesel::DateTime;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{NewNotificationApiKey, NotificationApiKey};
+
+/// Notification API key repository
+#[derive(Clone)]
+pub struct NotificationApiKeyRepository {
+    pool: AsyncDbPool,
+}
+
+impl NotificationApiKeyRepository {
+    /// Creates a new NotificationApiKeyRepository with the given connection pool.
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a new API key
+    ///
+    /// # Arguments
+    /// * `new_key` - The key data to insert
+    ///
+    /// # Returns
+    /// The created key with generated id and timestamps
+    pub async fn create(&self, new_key: NewNotificationApiKey) -> AppResult<NotificationApiKey> {
+        use crate::schema::notification_api_keys::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(notification_api_keys)
+            .values(&new_key)
+            .returning(NotificationApiKey::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Finds an API key by ID
+    ///
+    /// # Arguments
+    /// * `key_id` - The ID of the key to find
+    ///
+    /// # Returns
+    /// Some(NotificationApiKey) if found, None otherwise
+    pub async fn find_by_id(&self, key_id: i32) -> AppResult<Option<NotificationApiKey>> {
+        use crate::schema::notification_api_keys::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_api_keys
+            .filter(id.eq(key_id))
+            .select(NotificationApiKey::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Finds an API key by its lookup prefix
+    ///
+    /// # Arguments
+    /// * `prefix` - The public prefix of the key
+    ///
+    /// # Returns
+    /// Some(NotificationApiKey) if found, None otherwise
+    pub async fn find_by_prefix(&self, prefix: &str) -> AppResult<Option<NotificationApiKey>> {
+        use crate::schema::notification_api_keys::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_api_keys
+            .filter(key_prefix.eq(prefix))
+            .select(NotificationApiKey::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Lists all API keys of a user
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    ///
+    /// # Returns
+    /// Vector of keys, newest first
+    pub async fn find_by_user_id(&self, uid: i32) -> AppResult<Vec<NotificationApiKey>> {
+        use crate::schema::notification_api_keys::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_api_keys
+            .filter(user_id.eq(uid))
+            .order(created_at.desc())
+            .select(NotificationApiKey::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Counts a request against a key's fixed rate-limit window
+    ///
+    /// Starts a new window when the current one began at or before
+    /// `window_cutoff`, otherwise increments the current window. Also
+    /// records the key as used.
+    ///
+    /// # Arguments
+    /// * `key_id` - The key ID
+    /// * `now` - The current time
+    /// * `window_cutoff` - Windows started at or before this are expired
+    ///
+    /// # Returns
+    /// The number of requests in the current window, including this one
+    pub async fn record_request(
+        &self,
+        key_id: i32,
+        now: DateTime,
+        window_cutoff: DateTime,
+    ) -> AppResult<i32> {
+        use crate::schema::notification_api_keys::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        // Only one of two concurrent requests can restart an expired window;
+        // the other sees the new window start and increments it instead.
+        let restarted: Option<i32> = diesel::update(
+            notification_api_keys.filter(id.eq(key_id)).filter(
+                window_started_at
+                    .is_null()
+                    .or(window_started_at.le(window_cutoff)),
+            ),
+        )
+        .set((
+            window_started_at.eq(now),
+            window_count.eq(1),
+            last_used_at.eq(now),
+        ))
+        .returning(window_count)
+        .get_result(&mut conn)
+        .await
+        .optional()
+        .map_err(AppError::from)?;
+
+        if let Some(count) = restarted {
+            return Ok(count);
+        }
+
+        diesel::update(notification_api_keys.filter(id.eq(key_id)))
+            .set((window_count.eq(window_count + 1), last_used_at.eq(now)))
+            .returning(window_count)
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Deletes an API key by ID
+    ///
+    /// # Arguments
+    /// * `key_id` - The ID of the key to delete
+    ///
+    /// # Returns
+    /// Number of rows affected (1 if deleted, 0 if not found)
+    pub async fn delete(&self, key_id: i32) -> AppResult<usize> {
+        use crate::schema::notification_api_keys::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::delete(notification_api_keys.filter(id.eq(key_id)))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+}
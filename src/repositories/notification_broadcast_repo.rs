@@ -0,0 +1,334 @@
+//! Notification broadcast repository for async database operations.
+//!
+//! Provides operations for notification_broadcasts and the
+//! notification_broadcast_recipients delivery queue.
+
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    BroadcastRecipient, BroadcastRecipientStatus, BroadcastStatus, NewBroadcastRecipient,
+    NewNotificationBroadcast, NotificationBroadcast,
+};
+
+/// Number of recipients inserted per statement when queuing a broadcast
+const RECIPIENT_INSERT_CHUNK: usize = 5000;
+
+/// Notification broadcast repository
+#[derive(Clone)]
+pub struct NotificationBroadcastRepository {
+    pool: AsyncDbPool,
+}
+
+impl NotificationBroadcastRepository {
+    /// Creates a new NotificationBroadcastRepository with the given connection pool.
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a broadcast and queues its recipients
+    ///
+    /// # Arguments
+    /// * `new_broadcast` - The broadcast data to insert
+    /// * `recipient_ids` - IDs of the users to deliver to
+    ///
+    /// # Returns
+    /// The created broadcast
+    pub async fn create(
+        &self,
+        new_broadcast: NewNotificationBroadcast,
+        recipient_ids: &[i32],
+    ) -> AppResult<NotificationBroadcast> {
+        use crate::schema::{notification_broadcast_recipients, notification_broadcasts};
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let broadcast = diesel::insert_into(notification_broadcasts::table)
+                    .values((
+                        &new_broadcast,
+                        notification_broadcasts::total_recipients.eq(recipient_ids.len() as i32),
+                    ))
+                    .returning(NotificationBroadcast::as_returning())
+                    .get_result(conn)
+                    .await?;
+
+                for chunk in recipient_ids.chunks(RECIPIENT_INSERT_CHUNK) {
+                    let recipients: Vec<NewBroadcastRecipient> = chunk
+                        .iter()
+                        .map(|&user_id| NewBroadcastRecipient {
+                            broadcast_id: broadcast.id,
+                            user_id,
+                        })
+                        .collect();
+
+                    diesel::insert_into(notification_broadcast_recipients::table)
+                        .values(&recipients)
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(broadcast)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Finds a broadcast by ID
+    ///
+    /// # Arguments
+    /// * `broadcast_id` - The ID of the broadcast to find
+    ///
+    /// # Returns
+    /// Some(NotificationBroadcast) if found, None otherwise
+    pub async fn find_by_id(&self, broadcast_id: i32) -> AppResult<Option<NotificationBroadcast>> {
+        use crate::schema::notification_broadcasts::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_broadcasts
+            .filter(id.eq(broadcast_id))
+            .select(NotificationBroadcast::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Lists broadcasts, newest first, with pagination
+    ///
+    /// # Arguments
+    /// * `offset` - Number of records to skip (for pagination)
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Tuple of (broadcasts, total count)
+    pub async fn find_paginated(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<NotificationBroadcast>, i64)> {
+        use crate::schema::notification_broadcasts::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let total: i64 = notification_broadcasts
+            .count()
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        let broadcasts = notification_broadcasts
+            .order(created_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .select(NotificationBroadcast::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok((broadcasts, total))
+    }
+
+    /// Claims pending recipients for delivery
+    ///
+    /// Claimed rows move to `sending`; rows locked by another worker are
+    /// skipped.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of recipients to claim
+    ///
+    /// # Returns
+    /// The claimed recipients in queue order
+    pub async fn claim_pending(&self, limit: i64) -> AppResult<Vec<BroadcastRecipient>> {
+        use crate::schema::notification_broadcast_recipients::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let mut claimed = conn
+            .transaction::<_, AppError, _>(|conn| {
+                async move {
+                    let pending_ids: Vec<i64> = notification_broadcast_recipients
+                        .filter(status.eq(BroadcastRecipientStatus::Pending))
+                        .order(id.asc())
+                        .limit(limit)
+                        .select(id)
+                        .for_update()
+                        .skip_locked()
+                        .load(conn)
+                        .await?;
+
+                    let claimed = diesel::update(
+                        notification_broadcast_recipients.filter(id.eq_any(pending_ids)),
+                    )
+                    .set(status.eq(BroadcastRecipientStatus::Sending))
+                    .returning(BroadcastRecipient::as_returning())
+                    .get_results(conn)
+                    .await?;
+
+                    Ok(claimed)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        claimed.sort_by_key(|r| r.id);
+        Ok(claimed)
+    }
+
+    /// Records the delivery outcome for a recipient
+    ///
+    /// # Arguments
+    /// * `recipient_id` - The recipient row ID
+    /// * `outcome` - The final recipient status
+    /// * `error` - Why delivery failed, if it did
+    pub async fn finish_recipient(
+        &self,
+        recipient_id: i64,
+        outcome: BroadcastRecipientStatus,
+        error: Option<String>,
+    ) -> AppResult<()> {
+        use crate::schema::notification_broadcast_recipients::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(notification_broadcast_recipients.filter(id.eq(recipient_id)))
+            .set((
+                status.eq(outcome),
+                error_message.eq(error),
+                processed_at.eq(diesel::dsl::now.nullable()),
+            ))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Marks pending broadcasts as running
+    ///
+    /// # Arguments
+    /// * `broadcast_ids` - Broadcasts whose recipients are being delivered
+    pub async fn mark_started(&self, broadcast_ids: &[i32]) -> AppResult<()> {
+        use crate::schema::notification_broadcasts::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            notification_broadcasts
+                .filter(id.eq_any(broadcast_ids))
+                .filter(status.eq(BroadcastStatus::Pending)),
+        )
+        .set((
+            status.eq(BroadcastStatus::Running),
+            started_at.eq(diesel::dsl::now.nullable()),
+        ))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Recomputes a broadcast's delivery stats from its recipients
+    ///
+    /// Completes the broadcast once no recipient is pending or sending.
+    ///
+    /// # Arguments
+    /// * `bid` - The broadcast ID
+    ///
+    /// # Returns
+    /// The updated broadcast
+    pub async fn refresh_stats(&self, bid: i32) -> AppResult<NotificationBroadcast> {
+        use crate::schema::{notification_broadcast_recipients as r, notification_broadcasts as b};
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let counts: Vec<(BroadcastRecipientStatus, i64)> = r::table
+            .filter(r::broadcast_id.eq(bid))
+            .group_by(r::status)
+            .select((r::status, diesel::dsl::count_star()))
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        let count = |wanted: BroadcastRecipientStatus| {
+            counts
+                .iter()
+                .filter(|(s, _)| *s == wanted)
+                .map(|(_, n)| *n as i32)
+                .sum::<i32>()
+        };
+
+        let outstanding =
+            count(BroadcastRecipientStatus::Pending) + count(BroadcastRecipientStatus::Sending);
+
+        let target = b::table.filter(b::id.eq(bid));
+        let stats = (
+            b::delivered_count.eq(count(BroadcastRecipientStatus::Delivered)),
+            b::deferred_count.eq(count(BroadcastRecipientStatus::Deferred)),
+            b::dropped_count.eq(count(BroadcastRecipientStatus::Dropped)),
+            b::failed_count.eq(count(BroadcastRecipientStatus::Failed)),
+        );
+
+        let query = if outstanding == 0 {
+            diesel::update(target)
+                .set((
+                    stats,
+                    b::status.eq(BroadcastStatus::Completed),
+                    b::completed_at.eq(diesel::dsl::now.nullable()),
+                ))
+                .returning(NotificationBroadcast::as_returning())
+                .get_result(&mut conn)
+                .await
+        } else {
+            diesel::update(target)
+                .set(stats)
+                .returning(NotificationBroadcast::as_returning())
+                .get_result(&mut conn)
+                .await
+        };
+
+        query.map_err(AppError::from)
+    }
+}
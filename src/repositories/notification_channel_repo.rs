@@ -149,6 +149,65 @@ impl NotificationChannelRepository {
             .map_err(AppError::from)
     }
 
+    /// Lists channels of all users in ID order, for batch processing
+    ///
+    /// # Arguments
+    /// * `after_id` - Only channels with a greater ID are returned
+    /// * `limit` - Maximum number of channels to return
+    ///
+    /// # Returns
+    /// Vector of channels ordered by ID
+    pub async fn find_batch_after(
+        &self,
+        after_id: i32,
+        limit: i64,
+    ) -> AppResult<Vec<NotificationChannel>> {
+        use crate::schema::notification_channels::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_channels
+            .filter(id.gt(after_id))
+            .order(id.asc())
+            .limit(limit)
+            .select(NotificationChannel::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Lists enabled channels for a user, ordered by priority
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    ///
+    /// # Returns
+    /// Vector of enabled notification channels ordered by priority (highest first)
+    pub async fn find_enabled_by_user_id(&self, uid: i32) -> AppResult<Vec<NotificationChannel>> {
+        use crate::schema::notification_channels::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_channels
+            .filter(user_id.eq(uid))
+            .filter(enabled.eq(true))
+            .order(priority.desc())
+            .select(NotificationChannel::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
     /// Lists enabled channels for a user by type, ordered by priority
     ///
     /// # Arguments
@@ -212,6 +271,107 @@ impl NotificationChannelRepository {
             .map_err(AppError::from)
     }
 
+    /// Records a successful delivery and resets the failure streak
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel that delivered a message
+    pub async fn record_success(&self, channel_id: i32) -> AppResult<()> {
+        use crate::schema::notification_channels::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(notification_channels.filter(id.eq(channel_id)))
+            .set((
+                consecutive_failures.eq(0),
+                success_count.eq(success_count + 1),
+                last_success_at.eq(diesel::dsl::now.nullable()),
+            ))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    /// Records a failed delivery
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel that failed to deliver a message
+    /// * `error` - Description of the failure
+    ///
+    /// # Returns
+    /// The channel with its updated failure counters
+    pub async fn record_failure(
+        &self,
+        channel_id: i32,
+        error: &str,
+    ) -> AppResult<NotificationChannel> {
+        use crate::schema::notification_channels::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(notification_channels.filter(id.eq(channel_id)))
+            .set((
+                consecutive_failures.eq(consecutive_failures + 1),
+                failure_count.eq(failure_count + 1),
+                last_failure_at.eq(diesel::dsl::now.nullable()),
+                last_error.eq(error),
+            ))
+            .returning(NotificationChannel::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Disables an enabled channel and records why
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel to disable
+    /// * `reason` - Why the channel was disabled
+    ///
+    /// # Returns
+    /// Some(channel) if it was disabled by this call, None if it was already disabled
+    pub async fn auto_disable(
+        &self,
+        channel_id: i32,
+        reason: &str,
+    ) -> AppResult<Option<NotificationChannel>> {
+        use crate::schema::notification_channels::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            notification_channels
+                .filter(id.eq(channel_id))
+                .filter(enabled.eq(true)),
+        )
+        .set((
+            enabled.eq(false),
+            disabled_reason.eq(reason),
+            disabled_at.eq(diesel::dsl::now.nullable()),
+        ))
+        .returning(NotificationChannel::as_returning())
+        .get_result(&mut conn)
+        .await
+        .optional()
+        .map_err(AppError::from)
+    }
+
     /// Deletes a notification channel
     ///
     /// # Arguments
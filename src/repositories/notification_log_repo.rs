@@ -1,13 +1,34 @@
 //! Notification log repository for async database operations.
 //!
-//! Provides operations for notification_logs table.
+//! Provides operations for notification_logs table and the
+//! notification_log_stats rollups that outlive purged logs.
 
+use diesel::pg::Pg;
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel::upsert::excluded;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use jiff_diesel::DateTime;
 
 use crate::db::AsyncDbPool;
 use crate::error::{AppError, AppResult};
-use crate::models::{NewNotificationLog, NotificationLog, NotificationStatus};
+use crate::models::{
+    NewNotificationLog, NewNotificationLogStat, NotificationDeliveryStats, NotificationLog,
+    NotificationLogTotals, NotificationStatus, StatsBucket,
+};
+
+/// Filters for searching notification logs
+///
+/// All set fields must match; `text` is a case-insensitive substring match
+/// against the message, error message and response body.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationLogFilter {
+    pub channel_id: Option<i32>,
+    pub status: Option<NotificationStatus>,
+    pub from: Option<DateTime>,
+    pub to: Option<DateTime>,
+    pub text: Option<String>,
+}
 
 /// Notification log repository
 #[derive(Clone)]
@@ -46,6 +67,117 @@ impl NotificationLogRepository {
             .map_err(AppError::from)
     }
 
+    /// Finds a log entry by ID
+    ///
+    /// # Arguments
+    /// * `log_id` - The ID of the log to find
+    ///
+    /// # Returns
+    /// Some(NotificationLog) if found, None otherwise
+    pub async fn find_by_id(&self, log_id: i64) -> AppResult<Option<NotificationLog>> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_logs
+            .filter(id.eq(log_id))
+            .select(NotificationLog::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Searches logs of a user's channels with pagination
+    ///
+    /// # Arguments
+    /// * `uid` - The owner of the channels
+    /// * `filter` - Search filters
+    /// * `offset` - Number of records to skip
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Tuple of (logs vector, total count), newest first
+    pub async fn search(
+        &self,
+        uid: i32,
+        filter: &NotificationLogFilter,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<NotificationLog>, i64)> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let logs = Self::filtered(uid, filter)
+            .order((sent_at.desc(), id.desc()))
+            .offset(offset)
+            .limit(limit)
+            .select(NotificationLog::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        let total = Self::filtered(uid, filter)
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok((logs, total))
+    }
+
+    /// Builds the filtered log query shared by search and its count
+    fn filtered<'a>(
+        uid: i32,
+        filter: &'a NotificationLogFilter,
+    ) -> crate::schema::notification_logs::BoxedQuery<'a, Pg> {
+        use crate::schema::notification_channels;
+        use crate::schema::notification_logs::dsl::*;
+
+        let owned_channels = notification_channels::table
+            .filter(notification_channels::user_id.eq(uid))
+            .select(notification_channels::id);
+
+        let mut query = notification_logs
+            .filter(channel_id.eq_any(owned_channels))
+            .into_boxed();
+
+        if let Some(cid) = filter.channel_id {
+            query = query.filter(channel_id.eq(cid));
+        }
+        if let Some(status_filter) = filter.status {
+            query = query.filter(status.eq(status_filter));
+        }
+        if let Some(from) = filter.from {
+            query = query.filter(sent_at.ge(from));
+        }
+        if let Some(to) = filter.to {
+            query = query.filter(sent_at.lt(to));
+        }
+        if let Some(text) = filter.text.as_deref().filter(|t| !t.is_empty()) {
+            let pattern = format!("%{}%", escape_like(text));
+            query = query.filter(
+                message
+                    .ilike(pattern.clone())
+                    .or(error_message.ilike(pattern.clone()))
+                    .or(response_body.ilike(pattern)),
+            );
+        }
+
+        query
+    }
+
     /// Finds logs for a specific channel with pagination
     ///
     /// # Arguments
@@ -137,4 +269,374 @@ impl NotificationLogRepository {
 
         Ok((logs, total))
     }
+
+    /// Counts messages actually handed to the provider since a point in time
+    ///
+    /// Suppressed and batched entries do not count towards rate limits.
+    ///
+    /// # Arguments
+    /// * `cid` - The channel ID
+    /// * `since` - Start of the rate-limit window
+    ///
+    /// # Returns
+    /// Number of sent or failed attempts in the window
+    pub async fn count_attempts_since(&self, cid: i32, since: DateTime) -> AppResult<i64> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_logs
+            .filter(channel_id.eq(cid))
+            .filter(status.eq_any([NotificationStatus::Sent, NotificationStatus::Failed]))
+            .filter(sent_at.ge(since))
+            .count()
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Finds the latest delivered log with a dedup key since a point in time
+    ///
+    /// # Arguments
+    /// * `cid` - The channel ID
+    /// * `key` - The dedup key
+    /// * `since` - Start of the suppression window
+    ///
+    /// # Returns
+    /// Some(NotificationLog) if a matching message was sent or batched
+    pub async fn find_recent_by_dedup_key(
+        &self,
+        cid: i32,
+        key: &str,
+        since: DateTime,
+    ) -> AppResult<Option<NotificationLog>> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_logs
+            .filter(channel_id.eq(cid))
+            .filter(dedup_key.eq(key))
+            .filter(status.eq_any([
+                NotificationStatus::Sent,
+                NotificationStatus::Batched,
+                NotificationStatus::Digested,
+            ]))
+            .filter(sent_at.ge(since))
+            .order(sent_at.desc())
+            .select(NotificationLog::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Lists channels that have messages waiting for a digest
+    ///
+    /// # Returns
+    /// Distinct channel IDs with batched logs
+    pub async fn find_channels_with_batched(&self) -> AppResult<Vec<i32>> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_logs
+            .filter(status.eq(NotificationStatus::Batched))
+            .select(channel_id)
+            .distinct()
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Claims a channel's batched logs for a digest
+    ///
+    /// Marks them as digested in a single statement so concurrent flushes
+    /// never include the same message twice.
+    ///
+    /// # Arguments
+    /// * `cid` - The channel ID
+    ///
+    /// # Returns
+    /// The claimed logs, oldest first
+    pub async fn claim_batched(&self, cid: i32) -> AppResult<Vec<NotificationLog>> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let mut claimed = diesel::update(
+            notification_logs
+                .filter(channel_id.eq(cid))
+                .filter(status.eq(NotificationStatus::Batched)),
+        )
+        .set(status.eq(NotificationStatus::Digested))
+        .returning(NotificationLog::as_returning())
+        .get_results(&mut conn)
+        .await
+        .map_err(AppError::from)?;
+
+        claimed.sort_by_key(|log| log.id);
+        Ok(claimed)
+    }
+
+    /// Returns claimed logs to the batch
+    ///
+    /// Used when a digest could not be delivered, so the messages are
+    /// included in the next flush instead of being lost.
+    ///
+    /// # Arguments
+    /// * `ids` - IDs of the logs returned by `claim_batched`
+    ///
+    /// # Returns
+    /// Number of logs put back
+    pub async fn release_claimed(&self, ids: &[i64]) -> AppResult<usize> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(
+            notification_logs
+                .filter(id.eq_any(ids))
+                .filter(status.eq(NotificationStatus::Digested)),
+        )
+        .set(status.eq(NotificationStatus::Batched))
+        .execute(&mut conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// Finds the oldest logs sent before a point in time
+    ///
+    /// Batched logs are skipped because they still wait for a digest.
+    ///
+    /// # Arguments
+    /// * `cutoff` - Only logs sent before this time are returned
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// The logs, oldest first
+    pub async fn find_sent_before(
+        &self,
+        cutoff: DateTime,
+        limit: i64,
+    ) -> AppResult<Vec<NotificationLog>> {
+        use crate::schema::notification_logs::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_logs
+            .filter(sent_at.lt(cutoff))
+            .filter(status.ne(NotificationStatus::Batched))
+            .order(id.asc())
+            .limit(limit)
+            .select(NotificationLog::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Deletes logs and adds them to the daily stats
+    ///
+    /// Only logs actually deleted by this call are counted, so overlapping
+    /// purges never count a log twice.
+    ///
+    /// # Arguments
+    /// * `ids` - IDs of the logs to purge
+    ///
+    /// # Returns
+    /// Number of logs deleted
+    pub async fn purge(&self, ids: &[i64]) -> AppResult<usize> {
+        use crate::schema::{notification_log_stats, notification_logs};
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let deleted = diesel::delete(
+                    notification_logs::table.filter(notification_logs::id.eq_any(ids)),
+                )
+                .returning(NotificationLog::as_returning())
+                .get_results(conn)
+                .await?;
+
+                let stats = NewNotificationLogStat::rollup(&deleted);
+                if !stats.is_empty() {
+                    diesel::insert_into(notification_log_stats::table)
+                        .values(&stats)
+                        .on_conflict((
+                            notification_log_stats::day,
+                            notification_log_stats::channel_id,
+                            notification_log_stats::status,
+                        ))
+                        .do_update()
+                        .set((
+                            notification_log_stats::message_count
+                                .eq(notification_log_stats::message_count
+                                    + excluded(notification_log_stats::message_count)),
+                            notification_log_stats::retry_count
+                                .eq(notification_log_stats::retry_count
+                                    + excluded(notification_log_stats::retry_count)),
+                            notification_log_stats::total_duration_ms
+                                .eq(notification_log_stats::total_duration_ms
+                                    + excluded(notification_log_stats::total_duration_ms)),
+                            notification_log_stats::timed_count
+                                .eq(notification_log_stats::timed_count
+                                    + excluded(notification_log_stats::timed_count)),
+                            notification_log_stats::updated_at.eq(diesel::dsl::now),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(deleted.len())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Sums a channel's delivery counts per status
+    ///
+    /// Rolled-up stats of purged logs are added to the stored logs, so the
+    /// totals cover the channel's whole history.
+    ///
+    /// # Arguments
+    /// * `cid` - The channel ID
+    ///
+    /// # Returns
+    /// One entry per status that has any messages
+    pub async fn totals_by_channel(&self, cid: i32) -> AppResult<Vec<NotificationLogTotals>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::sql_query(
+            "SELECT status, \
+                 SUM(message_count)::BIGINT AS message_count, \
+                 SUM(retry_count)::BIGINT AS retry_count, \
+                 SUM(total_duration_ms)::BIGINT AS total_duration_ms, \
+                 SUM(timed_count)::BIGINT AS timed_count \
+             FROM ( \
+                 SELECT status, message_count, retry_count, total_duration_ms, timed_count \
+                 FROM notification_log_stats WHERE channel_id = $1 \
+                 UNION ALL \
+                 SELECT status, COUNT(*), COALESCE(SUM(retry_count), 0), \
+                     COALESCE(SUM(duration_ms), 0), COUNT(duration_ms) \
+                 FROM notification_logs WHERE channel_id = $1 GROUP BY status \
+             ) totals \
+             GROUP BY status \
+             ORDER BY status",
+        )
+        .bind::<diesel::sql_types::Integer, _>(cid)
+        .load(&mut conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// Aggregates delivery counts and latency of a user's channels per provider
+    ///
+    /// Returns one row per provider and time bucket, plus one row per
+    /// provider without a bucket covering the whole range (percentiles
+    /// cannot be combined from the bucket rows).
+    ///
+    /// # Arguments
+    /// * `uid` - The channel owner
+    /// * `cid` - Only logs of this channel
+    /// * `from` - Start of the range (inclusive)
+    /// * `to` - End of the range (exclusive)
+    /// * `bucket` - Time bucket size
+    ///
+    /// # Returns
+    /// Rows ordered by provider, with the range total before its buckets
+    pub async fn delivery_stats(
+        &self,
+        uid: i32,
+        cid: Option<i32>,
+        from: DateTime,
+        to: DateTime,
+        bucket: StatsBucket,
+    ) -> AppResult<Vec<NotificationDeliveryStats>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::sql_query(
+            "SELECT bucket, channel_type, \
+                 COUNT(*)::BIGINT AS sends, \
+                 COUNT(*) FILTER (WHERE status = 'failed')::BIGINT AS failures, \
+                 COALESCE(SUM(retry_count), 0)::BIGINT AS retries, \
+                 percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms) AS p50_duration_ms, \
+                 percentile_cont(0.95) WITHIN GROUP (ORDER BY duration_ms) AS p95_duration_ms \
+             FROM ( \
+                 SELECT date_trunc($1, l.sent_at) AS bucket, c.channel_type, l.status, \
+                     l.retry_count, l.duration_ms \
+                 FROM notification_logs l \
+                 JOIN notification_channels c ON c.id = l.channel_id \
+                 WHERE c.user_id = $2 \
+                     AND ($3::INTEGER IS NULL OR l.channel_id = $3) \
+                     AND l.sent_at >= $4 AND l.sent_at < $5 \
+                     AND l.status IN ('sent', 'failed') \
+             ) deliveries \
+             GROUP BY GROUPING SETS ((channel_type, bucket), (channel_type)) \
+             ORDER BY channel_type, bucket NULLS FIRST",
+        )
+        .bind::<diesel::sql_types::Text, _>(bucket.as_sql())
+        .bind::<diesel::sql_types::Integer, _>(uid)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(cid)
+        .bind::<diesel::sql_types::Timestamp, _>(from)
+        .bind::<diesel::sql_types::Timestamp, _>(to)
+        .load(&mut conn)
+        .await
+        .map_err(AppError::from)
+    }
+}
+
+/// Escapes LIKE wildcards so user text matches literally
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
@@ -0,0 +1,251 @@
+//! Notification routing repository for async database operations.
+//!
+//! Provides CRUD operations for notification_routing_rules and
+//! notification_quiet_hours tables.
+
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    NewNotificationQuietHours, NewNotificationRoutingRule, NotificationQuietHours,
+    NotificationRoutingRule, UpdateNotificationRoutingRule,
+};
+
+/// Notification routing repository
+#[derive(Clone)]
+pub struct NotificationRoutingRepository {
+    pool: AsyncDbPool,
+}
+
+impl NotificationRoutingRepository {
+    /// Creates a new NotificationRoutingRepository with the given connection pool.
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    // ========================================================================
+    // Routing Rules
+    // ========================================================================
+
+    /// Creates a new routing rule
+    ///
+    /// # Arguments
+    /// * `new_rule` - The rule data to insert
+    ///
+    /// # Returns
+    /// The created rule with generated id and timestamps
+    pub async fn create_rule(
+        &self,
+        new_rule: NewNotificationRoutingRule,
+    ) -> AppResult<NotificationRoutingRule> {
+        use crate::schema::notification_routing_rules::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(notification_routing_rules)
+            .values(&new_rule)
+            .returning(NotificationRoutingRule::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Finds a routing rule by ID
+    ///
+    /// # Arguments
+    /// * `rule_id` - The ID of the rule to find
+    ///
+    /// # Returns
+    /// Some(NotificationRoutingRule) if found, None otherwise
+    pub async fn find_rule_by_id(
+        &self,
+        rule_id: i32,
+    ) -> AppResult<Option<NotificationRoutingRule>> {
+        use crate::schema::notification_routing_rules::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_routing_rules
+            .filter(id.eq(rule_id))
+            .select(NotificationRoutingRule::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Lists all routing rules for a user in evaluation order
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    ///
+    /// # Returns
+    /// Vector of rules ordered by priority (highest first)
+    pub async fn find_rules_by_user_id(&self, uid: i32) -> AppResult<Vec<NotificationRoutingRule>> {
+        use crate::schema::notification_routing_rules::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_routing_rules
+            .filter(user_id.eq(uid))
+            .order((priority.desc(), id.asc()))
+            .select(NotificationRoutingRule::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Updates a routing rule
+    ///
+    /// # Arguments
+    /// * `rule_id` - The ID of the rule to update
+    /// * `update_data` - The update data
+    ///
+    /// # Returns
+    /// The updated routing rule
+    pub async fn update_rule(
+        &self,
+        rule_id: i32,
+        update_data: UpdateNotificationRoutingRule,
+    ) -> AppResult<NotificationRoutingRule> {
+        use crate::schema::notification_routing_rules::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(notification_routing_rules.filter(id.eq(rule_id)))
+            .set(&update_data)
+            .returning(NotificationRoutingRule::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Deletes a routing rule
+    ///
+    /// # Arguments
+    /// * `rule_id` - The ID of the rule to delete
+    ///
+    /// # Returns
+    /// Number of rows affected (1 if deleted, 0 if not found)
+    pub async fn delete_rule(&self, rule_id: i32) -> AppResult<usize> {
+        use crate::schema::notification_routing_rules::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::delete(notification_routing_rules.filter(id.eq(rule_id)))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    // ========================================================================
+    // Quiet Hours
+    // ========================================================================
+
+    /// Finds the quiet hours configured for a user
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    ///
+    /// # Returns
+    /// Some(NotificationQuietHours) if configured, None otherwise
+    pub async fn find_quiet_hours(&self, uid: i32) -> AppResult<Option<NotificationQuietHours>> {
+        use crate::schema::notification_quiet_hours::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_quiet_hours
+            .filter(user_id.eq(uid))
+            .select(NotificationQuietHours::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Creates or replaces the quiet hours for a user
+    ///
+    /// # Arguments
+    /// * `quiet_hours` - The quiet hours configuration
+    ///
+    /// # Returns
+    /// The stored quiet hours
+    pub async fn upsert_quiet_hours(
+        &self,
+        quiet_hours: NewNotificationQuietHours,
+    ) -> AppResult<NotificationQuietHours> {
+        use crate::schema::notification_quiet_hours::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(notification_quiet_hours)
+            .values(&quiet_hours)
+            .on_conflict(user_id)
+            .do_update()
+            .set(&quiet_hours)
+            .returning(NotificationQuietHours::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Deletes the quiet hours for a user
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    ///
+    /// # Returns
+    /// Number of rows affected (1 if deleted, 0 if not configured)
+    pub async fn delete_quiet_hours(&self, uid: i32) -> AppResult<usize> {
+        use crate::schema::notification_quiet_hours::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::delete(notification_quiet_hours.filter(user_id.eq(uid)))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+}
@@ -0,0 +1,196 @@
+//! Notification template repository for async database operations.
+//!
+//! Provides CRUD operations for notification_templates table.
+
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{NewNotificationTemplate, NotificationTemplate, UpdateNotificationTemplate};
+
+/// Notification template repository
+#[derive(Clone)]
+pub struct NotificationTemplateRepository {
+    pool: AsyncDbPool,
+}
+
+impl NotificationTemplateRepository {
+    /// Creates a new NotificationTemplateRepository with the given connection pool.
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a new notification template
+    ///
+    /// # Arguments
+    /// * `new_template` - The template data to insert
+    ///
+    /// # Returns
+    /// The created template with generated id and timestamps
+    pub async fn create(
+        &self,
+        new_template: NewNotificationTemplate,
+    ) -> AppResult<NotificationTemplate> {
+        use crate::schema::notification_templates::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::insert_into(notification_templates)
+            .values(&new_template)
+            .returning(NotificationTemplate::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Finds a template by ID
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to find
+    ///
+    /// # Returns
+    /// Some(NotificationTemplate) if found, None otherwise
+    pub async fn find_by_id(&self, template_id: i32) -> AppResult<Option<NotificationTemplate>> {
+        use crate::schema::notification_templates::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_templates
+            .filter(id.eq(template_id))
+            .select(NotificationTemplate::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Lists templates visible to a user (their own plus system templates)
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    ///
+    /// # Returns
+    /// Vector of templates ordered by key
+    pub async fn find_visible_to_user(&self, uid: i32) -> AppResult<Vec<NotificationTemplate>> {
+        use crate::schema::notification_templates::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        notification_templates
+            .filter(user_id.eq(uid).or(user_id.is_null()))
+            .order((template_key.asc(), id.asc()))
+            .select(NotificationTemplate::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Resolves a template by key for a user
+    ///
+    /// The user's own template takes precedence over a system template
+    /// with the same key.
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID
+    /// * `key` - The template key
+    ///
+    /// # Returns
+    /// Some(NotificationTemplate) if either exists, None otherwise
+    pub async fn find_by_key(
+        &self,
+        uid: i32,
+        key: &str,
+    ) -> AppResult<Option<NotificationTemplate>> {
+        use crate::schema::notification_templates::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        let candidates = notification_templates
+            .filter(template_key.eq(key))
+            .filter(user_id.eq(uid).or(user_id.is_null()))
+            .select(NotificationTemplate::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+
+        // At most two rows: the user's override and the system default
+        let (user_owned, system): (Vec<_>, Vec<_>) =
+            candidates.into_iter().partition(|t| t.user_id.is_some());
+
+        Ok(user_owned.into_iter().chain(system).next())
+    }
+
+    /// Updates a notification template
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to update
+    /// * `update_data` - The update data
+    ///
+    /// # Returns
+    /// The updated notification template
+    pub async fn update(
+        &self,
+        template_id: i32,
+        update_data: UpdateNotificationTemplate,
+    ) -> AppResult<NotificationTemplate> {
+        use crate::schema::notification_templates::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(notification_templates.filter(id.eq(template_id)))
+            .set(&update_data)
+            .returning(NotificationTemplate::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Deletes a notification template
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to delete
+    ///
+    /// # Returns
+    /// Number of rows affected (1 if deleted, 0 if not found)
+    pub async fn delete(&self, template_id: i32) -> AppResult<usize> {
+        use crate::schema::notification_templates::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::delete(notification_templates.filter(id.eq(template_id)))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+}
@@ -0,0 +1,249 @@
+//! User group repository for async database operations.
+//!
+//! Provides CRUD operations for user_groups and user_group_members tables.
+
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::db::AsyncDbPool;
+use crate::error::{AppError, AppResult};
+use crate::models::{NewUserGroup, NewUserGroupMember, UserGroup};
+
+/// User group repository
+#[derive(Clone)]
+pub struct UserGroupRepository {
+    pool: AsyncDbPool,
+}
+
+impl UserGroupRepository {
+    /// Creates a new UserGroupRepository with the given connection pool.
+    pub fn new(pool: AsyncDbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a group with its initial members
+    ///
+    /// # Arguments
+    /// * `new_group` - The group data to insert
+    /// * `member_ids` - IDs of the users in the group
+    ///
+    /// # Returns
+    /// The created group with generated id and timestamps
+    pub async fn create(
+        &self,
+        new_group: NewUserGroup,
+        member_ids: &[i32],
+    ) -> AppResult<UserGroup> {
+        use crate::schema::{user_group_members, user_groups};
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let group = diesel::insert_into(user_groups::table)
+                    .values(&new_group)
+                    .returning(UserGroup::as_returning())
+                    .get_result(conn)
+                    .await?;
+
+                let members: Vec<NewUserGroupMember> = member_ids
+                    .iter()
+                    .map(|&user_id| NewUserGroupMember {
+                        group_id: group.id,
+                        user_id,
+                    })
+                    .collect();
+
+                if !members.is_empty() {
+                    diesel::insert_into(user_group_members::table)
+                        .values(&members)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(group)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Finds a group by ID
+    ///
+    /// # Arguments
+    /// * `group_id` - The ID of the group to find
+    ///
+    /// # Returns
+    /// Some(UserGroup) if found, None otherwise
+    pub async fn find_by_id(&self, group_id: i32) -> AppResult<Option<UserGroup>> {
+        use crate::schema::user_groups::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        user_groups
+            .filter(id.eq(group_id))
+            .select(UserGroup::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// Lists all groups ordered by name
+    ///
+    /// # Returns
+    /// Vector of groups
+    pub async fn find_all(&self) -> AppResult<Vec<UserGroup>> {
+        use crate::schema::user_groups::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        user_groups
+            .order(name.asc())
+            .select(UserGroup::as_select())
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Lists the user IDs in a group
+    ///
+    /// # Arguments
+    /// * `gid` - The group ID
+    ///
+    /// # Returns
+    /// Member user IDs in ascending order
+    pub async fn find_member_ids(&self, gid: i32) -> AppResult<Vec<i32>> {
+        use crate::schema::user_group_members::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        user_group_members
+            .filter(group_id.eq(gid))
+            .order(user_id.asc())
+            .select(user_id)
+            .load(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Resolves the users of a broadcast audience
+    ///
+    /// # Arguments
+    /// * `gid` - The group to target, or None for every user
+    ///
+    /// # Returns
+    /// User IDs in ascending order
+    pub async fn find_audience_ids(&self, gid: Option<i32>) -> AppResult<Vec<i32>> {
+        use crate::schema::users;
+
+        match gid {
+            Some(gid) => self.find_member_ids(gid).await,
+            None => {
+                let mut conn = self
+                    .pool
+                    .get()
+                    .await
+                    .map_err(|e| AppError::ConnectionPool {
+                        source: anyhow::Error::from(e),
+                    })?;
+
+                users::table
+                    .order(users::id.asc())
+                    .select(users::id)
+                    .load(&mut conn)
+                    .await
+                    .map_err(AppError::from)
+            }
+        }
+    }
+
+    /// Replaces the members of a group
+    ///
+    /// # Arguments
+    /// * `gid` - The group ID
+    /// * `member_ids` - IDs of the users that make up the group
+    pub async fn set_members(&self, gid: i32, member_ids: &[i32]) -> AppResult<()> {
+        use crate::schema::user_group_members::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                diesel::delete(user_group_members.filter(group_id.eq(gid)))
+                    .execute(conn)
+                    .await?;
+
+                let members: Vec<NewUserGroupMember> = member_ids
+                    .iter()
+                    .map(|&uid| NewUserGroupMember {
+                        group_id: gid,
+                        user_id: uid,
+                    })
+                    .collect();
+
+                if !members.is_empty() {
+                    diesel::insert_into(user_group_members)
+                        .values(&members)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Deletes a group and its memberships
+    ///
+    /// # Arguments
+    /// * `group_id` - The ID of the group to delete
+    ///
+    /// # Returns
+    /// Number of rows affected (1 if deleted, 0 if not found)
+    pub async fn delete(&self, group_id: i32) -> AppResult<usize> {
+        use crate::schema::user_groups::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::delete(user_groups.filter(id.eq(group_id)))
+            .execute(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+}
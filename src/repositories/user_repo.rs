@@ -185,6 +185,32 @@ impl UserRepository {
             .map_err(AppError::from)
     }
 
+    /// Sets whether a user is an administrator.
+    ///
+    /// # Arguments
+    /// * `user_id` - The user's ID
+    /// * `admin` - Whether the user is an administrator
+    ///
+    /// # Returns
+    /// The updated user
+    pub async fn set_admin(&self, user_id: i32, admin: bool) -> AppResult<User> {
+        use crate::schema::users::dsl::*;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::ConnectionPool {
+                source: anyhow::Error::from(e),
+            })?;
+
+        diesel::update(users.filter(id.eq(user_id)))
+            .set(is_admin.eq(admin))
+            .returning(User::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
     /// Deletes a user from the database.
     ///
     /// # Arguments
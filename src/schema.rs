@@ -1,17 +1,92 @@
 // @generated automatically by Diesel CLI.
 
 pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "broadcast_recipient_status"))]
+    pub struct BroadcastRecipientStatus;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "broadcast_status"))]
+    pub struct BroadcastStatus;
+
     #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "channel_type"))]
     pub struct ChannelType;
 
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "job_alert_event"))]
+    pub struct JobAlertEvent;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "job_log_level"))]
+    pub struct JobLogLevel;
+
     #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "job_status"))]
     pub struct JobStatus;
 
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "notification_severity"))]
+    pub struct NotificationSeverity;
+
     #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "notification_status"))]
     pub struct NotificationStatus;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "queued_task_status"))]
+    pub struct QueuedTaskStatus;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "quiet_hours_action"))]
+    pub struct QuietHoursAction;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "retry_jitter"))]
+    pub struct RetryJitter;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "routing_mode"))]
+    pub struct RoutingMode;
+}
+
+diesel::table! {
+    deferred_notifications (id) {
+        id -> Int8,
+        user_id -> Int4,
+        message -> Jsonb,
+        deliver_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::JobAlertEvent;
+
+    job_alert_rules (id) {
+        id -> Int4,
+        job_id -> Int4,
+        event -> JobAlertEvent,
+        threshold -> Nullable<Int4>,
+        channel_ids -> Array<Int4>,
+        created_at -> Timestamp,
+        user_id -> Int4,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::JobLogLevel;
+
+    job_execution_logs (id) {
+        id -> Int8,
+        execution_id -> Uuid,
+        level -> JobLogLevel,
+        message -> Text,
+        fields -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+    }
 }
 
 diesel::table! {
@@ -32,6 +107,165 @@ diesel::table! {
         error_message -> Nullable<Text>,
         error_details -> Nullable<Jsonb>,
         result -> Nullable<Jsonb>,
+        progress_percent -> Nullable<Int2>,
+        progress_message -> Nullable<Text>,
+        cancel_requested -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::QueuedTaskStatus;
+
+    job_queue (id) {
+        id -> Int8,
+        #[max_length = 100]
+        job_type -> Varchar,
+        payload -> Jsonb,
+        priority -> Int4,
+        run_at -> Timestamp,
+        status -> QueuedTaskStatus,
+        attempts -> Int4,
+        max_attempts -> Int4,
+        retry_delay_seconds -> Int4,
+        visibility_timeout_seconds -> Int4,
+        locked_until -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        completed_at -> Nullable<Timestamp>,
+        result -> Nullable<Jsonb>,
+        progress_percent -> Nullable<Int2>,
+        progress_message -> Nullable<Text>,
+        execution_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::JobLogLevel;
+
+    job_queue_logs (id) {
+        id -> Int8,
+        task_id -> Int8,
+        attempt -> Int4,
+        level -> JobLogLevel,
+        message -> Text,
+        fields -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::JobStatus;
+
+    job_workflow_runs (id) {
+        id -> Int8,
+        workflow_id -> Int4,
+        run_id -> Uuid,
+        status -> JobStatus,
+        started_at -> Timestamp,
+        completed_at -> Nullable<Timestamp>,
+        locked_until -> Nullable<Timestamp>,
+        locked_by -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::JobStatus;
+
+    job_workflow_step_runs (id) {
+        id -> Int8,
+        workflow_run_id -> Int8,
+        #[max_length = 100]
+        step_name -> Varchar,
+        job_id -> Int4,
+        depends_on -> Array<Text>,
+        status -> JobStatus,
+        execution_id -> Nullable<Uuid>,
+        started_at -> Nullable<Timestamp>,
+        completed_at -> Nullable<Timestamp>,
+        error_message -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    job_workflow_steps (id) {
+        id -> Int4,
+        workflow_id -> Int4,
+        #[max_length = 100]
+        step_name -> Varchar,
+        job_id -> Int4,
+        depends_on -> Array<Text>,
+    }
+}
+
+diesel::table! {
+    job_workflows (id) {
+        id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    notification_api_keys (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 32]
+        key_prefix -> Varchar,
+        #[max_length = 255]
+        key_hash -> Varchar,
+        allowed_ips -> Array<Text>,
+        rate_limit_count -> Nullable<Int4>,
+        rate_limit_window_seconds -> Int4,
+        window_started_at -> Nullable<Timestamp>,
+        window_count -> Int4,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BroadcastRecipientStatus;
+
+    notification_broadcast_recipients (id) {
+        id -> Int8,
+        broadcast_id -> Int4,
+        user_id -> Int4,
+        status -> BroadcastRecipientStatus,
+        error_message -> Nullable<Text>,
+        processed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BroadcastStatus;
+
+    notification_broadcasts (id) {
+        id -> Int4,
+        created_by -> Nullable<Int4>,
+        group_id -> Nullable<Int4>,
+        message -> Jsonb,
+        status -> BroadcastStatus,
+        total_recipients -> Int4,
+        delivered_count -> Int4,
+        deferred_count -> Int4,
+        dropped_count -> Int4,
+        failed_count -> Int4,
+        created_at -> Timestamp,
+        started_at -> Nullable<Timestamp>,
+        completed_at -> Nullable<Timestamp>,
     }
 }
 
@@ -50,6 +284,35 @@ diesel::table! {
         priority -> Int4,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        rate_limit_count -> Nullable<Int4>,
+        rate_limit_window_seconds -> Int4,
+        dedup_window_seconds -> Int4,
+        digest_enabled -> Bool,
+        failure_threshold -> Int4,
+        consecutive_failures -> Int4,
+        success_count -> Int8,
+        failure_count -> Int8,
+        last_success_at -> Nullable<Timestamp>,
+        last_failure_at -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+        disabled_reason -> Nullable<Text>,
+        disabled_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::NotificationStatus;
+
+    notification_log_stats (day, channel_id, status) {
+        day -> Date,
+        channel_id -> Int4,
+        status -> NotificationStatus,
+        message_count -> Int8,
+        retry_count -> Int8,
+        total_duration_ms -> Int8,
+        timed_count -> Int8,
+        updated_at -> Timestamp,
     }
 }
 
@@ -65,12 +328,76 @@ diesel::table! {
         error_message -> Nullable<Text>,
         retry_count -> Int4,
         sent_at -> Timestamp,
+        #[max_length = 255]
+        dedup_key -> Nullable<Varchar>,
+        request_meta -> Nullable<Jsonb>,
+        response_status -> Nullable<Int4>,
+        response_body -> Nullable<Text>,
+        duration_ms -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::NotificationSeverity;
+    use super::sql_types::QuietHoursAction;
+
+    notification_quiet_hours (id) {
+        id -> Int4,
+        user_id -> Int4,
+        start_time -> Time,
+        end_time -> Time,
+        #[max_length = 64]
+        timezone -> Varchar,
+        bypass_severity -> NotificationSeverity,
+        action -> QuietHoursAction,
+        enabled -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::NotificationSeverity;
+    use super::sql_types::RoutingMode;
+
+    notification_routing_rules (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        priority -> Int4,
+        enabled -> Bool,
+        match_tags -> Array<Text>,
+        match_metadata -> Jsonb,
+        min_severity -> Nullable<NotificationSeverity>,
+        channel_ids -> Array<Int4>,
+        mode -> RoutingMode,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    notification_templates (id) {
+        id -> Int4,
+        user_id -> Nullable<Int4>,
+        #[max_length = 100]
+        template_key -> Varchar,
+        description -> Nullable<Text>,
+        title_template -> Nullable<Text>,
+        body_template -> Text,
+        variants -> Jsonb,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::JobStatus;
+    use super::sql_types::RetryJitter;
 
     scheduled_jobs (id) {
         id -> Int4,
@@ -96,6 +423,32 @@ diesel::table! {
         updated_at -> Timestamp,
         #[max_length = 255]
         created_by -> Nullable<Varchar>,
+        last_fired_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        timezone -> Varchar,
+        consecutive_failures -> Int4,
+        retry_max_delay_seconds -> Int4,
+        retry_jitter -> RetryJitter,
+        retry_on_timeout -> Bool,
+    }
+}
+
+diesel::table! {
+    user_group_members (group_id, user_id) {
+        group_id -> Int4,
+        user_id -> Int4,
+        added_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_groups (id) {
+        id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -110,17 +463,55 @@ diesel::table! {
         password -> Varchar,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        is_admin -> Bool,
     }
 }
 
+diesel::joinable!(deferred_notifications -> users (user_id));
+diesel::joinable!(job_alert_rules -> scheduled_jobs (job_id));
+diesel::joinable!(job_alert_rules -> users (user_id));
 diesel::joinable!(job_executions -> scheduled_jobs (job_id));
+diesel::joinable!(job_queue_logs -> job_queue (task_id));
+diesel::joinable!(job_workflow_runs -> job_workflows (workflow_id));
+diesel::joinable!(job_workflow_step_runs -> job_workflow_runs (workflow_run_id));
+diesel::joinable!(job_workflow_steps -> job_workflows (workflow_id));
+diesel::joinable!(job_workflow_steps -> scheduled_jobs (job_id));
+diesel::joinable!(notification_api_keys -> users (user_id));
+diesel::joinable!(notification_broadcast_recipients -> notification_broadcasts (broadcast_id));
+diesel::joinable!(notification_broadcast_recipients -> users (user_id));
+diesel::joinable!(notification_broadcasts -> user_groups (group_id));
+diesel::joinable!(notification_broadcasts -> users (created_by));
 diesel::joinable!(notification_channels -> users (user_id));
+diesel::joinable!(notification_log_stats -> notification_channels (channel_id));
 diesel::joinable!(notification_logs -> notification_channels (channel_id));
+diesel::joinable!(notification_quiet_hours -> users (user_id));
+diesel::joinable!(notification_routing_rules -> users (user_id));
+diesel::joinable!(notification_templates -> users (user_id));
+diesel::joinable!(user_group_members -> user_groups (group_id));
+diesel::joinable!(user_group_members -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    deferred_notifications,
+    job_alert_rules,
+    job_execution_logs,
     job_executions,
+    job_queue,
+    job_queue_logs,
+    job_workflow_runs,
+    job_workflow_step_runs,
+    job_workflow_steps,
+    job_workflows,
+    notification_api_keys,
+    notification_broadcast_recipients,
+    notification_broadcasts,
     notification_channels,
+    notification_log_stats,
     notification_logs,
+    notification_quiet_hours,
+    notification_routing_rules,
+    notification_templates,
     scheduled_jobs,
+    user_group_members,
+    user_groups,
     users,
 );
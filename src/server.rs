@@ -6,7 +6,10 @@ use crate::api::routes::create_router;
 use crate::cache::{CacheManager, init_cache};
 use crate::config::{Environment, settings::Settings};
 use crate::db::establish_async_connection_pool;
+use crate::repositories::Repositories;
+use crate::services::Services;
 use crate::state::AppState;
+use std::net::SocketAddr;
 use tokio::net::TcpListener;
 use tokio::signal;
 
@@ -48,6 +51,33 @@ impl Server {
         Ok(())
     }
 
+    /// Initialize the secret cipher used for channel credentials
+    fn initialize_encryption(&self) -> anyhow::Result<()> {
+        self.settings.encryption.validate()?;
+
+        if crate::utils::secret::init_secret_cipher(&self.settings.encryption)? {
+            tracing::info!(
+                key_id = %self.settings.encryption.key_id,
+                "Secret encryption enabled"
+            );
+        } else {
+            tracing::warn!("No encryption key configured; channel secrets are stored unencrypted");
+        }
+        Ok(())
+    }
+
+    /// Initialize the VAPID key pair used for Web Push channels
+    fn initialize_webpush(&self) -> anyhow::Result<()> {
+        self.settings.webpush.validate()?;
+
+        if crate::utils::webpush::init_vapid(&self.settings.webpush)? {
+            tracing::info!("Web Push enabled");
+        } else {
+            tracing::info!("No VAPID keys configured; Web Push channels are disabled");
+        }
+        Ok(())
+    }
+
     /// Initialize database connection pool
     async fn initialize_database(&self) -> anyhow::Result<crate::db::AsyncDbPool> {
         tracing::info!("Initializing database connection pool...");
@@ -66,8 +96,17 @@ impl Server {
 
             let mut registry = crate::jobs::JobRegistry::new();
             registry.register::<crate::jobs::tasks::DataCleanupTask>();
-
-            let job_scheduler = crate::jobs::JobScheduler::new(pool, registry).await?;
+            registry.register::<crate::jobs::tasks::DeferredNotificationTask>();
+            registry.register::<crate::jobs::tasks::NotificationDigestTask>();
+            registry.register::<crate::jobs::tasks::SecretRotationTask>();
+            registry.register::<crate::jobs::tasks::NotificationBroadcastTask>();
+            registry.register::<crate::jobs::tasks::NotificationLogRetentionTask>();
+            registry.register::<crate::jobs::tasks::WorkflowTask>();
+
+            // Alerts are sent with the same notification service as the API
+            let notifications = Services::new(Repositories::new(pool.clone())).notifications;
+            let job_scheduler =
+                crate::jobs::JobScheduler::new(pool, registry, notifications).await?;
             job_scheduler.start().await?;
 
             tracing::info!("Job scheduler started");
@@ -124,18 +163,20 @@ impl Server {
     /// This method:
     /// 1. Logs startup information
     /// 2. Validates configuration
-    /// 3. Initializes database connection pool
-    /// 4. Initializes job scheduler (if enabled)
-    /// 5. Initializes cache manager (if enabled)
-    /// 6. Creates application state
-    /// 7. Binds to configured address
-    /// 8. Starts the HTTP server with graceful shutdown
+    /// 3. Initializes secret encryption and Web Push keys
+    /// 4. Initializes database connection pool
+    /// 5. Initializes job scheduler (if enabled)
+    /// 6. Initializes cache manager (if enabled)
+    /// 7. Creates application state
+    /// 8. Binds to configured address
+    /// 9. Starts the HTTP server with graceful shutdown
     ///
     /// # Returns
     /// Returns Ok(()) on successful shutdown, or error on startup failure
     ///
     /// # Errors
     /// - Configuration validation errors
+    /// - Encryption key and VAPID key errors
     /// - Database connection pool initialization errors
     /// - Job scheduler initialization errors
     /// - Cache manager initialization errors
@@ -144,6 +185,8 @@ impl Server {
     pub async fn run(self) -> anyhow::Result<()> {
         self.log_startup_config();
         self.validate_config()?;
+        self.initialize_encryption()?;
+        self.initialize_webpush()?;
 
         let pool = self.initialize_database().await?;
         let scheduler = self.initialize_scheduler(pool.clone()).await?;
@@ -157,9 +200,13 @@ impl Server {
 
         let listener = self.bind_listener().await?;
 
-        axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown_signal())
-            .await?;
+        // Peer addresses are needed for API key IP allow-lists
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
 
         Self::shutdown_scheduler(&state).await?;
         tracing::info!("Server shutdown complete");
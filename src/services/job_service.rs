@@ -1,27 +1,65 @@
 //! Job service for business logic operations.
 
-use crate::error::AppResult;
-use crate::jobs::models::{JobExecution, NewScheduledJob, ScheduledJob, UpdateScheduledJob};
-use crate::repositories::{JobExecutionRepository, JobRepository};
+use jiff::Timestamp;
+use jiff_diesel::DateTime;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::jobs::alerts::MAX_ALERT_RULES;
+use crate::jobs::cron;
+use crate::jobs::models::{
+    JobAlertRule, JobExecution, JobExecutionLog, JobWorkflow, NewJobAlertRule, NewJobWorkflow,
+    NewScheduledJob, QueuedTask, QueuedTaskLog, ScheduledJob, UpdateScheduledJob, WorkflowRun,
+    WorkflowStep, WorkflowStepRun, WorkflowStepSpec,
+};
+use crate::jobs::types::QueuedTaskStatus;
+use crate::jobs::workflow::WorkflowGraph;
+use crate::repositories::{
+    JobAlertRepository, JobExecutionRepository, JobQueueRepository, JobRepository,
+    JobWorkflowRepository, NotificationChannelRepository,
+};
+use crate::utils::timezone::parse_timezone;
 
 /// Job service for handling job-related business logic.
 #[derive(Clone)]
 pub struct JobService {
     job_repo: JobRepository,
     execution_repo: JobExecutionRepository,
+    workflow_repo: JobWorkflowRepository,
+    queue_repo: JobQueueRepository,
+    alert_repo: JobAlertRepository,
+    channel_repo: NotificationChannelRepository,
 }
 
 impl JobService {
     /// Creates a new JobService with the given repositories.
-    pub fn new(job_repo: JobRepository, execution_repo: JobExecutionRepository) -> Self {
+    pub fn new(
+        job_repo: JobRepository,
+        execution_repo: JobExecutionRepository,
+        workflow_repo: JobWorkflowRepository,
+        queue_repo: JobQueueRepository,
+        alert_repo: JobAlertRepository,
+        channel_repo: NotificationChannelRepository,
+    ) -> Self {
         Self {
             job_repo,
             execution_repo,
+            workflow_repo,
+            queue_repo,
+            alert_repo,
+            channel_repo,
         }
     }
 
     /// Creates a new scheduled job.
-    pub async fn create_job(&self, new_job: NewScheduledJob) -> AppResult<ScheduledJob> {
+    ///
+    /// The cron expression and timezone are validated and, for enabled
+    /// jobs, the first fire time is stored as `next_run_at`.
+    pub async fn create_job(&self, mut new_job: NewScheduledJob) -> AppResult<ScheduledJob> {
+        let next = next_run_at(&new_job.cron_expression, &new_job.timezone)?;
+        if new_job.enabled {
+            new_job.next_run_at = next;
+        }
         self.job_repo.create(new_job).await
     }
 
@@ -36,12 +74,44 @@ impl JobService {
     }
 
     /// Updates a job.
+    ///
+    /// `next_run_at` is recomputed when the schedule, timezone or enabled
+    /// state changes; disabled jobs have none.
     pub async fn update_job(&self, id: i32, update: UpdateScheduledJob) -> AppResult<ScheduledJob> {
-        self.job_repo.update(id, update).await
+        if let Some(expression) = &update.cron_expression {
+            cron::parse(expression)?;
+        }
+        if let Some(timezone) = &update.timezone {
+            parse_timezone(timezone)?;
+        }
+        let reschedule = update.cron_expression.is_some()
+            || update.timezone.is_some()
+            || update.enabled.is_some();
+
+        let job = self.job_repo.update(id, update).await?;
+        if !reschedule {
+            return Ok(job);
+        }
+
+        let next = if job.enabled {
+            next_run_at(&job.cron_expression, &job.timezone)?
+        } else {
+            None
+        };
+        self.job_repo.set_next_run(id, next).await
     }
 
     /// Deletes a job.
+    ///
+    /// # Errors
+    /// - `UnprocessableContent` if a workflow step still runs the job
     pub async fn delete_job(&self, id: i32) -> AppResult<()> {
+        let workflows = self.workflow_repo.names_using_job(id).await?;
+        if !workflows.is_empty() {
+            return Err(AppError::UnprocessableContent {
+                message: format!("Job is used by workflows: {}", workflows.join(", ")),
+            });
+        }
         self.job_repo.delete(id).await
     }
 
@@ -51,7 +121,7 @@ impl JobService {
             enabled: Some(false),
             ..Default::default()
         };
-        self.job_repo.update(id, update).await
+        self.update_job(id, update).await
     }
 
     /// Resumes a job (enables it).
@@ -60,7 +130,28 @@ impl JobService {
             enabled: Some(true),
             ..Default::default()
         };
-        self.job_repo.update(id, update).await
+        self.update_job(id, update).await
+    }
+
+    /// Previews the upcoming fire times of a job.
+    ///
+    /// # Arguments
+    /// * `id` - Job ID
+    /// * `count` - Number of fire times to return
+    ///
+    /// # Returns
+    /// The job and its next `count` fire times in its timezone, regardless
+    /// of whether it is currently enabled
+    pub async fn upcoming_runs(
+        &self,
+        id: i32,
+        count: usize,
+    ) -> AppResult<(ScheduledJob, Vec<Timestamp>)> {
+        let job = self.job_repo.get_by_id(id).await?;
+        let schedule = cron::parse(&job.cron_expression)?;
+        let tz = parse_timezone(&job.timezone)?;
+        let runs = cron::upcoming(&schedule, &tz, Timestamp::now(), count);
+        Ok((job, runs))
     }
 
     /// Lists execution history for a job.
@@ -72,4 +163,237 @@ impl JobService {
     ) -> AppResult<Vec<JobExecution>> {
         self.execution_repo.list_by_job(job_id, limit, offset).await
     }
+
+    /// Gets an execution with the log lines its task appended.
+    pub async fn get_job_execution(
+        &self,
+        execution_id: Uuid,
+    ) -> AppResult<(JobExecution, Vec<JobExecutionLog>)> {
+        let execution = self
+            .execution_repo
+            .get_by_execution_id(execution_id)
+            .await?;
+        let logs = self.execution_repo.list_logs(execution_id).await?;
+        Ok((execution, logs))
+    }
+
+    /// Lists a user's alert rules on a job.
+    ///
+    /// # Errors
+    /// - `NotFound` if the job does not exist
+    pub async fn get_alert_rules(&self, job_id: i32, user_id: i32) -> AppResult<Vec<JobAlertRule>> {
+        self.job_repo.get_by_id(job_id).await?;
+        self.alert_repo.list_by_job_and_user(job_id, user_id).await
+    }
+
+    /// Replaces a user's alert rules on a job.
+    ///
+    /// Rules other users set on the job are left unchanged.
+    ///
+    /// # Arguments
+    /// * `job_id` - Job whose rules are replaced
+    /// * `user_id` - User setting the rules, who must own every channel
+    /// * `rules` - New rules; an empty list removes all of the user's rules
+    ///
+    /// # Errors
+    /// - `NotFound` if the job does not exist
+    /// - `Validation` if there are too many rules, or a rule targets no
+    ///   channel or a channel the user does not own
+    pub async fn set_alert_rules(
+        &self,
+        job_id: i32,
+        user_id: i32,
+        rules: Vec<NewJobAlertRule>,
+    ) -> AppResult<Vec<JobAlertRule>> {
+        self.job_repo.get_by_id(job_id).await?;
+
+        if rules.len() > MAX_ALERT_RULES {
+            return Err(AppError::Validation {
+                field: "rules".to_string(),
+                reason: format!("A job can have at most {} alert rules", MAX_ALERT_RULES),
+            });
+        }
+        if rules.iter().any(|rule| rule.channel_ids.is_empty()) {
+            return Err(AppError::Validation {
+                field: "channel_ids".to_string(),
+                reason: "At least one channel is required".to_string(),
+            });
+        }
+
+        let owned = self.channel_repo.find_by_user_id(user_id).await?;
+        if let Some(unknown) = rules
+            .iter()
+            .flat_map(|rule| &rule.channel_ids)
+            .find(|cid| !owned.iter().any(|c| c.id == **cid))
+        {
+            return Err(AppError::Validation {
+                field: "channel_ids".to_string(),
+                reason: format!("Channel {} does not belong to the user", unknown),
+            });
+        }
+
+        self.alert_repo.replace(job_id, user_id, rules).await
+    }
+
+    /// Creates a workflow with its steps.
+    ///
+    /// # Errors
+    /// - `Validation` if the steps do not form a valid DAG or reference an
+    ///   unknown job
+    /// - `Duplicate` if the name is taken
+    pub async fn create_workflow(
+        &self,
+        workflow: NewJobWorkflow,
+        steps: Vec<WorkflowStepSpec>,
+    ) -> AppResult<(JobWorkflow, Vec<WorkflowStep>)> {
+        self.validate_steps(&steps).await?;
+        self.workflow_repo.create(workflow, steps).await
+    }
+
+    /// Gets a workflow and its steps.
+    pub async fn get_workflow(&self, id: i32) -> AppResult<(JobWorkflow, Vec<WorkflowStep>)> {
+        let workflow = self.workflow_repo.get_by_id(id).await?;
+        let steps = self.workflow_repo.list_steps(id).await?;
+        Ok((workflow, steps))
+    }
+
+    /// Lists workflows with their steps.
+    pub async fn list_workflows(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<(JobWorkflow, Vec<WorkflowStep>)>> {
+        let workflows = self.workflow_repo.list(limit, offset).await?;
+        let mut result = Vec::with_capacity(workflows.len());
+        for workflow in workflows {
+            let steps = self.workflow_repo.list_steps(workflow.id).await?;
+            result.push((workflow, steps));
+        }
+        Ok(result)
+    }
+
+    /// Updates a workflow's description and, if given, replaces its steps.
+    pub async fn update_workflow(
+        &self,
+        id: i32,
+        description: Option<String>,
+        steps: Option<Vec<WorkflowStepSpec>>,
+    ) -> AppResult<(JobWorkflow, Vec<WorkflowStep>)> {
+        if let Some(steps) = &steps {
+            self.validate_steps(steps).await?;
+        }
+        self.workflow_repo.update(id, description, steps).await
+    }
+
+    /// Deletes a workflow and its run history.
+    pub async fn delete_workflow(&self, id: i32) -> AppResult<()> {
+        self.workflow_repo.delete(id).await
+    }
+
+    /// Checks that workflow steps form a valid DAG of existing jobs.
+    async fn validate_steps(&self, steps: &[WorkflowStepSpec]) -> AppResult<()> {
+        WorkflowGraph::new(
+            steps
+                .iter()
+                .map(|step| (step.step_name.clone(), step.depends_on.clone()))
+                .collect(),
+        )?;
+
+        for step in steps {
+            match self.job_repo.get_by_id(step.job_id).await {
+                Ok(_) => {}
+                Err(AppError::NotFound { .. }) => {
+                    return Err(AppError::Validation {
+                        field: "steps".to_string(),
+                        reason: format!(
+                            "Step {} references unknown job {}",
+                            step.step_name, step.job_id
+                        ),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists runs of a workflow, newest first.
+    pub async fn list_workflow_runs(
+        &self,
+        workflow_id: i32,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<WorkflowRun>> {
+        self.workflow_repo
+            .list_runs(workflow_id, limit, offset)
+            .await
+    }
+
+    /// Gets a workflow run with the state of each of its steps.
+    pub async fn get_workflow_run(
+        &self,
+        run_id: Uuid,
+    ) -> AppResult<(WorkflowRun, Vec<WorkflowStepRun>)> {
+        let run = self.workflow_repo.get_run(run_id).await?;
+        let steps = self.workflow_repo.list_step_runs(run.id).await?;
+        Ok((run, steps))
+    }
+
+    /// Lists queued tasks, newest first.
+    pub async fn list_queued_tasks(
+        &self,
+        status: Option<QueuedTaskStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<QueuedTask>> {
+        self.queue_repo.list(status, limit, offset).await
+    }
+
+    /// Gets a queued task with the log lines its attempts appended.
+    pub async fn get_queued_task(&self, id: i64) -> AppResult<(QueuedTask, Vec<QueuedTaskLog>)> {
+        let task = self.queue_repo.get_by_id(id).await?;
+        let logs = self.queue_repo.list_logs(id).await?;
+        Ok((task, logs))
+    }
+
+    /// Deletes a queued task that is not running.
+    ///
+    /// # Errors
+    /// - `NotFound` if the task does not exist
+    /// - `UnprocessableContent` if a worker is running the task
+    pub async fn delete_queued_task(&self, id: i64) -> AppResult<()> {
+        if self.queue_repo.delete_idle(id).await? {
+            return Ok(());
+        }
+        // Distinguish a missing task from a running one
+        self.queue_repo.get_by_id(id).await?;
+        Err(AppError::UnprocessableContent {
+            message: format!("Queued task {} is running", id),
+        })
+    }
+
+    /// Puts a dead-lettered task back in the queue with fresh attempts.
+    ///
+    /// # Errors
+    /// - `NotFound` if the task does not exist
+    /// - `UnprocessableContent` if the task is not dead
+    pub async fn requeue_task(&self, id: i64) -> AppResult<QueuedTask> {
+        if let Some(task) = self.queue_repo.requeue(id).await? {
+            return Ok(task);
+        }
+        let task = self.queue_repo.get_by_id(id).await?;
+        Err(AppError::UnprocessableContent {
+            message: format!(
+                "Only dead tasks can be requeued, task {} is {}",
+                id, task.status
+            ),
+        })
+    }
+}
+
+/// Validates a schedule and returns its next fire time from now
+fn next_run_at(cron_expression: &str, timezone: &str) -> AppResult<Option<DateTime>> {
+    let schedule = cron::parse(cron_expression)?;
+    let tz = parse_timezone(timezone)?;
+    Ok(cron::next_run_at(&schedule, &tz, Timestamp::now()))
 }
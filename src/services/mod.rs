@@ -33,10 +33,23 @@ impl Services {
         Self {
             users: UserService::new(repos.users),
             notifications: NotificationService::new(
-                repos.notification_channels,
+                repos.notification_channels.clone(),
                 repos.notification_logs,
+                repos.notification_templates,
+                repos.notification_routing,
+                repos.deferred_notifications,
+                repos.notification_broadcasts,
+                repos.user_groups,
+                repos.notification_api_keys,
+            ),
+            jobs: JobService::new(
+                repos.jobs,
+                repos.executions,
+                repos.workflows,
+                repos.job_queue,
+                repos.job_alerts,
+                repos.notification_channels,
             ),
-            jobs: JobService::new(repos.jobs, repos.executions),
             live: LiveService::new(),
         }
     }
@@ -0,0 +1,243 @@
+//! Built-in alert messages.
+//!
+//! Builds the messages for events the server raises itself. Text comes from
+//! the built-in templates (which users may override by key); media and
+//! links are attached here so every template gets them.
+
+use std::time::Duration;
+
+use super::provider::NotificationMessage;
+use super::template::MessageTemplate;
+use crate::external::live::{LivePlatform, RoomInfo};
+use crate::jobs::types::JobAlertEvent;
+use crate::models::NotificationSeverity;
+use serde_json::{Value as JsonValue, json};
+
+/// Template key of the go-live alert
+pub const LIVE_STARTED_TEMPLATE: &str = "live_started";
+
+/// Builds the template variables of a go-live alert
+///
+/// # Arguments
+/// * `platform` - The live platform
+/// * `anchor_name` - Display name of the anchor
+/// * `room` - The room that went live
+///
+/// # Returns
+/// JSON object with `anchor_name`, `platform`, `room_title`, `room_url`,
+/// `cover_url`, `area_name` and `online`
+pub fn live_started_vars(platform: LivePlatform, anchor_name: &str, room: &RoomInfo) -> JsonValue {
+    json!({
+        "anchor_name": anchor_name,
+        "platform": platform.to_string(),
+        "room_id": room.room_id,
+        "room_title": room.title,
+        "room_url": platform.room_url(&room.room_id),
+        "cover_url": room.cover_url,
+        "area_name": room.area_name,
+        "online": room.online,
+    })
+}
+
+/// Renders the go-live alert for a room
+///
+/// The message shows the room cover, links to the room, is tagged with
+/// `live` and the platform, and is deduplicated per room.
+///
+/// # Arguments
+/// * `template` - The compiled `live_started` template
+/// * `platform` - The live platform
+/// * `anchor_name` - Display name of the anchor
+/// * `room` - The room that went live
+pub fn live_started_message(
+    template: &MessageTemplate,
+    platform: LivePlatform,
+    anchor_name: &str,
+    room: &RoomInfo,
+) -> NotificationMessage {
+    let mut message = template.render(&live_started_vars(platform, anchor_name, room));
+    message.image_url = room.cover_url.clone().filter(|url| !url.is_empty());
+    message.click_url = Some(platform.room_url(&room.room_id));
+    message.tags = vec!["live".to_string(), platform.to_string()];
+    message.dedup_key = Some(format!("live_started:{}:{}", platform, room.room_id));
+    message
+}
+
+/// Template key of the alerts of each job alert event
+///
+/// Users override the alerts sent to their channels by creating a template
+/// with the same key.
+pub fn job_alert_template(event: JobAlertEvent) -> &'static str {
+    match event {
+        JobAlertEvent::Failure => "job_failed",
+        JobAlertEvent::Timeout => "job_timed_out",
+        JobAlertEvent::ConsecutiveFailures => "job_failure_streak",
+        JobAlertEvent::Recovery => "job_recovered",
+    }
+}
+
+/// Builds the template variables of a job alert
+///
+/// # Arguments
+/// * `event` - Event of the rule that triggered
+/// * `job_name` - Name of the job
+/// * `error` - Error of the run's last attempt, if any
+/// * `attempts` - Attempts made, including the first
+/// * `duration` - Time the run took, across all attempts
+/// * `streak` - Unsuccessful runs in a row, including this one
+///
+/// # Returns
+/// JSON object with `job_name`, `event`, `error`, `attempts`,
+/// `duration_ms` and `streak`
+pub fn job_alert_vars(
+    event: JobAlertEvent,
+    job_name: &str,
+    error: Option<&str>,
+    attempts: usize,
+    duration: Duration,
+    streak: i32,
+) -> JsonValue {
+    json!({
+        "job_name": job_name,
+        "event": event,
+        "error": error,
+        "attempts": attempts,
+        "duration_ms": duration.as_millis() as u64,
+        "streak": streak,
+    })
+}
+
+/// Renders the alert of a finished job run
+///
+/// Failures are sent with high severity, recoveries with normal severity;
+/// all are tagged with `jobs` and the job name.
+///
+/// # Arguments
+/// * `template` - The compiled template of the event, see
+///   [`job_alert_template`]
+/// * `event` - Event of the rule that triggered
+/// * `job_name` - Name of the job
+/// * `vars` - Variables built by [`job_alert_vars`]
+pub fn job_alert_message(
+    template: &MessageTemplate,
+    event: JobAlertEvent,
+    job_name: &str,
+    vars: &JsonValue,
+) -> NotificationMessage {
+    let mut message = template.render(vars);
+    message.severity = match event {
+        JobAlertEvent::Recovery => NotificationSeverity::Normal,
+        _ => NotificationSeverity::High,
+    };
+    message.tags = vec!["jobs".to_string(), job_name.to_string()];
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::live::LiveStatus;
+    use crate::models::TemplateVariants;
+
+    fn room(cover_url: Option<&str>) -> RoomInfo {
+        RoomInfo {
+            room_id: "21452505".to_string(),
+            uid: "1".to_string(),
+            title: "Chess night".to_string(),
+            live_status: LiveStatus::Live,
+            online: 1200,
+            cover_url: cover_url.map(str::to_string),
+            area_name: Some("Board games".to_string()),
+        }
+    }
+
+    fn template() -> MessageTemplate {
+        MessageTemplate::compile(
+            Some("{{ anchor_name }} is live"),
+            "{{ room_title }} - {{ room_url }}",
+            &TemplateVariants::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_live_started_message_carries_cover_and_link() {
+        let message = live_started_message(
+            &template(),
+            LivePlatform::Bilibili,
+            "Alice",
+            &room(Some("https://i0.hdslb.com/cover.jpg")),
+        );
+
+        assert_eq!(message.title.as_deref(), Some("Alice is live"));
+        assert_eq!(
+            message.body,
+            "Chess night - https://live.bilibili.com/21452505"
+        );
+        assert_eq!(
+            message.image_url.as_deref(),
+            Some("https://i0.hdslb.com/cover.jpg")
+        );
+        assert_eq!(
+            message.click_url.as_deref(),
+            Some("https://live.bilibili.com/21452505")
+        );
+        assert_eq!(message.tags, vec!["live", "bilibili"]);
+        assert_eq!(
+            message.dedup_key.as_deref(),
+            Some("live_started:bilibili:21452505")
+        );
+    }
+
+    #[test]
+    fn test_live_started_message_without_cover() {
+        let message =
+            live_started_message(&template(), LivePlatform::Douyu, "Bob", &room(Some("")));
+        assert_eq!(message.image_url, None);
+        assert_eq!(
+            message.click_url.as_deref(),
+            Some("https://www.douyu.com/21452505")
+        );
+    }
+
+    #[test]
+    fn test_job_alert_message() {
+        let template = MessageTemplate::compile(
+            Some("Job {{ job_name }} failed {{ streak }} times in a row"),
+            "{{ attempts }} attempt(s) in {{ duration_ms }}ms: {{ error }}",
+            &TemplateVariants::new(),
+        )
+        .unwrap();
+        let vars = job_alert_vars(
+            JobAlertEvent::ConsecutiveFailures,
+            "sync",
+            Some("connection reset"),
+            3,
+            Duration::from_millis(12_340),
+            4,
+        );
+        assert_eq!(vars["event"], "consecutive_failures");
+
+        let message =
+            job_alert_message(&template, JobAlertEvent::ConsecutiveFailures, "sync", &vars);
+        assert_eq!(
+            message.title.as_deref(),
+            Some("Job sync failed 4 times in a row")
+        );
+        assert_eq!(message.body, "3 attempt(s) in 12340ms: connection reset");
+        assert_eq!(message.severity, NotificationSeverity::High);
+        assert_eq!(message.tags, vec!["jobs", "sync"]);
+
+        let vars = job_alert_vars(
+            JobAlertEvent::Recovery,
+            "sync",
+            None,
+            1,
+            Duration::from_secs(2),
+            0,
+        );
+        let message = job_alert_message(&template, JobAlertEvent::Recovery, "sync", &vars);
+        assert_eq!(message.body, "1 attempt(s) in 2000ms: ");
+        assert_eq!(message.severity, NotificationSeverity::Normal);
+    }
+}
@@ -5,10 +5,10 @@
 //!
 //! Bark API Reference: https://github.com/Finb/Bark
 
-use super::provider::{NotificationMessage, NotificationProvider, NotificationResult};
+use super::provider::{NotificationMessage, NotificationProvider, NotificationResult, RequestInfo};
 use crate::error::{AppError, AppResult};
 use crate::external::client::HTTP_CLIENT;
-use crate::models::BarkConfig;
+use crate::models::{BarkConfig, NotificationSeverity};
 use async_trait::async_trait;
 use reqwest::Url;
 use serde_json::json;
@@ -83,8 +83,9 @@ impl BarkProvider {
             "body": message.body,
         });
 
-        // Add optional fields
-        if let Some(icon) = &self.config.icon {
+        // Add optional fields; message media takes precedence over the
+        // channel defaults
+        if let Some(icon) = message.image_url.as_ref().or(self.config.icon.as_ref()) {
             body["icon"] = json!(icon);
         }
 
@@ -92,11 +93,16 @@ impl BarkProvider {
             body["sound"] = json!(sound);
         }
 
-        if let Some(level) = &self.config.level {
+        if let Some(level) = self
+            .config
+            .level
+            .as_deref()
+            .or_else(|| severity_level(message.severity))
+        {
             body["level"] = json!(level);
         }
 
-        if let Some(url) = &self.config.url {
+        if let Some(url) = message.click_url.as_ref().or(self.config.url.as_ref()) {
             body["url"] = json!(url);
         }
 
@@ -123,6 +129,22 @@ impl BarkProvider {
     }
 }
 
+/// Maps a message severity to a Bark interruption level
+///
+/// # Arguments
+/// * `severity` - The message severity
+///
+/// # Returns
+/// The Bark level, or None to use the device default
+fn severity_level(severity: NotificationSeverity) -> Option<&'static str> {
+    match severity {
+        NotificationSeverity::Low => Some("passive"),
+        NotificationSeverity::Normal => None,
+        NotificationSeverity::High => Some("timeSensitive"),
+        NotificationSeverity::Critical => Some("critical"),
+    }
+}
+
 #[async_trait]
 impl NotificationProvider for BarkProvider {
     /// Sends a notification via Bark
@@ -144,13 +166,28 @@ impl NotificationProvider for BarkProvider {
         // Build URL
         let api_url = self.config.build_api_url();
 
-        // Send request
-        let response = HTTP_CLIENT
+        // Build request
+        let request = match HTTP_CLIENT
             .post(&api_url)
             .header("Content-Type", "application/json")
             .json(&request_body)
-            .send()
-            .await;
+            .build()
+        {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(NotificationResult {
+                    success: false,
+                    status_code: None,
+                    response: Some(e.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    request: None,
+                });
+            }
+        };
+        let request_info = RequestInfo::from_request(&request, &[self.config.device_key.as_str()]);
+
+        // Send request
+        let response = HTTP_CLIENT.execute(request).await;
 
         let duration_ms = start.elapsed().as_millis() as u64;
 
@@ -165,6 +202,7 @@ impl NotificationProvider for BarkProvider {
                     status_code: Some(status_code),
                     response: response_text,
                     duration_ms,
+                    request: Some(request_info),
                 })
             }
             Err(e) => Ok(NotificationResult {
@@ -172,6 +210,7 @@ impl NotificationProvider for BarkProvider {
                 status_code: None,
                 response: Some(e.to_string()),
                 duration_ms,
+                request: Some(request_info),
             }),
         }
     }
@@ -318,7 +357,7 @@ mod tests {
         let message = NotificationMessage {
             title: Some("Test Title".to_string()),
             body: "Test Body".to_string(),
-            metadata: std::collections::HashMap::new(),
+            ..Default::default()
         };
 
         let body = provider.build_request_body(&message);
@@ -347,7 +386,7 @@ mod tests {
         let message = NotificationMessage {
             title: Some("Test Title".to_string()),
             body: "Test Body".to_string(),
-            metadata: std::collections::HashMap::new(),
+            ..Default::default()
         };
 
         let body = provider.build_request_body(&message);
@@ -361,4 +400,35 @@ mod tests {
         assert_eq!(body["autoCopy"], 1);
         assert_eq!(body["isArchive"], 1);
     }
+
+    #[test]
+    fn test_build_request_body_message_media() {
+        let config = BarkConfig {
+            server_url: "https://bark.example.com".to_string(),
+            device_key: "test_key".to_string(),
+            icon: Some("https://example.com/icon.png".to_string()),
+            sound: None,
+            level: None,
+            url: Some("https://example.com/deep-link".to_string()),
+            group: None,
+            auto_copy: 0,
+            is_archive: 0,
+        };
+
+        let provider = BarkProvider::new(config);
+
+        let message = NotificationMessage {
+            title: Some("Alice is live".to_string()),
+            body: "Playing chess".to_string(),
+            severity: NotificationSeverity::High,
+            image_url: Some("https://example.com/cover.jpg".to_string()),
+            click_url: Some("https://live.bilibili.com/1".to_string()),
+            ..Default::default()
+        };
+
+        let body = provider.build_request_body(&message);
+        assert_eq!(body["icon"], "https://example.com/cover.jpg");
+        assert_eq!(body["url"], "https://live.bilibili.com/1");
+        assert_eq!(body["level"], "timeSensitive");
+    }
 }
@@ -0,0 +1,114 @@
+//! Broadcast delivery outcomes.
+//!
+//! Broadcasts deliver one message to many users through each recipient's
+//! own routing rules and quiet hours. This module maps a dispatch outcome
+//! to the status recorded for the recipient.
+
+use super::routing::DispatchOutcome;
+use crate::models::{BroadcastRecipientStatus, NotificationStatus};
+
+/// Maps a dispatch outcome to a broadcast recipient status
+///
+/// A delivered dispatch only counts as delivered if at least one channel
+/// accepted the message.
+///
+/// # Arguments
+/// * `outcome` - The result of dispatching to the recipient
+///
+/// # Returns
+/// The recipient status and, for failures, the reason
+pub fn recipient_status(outcome: &DispatchOutcome) -> (BroadcastRecipientStatus, Option<String>) {
+    match outcome {
+        DispatchOutcome::Delivered { logs, .. } => {
+            if logs
+                .iter()
+                .any(|log| log.status == NotificationStatus::Sent)
+            {
+                return (BroadcastRecipientStatus::Delivered, None);
+            }
+
+            let reason = logs
+                .iter()
+                .rev()
+                .find_map(|log| log.error_message.clone())
+                .unwrap_or_else(|| "No channel accepted the message".to_string());
+            (BroadcastRecipientStatus::Failed, Some(reason))
+        }
+        DispatchOutcome::Deferred { .. } => (BroadcastRecipientStatus::Deferred, None),
+        DispatchOutcome::Dropped => (BroadcastRecipientStatus::Dropped, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotificationLog;
+    use jiff_diesel::DateTime;
+
+    fn log(status: NotificationStatus, error: Option<&str>) -> NotificationLog {
+        NotificationLog {
+            id: 1,
+            channel_id: 1,
+            message: "{}".to_string(),
+            status,
+            error_message: error.map(str::to_string),
+            retry_count: 0,
+            sent_at: DateTime::from(jiff::civil::date(2024, 1, 1).at(0, 0, 0, 0)),
+            dedup_key: None,
+            request_meta: None,
+            response_status: None,
+            response_body: None,
+            duration_ms: None,
+        }
+    }
+
+    fn delivered(logs: Vec<NotificationLog>) -> DispatchOutcome {
+        DispatchOutcome::Delivered {
+            rule_id: None,
+            logs,
+        }
+    }
+
+    #[test]
+    fn test_delivered_when_any_channel_sent() {
+        let outcome = delivered(vec![
+            log(NotificationStatus::Failed, Some("HTTP 500")),
+            log(NotificationStatus::Sent, None),
+        ]);
+        assert_eq!(
+            recipient_status(&outcome),
+            (BroadcastRecipientStatus::Delivered, None)
+        );
+    }
+
+    #[test]
+    fn test_failed_when_no_channel_sent() {
+        let outcome = delivered(vec![log(NotificationStatus::Failed, Some("HTTP 410"))]);
+        assert_eq!(
+            recipient_status(&outcome),
+            (
+                BroadcastRecipientStatus::Failed,
+                Some("HTTP 410".to_string())
+            )
+        );
+
+        let (status, reason) = recipient_status(&delivered(vec![]));
+        assert_eq!(status, BroadcastRecipientStatus::Failed);
+        assert!(reason.is_some());
+    }
+
+    #[test]
+    fn test_quiet_hours_outcomes() {
+        assert_eq!(
+            recipient_status(&DispatchOutcome::Dropped).0,
+            BroadcastRecipientStatus::Dropped
+        );
+        assert_eq!(
+            recipient_status(&DispatchOutcome::Deferred {
+                deliver_at: jiff::Timestamp::UNIX_EPOCH
+            })
+            .0,
+            BroadcastRecipientStatus::Deferred
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Digest summaries for batched notifications.
+//!
+//! Channels in digest mode hold non-urgent messages and periodically send
+//! them as a single summary message.
+
+use super::provider::NotificationMessage;
+use crate::models::NotificationSeverity;
+
+/// Maximum number of entries listed individually in a digest body
+const MAX_DIGEST_LINES: usize = 50;
+
+/// Checks whether a message should bypass the digest and be sent immediately
+///
+/// # Arguments
+/// * `message` - The message being sent
+///
+/// # Returns
+/// true for high and critical severity messages
+pub fn bypasses_digest(message: &NotificationMessage) -> bool {
+    message.severity >= NotificationSeverity::High
+}
+
+/// Builds one summary message from batched messages
+///
+/// Each message becomes one line; repeats beyond `MAX_DIGEST_LINES` are
+/// summarised as a count. The digest inherits the highest severity.
+///
+/// # Arguments
+/// * `messages` - The batched messages, oldest first
+///
+/// # Returns
+/// The digest message
+pub fn build_digest(messages: &[NotificationMessage]) -> NotificationMessage {
+    let mut lines: Vec<String> = messages
+        .iter()
+        .take(MAX_DIGEST_LINES)
+        .map(|m| match m.title.as_deref() {
+            Some(title) if !title.is_empty() => format!("• {}: {}", title, m.body),
+            _ => format!("• {}", m.body),
+        })
+        .collect();
+
+    if messages.len() > MAX_DIGEST_LINES {
+        lines.push(format!("…and {} more", messages.len() - MAX_DIGEST_LINES));
+    }
+
+    let mut tags: Vec<String> = messages.iter().flat_map(|m| m.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+
+    NotificationMessage {
+        title: Some(format!(
+            "Digest: {} notification{}",
+            messages.len(),
+            if messages.len() == 1 { "" } else { "s" }
+        )),
+        body: lines.join("\n"),
+        severity: messages
+            .iter()
+            .map(|m| m.severity)
+            .max()
+            .unwrap_or_default(),
+        tags,
+        attachments: messages
+            .iter()
+            .flat_map(|m| m.attachments.clone())
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(title: Option<&str>, body: &str) -> NotificationMessage {
+        NotificationMessage {
+            title: title.map(str::to_string),
+            body: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_digest_lists_messages() {
+        let digest = build_digest(&[
+            message(Some("Alice"), "is live"),
+            message(None, "Backup finished"),
+        ]);
+
+        assert_eq!(digest.title.as_deref(), Some("Digest: 2 notifications"));
+        assert_eq!(digest.body, "• Alice: is live\n• Backup finished");
+    }
+
+    #[test]
+    fn test_build_digest_truncates_long_batches() {
+        let messages: Vec<_> = (0..60).map(|i| message(None, &i.to_string())).collect();
+        let digest = build_digest(&messages);
+
+        assert_eq!(digest.body.lines().count(), MAX_DIGEST_LINES + 1);
+        assert!(digest.body.ends_with("…and 10 more"));
+    }
+
+    #[test]
+    fn test_build_digest_uses_highest_severity() {
+        let mut urgent = message(None, "disk full");
+        urgent.severity = NotificationSeverity::High;
+        let digest = build_digest(&[message(None, "ok"), urgent]);
+
+        assert_eq!(digest.severity, NotificationSeverity::High);
+    }
+
+    #[test]
+    fn test_bypasses_digest() {
+        let mut m = message(None, "x");
+        assert!(!bypasses_digest(&m));
+        m.severity = NotificationSeverity::Critical;
+        assert!(bypasses_digest(&m));
+    }
+}
@@ -0,0 +1,345 @@
+//! Gotify notification provider implementation.
+//!
+//! Posts messages to a self-hosted Gotify server using an application
+//! token. Uses the global `HTTP_CLIENT` for connection pooling.
+//!
+//! Gotify API Reference: https://gotify.net/api-docs
+
+use super::provider::{
+    NotificationMessage, NotificationProvider, NotificationResult, RequestInfo, check_server_health,
+};
+use crate::error::{AppError, AppResult};
+use crate::external::client::HTTP_CLIENT;
+use crate::models::{GotifyConfig, MessageFormat, NotificationSeverity};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde_json::json;
+use std::time::Instant;
+
+/// Gotify notification provider
+///
+/// # Example
+/// ```ignore
+/// let config = GotifyConfig {
+///     server_url: "https://gotify.example.com".to_string(),
+///     app_token: "AbCdEf123456".to_string(),
+///     priority: None,
+/// };
+/// let provider = GotifyProvider::new(config);
+/// let result = provider.send(&message).await?;
+/// ```
+#[derive(Clone)]
+pub struct GotifyProvider {
+    config: GotifyConfig,
+}
+
+impl GotifyProvider {
+    /// Creates a new Gotify provider with configuration
+    ///
+    /// # Arguments
+    /// * `config` - Gotify configuration (server_url, app_token, priority)
+    pub fn new(config: GotifyConfig) -> Self {
+        Self { config }
+    }
+
+    /// Validates the Gotify server URL
+    ///
+    /// # Returns
+    /// Ok(()) if URL is a valid HTTP(S) URL
+    fn validate_server_url(&self) -> Result<(), AppError> {
+        let url = Url::parse(&self.config.server_url).map_err(|_| AppError::Validation {
+            field: "server_url".to_string(),
+            reason: "Invalid URL format".to_string(),
+        })?;
+
+        if url.scheme() != "https" && url.scheme() != "http" {
+            return Err(AppError::Validation {
+                field: "server_url".to_string(),
+                reason: "URL must use http or https protocol".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Builds the request body for the Gotify message API
+    ///
+    /// Markdown messages are marked for rendering, and the click URL and
+    /// image are passed as client notification extras.
+    ///
+    /// # Arguments
+    /// * `message` - The notification message to send
+    ///
+    /// # Returns
+    /// JSON object for the Gotify message request body
+    fn build_request_body(&self, message: &NotificationMessage) -> serde_json::Value {
+        let mut body = json!({
+            "message": message.body,
+            "priority": self
+                .config
+                .priority
+                .unwrap_or_else(|| severity_priority(message.severity)),
+        });
+
+        if let Some(title) = &message.title {
+            body["title"] = json!(title);
+        }
+
+        let mut extras = serde_json::Map::new();
+        if message.format == MessageFormat::Markdown {
+            extras.insert(
+                "client::display".to_string(),
+                json!({ "contentType": "text/markdown" }),
+            );
+        }
+
+        let mut notification = serde_json::Map::new();
+        if let Some(url) = &message.click_url {
+            notification.insert("click".to_string(), json!({ "url": url }));
+        }
+        if let Some(image) = &message.image_url {
+            notification.insert("bigImageUrl".to_string(), json!(image));
+        }
+        if !notification.is_empty() {
+            extras.insert("client::notification".to_string(), notification.into());
+        }
+
+        if !extras.is_empty() {
+            body["extras"] = extras.into();
+        }
+
+        body
+    }
+}
+
+/// Maps a message severity to a Gotify priority
+///
+/// Gotify clients treat 8 and above as high priority.
+fn severity_priority(severity: NotificationSeverity) -> u8 {
+    match severity {
+        NotificationSeverity::Low => 2,
+        NotificationSeverity::Normal => 5,
+        NotificationSeverity::High => 8,
+        NotificationSeverity::Critical => 10,
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for GotifyProvider {
+    /// Sends a notification via Gotify
+    ///
+    /// # Arguments
+    /// * `message` - The notification message to send
+    ///
+    /// # Returns
+    /// NotificationResult with success status, HTTP status code, response body, and duration
+    async fn send(&self, message: &NotificationMessage) -> AppResult<NotificationResult> {
+        let start = Instant::now();
+
+        let request_body = self.build_request_body(message);
+        let api_url = self.config.build_api_url();
+
+        let request = match HTTP_CLIENT
+            .post(&api_url)
+            .header("X-Gotify-Key", &self.config.app_token)
+            .json(&request_body)
+            .build()
+        {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(NotificationResult {
+                    success: false,
+                    status_code: None,
+                    response: Some(e.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    request: None,
+                });
+            }
+        };
+        let request_info = RequestInfo::from_request(&request, &[]);
+
+        let response = HTTP_CLIENT.execute(request).await;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        match response {
+            Ok(resp) => {
+                let status_code = resp.status().as_u16();
+                let success = resp.status().is_success();
+                let response_text = resp.text().await.ok();
+
+                Ok(NotificationResult {
+                    success,
+                    status_code: Some(status_code),
+                    response: response_text,
+                    duration_ms,
+                    request: Some(request_info),
+                })
+            }
+            Err(e) => Ok(NotificationResult {
+                success: false,
+                status_code: None,
+                response: Some(e.to_string()),
+                duration_ms,
+                request: Some(request_info),
+            }),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "gotify"
+    }
+
+    /// Validates Gotify configuration
+    ///
+    /// Checks that:
+    /// - server_url is a valid URL
+    /// - app_token is not empty
+    /// - priority is within 0-10
+    /// - the server answers its health endpoint
+    ///
+    /// # Returns
+    /// Ok(()) if valid, Err with validation details otherwise
+    async fn validate_config(&self) -> AppResult<()> {
+        self.validate_server_url()?;
+
+        if self.config.app_token.is_empty() {
+            return Err(AppError::Validation {
+                field: "app_token".to_string(),
+                reason: "App token cannot be empty".to_string(),
+            });
+        }
+
+        if self.config.priority.is_some_and(|p| p > 10) {
+            return Err(AppError::Validation {
+                field: "priority".to_string(),
+                reason: "Priority must be between 0 and 10".to_string(),
+            });
+        }
+
+        check_server_health(&format!(
+            "{}/health",
+            self.config.server_url.trim_end_matches('/')
+        ))
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> GotifyConfig {
+        GotifyConfig {
+            server_url: "https://gotify.example.com/".to_string(),
+            app_token: "token".to_string(),
+            priority: None,
+        }
+    }
+
+    #[test]
+    fn test_build_api_url() {
+        assert_eq!(
+            config().build_api_url(),
+            "https://gotify.example.com/message"
+        );
+    }
+
+    #[test]
+    fn test_build_request_body_minimal() {
+        let provider = GotifyProvider::new(config());
+        let message = NotificationMessage {
+            body: "Backup finished".to_string(),
+            ..Default::default()
+        };
+
+        let body = provider.build_request_body(&message);
+        assert_eq!(body["message"], "Backup finished");
+        assert_eq!(body["priority"], 5);
+        assert!(body.get("title").is_none());
+        assert!(body.get("extras").is_none());
+    }
+
+    #[test]
+    fn test_build_request_body_full() {
+        let provider = GotifyProvider::new(config());
+        let message = NotificationMessage {
+            title: Some("Alice is live".to_string()),
+            body: "**Playing** chess".to_string(),
+            severity: NotificationSeverity::Critical,
+            format: MessageFormat::Markdown,
+            image_url: Some("https://example.com/cover.jpg".to_string()),
+            click_url: Some("https://live.bilibili.com/1".to_string()),
+            ..Default::default()
+        };
+
+        let body = provider.build_request_body(&message);
+        assert_eq!(body["title"], "Alice is live");
+        assert_eq!(body["priority"], 10);
+        assert_eq!(
+            body["extras"]["client::display"]["contentType"],
+            "text/markdown"
+        );
+        assert_eq!(
+            body["extras"]["client::notification"]["click"]["url"],
+            "https://live.bilibili.com/1"
+        );
+        assert_eq!(
+            body["extras"]["client::notification"]["bigImageUrl"],
+            "https://example.com/cover.jpg"
+        );
+    }
+
+    #[test]
+    fn test_configured_priority_overrides_severity() {
+        let mut config = config();
+        config.priority = Some(3);
+        let provider = GotifyProvider::new(config);
+        let message = NotificationMessage {
+            severity: NotificationSeverity::High,
+            ..Default::default()
+        };
+
+        assert_eq!(provider.build_request_body(&message)["priority"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_rejects_bad_priority() {
+        let mut config = config();
+        config.priority = Some(11);
+
+        let result = GotifyProvider::new(config).validate_config().await;
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "priority"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_unreachable_server() {
+        let mut config = config();
+        config.server_url = "http://127.0.0.1:9".to_string();
+
+        let result = GotifyProvider::new(config).validate_config().await;
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "server_url"));
+    }
+
+    /// Run against the `gotify` service from docker-compose with an
+    /// application token created in its web UI (admin/admin).
+    #[tokio::test]
+    #[ignore = "requires a local Gotify server"]
+    async fn test_send_local_server() {
+        let provider = GotifyProvider::new(GotifyConfig {
+            server_url: std::env::var("GOTIFY_URL")
+                .unwrap_or_else(|_| "http://localhost:8081".to_string()),
+            app_token: std::env::var("GOTIFY_APP_TOKEN").expect("GOTIFY_APP_TOKEN"),
+            priority: None,
+        });
+        provider.validate_config().await.unwrap();
+
+        let message = NotificationMessage {
+            title: Some("fusion-rs".to_string()),
+            body: "Gotify provider test".to_string(),
+            ..Default::default()
+        };
+        let result = provider.send(&message).await.unwrap();
+        assert!(result.success, "{:?}", result.response);
+    }
+}
@@ -0,0 +1,50 @@
+//! Channel health tracking.
+//!
+//! Each delivery attempt updates the channel's failure streak. A channel
+//! whose streak reaches its failure threshold is disabled automatically.
+
+/// Checks whether a channel's failure streak disables it
+///
+/// # Arguments
+/// * `consecutive_failures` - Failed deliveries in a row, including the latest
+/// * `failure_threshold` - Streak that disables the channel; 0 never does
+///
+/// # Returns
+/// true once the streak has reached the threshold
+pub fn reaches_failure_threshold(consecutive_failures: i32, failure_threshold: i32) -> bool {
+    failure_threshold > 0 && consecutive_failures >= failure_threshold
+}
+
+/// Reason recorded on a channel disabled by its failure streak
+pub fn auto_disable_reason(consecutive_failures: i32) -> String {
+    format!(
+        "Disabled after {} consecutive failures",
+        consecutive_failures
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disables_exactly_at_threshold() {
+        assert!(!reaches_failure_threshold(1, 3));
+        assert!(!reaches_failure_threshold(2, 3));
+        assert!(reaches_failure_threshold(3, 3));
+    }
+
+    #[test]
+    fn test_zero_threshold_never_disables() {
+        assert!(!reaches_failure_threshold(0, 0));
+        assert!(!reaches_failure_threshold(1000, 0));
+    }
+
+    #[test]
+    fn test_auto_disable_reason() {
+        assert_eq!(
+            auto_disable_reason(5),
+            "Disabled after 5 consecutive failures"
+        );
+    }
+}
@@ -4,13 +4,38 @@
 //! The core trait `NotificationProvider` allows for easy extension to support
 //! different notification channels (webhook, email, SMS, etc.).
 
+mod alerts;
+mod apprise;
 mod bark_provider;
+mod broadcast;
+mod digest;
+mod gotify_provider;
+mod health;
+mod ntfy_provider;
 mod provider;
+mod retention;
+mod routing;
+mod secrets;
+mod template;
 mod webhook_provider;
+mod webpush_provider;
 
 pub mod notification_service;
 
+pub use alerts::{
+    LIVE_STARTED_TEMPLATE, job_alert_message, job_alert_template, job_alert_vars,
+    live_started_message, live_started_vars,
+};
+pub use apprise::{channel_url, parse_channel_url};
 pub use bark_provider::BarkProvider;
+pub use gotify_provider::GotifyProvider;
 pub use notification_service::NotificationService;
-pub use provider::{NotificationMessage, NotificationProvider, NotificationResult};
+pub use ntfy_provider::NtfyProvider;
+pub use provider::{
+    Attachment, MessageVariant, NotificationMessage, NotificationProvider, NotificationResult,
+    RequestInfo,
+};
+pub use routing::DispatchOutcome;
+pub use template::{MessageTemplate, Template};
 pub use webhook_provider::WebhookProvider;
+pub use webpush_provider::WebPushProvider;
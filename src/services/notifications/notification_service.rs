@@ -2,22 +2,67 @@
 //!
 //! Provides notification channel management and message sending functionality.
 
+use super::alerts::{
+    LIVE_STARTED_TEMPLATE, job_alert_message, job_alert_template, live_started_message,
+};
+use super::apprise::channel_url;
 use super::bark_provider::BarkProvider;
-use super::provider::{NotificationMessage, NotificationProvider};
+use super::broadcast::recipient_status;
+use super::digest::{build_digest, bypasses_digest};
+use super::gotify_provider::GotifyProvider;
+use super::health::{auto_disable_reason, reaches_failure_threshold};
+use super::ntfy_provider::NtfyProvider;
+use super::provider::{NotificationMessage, NotificationProvider, truncate_response};
+use super::retention::{append_archive, archive_path};
+use super::routing::{DispatchOutcome, QuietWindow, rule_matches};
+use super::secrets::{decrypt_secrets, encrypt_secrets, restore_masked, rotate_secrets};
+use super::template::MessageTemplate;
 use super::webhook_provider::WebhookProvider;
+use super::webpush_provider::WebPushProvider;
 use crate::error::{AppError, AppResult};
+use crate::external::live::{LivePlatform, RoomInfo};
+use crate::jobs::types::JobAlertEvent;
 use crate::models::{
-    BarkConfig, ChannelType, NewNotificationChannel, NewNotificationLog, NotificationChannel,
-    NotificationLog, NotificationStatus, UpdateNotificationChannel, WebhookConfig,
+    BarkConfig, BroadcastRecipientStatus, ChannelType, GotifyConfig, NewDeferredNotification,
+    NewNotificationApiKey, NewNotificationBroadcast, NewNotificationChannel, NewNotificationLog,
+    NewNotificationQuietHours, NewNotificationRoutingRule, NewNotificationTemplate, NewUserGroup,
+    NotificationApiKey, NotificationBroadcast, NotificationChannel, NotificationDeliveryStats,
+    NotificationLog, NotificationLogTotals, NotificationQuietHours, NotificationRoutingRule,
+    NotificationSeverity, NotificationStatus, NotificationTemplate, NtfyConfig, QuietHoursAction,
+    RoutingMode, StatsBucket, UpdateNotificationChannel, UpdateNotificationRoutingRule,
+    UpdateNotificationTemplate, UserGroup, WebPushSubscription, WebhookConfig,
+};
+use crate::repositories::{
+    DeferredNotificationRepository, NotificationApiKeyRepository, NotificationBroadcastRepository,
+    NotificationChannelRepository, NotificationLogFilter, NotificationLogRepository,
+    NotificationRoutingRepository, NotificationTemplateRepository, UserGroupRepository,
 };
-use crate::repositories::{NotificationChannelRepository, NotificationLogRepository};
+use crate::utils::api_key::{api_key_prefix, generate_api_key, ip_allowed, parse_ip_rule};
+use crate::utils::password::{hash_password, verify_password};
+use crate::utils::secret::secret_cipher;
+use crate::utils::timezone::parse_timezone;
+use futures::StreamExt;
+use jiff_diesel::DateTime;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
 use std::sync::Arc;
 
+/// Number of channels loaded per batch when rotating secrets
+const ROTATION_BATCH_SIZE: i64 = 100;
+
 /// Notification service handling channel management and message sending
 #[derive(Clone)]
 pub struct NotificationService {
     channel_repo: NotificationChannelRepository,
     log_repo: NotificationLogRepository,
+    template_repo: NotificationTemplateRepository,
+    routing_repo: NotificationRoutingRepository,
+    deferred_repo: DeferredNotificationRepository,
+    broadcast_repo: NotificationBroadcastRepository,
+    group_repo: UserGroupRepository,
+    api_key_repo: NotificationApiKeyRepository,
 }
 
 impl NotificationService {
@@ -26,13 +71,32 @@ impl NotificationService {
     /// # Arguments
     /// * `channel_repo` - Repository for notification channels
     /// * `log_repo` - Repository for notification logs
+    /// * `template_repo` - Repository for message templates
+    /// * `routing_repo` - Repository for routing rules and quiet hours
+    /// * `deferred_repo` - Repository for messages deferred by quiet hours
+    /// * `broadcast_repo` - Repository for broadcasts and their delivery queue
+    /// * `group_repo` - Repository for user groups
+    /// * `api_key_repo` - Repository for inbound API keys
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         channel_repo: NotificationChannelRepository,
         log_repo: NotificationLogRepository,
+        template_repo: NotificationTemplateRepository,
+        routing_repo: NotificationRoutingRepository,
+        deferred_repo: DeferredNotificationRepository,
+        broadcast_repo: NotificationBroadcastRepository,
+        group_repo: UserGroupRepository,
+        api_key_repo: NotificationApiKeyRepository,
     ) -> Self {
         Self {
             channel_repo,
             log_repo,
+            template_repo,
+            routing_repo,
+            deferred_repo,
+            broadcast_repo,
+            group_repo,
+            api_key_repo,
         }
     }
 
@@ -42,7 +106,8 @@ impl NotificationService {
 
     /// Creates a new notification channel
     ///
-    /// Validates the channel configuration before creating.
+    /// Validates the channel configuration before creating and encrypts its
+    /// secret fields.
     ///
     /// # Arguments
     /// * `new_channel` - The channel data to create
@@ -51,12 +116,18 @@ impl NotificationService {
     /// The created channel with generated id and timestamps
     pub async fn create_channel(
         &self,
-        new_channel: NewNotificationChannel,
+        mut new_channel: NewNotificationChannel,
     ) -> AppResult<NotificationChannel> {
         // Validate config based on channel type
         self.validate_channel_config(&new_channel.channel_type, &new_channel.config)
             .await?;
 
+        new_channel.config = encrypt_secrets(
+            new_channel.channel_type,
+            &new_channel.config,
+            secret_cipher(),
+        )?;
+
         self.channel_repo.create(new_channel).await
     }
 
@@ -78,6 +149,20 @@ impl NotificationService {
             })
     }
 
+    /// Exports a channel as an Apprise-style notification URL
+    ///
+    /// The URL contains the channel's decrypted secrets.
+    ///
+    /// # Arguments
+    /// * `channel` - The channel to export
+    ///
+    /// # Returns
+    /// The URL, or BadRequest if the config cannot be expressed as one
+    pub fn export_channel_url(&self, channel: &NotificationChannel) -> AppResult<String> {
+        let config = decrypt_secrets(channel.channel_type, &channel.config, secret_cipher())?;
+        channel_url(channel.channel_type, &config)
+    }
+
     /// Lists all channels for a user
     ///
     /// # Arguments
@@ -112,6 +197,7 @@ impl NotificationService {
     /// Updates a notification channel
     ///
     /// Verifies the channel exists and validates config if being updated.
+    /// Masked secrets in the new config keep their stored values.
     ///
     /// # Arguments
     /// * `id` - The channel ID to update
@@ -122,20 +208,78 @@ impl NotificationService {
     pub async fn update_channel(
         &self,
         id: i32,
-        update_data: UpdateNotificationChannel,
+        mut update_data: UpdateNotificationChannel,
     ) -> AppResult<NotificationChannel> {
         // Verify channel exists
         let channel = self.get_channel(id).await?;
 
         // Validate config if being updated
-        if let Some(ref config) = update_data.config {
-            self.validate_channel_config(&channel.channel_type, config)
+        if let Some(config) = update_data.config.take() {
+            let config = restore_masked(channel.channel_type, &config, &channel.config);
+            let config = decrypt_secrets(channel.channel_type, &config, secret_cipher())?;
+            self.validate_channel_config(&channel.channel_type, &config)
                 .await?;
+            update_data.config = Some(encrypt_secrets(
+                channel.channel_type,
+                &config,
+                secret_cipher(),
+            )?);
+        }
+
+        // Re-enabling starts a fresh failure streak
+        if update_data.enabled == Some(true) && !channel.enabled {
+            update_data.consecutive_failures = Some(0);
+            update_data.disabled_reason = Some(None);
+            update_data.disabled_at = Some(None);
         }
 
         self.channel_repo.update(id, update_data).await
     }
 
+    /// Re-encrypts channel secrets under the active master key
+    ///
+    /// Encrypts plaintext secrets left from before encryption was enabled
+    /// and re-wraps secrets written under a retired key. Run after rotating
+    /// `encryption.key`; retired keys can be removed once it completes.
+    ///
+    /// # Returns
+    /// Number of channels whose config was rewritten
+    pub async fn rotate_channel_secrets(&self) -> AppResult<usize> {
+        let Some(cipher) = secret_cipher() else {
+            return Err(AppError::Configuration {
+                key: "encryption.key".to_string(),
+                source: anyhow::anyhow!("No encryption key configured"),
+            });
+        };
+
+        let mut rotated = 0;
+        let mut after_id = 0;
+        loop {
+            let channels = self
+                .channel_repo
+                .find_batch_after(after_id, ROTATION_BATCH_SIZE)
+                .await?;
+            let Some(last) = channels.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            for channel in channels {
+                if let Some(config) = rotate_secrets(channel.channel_type, &channel.config, cipher)?
+                {
+                    let update = UpdateNotificationChannel {
+                        config: Some(config),
+                        ..Default::default()
+                    };
+                    self.channel_repo.update(channel.id, update).await?;
+                    rotated += 1;
+                }
+            }
+        }
+
+        Ok(rotated)
+    }
+
     /// Deletes a notification channel
     ///
     /// # Arguments
@@ -148,13 +292,102 @@ impl NotificationService {
         Ok(affected > 0)
     }
 
+    // ========================================================================
+    // Web Push Subscriptions
+    // ========================================================================
+
+    /// Registers a browser push subscription as a Web Push channel
+    ///
+    /// If the user already registered the endpoint, that channel gets the
+    /// new keys and is re-enabled instead, e.g. after the browser renewed
+    /// its subscription.
+    ///
+    /// # Arguments
+    /// * `user_id` - The subscribing user
+    /// * `name` - Name of a newly created channel
+    /// * `priority` - Priority of a newly created channel
+    /// * `subscription` - The browser's push subscription
+    ///
+    /// # Returns
+    /// The channel, and true if it was newly created
+    pub async fn register_push_subscription(
+        &self,
+        user_id: i32,
+        name: String,
+        priority: i32,
+        subscription: WebPushSubscription,
+    ) -> AppResult<(NotificationChannel, bool)> {
+        let config = subscription.to_json().map_err(|e| AppError::Internal {
+            source: anyhow::Error::from(e),
+        })?;
+
+        if let Some(channel) = self
+            .find_push_channel(user_id, &subscription.endpoint)
+            .await?
+        {
+            let update = UpdateNotificationChannel {
+                config: Some(config),
+                enabled: Some(true),
+                ..Default::default()
+            };
+            let channel = self.update_channel(channel.id, update).await?;
+            return Ok((channel, false));
+        }
+
+        let new_channel = NewNotificationChannel {
+            user_id,
+            channel_type: ChannelType::WebPush,
+            name,
+            config,
+            enabled: true,
+            priority,
+            rate_limit_count: None,
+            rate_limit_window_seconds: 60,
+            dedup_window_seconds: 300,
+            digest_enabled: false,
+            failure_threshold: 5,
+        };
+        Ok((self.create_channel(new_channel).await?, true))
+    }
+
+    /// Removes the Web Push channel of a browser subscription
+    ///
+    /// # Arguments
+    /// * `user_id` - The subscribing user
+    /// * `endpoint` - Push endpoint of the subscription
+    ///
+    /// # Returns
+    /// true if a channel was deleted, false if the endpoint is not registered
+    pub async fn remove_push_subscription(&self, user_id: i32, endpoint: &str) -> AppResult<bool> {
+        match self.find_push_channel(user_id, endpoint).await? {
+            Some(channel) => self.delete_channel(channel.id).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Finds a user's Web Push channel by subscription endpoint
+    async fn find_push_channel(
+        &self,
+        user_id: i32,
+        endpoint: &str,
+    ) -> AppResult<Option<NotificationChannel>> {
+        let channels = self.channel_repo.find_by_user_id(user_id).await?;
+        Ok(channels.into_iter().find(|channel| {
+            channel.channel_type == ChannelType::WebPush
+                && channel.config.get("endpoint").and_then(|e| e.as_str()) == Some(endpoint)
+        }))
+    }
+
     // ========================================================================
     // Message Sending
     // ========================================================================
 
     /// Sends a notification to a specific channel
     ///
-    /// Logs the send attempt to notification_logs table.
+    /// Picks the message variant matching the channel type, applies the
+    /// channel's dedup, rate-limit and digest settings, and logs the
+    /// outcome to notification_logs table. Suppressed and batched messages
+    /// are logged with their own status instead of being sent.
     ///
     /// # Arguments
     /// * `channel_id` - The channel ID to send via
@@ -175,30 +408,86 @@ impl NotificationService {
             });
         }
 
-        // Create provider
-        let provider = self.create_provider(&channel)?;
+        // Pick the rendering suited to this channel
+        let message = message.for_channel(channel.channel_type);
+
+        // Deduplication and rate limiting
+        if let Some(reason) = self.suppression_reason(&channel, &message).await? {
+            let log_entry = Self::log_entry(
+                channel_id,
+                &message,
+                NotificationStatus::Suppressed,
+                Some(reason),
+            );
+            return self.log_repo.create(log_entry).await;
+        }
+
+        // Non-urgent messages wait for the channel's next digest
+        if channel.digest_enabled && !bypasses_digest(&message) {
+            let log_entry =
+                Self::log_entry(channel_id, &message, NotificationStatus::Batched, None);
+            return self.log_repo.create(log_entry).await;
+        }
 
-        // Send notification
-        let result = provider.send(&message).await?;
+        self.deliver(&channel, &message).await
+    }
 
-        // Log the result
-        let log_entry = NewNotificationLog {
-            channel_id,
-            message: serde_json::to_string(&message).unwrap_or_default(),
-            status: if result.success {
-                NotificationStatus::Sent
-            } else {
-                NotificationStatus::Failed
-            },
-            error_message: if !result.success {
-                result.response.clone()
-            } else {
-                None
-            },
-            retry_count: 0,
-        };
+    /// Sends accumulated digests for every channel with batched messages
+    ///
+    /// Batched logs are marked as digested and replaced by one summary
+    /// message per channel. When the digest cannot be delivered the logs
+    /// go back to the batch for the next flush. Disabled channels keep
+    /// their batch until they are re-enabled, and a channel that fails to
+    /// load is logged and skipped.
+    ///
+    /// # Returns
+    /// Number of digests sent
+    pub async fn flush_digests(&self) -> AppResult<usize> {
+        let channel_ids = self.log_repo.find_channels_with_batched().await?;
+        let mut flushed = 0;
+
+        for channel_id in channel_ids {
+            let channel = match self.get_channel(channel_id).await {
+                Ok(channel) => channel,
+                Err(e) => {
+                    tracing::warn!(channel_id, error = %e, "Failed to load channel for digest");
+                    continue;
+                }
+            };
+            if !channel.enabled {
+                continue;
+            }
+
+            let batched = self.log_repo.claim_batched(channel_id).await?;
+            let messages: Vec<NotificationMessage> = batched
+                .iter()
+                .filter_map(|log| serde_json::from_str(&log.message).ok())
+                .collect();
+
+            if messages.is_empty() {
+                continue;
+            }
 
-        self.log_repo.create(log_entry).await
+            let digest = build_digest(&messages);
+            let error = match self.deliver(&channel, &digest).await {
+                Ok(log) if log.status == NotificationStatus::Sent => {
+                    flushed += 1;
+                    continue;
+                }
+                Ok(log) => log
+                    .error_message
+                    .unwrap_or_else(|| "delivery failed".to_string()),
+                Err(e) => e.to_string(),
+            };
+            tracing::warn!(channel_id, error = %error, "Failed to send notification digest");
+
+            let ids: Vec<i64> = batched.iter().map(|log| log.id).collect();
+            if let Err(e) = self.log_repo.release_claimed(&ids).await {
+                tracing::error!(channel_id, error = %e, "Failed to return digest messages to the batch");
+            }
+        }
+
+        Ok(flushed)
     }
 
     /// Sends a notification to all enabled channels of a specific type for a user
@@ -232,149 +521,1581 @@ impl NotificationService {
             });
         }
 
-        // Parallelize sends using futures::future::join_all
-        use futures::future::join_all;
+        let channel_ids = channels.iter().map(|c| c.id).collect();
+        Ok(self.send_to_all(channel_ids, message).await)
+    }
 
-        let send_futures: Vec<_> = channels
-            .into_iter()
-            .map(|channel| {
-                let channel_id = channel.id;
-                let msg = message.clone();
-                async move { self.send_to_channel(channel_id, msg).await }
-            })
-            .collect();
+    /// Dispatches a message through the user's routing rules and quiet hours
+    ///
+    /// Quiet hours are checked first: messages below the bypass severity are
+    /// deferred until the window ends or dropped. Otherwise the first
+    /// matching routing rule picks the channels; when no rule matches, the
+    /// user's enabled channels are tried as an escalation chain so exactly
+    /// one device is notified.
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    /// * `message` - The notification message
+    ///
+    /// # Returns
+    /// Whether the message was delivered, deferred, or dropped
+    pub async fn dispatch(
+        &self,
+        user_id: i32,
+        message: NotificationMessage,
+    ) -> AppResult<DispatchOutcome> {
+        if let Some(quiet_hours) = self.routing_repo.find_quiet_hours(user_id).await?
+            && quiet_hours.enabled
+        {
+            let window = QuietWindow::from_model(&quiet_hours)?;
+            let now = jiff::Timestamp::now();
 
-        // Wait for all sends to complete
-        let results = join_all(send_futures).await;
+            if window.holds(message.severity, now) {
+                return match window.action() {
+                    QuietHoursAction::Drop => Ok(DispatchOutcome::Dropped),
+                    QuietHoursAction::Defer => {
+                        let deliver_at = window.end_after(now)?;
+                        let message_json =
+                            serde_json::to_value(&message).map_err(|e| AppError::Internal {
+                                source: anyhow::Error::from(e),
+                            })?;
 
-        // Collect successful logs, log errors but don't fail
-        let mut logs = Vec::new();
-        for result in results {
-            match result {
-                Ok(log) => logs.push(log),
-                Err(_e) => {
-                    // Log the error but continue processing
-                    // tracing::error!("Failed to send notification: {}", e);
+                        self.deferred_repo
+                            .create(NewDeferredNotification {
+                                user_id,
+                                message: message_json,
+                                deliver_at: DateTime::from(
+                                    deliver_at.to_zoned(jiff::tz::TimeZone::UTC).datetime(),
+                                ),
+                            })
+                            .await?;
+
+                        Ok(DispatchOutcome::Deferred { deliver_at })
+                    }
+                };
+            }
+        }
+
+        let (rule_id, logs) = self.route(user_id, message).await?;
+        Ok(DispatchOutcome::Delivered { rule_id, logs })
+    }
+
+    /// Delivers deferred notifications whose quiet window has ended
+    ///
+    /// Messages are routed without re-checking quiet hours.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of deferred messages to deliver
+    ///
+    /// # Returns
+    /// Number of deferred messages taken from the queue
+    pub async fn deliver_deferred(&self, limit: i64) -> AppResult<usize> {
+        let due = self.deferred_repo.take_due(limit).await?;
+        let count = due.len();
+
+        for deferred in due {
+            let message: NotificationMessage = match serde_json::from_value(deferred.message) {
+                Ok(message) => message,
+                Err(e) => {
+                    tracing::warn!(
+                        deferred_id = deferred.id,
+                        error = %e,
+                        "Skipping malformed deferred notification"
+                    );
+                    continue;
                 }
+            };
+
+            if let Err(e) = self.route(deferred.user_id, message).await {
+                tracing::warn!(
+                    deferred_id = deferred.id,
+                    user_id = deferred.user_id,
+                    error = %e,
+                    "Failed to deliver deferred notification"
+                );
             }
         }
 
-        Ok(logs)
+        Ok(count)
     }
 
     // ========================================================================
-    // Log Queries
+    // Groups and Broadcasts
     // ========================================================================
 
-    /// Gets logs for a specific channel
+    /// Creates a user group
     ///
     /// # Arguments
-    /// * `channel_id` - The channel ID
-    /// * `offset` - Number of records to skip (for pagination)
-    /// * `limit` - Maximum number of records to return
+    /// * `new_group` - The group data to create
+    /// * `member_ids` - IDs of the users in the group
     ///
     /// # Returns
-    /// Tuple of (logs vector, total count)
-    pub async fn get_channel_logs(
+    /// The created group
+    pub async fn create_group(
         &self,
-        channel_id: i32,
-        offset: i64,
-        limit: i64,
-    ) -> AppResult<(Vec<NotificationLog>, i64)> {
-        self.log_repo
-            .find_by_channel_id(channel_id, offset, limit)
+        new_group: NewUserGroup,
+        member_ids: &[i32],
+    ) -> AppResult<UserGroup> {
+        self.group_repo.create(new_group, member_ids).await
+    }
+
+    /// Gets a user group by ID
+    ///
+    /// # Arguments
+    /// * `id` - The group ID
+    ///
+    /// # Returns
+    /// The group if found, NotFound error otherwise
+    pub async fn get_group(&self, id: i32) -> AppResult<UserGroup> {
+        self.group_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "user_group".to_string(),
+                field: "id".to_string(),
+                value: id.to_string(),
+            })
+    }
+
+    /// Lists all user groups
+    pub async fn list_groups(&self) -> AppResult<Vec<UserGroup>> {
+        self.group_repo.find_all().await
+    }
+
+    /// Lists the user IDs in a group
+    ///
+    /// # Arguments
+    /// * `id` - The group ID
+    pub async fn get_group_members(&self, id: i32) -> AppResult<Vec<i32>> {
+        self.group_repo.find_member_ids(id).await
+    }
+
+    /// Replaces the members of a group
+    ///
+    /// # Arguments
+    /// * `id` - The group ID
+    /// * `member_ids` - IDs of the users that make up the group
+    pub async fn set_group_members(&self, id: i32, member_ids: &[i32]) -> AppResult<()> {
+        self.get_group(id).await?;
+        self.group_repo.set_members(id, member_ids).await
+    }
+
+    /// Deletes a user group
+    ///
+    /// Broadcasts already sent to the group are kept.
+    ///
+    /// # Arguments
+    /// * `id` - The group ID
+    ///
+    /// # Returns
+    /// true if deleted, false if not found
+    pub async fn delete_group(&self, id: i32) -> AppResult<bool> {
+        let affected = self.group_repo.delete(id).await?;
+        Ok(affected > 0)
+    }
+
+    /// Queues a message for every user in an audience
+    ///
+    /// Recipients are resolved now, so later group changes do not affect
+    /// the broadcast. Delivery happens in the background through
+    /// [`Self::process_broadcasts`].
+    ///
+    /// # Arguments
+    /// * `created_by` - The administrator sending the broadcast
+    /// * `group_id` - The group to target, or None for every user
+    /// * `message` - The notification message
+    ///
+    /// # Returns
+    /// The queued broadcast
+    pub async fn create_broadcast(
+        &self,
+        created_by: i32,
+        group_id: Option<i32>,
+        message: NotificationMessage,
+    ) -> AppResult<NotificationBroadcast> {
+        if let Some(group_id) = group_id {
+            self.get_group(group_id).await?;
+        }
+
+        let recipients = self.group_repo.find_audience_ids(group_id).await?;
+        if recipients.is_empty() {
+            return Err(AppError::Validation {
+                field: "group_id".to_string(),
+                reason: "The audience has no users".to_string(),
+            });
+        }
+
+        let message = serde_json::to_value(&message).map_err(|e| AppError::Internal {
+            source: anyhow::Error::from(e),
+        })?;
+
+        self.broadcast_repo
+            .create(
+                NewNotificationBroadcast {
+                    created_by: Some(created_by),
+                    group_id,
+                    message,
+                },
+                &recipients,
+            )
             .await
     }
 
-    /// Gets logs by status
+    /// Gets a broadcast by ID
     ///
     /// # Arguments
-    /// * `status` - The status to filter by
-    /// * `offset` - Number of records to skip (for pagination)
+    /// * `id` - The broadcast ID
+    ///
+    /// # Returns
+    /// The broadcast if found, NotFound error otherwise
+    pub async fn get_broadcast(&self, id: i32) -> AppResult<NotificationBroadcast> {
+        self.broadcast_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "notification_broadcast".to_string(),
+                field: "id".to_string(),
+                value: id.to_string(),
+            })
+    }
+
+    /// Lists broadcasts, newest first, with pagination
+    ///
+    /// # Arguments
+    /// * `offset` - Number of records to skip
     /// * `limit` - Maximum number of records to return
     ///
     /// # Returns
-    /// Tuple of (logs vector, total count)
-    pub async fn get_logs_by_status(
+    /// Tuple of (broadcasts, total count)
+    pub async fn list_broadcasts(
         &self,
-        status: NotificationStatus,
         offset: i64,
         limit: i64,
-    ) -> AppResult<(Vec<NotificationLog>, i64)> {
-        self.log_repo.find_by_status(status, offset, limit).await
+    ) -> AppResult<(Vec<NotificationBroadcast>, i64)> {
+        self.broadcast_repo.find_paginated(offset, limit).await
     }
 
-    // ========================================================================
-    // Private Helpers
-    // ========================================================================
-
-    /// Creates a provider instance from channel configuration
+    /// Delivers a batch of queued broadcast recipients
     ///
-    /// Factory method pattern - returns Arc<dyn NotificationProvider> for
-    /// dynamic dispatch.
+    /// Each recipient gets the message through [`Self::dispatch`], so their
+    /// routing rules and quiet hours pick the channel. At most
+    /// `concurrency` recipients are dispatched at a time. Stats of the
+    /// affected broadcasts are refreshed afterwards.
     ///
     /// # Arguments
-    /// * `channel` - The notification channel
+    /// * `batch_size` - Maximum number of recipients to process
+    /// * `concurrency` - Maximum number of parallel dispatches
     ///
     /// # Returns
-    /// Arc-wrapped provider implementing NotificationProvider trait
-    fn create_provider(
+    /// Number of recipients processed
+    pub async fn process_broadcasts(
         &self,
-        channel: &NotificationChannel,
-    ) -> AppResult<Arc<dyn NotificationProvider>> {
-        match channel.channel_type {
-            ChannelType::Webhook => {
-                let config = WebhookConfig::from_json(&channel.config).map_err(|e| {
-                    AppError::Validation {
-                        field: "config".to_string(),
-                        reason: format!("Invalid webhook config: {}", e),
+        batch_size: i64,
+        concurrency: usize,
+    ) -> AppResult<usize> {
+        let recipients = self.broadcast_repo.claim_pending(batch_size).await?;
+        if recipients.is_empty() {
+            return Ok(0);
+        }
+
+        let mut broadcast_ids: Vec<i32> = recipients.iter().map(|r| r.broadcast_id).collect();
+        broadcast_ids.sort_unstable();
+        broadcast_ids.dedup();
+        self.broadcast_repo.mark_started(&broadcast_ids).await?;
+
+        let mut messages = HashMap::new();
+        for &broadcast_id in &broadcast_ids {
+            let broadcast = self.get_broadcast(broadcast_id).await?;
+            let message: Result<NotificationMessage, _> = serde_json::from_value(broadcast.message);
+            messages.insert(broadcast_id, message.map_err(|e| e.to_string()));
+        }
+
+        let processed = recipients.len();
+        futures::stream::iter(recipients)
+            .map(|recipient| {
+                let message = messages[&recipient.broadcast_id].clone();
+                async move {
+                    let (status, error) = match message {
+                        Ok(message) => match self.dispatch(recipient.user_id, message).await {
+                            Ok(outcome) => recipient_status(&outcome),
+                            Err(e) => (BroadcastRecipientStatus::Failed, Some(e.to_string())),
+                        },
+                        Err(e) => (
+                            BroadcastRecipientStatus::Failed,
+                            Some(format!("Malformed broadcast message: {}", e)),
+                        ),
+                    };
+
+                    if let Err(e) = self
+                        .broadcast_repo
+                        .finish_recipient(recipient.id, status, error)
+                        .await
+                    {
+                        tracing::warn!(
+                            broadcast_id = recipient.broadcast_id,
+                            user_id = recipient.user_id,
+                            error = %e,
+                            "Failed to record broadcast delivery"
+                        );
                     }
-                })?;
-                Ok(Arc::new(WebhookProvider::new(config)))
-            }
-            ChannelType::Bark => {
-                let config =
-                    BarkConfig::from_json(&channel.config).map_err(|e| AppError::Validation {
-                        field: "config".to_string(),
-                        reason: format!("Invalid bark config: {}", e),
-                    })?;
-                Ok(Arc::new(BarkProvider::new(config)))
-            }
-            // Future providers:
-            // ChannelType::Email => { ... }
-            // ChannelType::Sms => { ... }
-            _ => Err(AppError::BadRequest {
-                message: format!("Unsupported channel type: {:?}", channel.channel_type),
-            }),
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect::<Vec<()>>()
+            .await;
+
+        for broadcast_id in broadcast_ids {
+            self.broadcast_repo.refresh_stats(broadcast_id).await?;
         }
+
+        Ok(processed)
     }
 
-    /// Validates channel configuration based on type
+    // ========================================================================
+    // API Keys
+    // ========================================================================
+
+    /// Creates an API key that lets external systems send notifications
     ///
-    /// Validates both config parsing and provider-specific validation rules.
+    /// The full key is returned once; only its hash is stored.
     ///
     /// # Arguments
-    /// * `channel_type` - The type of channel
-    /// * `config` - The configuration JSONB value
+    /// * `user_id` - The user the key sends on behalf of
+    /// * `name` - Label for the key, unique per user
+    /// * `allowed_ips` - IP addresses or CIDR ranges allowed to use the key
+    /// * `rate_limit_count` - Requests allowed per window (None = unlimited)
+    /// * `rate_limit_window_seconds` - Length of the rate-limit window
+    /// * `expires_at` - When the key stops working (UTC), if ever
     ///
     /// # Returns
-    /// Ok(()) if valid, Err with validation details otherwise
-    async fn validate_channel_config(
+    /// The created key and the full key string
+    pub async fn create_api_key(
         &self,
-        channel_type: &ChannelType,
-        config: &serde_json::Value,
-    ) -> AppResult<()> {
-        match channel_type {
-            ChannelType::Webhook => {
-                // Parse config
-                let webhook_config =
-                    WebhookConfig::from_json(config).map_err(|e| AppError::Validation {
-                        field: "config".to_string(),
-                        reason: format!("Invalid webhook config: {}", e),
-                    })?;
+        user_id: i32,
+        name: String,
+        allowed_ips: Vec<String>,
+        rate_limit_count: Option<i32>,
+        rate_limit_window_seconds: i32,
+        expires_at: Option<DateTime>,
+    ) -> AppResult<(NotificationApiKey, String)> {
+        let allowed_ips: Vec<String> = allowed_ips
+            .iter()
+            .map(|rule| rule.trim().to_string())
+            .collect();
+        if let Some(rule) = allowed_ips
+            .iter()
+            .find(|rule| parse_ip_rule(rule).is_none())
+        {
+            return Err(AppError::Validation {
+                field: "allowed_ips".to_string(),
+                reason: format!("'{}' is not an IP address or CIDR range", rule),
+            });
+        }
 
-                // Create provider and validate
-                let provider = WebhookProvider::new(webhook_config);
+        let generated = generate_api_key()?;
+        let key_hash = hash_password(&generated.key)?;
+
+        let api_key = self
+            .api_key_repo
+            .create(NewNotificationApiKey {
+                user_id,
+                name,
+                key_prefix: generated.prefix,
+                key_hash,
+                allowed_ips,
+                rate_limit_count,
+                rate_limit_window_seconds,
+                expires_at,
+            })
+            .await?;
+
+        Ok((api_key, generated.key))
+    }
+
+    /// Gets an API key by ID
+    ///
+    /// # Arguments
+    /// * `id` - The key ID
+    ///
+    /// # Returns
+    /// The key if found, NotFound error otherwise
+    pub async fn get_api_key(&self, id: i32) -> AppResult<NotificationApiKey> {
+        self.api_key_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "notification_api_key".to_string(),
+                field: "id".to_string(),
+                value: id.to_string(),
+            })
+    }
+
+    /// Lists all API keys of a user
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    /// Vector of keys, newest first
+    pub async fn list_api_keys(&self, user_id: i32) -> AppResult<Vec<NotificationApiKey>> {
+        self.api_key_repo.find_by_user_id(user_id).await
+    }
+
+    /// Revokes an API key
+    ///
+    /// # Arguments
+    /// * `id` - The key ID
+    ///
+    /// # Returns
+    /// true if deleted, false if not found
+    pub async fn delete_api_key(&self, id: i32) -> AppResult<bool> {
+        let affected = self.api_key_repo.delete(id).await?;
+        Ok(affected > 0)
+    }
+
+    /// Authenticates a request made with an API key
+    ///
+    /// Checks the key hash, expiry and IP allow-list, then counts the
+    /// request against the key's rate limit.
+    ///
+    /// # Arguments
+    /// * `key` - The full key presented by the client
+    /// * `client_ip` - The client address, if known
+    ///
+    /// # Returns
+    /// The key if the request may proceed; Unauthorized for unknown,
+    /// invalid or expired keys, Forbidden for disallowed addresses and
+    /// TooManyRequests when the rate limit is exceeded
+    pub async fn authenticate_api_key(
+        &self,
+        key: &str,
+        client_ip: Option<IpAddr>,
+    ) -> AppResult<NotificationApiKey> {
+        let invalid = || AppError::Unauthorized {
+            message: "Invalid API key".to_string(),
+        };
+
+        let prefix = api_key_prefix(key).ok_or_else(invalid)?;
+        let api_key = self
+            .api_key_repo
+            .find_by_prefix(prefix)
+            .await?
+            .ok_or_else(invalid)?;
+        if !verify_password(key, &api_key.key_hash)? {
+            return Err(invalid());
+        }
+
+        let now = window_start(0);
+        if api_key
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return Err(AppError::Unauthorized {
+                message: "API key has expired".to_string(),
+            });
+        }
+
+        if !ip_allowed(&api_key.allowed_ips, client_ip) {
+            return Err(AppError::Forbidden {
+                message: "API key is not allowed from this address".to_string(),
+            });
+        }
+
+        let count = self
+            .api_key_repo
+            .record_request(
+                api_key.id,
+                now,
+                window_start(api_key.rate_limit_window_seconds),
+            )
+            .await?;
+        if let Some(limit) = api_key.rate_limit_count
+            && count > limit
+        {
+            return Err(AppError::TooManyRequests {
+                message: format!(
+                    "API key rate limit of {} requests per {} seconds exceeded",
+                    limit, api_key.rate_limit_window_seconds
+                ),
+            });
+        }
+
+        Ok(api_key)
+    }
+
+    // ========================================================================
+    // Template Management
+    // ========================================================================
+
+    /// Creates a user-owned message template
+    ///
+    /// Compiles every template part before saving so syntax errors are
+    /// reported up front.
+    ///
+    /// # Arguments
+    /// * `new_template` - The template data to create
+    ///
+    /// # Returns
+    /// The created template
+    pub async fn create_template(
+        &self,
+        new_template: NewNotificationTemplate,
+    ) -> AppResult<NotificationTemplate> {
+        Self::compile_template_parts(
+            new_template.title_template.as_deref(),
+            &new_template.body_template,
+            &new_template.variants,
+        )?;
+
+        self.template_repo.create(new_template).await
+    }
+
+    /// Gets a template by ID
+    ///
+    /// # Arguments
+    /// * `id` - The template ID
+    ///
+    /// # Returns
+    /// The template if found, NotFound error otherwise
+    pub async fn get_template(&self, id: i32) -> AppResult<NotificationTemplate> {
+        self.template_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::NotFound {
+                entity: "notification_template".to_string(),
+                field: "id".to_string(),
+                value: id.to_string(),
+            })
+    }
+
+    /// Lists templates visible to a user (their own plus system templates)
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    /// Vector of templates ordered by key
+    pub async fn list_templates(&self, user_id: i32) -> AppResult<Vec<NotificationTemplate>> {
+        self.template_repo.find_visible_to_user(user_id).await
+    }
+
+    /// Updates a user-owned template
+    ///
+    /// System templates are read-only; users override them by creating a
+    /// template with the same key.
+    ///
+    /// # Arguments
+    /// * `id` - The template ID to update
+    /// * `update_data` - The update data
+    ///
+    /// # Returns
+    /// The updated template
+    pub async fn update_template(
+        &self,
+        id: i32,
+        update_data: UpdateNotificationTemplate,
+    ) -> AppResult<NotificationTemplate> {
+        let template = self.get_template(id).await?;
+
+        if template.is_system() {
+            return Err(AppError::Forbidden {
+                message: "System templates are read-only".to_string(),
+            });
+        }
+
+        // Validate the template as it will look after the update
+        let title = update_data
+            .title_template
+            .as_deref()
+            .or(template.title_template.as_deref());
+        let body = update_data
+            .body_template
+            .as_deref()
+            .unwrap_or(&template.body_template);
+        let variants = update_data.variants.as_ref().unwrap_or(&template.variants);
+        Self::compile_template_parts(title, body, variants)?;
+
+        self.template_repo.update(id, update_data).await
+    }
+
+    /// Deletes a user-owned template
+    ///
+    /// # Arguments
+    /// * `id` - The template ID to delete
+    ///
+    /// # Returns
+    /// true if deleted, false if not found
+    pub async fn delete_template(&self, id: i32) -> AppResult<bool> {
+        let affected = self.template_repo.delete(id).await?;
+        Ok(affected > 0)
+    }
+
+    /// Renders a template by key for a user
+    ///
+    /// The user's own template takes precedence over a system template with
+    /// the same key. The returned message carries every format variant so
+    /// each channel receives the rendering it supports.
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    /// * `template_key` - The template key (e.g., "live_started")
+    /// * `variables` - JSON object holding template variables
+    ///
+    /// # Returns
+    /// The rendered notification message
+    pub async fn render_template(
+        &self,
+        user_id: i32,
+        template_key: &str,
+        variables: &JsonValue,
+    ) -> AppResult<NotificationMessage> {
+        let template = self.find_template_by_key(user_id, template_key).await?;
+        Ok(MessageTemplate::from_model(&template)?.render(variables))
+    }
+
+    /// Renders the go-live alert for a room
+    ///
+    /// Uses the user's `live_started` template (or the system one) and
+    /// attaches the room cover and a link to the room.
+    ///
+    /// # Arguments
+    /// * `user_id` - The user to alert
+    /// * `platform` - The live platform
+    /// * `anchor_name` - Display name of the anchor
+    /// * `room` - The room that went live
+    ///
+    /// # Returns
+    /// The alert, ready to dispatch
+    pub async fn live_started_alert(
+        &self,
+        user_id: i32,
+        platform: LivePlatform,
+        anchor_name: &str,
+        room: &RoomInfo,
+    ) -> AppResult<NotificationMessage> {
+        let template = self
+            .find_template_by_key(user_id, LIVE_STARTED_TEMPLATE)
+            .await?;
+        Ok(live_started_message(
+            &MessageTemplate::from_model(&template)?,
+            platform,
+            anchor_name,
+            room,
+        ))
+    }
+
+    /// Sends a job alert to a channel
+    ///
+    /// The alert is rendered with the event's template as seen by the
+    /// channel's owner: their own template with that key, or the system
+    /// one.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel to alert
+    /// * `event` - Event of the rule that triggered
+    /// * `job_name` - Name of the job
+    /// * `vars` - Variables built by [`job_alert_vars`](super::job_alert_vars)
+    ///
+    /// # Returns
+    /// The log entry of the delivery attempt
+    pub async fn send_job_alert(
+        &self,
+        channel_id: i32,
+        event: JobAlertEvent,
+        job_name: &str,
+        vars: &JsonValue,
+    ) -> AppResult<NotificationLog> {
+        let channel = self.get_channel(channel_id).await?;
+        let template = self
+            .find_template_by_key(channel.user_id, job_alert_template(event))
+            .await?;
+        let message = job_alert_message(
+            &MessageTemplate::from_model(&template)?,
+            event,
+            job_name,
+            vars,
+        );
+        self.send_to_channel(channel_id, message).await
+    }
+
+    /// Resolves a template by key for a user
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    /// * `template_key` - The template key
+    ///
+    /// # Returns
+    /// The user's template, falling back to the system template
+    pub async fn find_template_by_key(
+        &self,
+        user_id: i32,
+        template_key: &str,
+    ) -> AppResult<NotificationTemplate> {
+        self.template_repo
+            .find_by_key(user_id, template_key)
+            .await?
+            .ok_or(AppError::NotFound {
+                entity: "notification_template".to_string(),
+                field: "template_key".to_string(),
+                value: template_key.to_string(),
+            })
+    }
+
+    // ========================================================================
+    // Routing Rules & Quiet Hours
+    // ========================================================================
+
+    /// Creates a routing rule
+    ///
+    /// Verifies every target channel belongs to the rule's owner.
+    ///
+    /// # Arguments
+    /// * `new_rule` - The rule data to create
+    ///
+    /// # Returns
+    /// The created rule
+    pub async fn create_routing_rule(
+        &self,
+        new_rule: NewNotificationRoutingRule,
+    ) -> AppResult<NotificationRoutingRule> {
+        self.validate_rule_channels(new_rule.user_id, &new_rule.channel_ids)
+            .await?;
+        self.routing_repo.create_rule(new_rule).await
+    }
+
+    /// Gets a routing rule by ID
+    ///
+    /// # Arguments
+    /// * `id` - The rule ID
+    ///
+    /// # Returns
+    /// The rule if found, NotFound error otherwise
+    pub async fn get_routing_rule(&self, id: i32) -> AppResult<NotificationRoutingRule> {
+        self.routing_repo
+            .find_rule_by_id(id)
+            .await?
+            .ok_or(AppError::NotFound {
+                entity: "notification_routing_rule".to_string(),
+                field: "id".to_string(),
+                value: id.to_string(),
+            })
+    }
+
+    /// Lists a user's routing rules in evaluation order
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    /// Vector of rules ordered by priority (highest first)
+    pub async fn list_routing_rules(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<NotificationRoutingRule>> {
+        self.routing_repo.find_rules_by_user_id(user_id).await
+    }
+
+    /// Updates a routing rule
+    ///
+    /// # Arguments
+    /// * `id` - The rule ID to update
+    /// * `update_data` - The update data
+    ///
+    /// # Returns
+    /// The updated rule
+    pub async fn update_routing_rule(
+        &self,
+        id: i32,
+        update_data: UpdateNotificationRoutingRule,
+    ) -> AppResult<NotificationRoutingRule> {
+        let rule = self.get_routing_rule(id).await?;
+
+        if let Some(ref channel_ids) = update_data.channel_ids {
+            self.validate_rule_channels(rule.user_id, channel_ids)
+                .await?;
+        }
+
+        self.routing_repo.update_rule(id, update_data).await
+    }
+
+    /// Deletes a routing rule
+    ///
+    /// # Arguments
+    /// * `id` - The rule ID to delete
+    ///
+    /// # Returns
+    /// true if deleted, false if not found
+    pub async fn delete_routing_rule(&self, id: i32) -> AppResult<bool> {
+        let affected = self.routing_repo.delete_rule(id).await?;
+        Ok(affected > 0)
+    }
+
+    /// Gets a user's quiet hours
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    /// The quiet hours if configured, NotFound error otherwise
+    pub async fn get_quiet_hours(&self, user_id: i32) -> AppResult<NotificationQuietHours> {
+        self.routing_repo
+            .find_quiet_hours(user_id)
+            .await?
+            .ok_or(AppError::NotFound {
+                entity: "notification_quiet_hours".to_string(),
+                field: "user_id".to_string(),
+                value: user_id.to_string(),
+            })
+    }
+
+    /// Creates or replaces a user's quiet hours
+    ///
+    /// # Arguments
+    /// * `quiet_hours` - The quiet hours configuration
+    ///
+    /// # Returns
+    /// The stored quiet hours
+    pub async fn set_quiet_hours(
+        &self,
+        quiet_hours: NewNotificationQuietHours,
+    ) -> AppResult<NotificationQuietHours> {
+        parse_timezone(&quiet_hours.timezone)?;
+
+        if quiet_hours.start_time == quiet_hours.end_time {
+            return Err(AppError::Validation {
+                field: "end_time".to_string(),
+                reason: "Quiet hours must not start and end at the same time".to_string(),
+            });
+        }
+
+        self.routing_repo.upsert_quiet_hours(quiet_hours).await
+    }
+
+    /// Removes a user's quiet hours
+    ///
+    /// # Arguments
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    /// true if deleted, false if not configured
+    pub async fn delete_quiet_hours(&self, user_id: i32) -> AppResult<bool> {
+        let affected = self.routing_repo.delete_quiet_hours(user_id).await?;
+        Ok(affected > 0)
+    }
+
+    // ========================================================================
+    // Log Queries
+    // ========================================================================
+
+    /// Gets logs for a specific channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `offset` - Number of records to skip (for pagination)
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Tuple of (logs vector, total count)
+    pub async fn get_channel_logs(
+        &self,
+        channel_id: i32,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<NotificationLog>, i64)> {
+        self.log_repo
+            .find_by_channel_id(channel_id, offset, limit)
+            .await
+    }
+
+    /// Gets logs by status
+    ///
+    /// # Arguments
+    /// * `status` - The status to filter by
+    /// * `offset` - Number of records to skip (for pagination)
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Tuple of (logs vector, total count)
+    pub async fn get_logs_by_status(
+        &self,
+        status: NotificationStatus,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<NotificationLog>, i64)> {
+        self.log_repo.find_by_status(status, offset, limit).await
+    }
+
+    /// Gets a log entry by ID
+    ///
+    /// # Arguments
+    /// * `id` - The log ID
+    ///
+    /// # Returns
+    /// The log entry or NotFound error
+    pub async fn get_log(&self, id: i64) -> AppResult<NotificationLog> {
+        self.log_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "notification_log".to_string(),
+                field: "id".to_string(),
+                value: id.to_string(),
+            })
+    }
+
+    /// Searches the logs of a user's channels
+    ///
+    /// # Arguments
+    /// * `user_id` - The channel owner
+    /// * `filter` - Channel, status, time range and text filters
+    /// * `offset` - Number of records to skip (for pagination)
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Tuple of (logs vector, total count)
+    pub async fn search_logs(
+        &self,
+        user_id: i32,
+        filter: &NotificationLogFilter,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<NotificationLog>, i64)> {
+        self.log_repo.search(user_id, filter, offset, limit).await
+    }
+
+    /// Sends the message of a logged notification again
+    ///
+    /// The stored message goes straight to the channel's provider, skipping
+    /// dedup, rate limits and digests, and the attempt is recorded as a new
+    /// log entry with an incremented retry count.
+    ///
+    /// # Arguments
+    /// * `log_id` - The log entry to resend
+    ///
+    /// # Returns
+    /// The log entry for the new attempt
+    pub async fn resend_log(&self, log_id: i64) -> AppResult<NotificationLog> {
+        let log = self.get_log(log_id).await?;
+        let channel = self.get_channel(log.channel_id).await?;
+
+        if !channel.enabled {
+            return Err(AppError::BadRequest {
+                message: format!("Channel {} is disabled", channel.id),
+            });
+        }
+
+        let message: NotificationMessage =
+            serde_json::from_str(&log.message).map_err(|e| AppError::BadRequest {
+                message: format!("Logged message cannot be resent: {}", e),
+            })?;
+
+        let entry = self.attempt(&channel, &message).await;
+        self.record_health(&channel, &entry).await;
+
+        let mut entry = entry?;
+        entry.retry_count = log.retry_count + 1;
+        self.log_repo.create(entry).await
+    }
+
+    /// Gets a channel's all-time delivery counts per status
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    /// Totals per status, including logs removed by retention
+    pub async fn get_channel_stats(
+        &self,
+        channel_id: i32,
+    ) -> AppResult<Vec<NotificationLogTotals>> {
+        self.log_repo.totals_by_channel(channel_id).await
+    }
+
+    /// Aggregates delivery counts and latency of a user's channels per provider
+    ///
+    /// Without `to` the range ends now; without `from` it covers the
+    /// bucket's default range (a day of hours or 30 days). Only logs still
+    /// stored count, so ranges beyond the log retention period are partial.
+    ///
+    /// # Arguments
+    /// * `user_id` - The channel owner
+    /// * `channel_id` - Only logs of this channel
+    /// * `from` - Start of the range (inclusive)
+    /// * `to` - End of the range (exclusive)
+    /// * `bucket` - Time bucket size
+    ///
+    /// # Returns
+    /// The resolved range and per-provider rows (see
+    /// `NotificationLogRepository::delivery_stats`)
+    pub async fn get_delivery_stats(
+        &self,
+        user_id: i32,
+        channel_id: Option<i32>,
+        from: Option<jiff::Timestamp>,
+        to: Option<jiff::Timestamp>,
+        bucket: StatsBucket,
+    ) -> AppResult<(
+        jiff::Timestamp,
+        jiff::Timestamp,
+        Vec<NotificationDeliveryStats>,
+    )> {
+        let to = to.unwrap_or_else(jiff::Timestamp::now);
+        let from = match from {
+            Some(from) => from,
+            None => to
+                .checked_sub(bucket.default_range())
+                .map_err(|e| AppError::BadRequest {
+                    message: format!("Invalid time range: {}", e),
+                })?,
+        };
+
+        if from >= to {
+            return Err(AppError::BadRequest {
+                message: "'from' must be before 'to'".to_string(),
+            });
+        }
+        if to.duration_since(from) > bucket.max_range() {
+            return Err(AppError::BadRequest {
+                message: format!(
+                    "Time range is too long for {} buckets (max {} days)",
+                    bucket.as_sql(),
+                    bucket.max_range().as_hours() / 24
+                ),
+            });
+        }
+
+        let rows = self
+            .log_repo
+            .delivery_stats(
+                user_id,
+                channel_id,
+                utc_datetime(from),
+                utc_datetime(to),
+                bucket,
+            )
+            .await?;
+        Ok((from, to, rows))
+    }
+
+    /// Deletes logs older than the retention period
+    ///
+    /// Logs are removed in batches, oldest first. Each batch is rolled up
+    /// into the daily stats and, when an archive directory is given,
+    /// appended to a gzipped JSONL file before it is deleted. Logs waiting
+    /// for a digest are kept.
+    ///
+    /// # Arguments
+    /// * `retention_days` - Age in days after which logs are purged
+    /// * `batch_size` - Number of logs deleted per batch
+    /// * `archive_dir` - Directory for the archive file, if any
+    ///
+    /// # Returns
+    /// Number of logs purged
+    pub async fn purge_logs(
+        &self,
+        retention_days: i64,
+        batch_size: i64,
+        archive_dir: Option<&Path>,
+    ) -> AppResult<usize> {
+        if retention_days < 1 || batch_size < 1 {
+            return Err(AppError::BadRequest {
+                message: "retention_days and batch_size must be at least 1".to_string(),
+            });
+        }
+
+        let now = jiff::Timestamp::now();
+        let cutoff_ts = now - jiff::SignedDuration::from_hours(retention_days * 24);
+        let cutoff = DateTime::from(cutoff_ts.to_zoned(jiff::tz::TimeZone::UTC).datetime());
+
+        let archive = match archive_dir {
+            Some(dir) => {
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .map_err(|e| AppError::Internal {
+                        source: anyhow::Error::from(e).context(format!(
+                            "Failed to create archive directory {}",
+                            dir.display()
+                        )),
+                    })?;
+                Some(archive_path(dir, now))
+            }
+            None => None,
+        };
+
+        let mut purged = 0;
+        loop {
+            let logs = self.log_repo.find_sent_before(cutoff, batch_size).await?;
+            if logs.is_empty() {
+                break;
+            }
+
+            if let Some(path) = &archive {
+                append_archive(path, &logs).await?;
+            }
+
+            let ids: Vec<i64> = logs.iter().map(|log| log.id).collect();
+            purged += self.log_repo.purge(&ids).await?;
+
+            if (logs.len() as i64) < batch_size {
+                break;
+            }
+        }
+
+        Ok(purged)
+    }
+
+    // ========================================================================
+    // Private Helpers
+    // ========================================================================
+
+    /// Sends a message through the channel's provider and logs the result
+    ///
+    /// The outcome is recorded in the channel's health counters.
+    async fn deliver(
+        &self,
+        channel: &NotificationChannel,
+        message: &NotificationMessage,
+    ) -> AppResult<NotificationLog> {
+        let log_entry = self.attempt(channel, message).await;
+        self.record_health(channel, &log_entry).await;
+        self.log_repo.create(log_entry?).await
+    }
+
+    /// Updates a channel's health after a delivery attempt
+    ///
+    /// Disables the channel once its failure streak reaches the threshold
+    /// and tells the owner through their other channels. Errors are logged
+    /// rather than failing the send.
+    async fn record_health(
+        &self,
+        channel: &NotificationChannel,
+        attempt: &AppResult<NewNotificationLog>,
+    ) {
+        let error = match attempt {
+            Ok(entry) if entry.status == NotificationStatus::Sent => None,
+            Ok(entry) => Some(
+                entry
+                    .error_message
+                    .clone()
+                    .unwrap_or_else(|| "Delivery failed".to_string()),
+            ),
+            Err(e) => Some(e.to_string()),
+        };
+
+        let result = match error {
+            None => self.channel_repo.record_success(channel.id).await,
+            Some(error) => self.record_failure(channel, &error).await,
+        };
+
+        if let Err(e) = result {
+            tracing::warn!(channel_id = channel.id, error = %e, "Failed to record channel health");
+        }
+    }
+
+    /// Records a failure and disables the channel past its threshold
+    async fn record_failure(&self, channel: &NotificationChannel, error: &str) -> AppResult<()> {
+        let updated = self.channel_repo.record_failure(channel.id, error).await?;
+
+        if !reaches_failure_threshold(updated.consecutive_failures, updated.failure_threshold) {
+            return Ok(());
+        }
+
+        let reason = auto_disable_reason(updated.consecutive_failures);
+        if let Some(disabled) = self.channel_repo.auto_disable(channel.id, &reason).await? {
+            tracing::warn!(
+                channel_id = disabled.id,
+                user_id = disabled.user_id,
+                failures = disabled.consecutive_failures,
+                "Notification channel disabled automatically"
+            );
+            self.notify_disabled(&disabled).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Tells a channel's owner that it was disabled
+    ///
+    /// Tries the owner's remaining enabled channels in priority order until
+    /// one delivers. The alert bypasses dedup, rate limits and digests, and
+    /// does not count towards channel health.
+    async fn notify_disabled(&self, channel: &NotificationChannel) -> AppResult<()> {
+        let others = self
+            .channel_repo
+            .find_enabled_by_user_id(channel.user_id)
+            .await?;
+
+        if others.is_empty() {
+            tracing::warn!(
+                channel_id = channel.id,
+                user_id = channel.user_id,
+                "No other channel to report the disabled channel to"
+            );
+            return Ok(());
+        }
+
+        let message = NotificationMessage {
+            title: Some(format!(
+                "Notification channel \"{}\" disabled",
+                channel.name
+            )),
+            body: format!(
+                "{}. Last error: {}\nFix the channel configuration and re-enable it.",
+                channel.disabled_reason.as_deref().unwrap_or("Disabled"),
+                channel.last_error.as_deref().unwrap_or("unknown")
+            ),
+            severity: NotificationSeverity::High,
+            tags: vec!["channel-health".to_string()],
+            ..Default::default()
+        };
+
+        for other in others {
+            let message = message.for_channel(other.channel_type);
+            match self.attempt(&other, &message).await {
+                Ok(entry) => {
+                    let delivered = entry.status == NotificationStatus::Sent;
+                    self.log_repo.create(entry).await?;
+                    if delivered {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(channel_id = other.id, error = %e, "Failed to send channel alert");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends a message through the channel's provider
+    ///
+    /// # Returns
+    /// A log entry carrying the request summary, truncated response and
+    /// duration of the attempt
+    async fn attempt(
+        &self,
+        channel: &NotificationChannel,
+        message: &NotificationMessage,
+    ) -> AppResult<NewNotificationLog> {
+        let provider = self.create_provider(channel)?;
+        let result = provider.send(message).await?;
+        let response = result.response.as_deref().map(truncate_response);
+
+        let (status, error_message) = if result.success {
+            (NotificationStatus::Sent, None)
+        } else {
+            (NotificationStatus::Failed, response.clone())
+        };
+
+        Ok(NewNotificationLog {
+            request_meta: result
+                .request
+                .as_ref()
+                .and_then(|request| serde_json::to_value(request).ok()),
+            response_status: result.status_code.map(i32::from),
+            response_body: response,
+            duration_ms: Some(result.duration_ms as i64),
+            ..Self::log_entry(channel.id, message, status, error_message)
+        })
+    }
+
+    /// Builds a log entry for a message
+    fn log_entry(
+        channel_id: i32,
+        message: &NotificationMessage,
+        status: NotificationStatus,
+        error_message: Option<String>,
+    ) -> NewNotificationLog {
+        NewNotificationLog {
+            channel_id,
+            message: serde_json::to_string(message).unwrap_or_default(),
+            status,
+            error_message,
+            retry_count: 0,
+            dedup_key: message.dedup_key.clone(),
+            request_meta: None,
+            response_status: None,
+            response_body: None,
+            duration_ms: None,
+        }
+    }
+
+    /// Checks dedup and rate-limit rules for a channel
+    ///
+    /// # Returns
+    /// Some(reason) if the message must be suppressed, None otherwise
+    async fn suppression_reason(
+        &self,
+        channel: &NotificationChannel,
+        message: &NotificationMessage,
+    ) -> AppResult<Option<String>> {
+        if let Some(ref key) = message.dedup_key
+            && channel.dedup_window_seconds > 0
+        {
+            let since = window_start(channel.dedup_window_seconds);
+            if let Some(previous) = self
+                .log_repo
+                .find_recent_by_dedup_key(channel.id, key, since)
+                .await?
+            {
+                return Ok(Some(format!(
+                    "Duplicate of log {} within {}s",
+                    previous.id, channel.dedup_window_seconds
+                )));
+            }
+        }
+
+        if let Some(limit) = channel.rate_limit_count {
+            let since = window_start(channel.rate_limit_window_seconds);
+            let attempts = self
+                .log_repo
+                .count_attempts_since(channel.id, since)
+                .await?;
+            if attempts >= i64::from(limit) {
+                return Ok(Some(format!(
+                    "Rate limit of {} per {}s exceeded",
+                    limit, channel.rate_limit_window_seconds
+                )));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Picks channels for a message using the user's routing rules and sends it
+    ///
+    /// # Returns
+    /// The ID of the matching rule (None for the default chain) and the logs
+    async fn route(
+        &self,
+        user_id: i32,
+        message: NotificationMessage,
+    ) -> AppResult<(Option<i32>, Vec<NotificationLog>)> {
+        let channels = self.channel_repo.find_enabled_by_user_id(user_id).await?;
+
+        if channels.is_empty() {
+            return Err(AppError::NotFound {
+                entity: "notification_channel".to_string(),
+                field: "user_id,enabled".to_string(),
+                value: format!("{},true", user_id),
+            });
+        }
+
+        let rules = self.routing_repo.find_rules_by_user_id(user_id).await?;
+
+        for rule in rules.iter().filter(|r| rule_matches(r, &message)) {
+            // Keep channel priority order; skip disabled or deleted targets
+            let targets: Vec<i32> = channels
+                .iter()
+                .filter(|c| rule.channel_ids.contains(&c.id))
+                .map(|c| c.id)
+                .collect();
+
+            if targets.is_empty() {
+                continue;
+            }
+
+            let logs = match rule.mode {
+                RoutingMode::All => self.send_to_all(targets, message).await,
+                RoutingMode::Escalation => self.send_escalation(targets, message).await,
+            };
+            return Ok((Some(rule.id), logs));
+        }
+
+        let targets = channels.iter().map(|c| c.id).collect();
+        Ok((None, self.send_escalation(targets, message).await))
+    }
+
+    /// Sends a message to every channel in parallel
+    ///
+    /// Continues sending to all channels even if some fail.
+    async fn send_to_all(
+        &self,
+        channel_ids: Vec<i32>,
+        message: NotificationMessage,
+    ) -> Vec<NotificationLog> {
+        use futures::future::join_all;
+
+        let send_futures: Vec<_> = channel_ids
+            .into_iter()
+            .map(|channel_id| {
+                let msg = message.clone();
+                async move { (channel_id, self.send_to_channel(channel_id, msg).await) }
+            })
+            .collect();
+
+        let mut logs = Vec::new();
+        for (channel_id, result) in join_all(send_futures).await {
+            match result {
+                Ok(log) => logs.push(log),
+                Err(e) => {
+                    tracing::warn!(channel_id, error = %e, "Failed to send notification");
+                }
+            }
+        }
+
+        logs
+    }
+
+    /// Tries channels in order, stopping after the first successful send
+    async fn send_escalation(
+        &self,
+        channel_ids: Vec<i32>,
+        message: NotificationMessage,
+    ) -> Vec<NotificationLog> {
+        let mut logs = Vec::new();
+
+        for channel_id in channel_ids {
+            match self.send_to_channel(channel_id, message.clone()).await {
+                Ok(log) => {
+                    let delivered = log.status == NotificationStatus::Sent;
+                    logs.push(log);
+                    if delivered {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        channel_id,
+                        error = %e,
+                        "Escalation step failed, trying next channel"
+                    );
+                }
+            }
+        }
+
+        logs
+    }
+
+    /// Verifies routing targets exist and belong to the user
+    async fn validate_rule_channels(&self, user_id: i32, channel_ids: &[i32]) -> AppResult<()> {
+        if channel_ids.is_empty() {
+            return Err(AppError::Validation {
+                field: "channel_ids".to_string(),
+                reason: "At least one channel is required".to_string(),
+            });
+        }
+
+        let owned = self.channel_repo.find_by_user_id(user_id).await?;
+        if let Some(unknown) = channel_ids
+            .iter()
+            .find(|cid| !owned.iter().any(|c| c.id == **cid))
+        {
+            return Err(AppError::Validation {
+                field: "channel_ids".to_string(),
+                reason: format!("Channel {} does not belong to the user", unknown),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Compiles template parts, parsing the JSONB variants first
+    fn compile_template_parts(
+        title: Option<&str>,
+        body: &str,
+        variants: &JsonValue,
+    ) -> AppResult<MessageTemplate> {
+        let variants =
+            serde_json::from_value(variants.clone()).map_err(|e| AppError::Validation {
+                field: "variants".to_string(),
+                reason: format!("Invalid template variants: {}", e),
+            })?;
+
+        MessageTemplate::compile(title, body, &variants)
+    }
+
+    /// Creates a provider instance from channel configuration
+    ///
+    /// Factory method pattern - returns Arc<dyn NotificationProvider> for
+    /// dynamic dispatch.
+    ///
+    /// # Arguments
+    /// * `channel` - The notification channel
+    ///
+    /// # Returns
+    /// Arc-wrapped provider implementing NotificationProvider trait
+    fn create_provider(
+        &self,
+        channel: &NotificationChannel,
+    ) -> AppResult<Arc<dyn NotificationProvider>> {
+        let config = decrypt_secrets(channel.channel_type, &channel.config, secret_cipher())?;
+
+        match channel.channel_type {
+            ChannelType::Webhook => {
+                let config =
+                    WebhookConfig::from_json(&config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid webhook config: {}", e),
+                    })?;
+                Ok(Arc::new(WebhookProvider::new(config)))
+            }
+            ChannelType::Bark => {
+                let config = BarkConfig::from_json(&config).map_err(|e| AppError::Validation {
+                    field: "config".to_string(),
+                    reason: format!("Invalid bark config: {}", e),
+                })?;
+                Ok(Arc::new(BarkProvider::new(config)))
+            }
+            ChannelType::Gotify => {
+                let config =
+                    GotifyConfig::from_json(&config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid gotify config: {}", e),
+                    })?;
+                Ok(Arc::new(GotifyProvider::new(config)))
+            }
+            ChannelType::Ntfy => {
+                let config = NtfyConfig::from_json(&config).map_err(|e| AppError::Validation {
+                    field: "config".to_string(),
+                    reason: format!("Invalid ntfy config: {}", e),
+                })?;
+                Ok(Arc::new(NtfyProvider::new(config)))
+            }
+            ChannelType::WebPush => {
+                let subscription =
+                    WebPushSubscription::from_json(&config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid webpush subscription: {}", e),
+                    })?;
+                Ok(Arc::new(WebPushProvider::new(subscription)))
+            }
+            // Future providers:
+            // ChannelType::Email => { ... }
+            // ChannelType::Sms => { ... }
+            _ => Err(AppError::BadRequest {
+                message: format!("Unsupported channel type: {:?}", channel.channel_type),
+            }),
+        }
+    }
+
+    /// Validates channel configuration based on type
+    ///
+    /// Validates both config parsing and provider-specific validation rules.
+    ///
+    /// # Arguments
+    /// * `channel_type` - The type of channel
+    /// * `config` - The configuration JSONB value
+    ///
+    /// # Returns
+    /// Ok(()) if valid, Err with validation details otherwise
+    async fn validate_channel_config(
+        &self,
+        channel_type: &ChannelType,
+        config: &serde_json::Value,
+    ) -> AppResult<()> {
+        match channel_type {
+            ChannelType::Webhook => {
+                // Parse config
+                let webhook_config =
+                    WebhookConfig::from_json(config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid webhook config: {}", e),
+                    })?;
+
+                // Create provider and validate
+                let provider = WebhookProvider::new(webhook_config);
                 provider.validate_config().await?;
             }
             ChannelType::Bark => {
@@ -389,6 +2110,39 @@ impl NotificationService {
                 let provider = BarkProvider::new(bark_config);
                 provider.validate_config().await?;
             }
+            ChannelType::Gotify => {
+                let gotify_config =
+                    GotifyConfig::from_json(config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid gotify config: {}", e),
+                    })?;
+
+                // Also checks that the server is reachable
+                let provider = GotifyProvider::new(gotify_config);
+                provider.validate_config().await?;
+            }
+            ChannelType::Ntfy => {
+                let ntfy_config =
+                    NtfyConfig::from_json(config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid ntfy config: {}", e),
+                    })?;
+
+                // Also checks that the server is reachable
+                let provider = NtfyProvider::new(ntfy_config);
+                provider.validate_config().await?;
+            }
+            ChannelType::WebPush => {
+                let subscription =
+                    WebPushSubscription::from_json(config).map_err(|e| AppError::Validation {
+                        field: "config".to_string(),
+                        reason: format!("Invalid webpush subscription: {}", e),
+                    })?;
+
+                // Also checks that VAPID keys are configured
+                let provider = WebPushProvider::new(subscription);
+                provider.validate_config().await?;
+            }
             // Future validations:
             // ChannelType::Email => { ... }
             _ => {
@@ -400,3 +2154,188 @@ impl NotificationService {
         Ok(())
     }
 }
+
+/// Returns the UTC start of a window ending now
+fn window_start(seconds: i32) -> DateTime {
+    utc_datetime(jiff::Timestamp::now() - jiff::SignedDuration::from_secs(i64::from(seconds)))
+}
+
+/// Converts an instant to the UTC datetime stored in the database
+fn utc_datetime(ts: jiff::Timestamp) -> DateTime {
+    DateTime::from(ts.to_zoned(jiff::tz::TimeZone::UTC).datetime())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::DatabaseConfig;
+    use crate::db::establish_async_connection_pool;
+    use crate::models::{NewNotificationChannel, NewUser};
+    use crate::repositories::{Repositories, UserRepository};
+    use crate::services::Services;
+
+    /// Service backed by the database in `FUSION_DATABASE__URL`, and a
+    /// fresh user that owns the test channels
+    async fn setup() -> (NotificationService, UserRepository, i32) {
+        let config = DatabaseConfig {
+            url: std::env::var("FUSION_DATABASE__URL")
+                .unwrap_or_else(|_| "postgres://postgres@localhost:5432/fusion".to_string()),
+            ..Default::default()
+        };
+        let pool = establish_async_connection_pool(&config).await.unwrap();
+        let users = UserRepository::new(pool.clone());
+        let name = format!("health-{}", uuid::Uuid::new_v4().simple());
+        let user = users
+            .create(NewUser {
+                username: name.clone(),
+                email: format!("{}@example.com", name),
+                password: "unused".to_string(),
+            })
+            .await
+            .unwrap();
+        let service = Services::new(Repositories::new(pool)).notifications;
+        (service, users, user.id)
+    }
+
+    /// Webhook channel whose deliveries always fail
+    async fn unreachable_channel(
+        service: &NotificationService,
+        user_id: i32,
+        failure_threshold: i32,
+    ) -> NotificationChannel {
+        service
+            .channel_repo
+            .create(NewNotificationChannel {
+                user_id,
+                channel_type: ChannelType::Webhook,
+                name: format!("unreachable-{}", failure_threshold),
+                config: serde_json::json!({"url": "http://127.0.0.1:9", "timeout_seconds": 1}),
+                enabled: true,
+                priority: 0,
+                rate_limit_count: None,
+                rate_limit_window_seconds: 60,
+                dedup_window_seconds: 0,
+                digest_enabled: false,
+                failure_threshold,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn reload(
+        service: &NotificationService,
+        channel: &NotificationChannel,
+    ) -> NotificationChannel {
+        service.get_channel(channel.id).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local PostgreSQL database"]
+    async fn test_failures_disable_channel_at_threshold() {
+        let (service, users, user_id) = setup().await;
+        let channel = unreachable_channel(&service, user_id, 3).await;
+
+        for streak in 1..=2 {
+            service.record_failure(&channel, "boom").await.unwrap();
+            let current = reload(&service, &channel).await;
+            assert_eq!(current.consecutive_failures, streak);
+            assert!(current.enabled);
+        }
+
+        service.record_failure(&channel, "boom").await.unwrap();
+        let current = reload(&service, &channel).await;
+        assert_eq!(current.consecutive_failures, 3);
+        assert_eq!(current.last_error.as_deref(), Some("boom"));
+        assert!(!current.enabled);
+        assert_eq!(
+            current.disabled_reason.as_deref(),
+            Some("Disabled after 3 consecutive failures")
+        );
+
+        users.delete(user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local PostgreSQL database"]
+    async fn test_zero_threshold_never_disables_channel() {
+        let (service, users, user_id) = setup().await;
+        let channel = unreachable_channel(&service, user_id, 0).await;
+
+        for _ in 0..5 {
+            service.record_failure(&channel, "boom").await.unwrap();
+        }
+        let current = reload(&service, &channel).await;
+        assert_eq!(current.consecutive_failures, 5);
+        assert!(current.enabled);
+
+        users.delete(user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local PostgreSQL database"]
+    async fn test_success_resets_failure_streak() {
+        let (service, users, user_id) = setup().await;
+        let channel = unreachable_channel(&service, user_id, 3).await;
+
+        service.record_failure(&channel, "boom").await.unwrap();
+        service.record_failure(&channel, "boom").await.unwrap();
+        service
+            .channel_repo
+            .record_success(channel.id)
+            .await
+            .unwrap();
+        let current = reload(&service, &channel).await;
+        assert_eq!(current.consecutive_failures, 0);
+
+        // The streak starts over, so one more failure does not disable it
+        service.record_failure(&channel, "boom").await.unwrap();
+        let current = reload(&service, &channel).await;
+        assert_eq!(current.consecutive_failures, 1);
+        assert!(current.enabled);
+
+        users.delete(user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local PostgreSQL database"]
+    async fn test_record_health_counts_failed_attempts() {
+        let (service, users, user_id) = setup().await;
+        let channel = unreachable_channel(&service, user_id, 2).await;
+        let message = NotificationMessage {
+            body: "health check".to_string(),
+            ..Default::default()
+        };
+
+        let attempt = service.attempt(&channel, &message).await;
+        service.record_health(&channel, &attempt).await;
+        let current = reload(&service, &channel).await;
+        assert_eq!(current.consecutive_failures, 1);
+        assert!(current.enabled);
+
+        users.delete(user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local PostgreSQL database"]
+    async fn test_notify_disabled_alerts_other_channels() {
+        let (service, users, user_id) = setup().await;
+        let broken = unreachable_channel(&service, user_id, 1).await;
+        let other = unreachable_channel(&service, user_id, 0).await;
+
+        service.record_failure(&broken, "boom").await.unwrap();
+        assert!(!reload(&service, &broken).await.enabled);
+
+        // The alert was attempted on the owner's remaining channel
+        let (logs, total) = service
+            .log_repo
+            .find_by_channel_id(other.id, 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+        assert!(logs[0].message.contains("disabled"));
+        // Alerts do not count towards the health of the channel sending them
+        assert_eq!(reload(&service, &other).await.consecutive_failures, 0);
+
+        users.delete(user_id).await.unwrap();
+    }
+}
@@ -0,0 +1,361 @@
+//! ntfy notification provider implementation.
+//!
+//! Publishes messages to a topic on ntfy.sh or a self-hosted ntfy server
+//! using its JSON publishing API. Uses the global `HTTP_CLIENT` for
+//! connection pooling.
+//!
+//! ntfy API Reference: https://docs.ntfy.sh/publish/
+
+use super::provider::{
+    NotificationMessage, NotificationProvider, NotificationResult, RequestInfo, check_server_health,
+};
+use crate::error::{AppError, AppResult};
+use crate::external::client::HTTP_CLIENT;
+use crate::models::{MessageFormat, NotificationSeverity, NtfyConfig};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde_json::json;
+use std::time::Instant;
+
+/// ntfy notification provider
+///
+/// # Example
+/// ```ignore
+/// let config = NtfyConfig {
+///     server_url: "https://ntfy.example.com".to_string(),
+///     topic: "alerts".to_string(),
+///     token: Some("tk_abc123".to_string()),
+///     username: None,
+///     password: None,
+///     priority: None,
+///     tags: vec!["warning".to_string()],
+/// };
+/// let provider = NtfyProvider::new(config);
+/// let result = provider.send(&message).await?;
+/// ```
+#[derive(Clone)]
+pub struct NtfyProvider {
+    config: NtfyConfig,
+}
+
+impl NtfyProvider {
+    /// Creates a new ntfy provider with configuration
+    ///
+    /// # Arguments
+    /// * `config` - ntfy configuration (server_url, topic, credentials, optional settings)
+    pub fn new(config: NtfyConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the server URL without a trailing slash
+    fn base_url(&self) -> &str {
+        self.config.server_url.trim_end_matches('/')
+    }
+
+    /// Validates the ntfy server URL
+    ///
+    /// # Returns
+    /// Ok(()) if URL is a valid HTTP(S) URL
+    fn validate_server_url(&self) -> Result<(), AppError> {
+        let url = Url::parse(&self.config.server_url).map_err(|_| AppError::Validation {
+            field: "server_url".to_string(),
+            reason: "Invalid URL format".to_string(),
+        })?;
+
+        if url.scheme() != "https" && url.scheme() != "http" {
+            return Err(AppError::Validation {
+                field: "server_url".to_string(),
+                reason: "URL must use http or https protocol".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Builds the request body for the ntfy JSON publishing API
+    ///
+    /// Message tags are added after the configured tags. ntfy supports a
+    /// single attachment, so only the first one is sent.
+    ///
+    /// # Arguments
+    /// * `message` - The notification message to send
+    ///
+    /// # Returns
+    /// JSON object for the ntfy publish request body
+    fn build_request_body(&self, message: &NotificationMessage) -> serde_json::Value {
+        let mut body = json!({
+            "topic": self.config.topic,
+            "message": message.body,
+            "priority": self
+                .config
+                .priority
+                .unwrap_or_else(|| severity_priority(message.severity)),
+        });
+
+        if let Some(title) = &message.title {
+            body["title"] = json!(title);
+        }
+
+        let mut tags = self.config.tags.clone();
+        for tag in &message.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        if !tags.is_empty() {
+            body["tags"] = json!(tags);
+        }
+
+        if message.format == MessageFormat::Markdown {
+            body["markdown"] = json!(true);
+        }
+
+        if let Some(url) = &message.click_url {
+            body["click"] = json!(url);
+        }
+
+        if let Some(image) = &message.image_url {
+            body["icon"] = json!(image);
+        }
+
+        if let Some(attachment) = message.attachments.first() {
+            body["attach"] = json!(attachment.url);
+            body["filename"] = json!(attachment.filename);
+        }
+
+        body
+    }
+}
+
+/// Maps a message severity to an ntfy priority (1 = min, 5 = max)
+fn severity_priority(severity: NotificationSeverity) -> u8 {
+    match severity {
+        NotificationSeverity::Low => 2,
+        NotificationSeverity::Normal => 3,
+        NotificationSeverity::High => 4,
+        NotificationSeverity::Critical => 5,
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for NtfyProvider {
+    /// Sends a notification via ntfy
+    ///
+    /// # Arguments
+    /// * `message` - The notification message to send
+    ///
+    /// # Returns
+    /// NotificationResult with success status, HTTP status code, response body, and duration
+    async fn send(&self, message: &NotificationMessage) -> AppResult<NotificationResult> {
+        let start = Instant::now();
+
+        let request_body = self.build_request_body(message);
+
+        let mut builder = HTTP_CLIENT.post(self.base_url()).json(&request_body);
+        if let Some(token) = &self.config.token {
+            builder = builder.bearer_auth(token);
+        } else if let Some(username) = &self.config.username {
+            builder = builder.basic_auth(username, self.config.password.as_ref());
+        }
+
+        let request = match builder.build() {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(NotificationResult {
+                    success: false,
+                    status_code: None,
+                    response: Some(e.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    request: None,
+                });
+            }
+        };
+        let request_info = RequestInfo::from_request(&request, &[]);
+
+        let response = HTTP_CLIENT.execute(request).await;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        match response {
+            Ok(resp) => {
+                let status_code = resp.status().as_u16();
+                let success = resp.status().is_success();
+                let response_text = resp.text().await.ok();
+
+                Ok(NotificationResult {
+                    success,
+                    status_code: Some(status_code),
+                    response: response_text,
+                    duration_ms,
+                    request: Some(request_info),
+                })
+            }
+            Err(e) => Ok(NotificationResult {
+                success: false,
+                status_code: None,
+                response: Some(e.to_string()),
+                duration_ms,
+                request: Some(request_info),
+            }),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ntfy"
+    }
+
+    /// Validates ntfy configuration
+    ///
+    /// Checks that:
+    /// - server_url is a valid URL
+    /// - topic is a valid ntfy topic name
+    /// - priority is within 1-5
+    /// - a password is only given together with a username
+    /// - the server answers its health endpoint
+    ///
+    /// # Returns
+    /// Ok(()) if valid, Err with validation details otherwise
+    async fn validate_config(&self) -> AppResult<()> {
+        self.validate_server_url()?;
+
+        let topic = &self.config.topic;
+        if topic.is_empty()
+            || topic.len() > 64
+            || !topic
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(AppError::Validation {
+                field: "topic".to_string(),
+                reason: "Topic must be 1-64 letters, digits, '-' or '_'".to_string(),
+            });
+        }
+
+        if self.config.priority.is_some_and(|p| !(1..=5).contains(&p)) {
+            return Err(AppError::Validation {
+                field: "priority".to_string(),
+                reason: "Priority must be between 1 and 5".to_string(),
+            });
+        }
+
+        if self.config.password.is_some() && self.config.username.is_none() {
+            return Err(AppError::Validation {
+                field: "username".to_string(),
+                reason: "A password requires a username".to_string(),
+            });
+        }
+
+        check_server_health(&format!("{}/v1/health", self.base_url())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::notifications::Attachment;
+
+    fn config() -> NtfyConfig {
+        NtfyConfig {
+            server_url: "https://ntfy.example.com/".to_string(),
+            topic: "alerts".to_string(),
+            token: None,
+            username: None,
+            password: None,
+            priority: None,
+            tags: vec!["fusion".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_build_request_body_minimal() {
+        let provider = NtfyProvider::new(config());
+        let message = NotificationMessage {
+            body: "Backup finished".to_string(),
+            ..Default::default()
+        };
+
+        let body = provider.build_request_body(&message);
+        assert_eq!(body["topic"], "alerts");
+        assert_eq!(body["message"], "Backup finished");
+        assert_eq!(body["priority"], 3);
+        assert_eq!(body["tags"], json!(["fusion"]));
+        assert!(body.get("title").is_none());
+        assert!(body.get("markdown").is_none());
+    }
+
+    #[test]
+    fn test_build_request_body_full() {
+        let provider = NtfyProvider::new(config());
+        let message = NotificationMessage {
+            title: Some("Alice is live".to_string()),
+            body: "**Playing** chess".to_string(),
+            severity: NotificationSeverity::High,
+            tags: vec!["live".to_string(), "fusion".to_string()],
+            format: MessageFormat::Markdown,
+            image_url: Some("https://example.com/cover.jpg".to_string()),
+            click_url: Some("https://live.bilibili.com/1".to_string()),
+            attachments: vec![Attachment {
+                filename: "report.pdf".to_string(),
+                url: "https://example.com/report.pdf".to_string(),
+                content_type: None,
+            }],
+            ..Default::default()
+        };
+
+        let body = provider.build_request_body(&message);
+        assert_eq!(body["title"], "Alice is live");
+        assert_eq!(body["priority"], 4);
+        assert_eq!(body["tags"], json!(["fusion", "live"]));
+        assert_eq!(body["markdown"], true);
+        assert_eq!(body["click"], "https://live.bilibili.com/1");
+        assert_eq!(body["icon"], "https://example.com/cover.jpg");
+        assert_eq!(body["attach"], "https://example.com/report.pdf");
+        assert_eq!(body["filename"], "report.pdf");
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_rejects_bad_topic() {
+        let mut config = config();
+        config.topic = "no spaces/allowed".to_string();
+
+        let result = NtfyProvider::new(config).validate_config().await;
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "topic"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_rejects_bad_priority() {
+        let mut config = config();
+        config.priority = Some(0);
+
+        let result = NtfyProvider::new(config).validate_config().await;
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "priority"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_unreachable_server() {
+        let mut config = config();
+        config.server_url = "http://127.0.0.1:9".to_string();
+
+        let result = NtfyProvider::new(config).validate_config().await;
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "server_url"));
+    }
+
+    /// Run against the `ntfy` service from docker-compose.
+    #[tokio::test]
+    #[ignore = "requires a local ntfy server"]
+    async fn test_send_local_server() {
+        let mut config = config();
+        config.server_url =
+            std::env::var("NTFY_URL").unwrap_or_else(|_| "http://localhost:8082".to_string());
+        let provider = NtfyProvider::new(config);
+        provider.validate_config().await.unwrap();
+
+        let message = NotificationMessage {
+            title: Some("fusion-rs".to_string()),
+            body: "ntfy provider test".to_string(),
+            ..Default::default()
+        };
+        let result = provider.send(&message).await.unwrap();
+        assert!(result.success, "{:?}", result.response);
+    }
+}
@@ -0,0 +1,161 @@
+//! Archive helpers for notification log retention.
+//!
+//! Purged logs can be kept as gzip-compressed JSON Lines, one object per
+//! log. Each purge batch is appended as its own gzip member, which `zcat`
+//! and `gzip -d` read back as one continuous file.
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde_json::{Value as JsonValue, json};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use crate::error::{AppError, AppResult};
+use crate::models::NotificationLog;
+
+/// Builds the archive file path for a purge run
+///
+/// # Arguments
+/// * `dir` - The archive directory
+/// * `now` - Start time of the run
+///
+/// # Returns
+/// `<dir>/notification_logs-<UTC timestamp>.jsonl.gz`
+pub fn archive_path(dir: &Path, now: jiff::Timestamp) -> PathBuf {
+    dir.join(format!(
+        "notification_logs-{}.jsonl.gz",
+        now.strftime("%Y%m%dT%H%M%SZ")
+    ))
+}
+
+/// Converts a log to its archived JSON form
+///
+/// The message is kept as the stored JSON string so archives match the
+/// database exactly.
+pub fn archive_record(log: &NotificationLog) -> JsonValue {
+    json!({
+        "id": log.id,
+        "channel_id": log.channel_id,
+        "message": log.message,
+        "status": log.status,
+        "error_message": log.error_message,
+        "retry_count": log.retry_count,
+        "sent_at": log.sent_at.to_jiff().to_string(),
+        "dedup_key": log.dedup_key,
+        "request": log.request_meta,
+        "response_status": log.response_status,
+        "response_body": log.response_body,
+        "duration_ms": log.duration_ms,
+    })
+}
+
+/// Encodes logs as one gzip member of JSON Lines
+///
+/// # Arguments
+/// * `logs` - The logs to encode
+///
+/// # Returns
+/// The compressed bytes
+pub fn encode_archive(logs: &[NotificationLog]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    for log in logs {
+        serde_json::to_writer(&mut encoder, &archive_record(log))?;
+        encoder.write_all(b"\n")?;
+    }
+    encoder.finish()
+}
+
+/// Appends logs to an archive file, creating it if needed
+///
+/// # Arguments
+/// * `path` - The archive file
+/// * `logs` - The logs to append
+pub async fn append_archive(path: &Path, logs: &[NotificationLog]) -> AppResult<()> {
+    let archive_error = |e: std::io::Error| AppError::Internal {
+        source: anyhow::Error::from(e).context(format!(
+            "Failed to write notification log archive {}",
+            path.display()
+        )),
+    };
+
+    let bytes = encode_archive(logs).map_err(archive_error)?;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(archive_error)?;
+    file.write_all(&bytes).await.map_err(archive_error)?;
+    file.sync_all().await.map_err(archive_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotificationStatus;
+    use flate2::read::MultiGzDecoder;
+    use jiff_diesel::DateTime;
+    use std::io::Read;
+
+    fn log(id: i64) -> NotificationLog {
+        NotificationLog {
+            id,
+            channel_id: 7,
+            message: r#"{"body":"hi"}"#.to_string(),
+            status: NotificationStatus::Sent,
+            error_message: None,
+            retry_count: 0,
+            sent_at: DateTime::from(jiff::civil::date(2026, 1, 2).at(3, 4, 5, 0)),
+            dedup_key: None,
+            request_meta: None,
+            response_status: Some(200),
+            response_body: None,
+            duration_ms: Some(12),
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Vec<JsonValue> {
+        let mut text = String::new();
+        MultiGzDecoder::new(bytes)
+            .read_to_string(&mut text)
+            .unwrap();
+        text.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_archive_path() {
+        let now: jiff::Timestamp = "2026-01-02T03:04:05Z".parse().unwrap();
+        assert_eq!(
+            archive_path(Path::new("/var/archive"), now),
+            PathBuf::from("/var/archive/notification_logs-20260102T030405Z.jsonl.gz")
+        );
+    }
+
+    #[test]
+    fn test_encode_archive_writes_one_line_per_log() {
+        let records = decode(&encode_archive(&[log(1), log(2)]).unwrap());
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["id"], 1);
+        assert_eq!(records[0]["status"], "sent");
+        assert_eq!(records[0]["message"], r#"{"body":"hi"}"#);
+        assert_eq!(records[0]["sent_at"], "2026-01-02T03:04:05");
+        assert_eq!(records[1]["id"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_append_archive_concatenates_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs.jsonl.gz");
+
+        append_archive(&path, &[log(1)]).await.unwrap();
+        append_archive(&path, &[log(2), log(3)]).await.unwrap();
+
+        let records = decode(&std::fs::read(&path).unwrap());
+        let ids: Vec<i64> = records.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+}
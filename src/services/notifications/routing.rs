@@ -0,0 +1,316 @@
+//! Routing rule matching and quiet-hours evaluation.
+//!
+//! Routing decides which of a user's channels receive a dispatched message.
+//! Rules are evaluated in priority order and the first matching rule with at
+//! least one enabled channel wins. Quiet hours hold back or drop messages
+//! below a severity threshold while the user's local time is inside the
+//! quiet window.
+
+use super::provider::NotificationMessage;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    NotificationLog, NotificationQuietHours, NotificationRoutingRule, NotificationSeverity,
+    QuietHoursAction,
+};
+use crate::utils::timezone::parse_timezone;
+use jiff::Timestamp;
+use jiff::civil::Time;
+use jiff::tz::TimeZone;
+use serde_json::Value as JsonValue;
+
+/// Outcome of dispatching a message through routing and quiet hours
+#[derive(Debug, Clone)]
+pub enum DispatchOutcome {
+    /// The message was sent; `rule_id` is None when no rule matched
+    Delivered {
+        rule_id: Option<i32>,
+        logs: Vec<NotificationLog>,
+    },
+    /// The message was held back until the quiet window ends
+    Deferred { deliver_at: Timestamp },
+    /// The message was discarded during quiet hours
+    Dropped,
+}
+
+/// Checks whether a routing rule applies to a message
+///
+/// All configured conditions must hold: the message severity must reach
+/// `min_severity`, it must carry at least one of `match_tags`, and every
+/// `match_metadata` entry must equal the message metadata value.
+///
+/// # Arguments
+/// * `rule` - The routing rule
+/// * `message` - The message being dispatched
+///
+/// # Returns
+/// true if the rule is enabled and all its conditions match
+pub fn rule_matches(rule: &NotificationRoutingRule, message: &NotificationMessage) -> bool {
+    if !rule.enabled {
+        return false;
+    }
+
+    if rule.min_severity.is_some_and(|min| message.severity < min) {
+        return false;
+    }
+
+    if !rule.match_tags.is_empty() && !rule.match_tags.iter().any(|t| message.tags.contains(t)) {
+        return false;
+    }
+
+    match &rule.match_metadata {
+        JsonValue::Object(expected) => expected.iter().all(|(key, value)| {
+            message.metadata.get(key).is_some_and(|actual| match value {
+                JsonValue::String(s) => actual == s,
+                other => {
+                    let expected = other.to_string();
+                    *actual == expected
+                }
+            })
+        }),
+        _ => true,
+    }
+}
+
+/// A user's quiet window resolved against its timezone
+#[derive(Debug, Clone)]
+pub struct QuietWindow {
+    start: Time,
+    end: Time,
+    tz: TimeZone,
+    bypass_severity: NotificationSeverity,
+    action: QuietHoursAction,
+}
+
+impl QuietWindow {
+    /// Builds a quiet window from stored quiet hours
+    ///
+    /// # Arguments
+    /// * `quiet_hours` - The user's quiet hours configuration
+    ///
+    /// # Returns
+    /// The resolved window, or a validation error for an unknown timezone
+    pub fn from_model(quiet_hours: &NotificationQuietHours) -> AppResult<Self> {
+        Ok(Self {
+            start: quiet_hours.start_time.to_jiff(),
+            end: quiet_hours.end_time.to_jiff(),
+            tz: parse_timezone(&quiet_hours.timezone)?,
+            bypass_severity: quiet_hours.bypass_severity,
+            action: quiet_hours.action,
+        })
+    }
+
+    /// Returns the configured action for held-back messages
+    pub fn action(&self) -> QuietHoursAction {
+        self.action
+    }
+
+    /// Checks whether a message should be held back at the given instant
+    ///
+    /// # Arguments
+    /// * `severity` - The message severity
+    /// * `at` - The instant to evaluate
+    ///
+    /// # Returns
+    /// true if `at` falls inside the window and the severity is below the
+    /// bypass threshold
+    pub fn holds(&self, severity: NotificationSeverity, at: Timestamp) -> bool {
+        severity < self.bypass_severity && self.contains(at)
+    }
+
+    /// Checks whether an instant falls inside the quiet window
+    ///
+    /// Windows whose start is after their end wrap past midnight.
+    pub fn contains(&self, at: Timestamp) -> bool {
+        let local = at.to_zoned(self.tz.clone()).time();
+        if self.start < self.end {
+            self.start <= local && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        }
+    }
+
+    /// Returns the next end of the quiet window after an instant
+    ///
+    /// # Arguments
+    /// * `at` - The instant to start from
+    ///
+    /// # Returns
+    /// The instant at which the window ends
+    pub fn end_after(&self, at: Timestamp) -> AppResult<Timestamp> {
+        let zoned = at.to_zoned(self.tz.clone());
+        let mut date = zoned.date();
+        if zoned.time() >= self.end {
+            date = date.tomorrow().map_err(|e| AppError::Internal {
+                source: anyhow::Error::from(e),
+            })?;
+        }
+
+        date.to_datetime(self.end)
+            .to_zoned(self.tz.clone())
+            .map(|end| end.timestamp())
+            .map_err(|e| AppError::Internal {
+                source: anyhow::Error::from(e),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RoutingMode;
+    use jiff::civil::time;
+    use jiff_diesel::DateTime;
+    use serde_json::json;
+
+    fn rule() -> NotificationRoutingRule {
+        let epoch = DateTime::from(jiff::civil::DateTime::constant(1970, 1, 1, 0, 0, 0, 0));
+        NotificationRoutingRule {
+            id: 1,
+            user_id: 1,
+            name: "alerts".to_string(),
+            priority: 0,
+            enabled: true,
+            match_tags: vec![],
+            match_metadata: json!({}),
+            min_severity: None,
+            channel_ids: vec![1],
+            mode: RoutingMode::All,
+            created_at: epoch,
+            updated_at: epoch,
+        }
+    }
+
+    fn message(severity: NotificationSeverity, tags: &[&str]) -> NotificationMessage {
+        NotificationMessage {
+            body: "body".to_string(),
+            severity,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn window(start: Time, end: Time) -> QuietWindow {
+        QuietWindow {
+            start,
+            end,
+            tz: TimeZone::get("Asia/Shanghai").unwrap(),
+            bypass_severity: NotificationSeverity::High,
+            action: QuietHoursAction::Defer,
+        }
+    }
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_rule_matches_empty_conditions() {
+        assert!(rule_matches(
+            &rule(),
+            &message(NotificationSeverity::Low, &[])
+        ));
+    }
+
+    #[test]
+    fn test_rule_disabled_never_matches() {
+        let mut r = rule();
+        r.enabled = false;
+        assert!(!rule_matches(
+            &r,
+            &message(NotificationSeverity::Critical, &[])
+        ));
+    }
+
+    #[test]
+    fn test_rule_matches_min_severity() {
+        let mut r = rule();
+        r.min_severity = Some(NotificationSeverity::High);
+        assert!(!rule_matches(
+            &r,
+            &message(NotificationSeverity::Normal, &[])
+        ));
+        assert!(rule_matches(&r, &message(NotificationSeverity::High, &[])));
+        assert!(rule_matches(
+            &r,
+            &message(NotificationSeverity::Critical, &[])
+        ));
+    }
+
+    #[test]
+    fn test_rule_matches_any_tag() {
+        let mut r = rule();
+        r.match_tags = vec!["live".to_string(), "jobs".to_string()];
+        assert!(rule_matches(
+            &r,
+            &message(NotificationSeverity::Normal, &["jobs"])
+        ));
+        assert!(!rule_matches(
+            &r,
+            &message(NotificationSeverity::Normal, &["other"])
+        ));
+        assert!(!rule_matches(
+            &r,
+            &message(NotificationSeverity::Normal, &[])
+        ));
+    }
+
+    #[test]
+    fn test_rule_matches_metadata() {
+        let mut r = rule();
+        r.match_metadata = json!({"platform": "bilibili", "room": 42});
+
+        let mut m = message(NotificationSeverity::Normal, &[]);
+        m.metadata
+            .insert("platform".to_string(), "bilibili".to_string());
+        assert!(!rule_matches(&r, &m));
+
+        m.metadata.insert("room".to_string(), "42".to_string());
+        assert!(rule_matches(&r, &m));
+
+        m.metadata
+            .insert("platform".to_string(), "douyu".to_string());
+        assert!(!rule_matches(&r, &m));
+    }
+
+    #[test]
+    fn test_quiet_window_same_day() {
+        let w = window(time(12, 0, 0, 0), time(14, 0, 0, 0));
+        // Asia/Shanghai is UTC+8
+        assert!(w.contains(ts("2026-01-15T05:00:00Z")));
+        assert!(!w.contains(ts("2026-01-15T06:00:00Z")));
+        assert!(!w.contains(ts("2026-01-15T03:59:59Z")));
+    }
+
+    #[test]
+    fn test_quiet_window_wraps_midnight() {
+        let w = window(time(22, 0, 0, 0), time(7, 0, 0, 0));
+        assert!(w.contains(ts("2026-01-15T15:00:00Z"))); // 23:00 local
+        assert!(w.contains(ts("2026-01-15T19:00:00Z"))); // 03:00 local
+        assert!(!w.contains(ts("2026-01-15T23:00:00Z"))); // 07:00 local
+        assert!(!w.contains(ts("2026-01-15T04:00:00Z"))); // 12:00 local
+    }
+
+    #[test]
+    fn test_quiet_window_holds_only_low_severity() {
+        let w = window(time(22, 0, 0, 0), time(7, 0, 0, 0));
+        let night = ts("2026-01-15T15:00:00Z");
+        assert!(w.holds(NotificationSeverity::Normal, night));
+        assert!(!w.holds(NotificationSeverity::High, night));
+        assert!(!w.holds(NotificationSeverity::Low, ts("2026-01-15T04:00:00Z")));
+    }
+
+    #[test]
+    fn test_quiet_window_end_after() {
+        let w = window(time(22, 0, 0, 0), time(7, 0, 0, 0));
+        // 23:00 local on Jan 15 -> 07:00 local on Jan 16
+        assert_eq!(
+            w.end_after(ts("2026-01-15T15:00:00Z")).unwrap(),
+            ts("2026-01-15T23:00:00Z")
+        );
+        // 03:00 local on Jan 16 -> 07:00 local the same day
+        assert_eq!(
+            w.end_after(ts("2026-01-15T19:00:00Z")).unwrap(),
+            ts("2026-01-15T23:00:00Z")
+        );
+    }
+}
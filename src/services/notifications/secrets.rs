@@ -0,0 +1,256 @@
+//! Encryption and masking of secret channel config fields.
+//!
+//! Secret fields are declared per channel type by
+//! [`ChannelType::secret_fields`]. They are encrypted before a config is
+//! stored and decrypted just before a provider is built, so plaintext
+//! secrets never reach the database when a master key is configured.
+
+use crate::error::{AppError, AppResult};
+use crate::models::{ChannelType, MASKED_SECRET};
+use crate::utils::secret::{SecretCipher, is_encrypted};
+use serde_json::Value as JsonValue;
+
+/// Encrypts the secret fields of a config
+///
+/// Values that are already encrypted are kept as-is. Without a cipher the
+/// config is returned unchanged.
+///
+/// # Arguments
+/// * `channel_type` - The channel type, which determines the secret fields
+/// * `config` - Channel config JSON
+/// * `cipher` - The configured secret cipher, if any
+pub fn encrypt_secrets(
+    channel_type: ChannelType,
+    config: &JsonValue,
+    cipher: Option<&SecretCipher>,
+) -> AppResult<JsonValue> {
+    let Some(cipher) = cipher else {
+        return Ok(config.clone());
+    };
+
+    map_secrets(channel_type, config, |value| {
+        if is_encrypted(value) {
+            Ok(value.to_string())
+        } else {
+            cipher.encrypt(value)
+        }
+    })
+}
+
+/// Decrypts the secret fields of a stored config
+///
+/// # Arguments
+/// * `channel_type` - The channel type, which determines the secret fields
+/// * `config` - Stored channel config JSON
+/// * `cipher` - The configured secret cipher, if any
+///
+/// # Returns
+/// The plaintext config, or an error if an encrypted value cannot be decrypted
+pub fn decrypt_secrets(
+    channel_type: ChannelType,
+    config: &JsonValue,
+    cipher: Option<&SecretCipher>,
+) -> AppResult<JsonValue> {
+    map_secrets(channel_type, config, |value| {
+        if !is_encrypted(value) {
+            return Ok(value.to_string());
+        }
+        match cipher {
+            Some(cipher) => cipher.decrypt(value),
+            None => Err(AppError::Configuration {
+                key: "encryption.key".to_string(),
+                source: anyhow::anyhow!("Channel secrets are encrypted but no key is configured"),
+            }),
+        }
+    })
+}
+
+/// Replaces masked secrets in an updated config with the stored values
+///
+/// Lets clients send back a config read from the API without re-entering
+/// every secret.
+///
+/// # Arguments
+/// * `channel_type` - The channel type, which determines the secret fields
+/// * `config` - The config submitted by the client
+/// * `stored` - The config currently stored for the channel
+pub fn restore_masked(
+    channel_type: ChannelType,
+    config: &JsonValue,
+    stored: &JsonValue,
+) -> JsonValue {
+    let mut restored = config.clone();
+    for pointer in channel_type.secret_pointers(config) {
+        if restored.pointer(&pointer).and_then(JsonValue::as_str) != Some(MASKED_SECRET) {
+            continue;
+        }
+        if let (Some(target), Some(original)) =
+            (restored.pointer_mut(&pointer), stored.pointer(&pointer))
+        {
+            *target = original.clone();
+        }
+    }
+    restored
+}
+
+/// Re-encrypts a config's secrets under the active master key
+///
+/// # Arguments
+/// * `channel_type` - The channel type, which determines the secret fields
+/// * `config` - Stored channel config JSON
+/// * `cipher` - The configured secret cipher
+///
+/// # Returns
+/// Some(config) if any secret was plaintext or under a retired key, None otherwise
+pub fn rotate_secrets(
+    channel_type: ChannelType,
+    config: &JsonValue,
+    cipher: &SecretCipher,
+) -> AppResult<Option<JsonValue>> {
+    let stale = channel_type
+        .secret_pointers(config)
+        .iter()
+        .filter_map(|pointer| config.pointer(pointer).and_then(JsonValue::as_str))
+        .any(|value| cipher.needs_rotation(value));
+
+    if !stale {
+        return Ok(None);
+    }
+
+    map_secrets(channel_type, config, |value| cipher.rotate(value)).map(Some)
+}
+
+/// Applies a transformation to every secret string value of a config
+fn map_secrets(
+    channel_type: ChannelType,
+    config: &JsonValue,
+    f: impl Fn(&str) -> AppResult<String>,
+) -> AppResult<JsonValue> {
+    let mut mapped = config.clone();
+    for pointer in channel_type.secret_pointers(config) {
+        if let Some(value) = mapped.pointer_mut(&pointer)
+            && let Some(secret) = value.as_str()
+        {
+            *value = JsonValue::String(f(secret)?);
+        }
+    }
+    Ok(mapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::EncryptionConfig;
+    use serde_json::json;
+
+    fn cipher() -> SecretCipher {
+        let config = EncryptionConfig {
+            key: "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=".to_string(),
+            ..Default::default()
+        };
+        SecretCipher::from_config(&config).unwrap().unwrap()
+    }
+
+    fn webhook_config() -> JsonValue {
+        json!({
+            "url": "https://example.com/hook",
+            "headers": {"Authorization": "Bearer abc", "X-Source": "fusion"},
+            "signing": {"secret": "s3cret"},
+            "auth": {"type": "bearer", "token": "tok"},
+            "timeout_seconds": 30
+        })
+    }
+
+    #[test]
+    fn test_secret_pointers() {
+        let mut pointers = ChannelType::Webhook.secret_pointers(&webhook_config());
+        pointers.sort();
+        assert_eq!(
+            pointers,
+            vec![
+                "/auth/token",
+                "/headers/Authorization",
+                "/headers/X-Source",
+                "/signing/secret"
+            ]
+        );
+
+        assert_eq!(
+            ChannelType::Bark.secret_pointers(&json!({"device_key": "k", "sound": "x"})),
+            vec!["/device_key"]
+        );
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_secrets() {
+        let c = cipher();
+        let config = webhook_config();
+
+        let encrypted = encrypt_secrets(ChannelType::Webhook, &config, Some(&c)).unwrap();
+        assert_eq!(encrypted["url"], config["url"]);
+        assert_eq!(encrypted["timeout_seconds"], 30);
+        assert!(is_encrypted(
+            encrypted["signing"]["secret"].as_str().unwrap()
+        ));
+        assert!(is_encrypted(
+            encrypted["headers"]["Authorization"].as_str().unwrap()
+        ));
+
+        // Encrypting twice does not double-encrypt
+        let again = encrypt_secrets(ChannelType::Webhook, &encrypted, Some(&c)).unwrap();
+        assert_eq!(again, encrypted);
+
+        let decrypted = decrypt_secrets(ChannelType::Webhook, &encrypted, Some(&c)).unwrap();
+        assert_eq!(decrypted, config);
+    }
+
+    #[test]
+    fn test_without_cipher() {
+        let config = webhook_config();
+        assert_eq!(
+            encrypt_secrets(ChannelType::Webhook, &config, None).unwrap(),
+            config
+        );
+        assert_eq!(
+            decrypt_secrets(ChannelType::Webhook, &config, None).unwrap(),
+            config
+        );
+
+        let encrypted = encrypt_secrets(ChannelType::Webhook, &config, Some(&cipher())).unwrap();
+        assert!(decrypt_secrets(ChannelType::Webhook, &encrypted, None).is_err());
+    }
+
+    #[test]
+    fn test_restore_masked() {
+        let stored = json!({"device_key": "enc:v1:primary:a:b", "sound": "old.wav"});
+        let submitted = json!({"device_key": MASKED_SECRET, "sound": "new.wav"});
+
+        let restored = restore_masked(ChannelType::Bark, &submitted, &stored);
+        assert_eq!(
+            restored,
+            json!({"device_key": "enc:v1:primary:a:b", "sound": "new.wav"})
+        );
+
+        let replaced = json!({"device_key": "new-key"});
+        assert_eq!(
+            restore_masked(ChannelType::Bark, &replaced, &stored),
+            replaced
+        );
+    }
+
+    #[test]
+    fn test_rotate_secrets() {
+        let c = cipher();
+        let plain = json!({"device_key": "k"});
+
+        let rotated = rotate_secrets(ChannelType::Bark, &plain, &c)
+            .unwrap()
+            .unwrap();
+        assert!(is_encrypted(rotated["device_key"].as_str().unwrap()));
+        assert!(
+            rotate_secrets(ChannelType::Bark, &rotated, &c)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! Template rendering for notification messages.
+//!
+//! Templates use mustache-style `{{ variable }}` placeholders. Variables are
+//! looked up in a JSON object and may use dotted paths (`{{ room.title }}`)
+//! or array indexes (`{{ tags.0 }}`). Missing variables render as an empty
+//! string so a template never blocks delivery; the preview endpoint reports
+//! them instead.
+
+use super::provider::{MessageVariant, NotificationMessage};
+use crate::error::{AppError, AppResult};
+use crate::models::{MessageFormat, NotificationTemplate, TemplateVariants};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeSet, HashMap};
+
+/// A piece of a parsed template
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Text copied verbatim
+    Literal(String),
+    /// Placeholder replaced with a variable value
+    Variable(String),
+}
+
+/// A parsed template string
+///
+/// # Example
+/// ```ignore
+/// let template = Template::parse("{{ anchor_name }} is live")?;
+/// let text = template.render(&json!({"anchor_name": "Alice"}));
+/// assert_eq!(text, "Alice is live");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parses a template string
+    ///
+    /// # Arguments
+    /// * `source` - The template text
+    ///
+    /// # Returns
+    /// The parsed template, or a validation error for unclosed or
+    /// malformed placeholders
+    pub fn parse(source: &str) -> AppResult<Self> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+
+            let after_open = &rest[start + 2..];
+            let end = after_open.find("}}").ok_or_else(|| AppError::Validation {
+                field: "template".to_string(),
+                reason: format!(
+                    "Unclosed placeholder at position {}",
+                    source.len() - rest.len() + start
+                ),
+            })?;
+
+            let name = after_open[..end].trim();
+            if !is_valid_variable_name(name) {
+                return Err(AppError::Validation {
+                    field: "template".to_string(),
+                    reason: format!("Invalid placeholder '{{{{{}}}}}'", &after_open[..end]),
+                });
+            }
+
+            segments.push(Segment::Variable(name.to_string()));
+            rest = &after_open[end + 2..];
+        }
+
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Renders the template with the given variables
+    ///
+    /// # Arguments
+    /// * `vars` - JSON object holding variable values
+    ///
+    /// # Returns
+    /// The rendered text; unknown variables render as empty strings
+    pub fn render(&self, vars: &JsonValue) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Variable(name) => {
+                    lookup(vars, name).map(value_to_string).unwrap_or_default()
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the template, preserving the JSON type of a lone placeholder
+    ///
+    /// A template consisting of exactly one placeholder (e.g. `"{{ tags }}"`)
+    /// yields the variable's JSON value instead of its string form, so JSON
+    /// body templates can carry arrays, numbers and objects.
+    ///
+    /// # Arguments
+    /// * `vars` - JSON object holding variable values
+    pub fn render_value(&self, vars: &JsonValue) -> JsonValue {
+        if let [Segment::Variable(name)] = self.segments.as_slice()
+            && let Some(value) = lookup(vars, name)
+        {
+            return value.clone();
+        }
+
+        JsonValue::String(self.render(vars))
+    }
+
+    /// Returns the variable names referenced by the template
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+}
+
+/// A compiled message template with optional format variants
+///
+/// Rendering produces a `NotificationMessage` that carries every variant, so
+/// the right one can be chosen per channel at delivery time.
+#[derive(Debug, Clone)]
+pub struct MessageTemplate {
+    title: Option<Template>,
+    body: Template,
+    variants: HashMap<MessageFormat, (Option<Template>, Template)>,
+}
+
+impl MessageTemplate {
+    /// Compiles template parts, labelling errors with the offending field
+    ///
+    /// # Arguments
+    /// * `title` - Optional title template
+    /// * `body` - Body template
+    /// * `variants` - Format-specific variants
+    ///
+    /// # Returns
+    /// The compiled template or a validation error
+    pub fn compile(
+        title: Option<&str>,
+        body: &str,
+        variants: &TemplateVariants,
+    ) -> AppResult<Self> {
+        let mut compiled_variants = HashMap::new();
+        for (format, variant) in variants {
+            let field = format!("variants.{}", format_name(*format));
+            let variant_title = variant
+                .title
+                .as_deref()
+                .map(|t| compile_field(&format!("{}.title", field), t))
+                .transpose()?;
+            let variant_body = compile_field(&format!("{}.body", field), &variant.body)?;
+            compiled_variants.insert(*format, (variant_title, variant_body));
+        }
+
+        Ok(Self {
+            title: title
+                .map(|t| compile_field("title_template", t))
+                .transpose()?,
+            body: compile_field("body_template", body)?,
+            variants: compiled_variants,
+        })
+    }
+
+    /// Compiles a stored template
+    ///
+    /// # Arguments
+    /// * `template` - The template model loaded from the database
+    pub fn from_model(template: &NotificationTemplate) -> AppResult<Self> {
+        let variants = template
+            .parsed_variants()
+            .map_err(|e| AppError::Validation {
+                field: "variants".to_string(),
+                reason: format!("Invalid template variants: {}", e),
+            })?;
+
+        Self::compile(
+            template.title_template.as_deref(),
+            &template.body_template,
+            &variants,
+        )
+    }
+
+    /// Renders the template into a notification message
+    ///
+    /// # Arguments
+    /// * `vars` - JSON object holding variable values
+    pub fn render(&self, vars: &JsonValue) -> NotificationMessage {
+        let variants = self
+            .variants
+            .iter()
+            .map(|(format, (title, body))| {
+                let variant = MessageVariant {
+                    title: title.as_ref().map(|t| t.render(vars)),
+                    body: body.render(vars),
+                };
+                (*format, variant)
+            })
+            .collect();
+
+        NotificationMessage {
+            title: self.title.as_ref().map(|t| t.render(vars)),
+            body: self.body.render(vars),
+            variants,
+            ..Default::default()
+        }
+    }
+
+    /// Lists referenced variables that are missing from `vars`
+    ///
+    /// # Arguments
+    /// * `vars` - JSON object holding variable values
+    ///
+    /// # Returns
+    /// Sorted, de-duplicated variable names
+    pub fn missing_variables(&self, vars: &JsonValue) -> Vec<String> {
+        let templates = self.title.iter().chain(std::iter::once(&self.body)).chain(
+            self.variants
+                .values()
+                .flat_map(|(title, body)| title.iter().chain(std::iter::once(body))),
+        );
+
+        templates
+            .flat_map(|t| t.variables())
+            .filter(|name| lookup(vars, name).is_none())
+            .map(str::to_string)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Renders every string inside a JSON template
+///
+/// Object keys are kept as-is; string values are treated as templates and
+/// rendered with [`Template::render_value`].
+///
+/// # Arguments
+/// * `template` - JSON value whose strings are templates
+/// * `vars` - JSON object holding variable values
+///
+/// # Returns
+/// The rendered JSON value, or a validation error for malformed templates
+pub(super) fn render_json(template: &JsonValue, vars: &JsonValue) -> AppResult<JsonValue> {
+    Ok(match template {
+        JsonValue::String(source) => Template::parse(source)?.render_value(vars),
+        JsonValue::Array(items) => JsonValue::Array(
+            items
+                .iter()
+                .map(|item| render_json(item, vars))
+                .collect::<AppResult<_>>()?,
+        ),
+        JsonValue::Object(map) => JsonValue::Object(
+            map.iter()
+                .map(|(key, value)| Ok((key.clone(), render_json(value, vars)?)))
+                .collect::<AppResult<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+/// Parses a template, reporting errors against the given field name
+pub(super) fn compile_field(field: &str, source: &str) -> AppResult<Template> {
+    Template::parse(source).map_err(|e| match e {
+        AppError::Validation { reason, .. } => AppError::Validation {
+            field: field.to_string(),
+            reason,
+        },
+        other => other,
+    })
+}
+
+fn format_name(format: MessageFormat) -> &'static str {
+    match format {
+        MessageFormat::Text => "text",
+        MessageFormat::Markdown => "markdown",
+        MessageFormat::Embed => "embed",
+    }
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// Resolves a dotted variable path inside a JSON value
+///
+/// Null values are treated as missing.
+pub(super) fn lookup<'a>(vars: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    path.split('.')
+        .try_fold(vars, |value, key| match value {
+            JsonValue::Object(map) => map.get(key),
+            JsonValue::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+        .filter(|value| !value.is_null())
+}
+
+fn value_to_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TemplateVariant;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_simple_variables() {
+        let template = Template::parse("{{ name }} is live on {{platform}}").unwrap();
+        let text = template.render(&json!({"name": "Alice", "platform": "bilibili"}));
+        assert_eq!(text, "Alice is live on bilibili");
+    }
+
+    #[test]
+    fn test_render_dotted_paths_and_non_strings() {
+        let template =
+            Template::parse("{{ room.title }} ({{ room.online }}) #{{ tags.1 }}").unwrap();
+        let vars = json!({"room": {"title": "Chill", "online": 42}, "tags": ["a", "b"]});
+        assert_eq!(template.render(&vars), "Chill (42) #b");
+    }
+
+    #[test]
+    fn test_render_missing_variable_is_empty() {
+        let template = Template::parse("Hello {{ missing }}!").unwrap();
+        assert_eq!(template.render(&json!({})), "Hello !");
+    }
+
+    #[test]
+    fn test_parse_unclosed_placeholder() {
+        let result = Template::parse("Hello {{ name");
+        assert!(matches!(result, Err(AppError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_parse_invalid_placeholder() {
+        assert!(Template::parse("{{ }}").is_err());
+        assert!(Template::parse("{{ a..b }}").is_err());
+        assert!(Template::parse("{{ a-b }}").is_err());
+    }
+
+    #[test]
+    fn test_render_value_preserves_types() {
+        let vars = json!({"tags": ["a", "b"], "count": 3, "name": "Alice"});
+        assert_eq!(
+            Template::parse("{{ tags }}").unwrap().render_value(&vars),
+            json!(["a", "b"])
+        );
+        assert_eq!(
+            Template::parse("{{count}}").unwrap().render_value(&vars),
+            json!(3)
+        );
+        assert_eq!(
+            Template::parse("n={{ count }}")
+                .unwrap()
+                .render_value(&vars),
+            json!("n=3")
+        );
+        assert_eq!(
+            Template::parse("{{ missing }}")
+                .unwrap()
+                .render_value(&vars),
+            json!("")
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let template = json!({
+            "msgtype": "text",
+            "text": {"content": "{{ title }}: {{ body }}"},
+            "tags": "{{ tags }}",
+            "retries": 0
+        });
+        let vars = json!({"title": "Hi", "body": "there", "tags": ["x"]});
+
+        let rendered = render_json(&template, &vars).unwrap();
+        assert_eq!(
+            rendered,
+            json!({
+                "msgtype": "text",
+                "text": {"content": "Hi: there"},
+                "tags": ["x"],
+                "retries": 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_message_template_renders_variants() {
+        let mut variants = TemplateVariants::new();
+        variants.insert(
+            MessageFormat::Markdown,
+            TemplateVariant {
+                title: None,
+                body: "**{{ name }}**".to_string(),
+            },
+        );
+
+        let template =
+            MessageTemplate::compile(Some("{{ name }}"), "{{ name }}", &variants).unwrap();
+        let message = template.render(&json!({"name": "Alice"}));
+
+        assert_eq!(message.title.as_deref(), Some("Alice"));
+        assert_eq!(message.body, "Alice");
+        assert_eq!(message.variants[&MessageFormat::Markdown].body, "**Alice**");
+    }
+
+    #[test]
+    fn test_message_template_error_names_field() {
+        let mut variants = TemplateVariants::new();
+        variants.insert(
+            MessageFormat::Embed,
+            TemplateVariant {
+                title: None,
+                body: "{{ broken".to_string(),
+            },
+        );
+
+        let err = MessageTemplate::compile(None, "ok", &variants).unwrap_err();
+        match err {
+            AppError::Validation { field, .. } => assert_eq!(field, "variants.embed.body"),
+            other => panic!("Expected Validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_variables() {
+        let template = MessageTemplate::compile(
+            Some("{{ job_name }}"),
+            "{{ job_name }} failed: {{ error }} ({{ attempts }})",
+            &TemplateVariants::new(),
+        )
+        .unwrap();
+
+        let missing = template.missing_variables(&json!({"job_name": "cleanup", "error": null}));
+        assert_eq!(missing, vec!["attempts".to_string(), "error".to_string()]);
+    }
+}
@@ -1,16 +1,26 @@
 //! Webhook notification provider implementation.
 //!
 //! Sends HTTP requests to configured webhook URLs using the global HTTP_CLIENT.
+//! Request bodies and query parameters can be templated, requests can be
+//! signed with HMAC-SHA256 and authenticated with basic or bearer auth, and
+//! the success of a delivery is decided by a configurable predicate.
 
-use super::provider::{NotificationMessage, NotificationProvider, NotificationResult};
+use super::provider::{NotificationMessage, NotificationProvider, NotificationResult, RequestInfo};
+use super::template::{compile_field, lookup, render_json};
 use crate::error::{AppError, AppResult};
 use crate::external::client::HTTP_CLIENT;
-use crate::models::WebhookConfig;
+use crate::models::{WebhookAuth, WebhookBody, WebhookConfig};
 use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::header::{CONTENT_TYPE, HeaderName};
 use reqwest::{Method, Url};
-use serde_json::json;
+use serde_json::{Value as JsonValue, json};
+use sha2::Sha256;
+use std::collections::BTreeMap;
 use std::time::Instant;
 
+type HmacSha256 = Hmac<Sha256>;
+
 /// Webhook notification provider
 ///
 /// Sends HTTP requests to configured webhook URLs.
@@ -18,12 +28,10 @@ use std::time::Instant;
 ///
 /// # Example
 /// ```ignore
-/// let config = WebhookConfig {
-///     url: "https://example.com/webhook".to_string(),
-///     method: "POST".to_string(),
-///     headers: HashMap::new(),
-///     timeout_seconds: 30,
-/// };
+/// let config = WebhookConfig::from_json(&json!({
+///     "url": "https://example.com/webhook",
+///     "signing": {"secret": "s3cret"}
+/// }))?;
 /// let provider = WebhookProvider::new(config);
 /// let result = provider.send(&message).await?;
 /// ```
@@ -35,7 +43,8 @@ impl WebhookProvider {
     /// Creates a new webhook provider with configuration
     ///
     /// # Arguments
-    /// * `config` - Webhook configuration (URL, method, headers, timeout)
+    /// * `config` - Webhook configuration (URL, method, headers, timeout,
+    ///   body format, signing, auth, query and success rules)
     pub fn new(config: WebhookConfig) -> Self {
         Self { config }
     }
@@ -53,14 +62,235 @@ impl WebhookProvider {
                 reason: format!("Invalid HTTP method: {}", self.config.method),
             })
     }
+
+    /// Builds the request URL with templated query parameters appended
+    ///
+    /// # Arguments
+    /// * `vars` - Template variables for the message
+    fn build_url(&self, vars: &JsonValue) -> AppResult<Url> {
+        let mut url = Url::parse(&self.config.url).map_err(|_| AppError::Validation {
+            field: "url".to_string(),
+            reason: "Invalid URL format".to_string(),
+        })?;
+
+        if !self.config.query.is_empty() {
+            let query: BTreeMap<_, _> = self.config.query.iter().collect();
+            let mut pairs = url.query_pairs_mut();
+            for (key, value) in query {
+                let value = compile_field(&format!("query.{}", key), value)?.render(vars);
+                pairs.append_pair(key, &value);
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// Renders the request body and its content type
+    ///
+    /// # Arguments
+    /// * `message` - The notification message being sent
+    /// * `vars` - Template variables for the message
+    ///
+    /// # Returns
+    /// Tuple of (body, content type)
+    fn build_body(
+        &self,
+        message: &NotificationMessage,
+        vars: &JsonValue,
+    ) -> AppResult<(String, String)> {
+        let body = match &self.config.body {
+            WebhookBody::Json { template } => {
+                let payload = match template {
+                    Some(template) => render_json(template, vars)?,
+                    None => json!({
+                        "title": message.title,
+                        "body": message.body,
+                        "metadata": message.metadata,
+                        "severity": message.severity,
+                        "tags": message.tags,
+                        "image_url": message.image_url,
+                        "click_url": message.click_url,
+                        "attachments": message.attachments,
+                    }),
+                };
+                (payload.to_string(), "application/json".to_string())
+            }
+            WebhookBody::Form { fields } => {
+                let rendered = fields
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            key.as_str(),
+                            compile_field(&format!("body.fields.{}", key), value)?.render(vars),
+                        ))
+                    })
+                    .collect::<AppResult<BTreeMap<_, _>>>()?;
+                let encoded =
+                    serde_urlencoded::to_string(&rendered).map_err(|e| AppError::Internal {
+                        source: anyhow::Error::from(e),
+                    })?;
+                (encoded, "application/x-www-form-urlencoded".to_string())
+            }
+            WebhookBody::Raw {
+                template,
+                content_type,
+            } => (
+                compile_field("body.template", template)?.render(vars),
+                content_type
+                    .clone()
+                    .unwrap_or_else(|| "text/plain; charset=utf-8".to_string()),
+            ),
+        };
+
+        Ok(body)
+    }
+
+    /// Returns configured secrets that must never appear in request logs
+    fn secrets(&self) -> Vec<&str> {
+        let mut secrets = Vec::new();
+        if let Some(signing) = &self.config.signing {
+            secrets.push(signing.secret.as_str());
+        }
+        match &self.config.auth {
+            Some(WebhookAuth::Basic { password, .. }) => secrets.push(password.as_str()),
+            Some(WebhookAuth::Bearer { token }) => secrets.push(token.as_str()),
+            None => {}
+        }
+        secrets
+    }
+
+    /// Checks a response against the configured success predicate
+    ///
+    /// # Arguments
+    /// * `status` - HTTP status code
+    /// * `body` - Response body, if it could be read
+    ///
+    /// # Returns
+    /// true if the status is in range and the JSON condition (if any) holds
+    fn is_success(&self, status: u16, body: Option<&str>) -> bool {
+        let rule = &self.config.success;
+        if status < rule.status_min || status > rule.status_max {
+            return false;
+        }
+
+        let Some(path) = &rule.json_path else {
+            return true;
+        };
+
+        let Some(json) = body.and_then(|b| serde_json::from_str::<JsonValue>(b).ok()) else {
+            return false;
+        };
+
+        match (lookup(&json, path), &rule.json_equals) {
+            (Some(actual), Some(expected)) => actual == expected,
+            (None, Some(expected)) => expected.is_null(),
+            (Some(actual), None) => is_truthy(actual),
+            (None, None) => false,
+        }
+    }
+}
+
+/// Builds template variables from a message
+///
+/// Besides the message fields, `embeds` holds a Discord/Slack-style embed
+/// list carrying the image and click URL (empty when the message has
+/// neither), so chat webhooks can show media with `"embeds": "{{ embeds }}"`.
+///
+/// # Arguments
+/// * `message` - The notification message
+/// * `timestamp` - Unix timestamp of the request in seconds
+fn template_vars(message: &NotificationMessage, timestamp: i64) -> JsonValue {
+    json!({
+        "title": message.title,
+        "body": message.body,
+        "metadata": message.metadata,
+        "severity": message.severity,
+        "tags": message.tags,
+        "image_url": message.image_url,
+        "click_url": message.click_url,
+        "attachments": message.attachments,
+        "embeds": embeds(message),
+        "timestamp": timestamp,
+    })
+}
+
+/// Builds the embed list for a message's media
+///
+/// # Arguments
+/// * `message` - The notification message
+///
+/// # Returns
+/// A single embed with the title, link and image, or an empty list
+fn embeds(message: &NotificationMessage) -> JsonValue {
+    if message.image_url.is_none() && message.click_url.is_none() {
+        return json!([]);
+    }
+
+    let mut embed = json!({});
+    if let Some(title) = &message.title {
+        embed["title"] = json!(title);
+    }
+    if let Some(url) = &message.click_url {
+        embed["url"] = json!(url);
+    }
+    if let Some(image) = &message.image_url {
+        embed["image"] = json!({ "url": image });
+    }
+    json!([embed])
+}
+
+/// Computes the request signature header value
+///
+/// # Arguments
+/// * `secret` - Shared HMAC secret
+/// * `timestamp` - Unix timestamp sent alongside the signature
+/// * `body` - The exact request body
+///
+/// # Returns
+/// `sha256=` followed by the lowercase hex HMAC-SHA256 of `"{timestamp}.{body}"`
+fn sign(secret: &str, timestamp: i64, body: &str) -> AppResult<String> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).map_err(|e| AppError::Internal {
+            source: anyhow::anyhow!("Invalid signing key: {}", e),
+        })?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("sha256={}", hex))
+}
+
+/// Interprets a JSON value as a boolean condition
+fn is_truthy(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => false,
+        JsonValue::Bool(b) => *b,
+        JsonValue::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        JsonValue::String(s) => !s.is_empty(),
+        JsonValue::Array(items) => !items.is_empty(),
+        JsonValue::Object(map) => !map.is_empty(),
+    }
+}
+
+/// Validates a configured header name
+fn validate_header_name(field: &str, name: &str) -> AppResult<()> {
+    HeaderName::from_bytes(name.as_bytes())
+        .map(|_| ())
+        .map_err(|_| AppError::Validation {
+            field: field.to_string(),
+            reason: format!("Invalid header name: {}", name),
+        })
 }
 
 #[async_trait]
 impl NotificationProvider for WebhookProvider {
     /// Sends a notification via webhook
     ///
-    /// Constructs an HTTP request with the configured method, headers, and timeout,
-    /// then sends the notification message as JSON in the request body.
+    /// Renders the body and query parameters, applies authentication and
+    /// signing headers, sends the request with the configured timeout and
+    /// evaluates the success predicate against the response.
     ///
     /// # Arguments
     /// * `message` - The notification message to send
@@ -69,38 +299,79 @@ impl NotificationProvider for WebhookProvider {
     /// NotificationResult with success status, HTTP status code, response body, and duration
     async fn send(&self, message: &NotificationMessage) -> AppResult<NotificationResult> {
         let start = Instant::now();
+        let timestamp = jiff::Timestamp::now().as_second();
+        let vars = template_vars(message, timestamp);
 
         // Build request
         let method = self.parse_method()?;
+        let url = self.build_url(&vars)?;
+        let (body, content_type) = self.build_body(message, &vars)?;
+
         let mut request = HTTP_CLIENT
-            .request(method, &self.config.url)
-            .timeout(std::time::Duration::from_secs(self.config.timeout_seconds))
-            .json(&json!({
-                "title": message.title,
-                "body": message.body,
-                "metadata": message.metadata,
-            }));
+            .request(method, url)
+            .timeout(std::time::Duration::from_secs(self.config.timeout_seconds));
+
+        // A Content-Type in the custom headers overrides the body format's
+        if !self
+            .config
+            .headers
+            .keys()
+            .any(|key| key.eq_ignore_ascii_case(CONTENT_TYPE.as_str()))
+        {
+            request = request.header(CONTENT_TYPE, content_type);
+        }
 
         // Add custom headers
         for (key, value) in &self.config.headers {
             request = request.header(key, value);
         }
 
+        match &self.config.auth {
+            Some(WebhookAuth::Basic { username, password }) => {
+                request = request.basic_auth(username, Some(password));
+            }
+            Some(WebhookAuth::Bearer { token }) => {
+                request = request.bearer_auth(token);
+            }
+            None => {}
+        }
+
+        if let Some(signing) = &self.config.signing {
+            request = request
+                .header(&signing.timestamp_header, timestamp.to_string())
+                .header(&signing.header, sign(&signing.secret, timestamp, &body)?);
+        }
+
+        let request = match request.body(body).build() {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(NotificationResult {
+                    success: false,
+                    status_code: None,
+                    response: Some(e.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    request: None,
+                });
+            }
+        };
+        let request_info = RequestInfo::from_request(&request, &self.secrets());
+
         // Send request
-        let response = request.send().await;
+        let response = HTTP_CLIENT.execute(request).await;
         let duration_ms = start.elapsed().as_millis() as u64;
 
         match response {
             Ok(resp) => {
                 let status_code = resp.status().as_u16();
-                let success = resp.status().is_success();
                 let response_text = resp.text().await.ok();
+                let success = self.is_success(status_code, response_text.as_deref());
 
                 Ok(NotificationResult {
                     success,
                     status_code: Some(status_code),
                     response: response_text,
                     duration_ms,
+                    request: Some(request_info),
                 })
             }
             Err(e) => {
@@ -111,6 +382,7 @@ impl NotificationProvider for WebhookProvider {
                     status_code: None,
                     response: Some(e.to_string()),
                     duration_ms,
+                    request: Some(request_info),
                 })
             }
         }
@@ -123,8 +395,11 @@ impl NotificationProvider for WebhookProvider {
     /// Validates webhook configuration
     ///
     /// Checks that:
-    /// - URL starts with http:// or https://
+    /// - URL is a valid HTTPS URL
     /// - HTTP method is valid
+    /// - Body and query templates parse
+    /// - Signing secret is non-empty and header names are valid
+    /// - Success status range is a valid HTTP status range
     ///
     /// # Returns
     /// Ok(()) if valid, Err with validation details otherwise
@@ -144,6 +419,217 @@ impl NotificationProvider for WebhookProvider {
         // Validate method
         self.parse_method()?;
 
+        // Rendering with sample variables surfaces template syntax errors
+        let vars = template_vars(&NotificationMessage::default(), 0);
+        self.build_url(&vars)?;
+        self.build_body(&NotificationMessage::default(), &vars)?;
+
+        if let Some(signing) = &self.config.signing {
+            if signing.secret.is_empty() {
+                return Err(AppError::Validation {
+                    field: "signing.secret".to_string(),
+                    reason: "Signing secret must not be empty".to_string(),
+                });
+            }
+            validate_header_name("signing.header", &signing.header)?;
+            validate_header_name("signing.timestamp_header", &signing.timestamp_header)?;
+        }
+
+        let success = &self.config.success;
+        if !(100..=599).contains(&success.status_min)
+            || !(100..=599).contains(&success.status_max)
+            || success.status_min > success.status_max
+        {
+            return Err(AppError::Validation {
+                field: "success".to_string(),
+                reason: "Status range must be within 100-599 and status_min <= status_max"
+                    .to_string(),
+            });
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(config: JsonValue) -> WebhookProvider {
+        WebhookProvider::new(WebhookConfig::from_json(&config).unwrap())
+    }
+
+    fn message() -> NotificationMessage {
+        NotificationMessage {
+            title: Some("Alice is live".to_string()),
+            body: "Playing chess".to_string(),
+            tags: vec!["live".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_body_is_json_payload() {
+        let p = provider(json!({"url": "https://example.com/hook"}));
+        let m = message();
+        let (body, content_type) = p.build_body(&m, &template_vars(&m, 0)).unwrap();
+
+        assert_eq!(content_type, "application/json");
+        let json: JsonValue = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["title"], "Alice is live");
+        assert_eq!(json["body"], "Playing chess");
+    }
+
+    #[test]
+    fn test_json_body_template() {
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "body": {
+                "format": "json",
+                "template": {"text": "{{ title }}: {{ body }}", "labels": "{{ tags }}"}
+            }
+        }));
+        let m = message();
+        let (body, _) = p.build_body(&m, &template_vars(&m, 0)).unwrap();
+
+        let json: JsonValue = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            json,
+            json!({"text": "Alice is live: Playing chess", "labels": ["live"]})
+        );
+    }
+
+    #[test]
+    fn test_media_template_vars() {
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "body": {
+                "format": "json",
+                "template": {"content": "{{ title }}", "embeds": "{{ embeds }}", "link": "{{ click_url }}"}
+            }
+        }));
+        let mut m = message();
+        m.image_url = Some("https://example.com/cover.jpg".to_string());
+        m.click_url = Some("https://live.bilibili.com/1".to_string());
+
+        let (body, _) = p.build_body(&m, &template_vars(&m, 0)).unwrap();
+        let json: JsonValue = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["link"], "https://live.bilibili.com/1");
+        assert_eq!(json["embeds"][0]["title"], "Alice is live");
+        assert_eq!(json["embeds"][0]["url"], "https://live.bilibili.com/1");
+        assert_eq!(
+            json["embeds"][0]["image"]["url"],
+            "https://example.com/cover.jpg"
+        );
+
+        let plain = message();
+        let (body, _) = p.build_body(&plain, &template_vars(&plain, 0)).unwrap();
+        let json: JsonValue = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["embeds"], json!([]));
+    }
+
+    #[test]
+    fn test_form_body_template() {
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "body": {"format": "form", "fields": {"msg": "{{ body }}", "sev": "{{ severity }}"}}
+        }));
+        let m = message();
+        let (body, content_type) = p.build_body(&m, &template_vars(&m, 0)).unwrap();
+
+        assert_eq!(content_type, "application/x-www-form-urlencoded");
+        assert_eq!(body, "msg=Playing+chess&sev=normal");
+    }
+
+    #[test]
+    fn test_raw_body_template() {
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "body": {"format": "raw", "template": "<b>{{ title }}</b>", "content_type": "text/html"}
+        }));
+        let m = message();
+        let (body, content_type) = p.build_body(&m, &template_vars(&m, 0)).unwrap();
+
+        assert_eq!(body, "<b>Alice is live</b>");
+        assert_eq!(content_type, "text/html");
+    }
+
+    #[test]
+    fn test_build_url_appends_query() {
+        let p = provider(json!({
+            "url": "https://example.com/hook?x=1",
+            "query": {"title": "{{ title }}", "src": "fusion"}
+        }));
+        let m = message();
+        let url = p.build_url(&template_vars(&m, 0)).unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://example.com/hook?x=1&src=fusion&title=Alice+is+live"
+        );
+    }
+
+    #[test]
+    fn test_sign_matches_known_vector() {
+        // echo -n '1700000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", 1_700_000_000, r#"{"a":1}"#).unwrap(),
+            "sha256=49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+        );
+    }
+
+    #[test]
+    fn test_success_status_range() {
+        let p = provider(json!({"url": "https://example.com/hook"}));
+        assert!(p.is_success(204, None));
+        assert!(!p.is_success(302, None));
+
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "success": {"status_min": 200, "status_max": 399}
+        }));
+        assert!(p.is_success(302, None));
+    }
+
+    #[test]
+    fn test_success_json_path() {
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "success": {"json_path": "result.ok"}
+        }));
+        assert!(p.is_success(200, Some(r#"{"result": {"ok": true}}"#)));
+        assert!(!p.is_success(200, Some(r#"{"result": {"ok": false}}"#)));
+        assert!(!p.is_success(200, Some("not json")));
+
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "success": {"json_path": "errcode", "json_equals": 0}
+        }));
+        assert!(p.is_success(200, Some(r#"{"errcode": 0}"#)));
+        assert!(!p.is_success(200, Some(r#"{"errcode": 40001}"#)));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_rejects_bad_templates() {
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "body": {"format": "raw", "template": "{{ title"}
+        }));
+        assert!(matches!(
+            p.validate_config().await,
+            Err(AppError::Validation { .. })
+        ));
+
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "success": {"status_min": 300, "status_max": 200}
+        }));
+        assert!(p.validate_config().await.is_err());
+
+        let p = provider(json!({
+            "url": "https://example.com/hook",
+            "signing": {"secret": ""}
+        }));
+        assert!(p.validate_config().await.is_err());
+    }
+}
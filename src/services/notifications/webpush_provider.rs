@@ -0,0 +1,277 @@
+//! Web Push notification provider implementation.
+//!
+//! Delivers messages to a browser push subscription through its push
+//! service. Payloads are encrypted for the subscription and requests are
+//! signed with the server's VAPID key (see [`crate::utils::webpush`]).
+//! Uses the global `HTTP_CLIENT` for connection pooling.
+//!
+//! Web Push Reference: https://datatracker.ietf.org/doc/html/rfc8030
+
+use super::provider::{NotificationMessage, NotificationProvider, NotificationResult, RequestInfo};
+use crate::error::{AppError, AppResult};
+use crate::external::client::HTTP_CLIENT;
+use crate::models::{NotificationSeverity, WebPushSubscription};
+use crate::utils::webpush::{self, MAX_PAYLOAD_LEN};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde_json::json;
+use std::time::Instant;
+
+/// Web Push notification provider
+///
+/// # Example
+/// ```ignore
+/// let subscription = WebPushSubscription::from_json(&channel.config)?;
+/// let provider = WebPushProvider::new(subscription);
+/// let result = provider.send(&message).await?;
+/// ```
+#[derive(Clone)]
+pub struct WebPushProvider {
+    subscription: WebPushSubscription,
+}
+
+impl WebPushProvider {
+    /// Creates a new Web Push provider for a subscription
+    ///
+    /// # Arguments
+    /// * `subscription` - Browser push subscription (endpoint and keys)
+    pub fn new(subscription: WebPushSubscription) -> Self {
+        Self { subscription }
+    }
+
+    /// Builds the JSON payload handed to the dashboard's service worker
+    ///
+    /// The body is shortened if the payload would not fit in a single
+    /// encrypted record.
+    ///
+    /// # Arguments
+    /// * `message` - The notification message to send
+    ///
+    /// # Returns
+    /// Serialized payload bytes
+    fn build_payload(&self, message: &NotificationMessage) -> Vec<u8> {
+        let mut payload = json!({
+            "title": message.title,
+            "body": message.body,
+            "severity": message.severity,
+        });
+
+        if !message.tags.is_empty() {
+            payload["tags"] = json!(message.tags);
+        }
+        if let Some(url) = &message.click_url {
+            payload["url"] = json!(url);
+        }
+        if let Some(image) = &message.image_url {
+            payload["image"] = json!(image);
+        }
+
+        let mut bytes = payload.to_string().into_bytes();
+        if bytes.len() > MAX_PAYLOAD_LEN {
+            let excess = bytes.len() - MAX_PAYLOAD_LEN;
+            // Escaping can grow the body when re-serialized, so leave headroom
+            let mut keep = message.body.len().saturating_sub(excess + 16);
+            while !message.body.is_char_boundary(keep) {
+                keep -= 1;
+            }
+            payload["body"] = json!(format!("{}…", &message.body[..keep]));
+            bytes = payload.to_string().into_bytes();
+        }
+        bytes
+    }
+}
+
+/// Maps a message severity to a Web Push `Urgency` header value
+fn severity_urgency(severity: NotificationSeverity) -> &'static str {
+    match severity {
+        NotificationSeverity::Low => "low",
+        NotificationSeverity::Normal => "normal",
+        NotificationSeverity::High | NotificationSeverity::Critical => "high",
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for WebPushProvider {
+    /// Sends a notification to the browser subscription
+    ///
+    /// Push services answer 404 or 410 once a subscription has expired or
+    /// was revoked by the user; such failures are reported like any other
+    /// and count towards the channel's failure threshold.
+    ///
+    /// # Arguments
+    /// * `message` - The notification message to send
+    ///
+    /// # Returns
+    /// NotificationResult with success status, HTTP status code, response body, and duration
+    async fn send(&self, message: &NotificationMessage) -> AppResult<NotificationResult> {
+        let start = Instant::now();
+
+        let Some(vapid) = webpush::vapid() else {
+            return Ok(NotificationResult {
+                success: false,
+                status_code: None,
+                response: Some("Web Push is not configured on this server".to_string()),
+                duration_ms: start.elapsed().as_millis() as u64,
+                request: None,
+            });
+        };
+
+        let endpoint = &self.subscription.endpoint;
+        let body = webpush::encrypt(
+            &self.subscription.keys.p256dh,
+            &self.subscription.keys.auth,
+            &self.build_payload(message),
+        )?;
+        let authorization = vapid.authorization(endpoint, jiff::Timestamp::now())?;
+
+        let request = match HTTP_CLIENT
+            .post(endpoint)
+            .header("Authorization", authorization)
+            .header("Content-Encoding", "aes128gcm")
+            .header("Content-Type", "application/octet-stream")
+            .header("TTL", vapid.ttl_seconds())
+            .header("Urgency", severity_urgency(message.severity))
+            .body(body)
+            .build()
+        {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(NotificationResult {
+                    success: false,
+                    status_code: None,
+                    response: Some(e.to_string()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    request: None,
+                });
+            }
+        };
+        let request_info = RequestInfo::from_request(&request, &[]);
+
+        let response = HTTP_CLIENT.execute(request).await;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        match response {
+            Ok(resp) => {
+                let status_code = resp.status().as_u16();
+                let success = resp.status().is_success();
+                let response_text = resp.text().await.ok();
+
+                Ok(NotificationResult {
+                    success,
+                    status_code: Some(status_code),
+                    response: response_text,
+                    duration_ms,
+                    request: Some(request_info),
+                })
+            }
+            Err(e) => Ok(NotificationResult {
+                success: false,
+                status_code: None,
+                response: Some(e.to_string()),
+                duration_ms,
+                request: Some(request_info),
+            }),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "webpush"
+    }
+
+    /// Validates the Web Push subscription
+    ///
+    /// Checks that:
+    /// - Web Push is configured on this server
+    /// - endpoint is a valid HTTPS URL
+    /// - keys can be used to encrypt a message
+    ///
+    /// # Returns
+    /// Ok(()) if valid, Err with validation details otherwise
+    async fn validate_config(&self) -> AppResult<()> {
+        if webpush::vapid().is_none() {
+            return Err(AppError::BadRequest {
+                message: "Web Push is not configured on this server".to_string(),
+            });
+        }
+
+        let url = Url::parse(&self.subscription.endpoint).map_err(|_| AppError::Validation {
+            field: "endpoint".to_string(),
+            reason: "Invalid URL format".to_string(),
+        })?;
+
+        if url.scheme() != "https" {
+            return Err(AppError::Validation {
+                field: "endpoint".to_string(),
+                reason: "Endpoint must use https protocol".to_string(),
+            });
+        }
+
+        webpush::encrypt(
+            &self.subscription.keys.p256dh,
+            &self.subscription.keys.auth,
+            b"",
+        )
+        .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WebPushKeys;
+
+    fn provider() -> WebPushProvider {
+        WebPushProvider::new(WebPushSubscription {
+            endpoint: "https://fcm.googleapis.com/fcm/send/abc".to_string(),
+            keys: WebPushKeys {
+                p256dh: "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4".to_string(),
+                auth: "BTBZMqHH6r4Tts7J_aSIgg".to_string(),
+            },
+        })
+    }
+
+    fn payload(message: &NotificationMessage) -> serde_json::Value {
+        serde_json::from_slice(&provider().build_payload(message)).unwrap()
+    }
+
+    #[test]
+    fn test_build_payload() {
+        let message = NotificationMessage {
+            title: Some("Alice is live".to_string()),
+            body: "Playing chess".to_string(),
+            severity: NotificationSeverity::High,
+            tags: vec!["live".to_string()],
+            image_url: Some("https://example.com/cover.jpg".to_string()),
+            click_url: Some("https://live.bilibili.com/1".to_string()),
+            ..Default::default()
+        };
+
+        let payload = payload(&message);
+        assert_eq!(payload["title"], "Alice is live");
+        assert_eq!(payload["body"], "Playing chess");
+        assert_eq!(payload["severity"], "high");
+        assert_eq!(payload["tags"], json!(["live"]));
+        assert_eq!(payload["url"], "https://live.bilibili.com/1");
+        assert_eq!(payload["image"], "https://example.com/cover.jpg");
+    }
+
+    #[test]
+    fn test_build_payload_truncates_long_body() {
+        let message = NotificationMessage {
+            body: "é".repeat(MAX_PAYLOAD_LEN),
+            ..Default::default()
+        };
+
+        let bytes = provider().build_payload(&message);
+        assert!(bytes.len() <= MAX_PAYLOAD_LEN);
+        let payload: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(payload["body"].as_str().unwrap().ends_with('…'));
+    }
+
+    #[test]
+    fn test_severity_urgency() {
+        assert_eq!(severity_urgency(NotificationSeverity::Low), "low");
+        assert_eq!(severity_urgency(NotificationSeverity::Critical), "high");
+    }
+}
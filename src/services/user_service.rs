@@ -114,6 +114,30 @@ impl UserService {
         self.repo.update(id, update_data).await
     }
 
+    /// Grants or revokes a user's administrator access.
+    ///
+    /// Not exposed through `update_user`, so users cannot change their own
+    /// access.
+    ///
+    /// # Arguments
+    /// * `email` - The user's email address
+    /// * `admin` - Whether the user is an administrator
+    ///
+    /// # Returns
+    /// The updated user, or `NotFound` error
+    pub async fn set_admin(&self, email: &str, admin: bool) -> AppResult<User> {
+        let user = self
+            .repo
+            .find_by_email(email)
+            .await?
+            .ok_or(AppError::NotFound {
+                entity: "user".to_string(),
+                field: "email".to_string(),
+                value: email.to_string(),
+            })?;
+        self.repo.set_admin(user.id, admin).await
+    }
+
     /// Deletes a user.
     ///
     /// # Arguments
@@ -0,0 +1,188 @@
+//! Notification API key helpers.
+//!
+//! Keys have the form `fsn_<prefix>_<secret>`. The prefix is stored in
+//! plain text to look the key up; the full key is only stored as an argon2
+//! hash (see [`crate::utils::password`]).
+
+use crate::error::{AppError, AppResult};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ipnet::IpNet;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::net::IpAddr;
+
+/// Marker at the start of every API key
+pub const API_KEY_MARKER: &str = "fsn_";
+
+/// Random bytes in the lookup prefix (hex encoded)
+const PREFIX_BYTES: usize = 6;
+
+/// Random bytes in the secret part (base64url encoded)
+const SECRET_BYTES: usize = 32;
+
+/// A freshly generated API key
+#[derive(Debug, Clone)]
+pub struct GeneratedApiKey {
+    /// The full key, shown to the user once
+    pub key: String,
+    /// The lookup prefix stored alongside the hash
+    pub prefix: String,
+}
+
+/// Generates a new random API key
+///
+/// # Returns
+/// The full key and its lookup prefix
+pub fn generate_api_key() -> AppResult<GeneratedApiKey> {
+    let rng = SystemRandom::new();
+    let mut prefix_bytes = [0u8; PREFIX_BYTES];
+    let mut secret_bytes = [0u8; SECRET_BYTES];
+    rng.fill(&mut prefix_bytes)
+        .and_then(|_| rng.fill(&mut secret_bytes))
+        .map_err(|_| AppError::Internal {
+            source: anyhow::anyhow!("Failed to generate random API key"),
+        })?;
+
+    let prefix: String = prefix_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    let key = format!(
+        "{}{}_{}",
+        API_KEY_MARKER,
+        prefix,
+        URL_SAFE_NO_PAD.encode(secret_bytes)
+    );
+
+    Ok(GeneratedApiKey { key, prefix })
+}
+
+/// Extracts the lookup prefix from an API key
+///
+/// # Arguments
+/// * `key` - The full key as presented by the client
+///
+/// # Returns
+/// The prefix, or None if the key is malformed
+pub fn api_key_prefix(key: &str) -> Option<&str> {
+    let (prefix, secret) = key.strip_prefix(API_KEY_MARKER)?.split_once('_')?;
+
+    let valid_prefix = prefix.len() == PREFIX_BYTES * 2
+        && prefix
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+
+    (valid_prefix && !secret.is_empty()).then_some(prefix)
+}
+
+/// Parses an allow-list entry
+///
+/// Entries are either a single address (`203.0.113.7`, `::1`) or a CIDR
+/// range (`10.0.0.0/8`, `2001:db8::/32`).
+///
+/// # Arguments
+/// * `rule` - The allow-list entry
+///
+/// # Returns
+/// The network the entry covers, or None if it is not valid
+pub fn parse_ip_rule(rule: &str) -> Option<IpNet> {
+    let rule = rule.trim();
+    rule.parse::<IpNet>()
+        .ok()
+        .or_else(|| rule.parse::<IpAddr>().ok().map(IpNet::from))
+}
+
+/// Checks a client address against an allow-list
+///
+/// An empty allow-list admits every client. Otherwise the client address
+/// must be known and fall inside one of the entries; IPv4-mapped IPv6
+/// addresses are compared as IPv4.
+///
+/// # Arguments
+/// * `rules` - The allow-list entries
+/// * `client` - The client address, if known
+///
+/// # Returns
+/// true if the client may use the key
+pub fn ip_allowed(rules: &[String], client: Option<IpAddr>) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+
+    let Some(client) = client.map(|ip| ip.to_canonical()) else {
+        return false;
+    };
+
+    rules
+        .iter()
+        .filter_map(|rule| parse_ip_rule(rule))
+        .any(|net| net.contains(&client))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key_round_trips_prefix() {
+        let generated = generate_api_key().unwrap();
+        assert!(generated.key.starts_with(API_KEY_MARKER));
+        assert_eq!(
+            api_key_prefix(&generated.key),
+            Some(generated.prefix.as_str())
+        );
+    }
+
+    #[test]
+    fn test_generated_keys_are_unique() {
+        let a = generate_api_key().unwrap();
+        let b = generate_api_key().unwrap();
+        assert_ne!(a.key, b.key);
+        assert_ne!(a.prefix, b.prefix);
+    }
+
+    #[test]
+    fn test_malformed_keys_have_no_prefix() {
+        assert_eq!(api_key_prefix("0123456789ab_secret"), None);
+        assert_eq!(api_key_prefix("fsn_0123456789ab"), None);
+        assert_eq!(api_key_prefix("fsn_0123456789ab_"), None);
+        assert_eq!(api_key_prefix("fsn_short_secret"), None);
+        assert_eq!(api_key_prefix("fsn_0123456789AB_secret"), None);
+        assert_eq!(
+            api_key_prefix("fsn_0123456789ab_se_cret"),
+            Some("0123456789ab")
+        );
+    }
+
+    #[test]
+    fn test_parse_ip_rule() {
+        assert!(parse_ip_rule("203.0.113.7").is_some());
+        assert!(parse_ip_rule("10.0.0.0/8").is_some());
+        assert!(parse_ip_rule(" 2001:db8::/32 ").is_some());
+        assert!(parse_ip_rule("::1").is_some());
+        assert!(parse_ip_rule("example.com").is_none());
+        assert!(parse_ip_rule("10.0.0.0/33").is_none());
+    }
+
+    #[test]
+    fn test_empty_allow_list_admits_everyone() {
+        assert!(ip_allowed(&[], None));
+        assert!(ip_allowed(&[], Some("198.51.100.1".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_allow_list_matching() {
+        let rules = vec!["10.0.0.0/8".to_string(), "203.0.113.7".to_string()];
+
+        assert!(ip_allowed(&rules, Some("10.1.2.3".parse().unwrap())));
+        assert!(ip_allowed(&rules, Some("203.0.113.7".parse().unwrap())));
+        assert!(!ip_allowed(&rules, Some("203.0.113.8".parse().unwrap())));
+        assert!(!ip_allowed(&rules, None));
+    }
+
+    #[test]
+    fn test_ipv4_mapped_addresses_match_ipv4_rules() {
+        let rules = vec!["192.168.0.0/16".to_string()];
+        assert!(ip_allowed(
+            &rules,
+            Some("::ffff:192.168.1.10".parse().unwrap())
+        ));
+    }
+}
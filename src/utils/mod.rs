@@ -1,3 +1,7 @@
+pub mod api_key;
 pub mod jwt;
 pub mod password;
+pub mod secret;
+pub mod timezone;
 pub mod validate;
+pub mod webpush;
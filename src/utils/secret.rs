@@ -0,0 +1,367 @@
+//! Envelope encryption for secrets stored in the database.
+//!
+//! Each secret is encrypted with its own random AES-256-GCM data key; the
+//! data key is encrypted ("wrapped") with the configured master key. Values
+//! are stored as strings of the form
+//! `enc:v1:<key id>:<wrapped data key>:<ciphertext>` so encrypted and
+//! plaintext values can coexist while existing data is migrated.
+
+use crate::config::settings::EncryptionConfig;
+use crate::error::{AppError, AppResult};
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Prefix marking an encrypted value
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Size of data and master keys in bytes
+const KEY_LEN: usize = 32;
+
+/// Global cipher, initialized once at startup
+static SECRET_CIPHER: OnceLock<Option<SecretCipher>> = OnceLock::new();
+
+/// Initializes the global secret cipher from configuration
+///
+/// Subsequent calls keep the first cipher.
+///
+/// # Arguments
+/// * `config` - Encryption settings
+///
+/// # Returns
+/// true if a master key is configured, false if secrets stay unencrypted
+pub fn init_secret_cipher(config: &EncryptionConfig) -> AppResult<bool> {
+    let cipher = SecretCipher::from_config(config)?;
+    Ok(SECRET_CIPHER.get_or_init(|| cipher).is_some())
+}
+
+/// Returns the global secret cipher
+///
+/// Returns `None` if no master key is configured or the cipher has not been
+/// initialized.
+pub fn secret_cipher() -> Option<&'static SecretCipher> {
+    SECRET_CIPHER.get().and_then(Option::as_ref)
+}
+
+/// Checks whether a stored value is encrypted
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Envelope cipher holding the active master key and retired keys
+pub struct SecretCipher {
+    active_id: String,
+    keys: HashMap<String, LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl SecretCipher {
+    /// Builds a cipher from configuration
+    ///
+    /// # Arguments
+    /// * `config` - Encryption settings
+    ///
+    /// # Returns
+    /// None if no master key is configured, the cipher otherwise
+    pub fn from_config(config: &EncryptionConfig) -> AppResult<Option<Self>> {
+        if config.key.is_empty() {
+            return Ok(None);
+        }
+
+        let mut keys = HashMap::new();
+        keys.insert(config.key_id.clone(), master_key(&config.key)?);
+        for (id, key) in &config.previous_keys {
+            keys.insert(id.clone(), master_key(key)?);
+        }
+
+        Ok(Some(Self {
+            active_id: config.key_id.clone(),
+            keys,
+            rng: SystemRandom::new(),
+        }))
+    }
+
+    /// Encrypts a secret under the active master key
+    ///
+    /// # Arguments
+    /// * `plaintext` - The secret value
+    ///
+    /// # Returns
+    /// The encoded encrypted value
+    pub fn encrypt(&self, plaintext: &str) -> AppResult<String> {
+        let mut data_key = [0u8; KEY_LEN];
+        self.rng.fill(&mut data_key).map_err(crypto_error)?;
+
+        let ciphertext = seal(&aead_key(&data_key)?, &self.rng, b"", plaintext.as_bytes())?;
+        self.envelope(&data_key, &ciphertext)
+    }
+
+    /// Decrypts a stored value
+    ///
+    /// Plaintext values are returned unchanged.
+    ///
+    /// # Arguments
+    /// * `value` - The stored value
+    ///
+    /// # Returns
+    /// The secret, or an error if the key is unknown or the value was tampered with
+    pub fn decrypt(&self, value: &str) -> AppResult<String> {
+        let Some(parts) = EncryptedValue::parse(value)? else {
+            return Ok(value.to_string());
+        };
+
+        let data_key = self.unwrap_data_key(&parts)?;
+        let plaintext = open(&aead_key(&data_key)?, b"", &parts.ciphertext)?;
+        String::from_utf8(plaintext).map_err(|e| AppError::Internal {
+            source: anyhow::Error::from(e),
+        })
+    }
+
+    /// Re-wraps a value's data key with the active master key
+    ///
+    /// Plaintext values are encrypted; values already under the active key
+    /// are returned unchanged. The ciphertext itself is never re-encrypted.
+    ///
+    /// # Arguments
+    /// * `value` - The stored value
+    pub fn rotate(&self, value: &str) -> AppResult<String> {
+        let Some(parts) = EncryptedValue::parse(value)? else {
+            return self.encrypt(value);
+        };
+
+        if parts.key_id == self.active_id {
+            return Ok(value.to_string());
+        }
+
+        let data_key = self.unwrap_data_key(&parts)?;
+        self.envelope(&data_key, &parts.ciphertext)
+    }
+
+    /// Checks whether a value is plaintext or under a retired master key
+    pub fn needs_rotation(&self, value: &str) -> bool {
+        match value.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(rest) => rest.split(':').next() != Some(self.active_id.as_str()),
+            None => true,
+        }
+    }
+
+    /// Wraps a data key with the active master key and encodes the value
+    fn envelope(&self, data_key: &[u8], ciphertext: &[u8]) -> AppResult<String> {
+        let master = &self.keys[&self.active_id];
+        let wrapped = seal(master, &self.rng, self.active_id.as_bytes(), data_key)?;
+
+        Ok(format!(
+            "{}{}:{}:{}",
+            ENCRYPTED_PREFIX,
+            self.active_id,
+            URL_SAFE_NO_PAD.encode(wrapped),
+            URL_SAFE_NO_PAD.encode(ciphertext)
+        ))
+    }
+
+    /// Decrypts a value's data key with the master key it names
+    fn unwrap_data_key(&self, parts: &EncryptedValue) -> AppResult<Vec<u8>> {
+        let master = self
+            .keys
+            .get(&parts.key_id)
+            .ok_or_else(|| AppError::Internal {
+                source: anyhow::anyhow!("Unknown encryption key id '{}'", parts.key_id),
+            })?;
+        open(master, parts.key_id.as_bytes(), &parts.wrapped_key)
+    }
+}
+
+/// Components of an encoded encrypted value
+struct EncryptedValue {
+    key_id: String,
+    wrapped_key: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedValue {
+    /// Splits an encoded value into its parts
+    ///
+    /// # Returns
+    /// None for plaintext values
+    fn parse(value: &str) -> AppResult<Option<Self>> {
+        let Some(rest) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(None);
+        };
+
+        let malformed = || AppError::Internal {
+            source: anyhow::anyhow!("Malformed encrypted value"),
+        };
+
+        let mut parts = rest.splitn(3, ':');
+        let (Some(key_id), Some(wrapped), Some(ciphertext)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed());
+        };
+
+        Ok(Some(Self {
+            key_id: key_id.to_string(),
+            wrapped_key: URL_SAFE_NO_PAD.decode(wrapped).map_err(|_| malformed())?,
+            ciphertext: URL_SAFE_NO_PAD
+                .decode(ciphertext)
+                .map_err(|_| malformed())?,
+        }))
+    }
+}
+
+/// Decodes a base64 master key from configuration
+fn master_key(encoded: &str) -> AppResult<LessSafeKey> {
+    let bytes = STANDARD.decode(encoded).map_err(|e| AppError::Internal {
+        source: anyhow::anyhow!("Invalid encryption key: {}", e),
+    })?;
+    aead_key(&bytes)
+}
+
+/// Builds an AES-256-GCM key
+fn aead_key(bytes: &[u8]) -> AppResult<LessSafeKey> {
+    UnboundKey::new(&AES_256_GCM, bytes)
+        .map(LessSafeKey::new)
+        .map_err(|_| AppError::Internal {
+            source: anyhow::anyhow!("Encryption key must be {} bytes", KEY_LEN),
+        })
+}
+
+/// Encrypts data with a fresh random nonce
+///
+/// # Returns
+/// The nonce followed by the ciphertext and tag
+fn seal(key: &LessSafeKey, rng: &SystemRandom, aad: &[u8], data: &[u8]) -> AppResult<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut nonce).map_err(crypto_error)?;
+
+    let mut in_out = data.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(aad),
+        &mut in_out,
+    )
+    .map_err(crypto_error)?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&in_out);
+    Ok(sealed)
+}
+
+/// Decrypts data produced by [`seal`]
+fn open(key: &LessSafeKey, aad: &[u8], sealed: &[u8]) -> AppResult<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(crypto_error(ring::error::Unspecified));
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(crypto_error)?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(aad), &mut in_out)
+        .map_err(crypto_error)?;
+    Ok(plaintext.to_vec())
+}
+
+fn crypto_error(_: ring::error::Unspecified) -> AppError {
+    AppError::Internal {
+        source: anyhow::anyhow!("Secret encryption or decryption failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+    const KEY_B: &str = "ZmVkY2JhOTg3NjU0MzIxMGZlZGNiYTk4NzY1NDMyMTA=";
+
+    fn cipher(key: &str, key_id: &str, previous: &[(&str, &str)]) -> SecretCipher {
+        let config = EncryptionConfig {
+            key: key.to_string(),
+            key_id: key_id.to_string(),
+            previous_keys: previous
+                .iter()
+                .map(|(id, key)| (id.to_string(), key.to_string()))
+                .collect(),
+        };
+        SecretCipher::from_config(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_no_key_disables_encryption() {
+        assert!(
+            SecretCipher::from_config(&EncryptionConfig::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let c = cipher(KEY_A, "k1", &[]);
+        let encrypted = c.encrypt("device-key-123").unwrap();
+
+        assert!(is_encrypted(&encrypted));
+        assert!(encrypted.starts_with("enc:v1:k1:"));
+        assert!(!encrypted.contains("device-key-123"));
+        assert_eq!(c.decrypt(&encrypted).unwrap(), "device-key-123");
+    }
+
+    #[test]
+    fn test_encryption_is_randomized() {
+        let c = cipher(KEY_A, "k1", &[]);
+        assert_ne!(c.encrypt("same").unwrap(), c.encrypt("same").unwrap());
+    }
+
+    #[test]
+    fn test_decrypt_plaintext_passthrough() {
+        let c = cipher(KEY_A, "k1", &[]);
+        assert_eq!(c.decrypt("plain").unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampering() {
+        let c = cipher(KEY_A, "k1", &[]);
+        let encrypted = c.encrypt("secret").unwrap();
+        let mut tampered = encrypted.clone();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == 'A' { 'B' } else { 'A' });
+
+        assert!(c.decrypt(&tampered).is_err());
+        assert!(c.decrypt("enc:v1:k1:garbage").is_err());
+    }
+
+    #[test]
+    fn test_rotation_rewraps_data_key() {
+        let old = cipher(KEY_A, "k1", &[]);
+        let encrypted = old.encrypt("secret").unwrap();
+
+        let new = cipher(KEY_B, "k2", &[("k1", KEY_A)]);
+        assert!(new.needs_rotation(&encrypted));
+        assert_eq!(new.decrypt(&encrypted).unwrap(), "secret");
+
+        let rotated = new.rotate(&encrypted).unwrap();
+        assert!(rotated.starts_with("enc:v1:k2:"));
+        assert!(!new.needs_rotation(&rotated));
+        assert_eq!(new.rotate(&rotated).unwrap(), rotated);
+
+        // The retired key is no longer needed once values are rotated
+        let only_new = cipher(KEY_B, "k2", &[]);
+        assert_eq!(only_new.decrypt(&rotated).unwrap(), "secret");
+        assert!(only_new.decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn test_rotation_encrypts_plaintext() {
+        let c = cipher(KEY_A, "k1", &[]);
+        assert!(c.needs_rotation("plain"));
+
+        let rotated = c.rotate("plain").unwrap();
+        assert!(is_encrypted(&rotated));
+        assert_eq!(c.decrypt(&rotated).unwrap(), "plain");
+    }
+}
@@ -0,0 +1,29 @@
+//! Timezone lookup shared by notification quiet hours and job schedules.
+
+use crate::error::{AppError, AppResult};
+use jiff::tz::TimeZone;
+
+/// Resolves a timezone name, reporting unknown names as validation errors
+///
+/// # Arguments
+/// * `name` - IANA timezone name (e.g., "Asia/Shanghai")
+pub fn parse_timezone(name: &str) -> AppResult<TimeZone> {
+    TimeZone::get(name).map_err(|e| AppError::Validation {
+        field: "timezone".to_string(),
+        reason: format!("Unknown timezone '{}': {}", name, e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timezone() {
+        assert!(parse_timezone("Europe/Berlin").is_ok());
+        assert!(matches!(
+            parse_timezone("Not/AZone"),
+            Err(AppError::Validation { .. })
+        ));
+    }
+}
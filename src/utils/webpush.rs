@@ -0,0 +1,501 @@
+//! Web Push message encryption and VAPID authentication.
+//!
+//! Payloads are encrypted for the subscribing browser with the `aes128gcm`
+//! content coding (RFC 8188) using the Web Push key derivation (RFC 8291).
+//! Requests are authenticated to the push service with a VAPID JWT signed
+//! by the server's P-256 key (RFC 8292).
+
+use crate::config::settings::WebPushConfig;
+use crate::error::{AppError, AppResult};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::Url;
+use ring::aead::{AES_128_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::agreement::{self, ECDH_P256, EphemeralPrivateKey, UnparsedPublicKey};
+use ring::hkdf::{self, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair};
+use serde_json::json;
+use std::sync::OnceLock;
+
+/// Record size announced in the content coding header
+const RECORD_SIZE: u32 = 4096;
+
+/// Length of an uncompressed P-256 public key
+const PUBLIC_KEY_LEN: usize = 65;
+
+/// Length of a P-256 private key
+const PRIVATE_KEY_LEN: usize = 32;
+
+/// Length of the subscription authentication secret
+const AUTH_SECRET_LEN: usize = 16;
+
+/// Length of the content coding salt
+const SALT_LEN: usize = 16;
+
+/// Length of the AES-GCM authentication tag
+const TAG_LEN: usize = 16;
+
+/// Length of the content coding header (salt, record size, key id length, key id)
+const HEADER_LEN: usize = SALT_LEN + 4 + 1 + PUBLIC_KEY_LEN;
+
+/// Largest plaintext that fits in a single record
+pub const MAX_PAYLOAD_LEN: usize = RECORD_SIZE as usize - HEADER_LEN - TAG_LEN - 1;
+
+/// Lifetime of VAPID tokens (push services reject more than 24 hours)
+const VAPID_TOKEN_LIFETIME_SECS: i64 = 12 * 60 * 60;
+
+/// Offset of the private key in the PKCS#8 document generated by ring
+const PKCS8_PRIVATE_KEY_OFFSET: usize = 36;
+
+/// Global VAPID signer, initialized once at startup
+static VAPID: OnceLock<Option<Vapid>> = OnceLock::new();
+
+/// Initializes the global VAPID signer from configuration
+///
+/// Subsequent calls keep the first signer.
+///
+/// # Arguments
+/// * `config` - Web Push settings
+///
+/// # Returns
+/// true if a VAPID key pair is configured, false if Web Push is disabled
+pub fn init_vapid(config: &WebPushConfig) -> AppResult<bool> {
+    let vapid = Vapid::from_config(config)?;
+    Ok(VAPID.get_or_init(|| vapid).is_some())
+}
+
+/// Returns the global VAPID signer
+///
+/// Returns `None` if no key pair is configured or the signer has not been
+/// initialized.
+pub fn vapid() -> Option<&'static Vapid> {
+    VAPID.get().and_then(Option::as_ref)
+}
+
+/// A base64url-encoded VAPID key pair
+#[derive(Debug, Clone)]
+pub struct VapidKeys {
+    /// Uncompressed P-256 public key, used as the browser's `applicationServerKey`
+    pub public_key: String,
+    /// P-256 private key
+    pub private_key: String,
+}
+
+/// Generates a new VAPID key pair
+///
+/// # Returns
+/// Keys in the format expected by `WebPushConfig`
+pub fn generate_vapid_keys() -> AppResult<VapidKeys> {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+        .map_err(crypto_error)?;
+    let document = pkcs8.as_ref();
+
+    let private_key = document
+        .get(PKCS8_PRIVATE_KEY_OFFSET..PKCS8_PRIVATE_KEY_OFFSET + PRIVATE_KEY_LEN)
+        .ok_or_else(|| crypto_error(ring::error::Unspecified))?;
+    let public_key = &document[document.len() - PUBLIC_KEY_LEN..];
+
+    // Loading the pair back checks that both keys were extracted correctly
+    EcdsaKeyPair::from_private_key_and_public_key(
+        &ECDSA_P256_SHA256_FIXED_SIGNING,
+        private_key,
+        public_key,
+        &rng,
+    )
+    .map_err(|e| AppError::Internal {
+        source: anyhow::anyhow!("Generated VAPID key pair is invalid: {}", e),
+    })?;
+
+    Ok(VapidKeys {
+        public_key: URL_SAFE_NO_PAD.encode(public_key),
+        private_key: URL_SAFE_NO_PAD.encode(private_key),
+    })
+}
+
+/// VAPID signer holding the server key pair
+pub struct Vapid {
+    key_pair: EcdsaKeyPair,
+    public_key: String,
+    subject: String,
+    ttl_seconds: u32,
+    rng: SystemRandom,
+}
+
+impl Vapid {
+    /// Builds a signer from configuration
+    ///
+    /// # Returns
+    /// `None` if no key pair is configured
+    pub fn from_config(config: &WebPushConfig) -> AppResult<Option<Self>> {
+        if config.public_key.is_empty() && config.private_key.is_empty() {
+            return Ok(None);
+        }
+
+        let invalid = |reason: String| AppError::Configuration {
+            key: "webpush".to_string(),
+            source: anyhow::anyhow!(reason),
+        };
+
+        let public_key = URL_SAFE_NO_PAD
+            .decode(&config.public_key)
+            .map_err(|e| invalid(format!("Invalid VAPID public key: {}", e)))?;
+        let private_key = URL_SAFE_NO_PAD
+            .decode(&config.private_key)
+            .map_err(|e| invalid(format!("Invalid VAPID private key: {}", e)))?;
+
+        let rng = SystemRandom::new();
+        let key_pair = EcdsaKeyPair::from_private_key_and_public_key(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            &private_key,
+            &public_key,
+            &rng,
+        )
+        .map_err(|e| invalid(format!("Invalid VAPID key pair: {}", e)))?;
+
+        Ok(Some(Self {
+            key_pair,
+            public_key: config.public_key.clone(),
+            subject: config.subject.clone(),
+            ttl_seconds: config.ttl_seconds,
+            rng,
+        }))
+    }
+
+    /// Returns the base64url-encoded public key
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// Returns how long push services should keep undelivered messages
+    pub fn ttl_seconds(&self) -> u32 {
+        self.ttl_seconds
+    }
+
+    /// Builds the `Authorization` header value for a push endpoint
+    ///
+    /// # Arguments
+    /// * `endpoint` - The subscription's push endpoint
+    /// * `now` - Current time, used for the token expiry
+    ///
+    /// # Returns
+    /// `vapid t=<JWT>, k=<public key>`
+    pub fn authorization(&self, endpoint: &str, now: jiff::Timestamp) -> AppResult<String> {
+        let url = Url::parse(endpoint).map_err(|_| AppError::Validation {
+            field: "endpoint".to_string(),
+            reason: "Invalid URL format".to_string(),
+        })?;
+
+        let header = json!({ "typ": "JWT", "alg": "ES256" });
+        let claims = json!({
+            "aud": url.origin().ascii_serialization(),
+            "exp": now.as_second() + VAPID_TOKEN_LIFETIME_SECS,
+            "sub": self.subject,
+        });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+
+        let signature = self
+            .key_pair
+            .sign(&self.rng, signing_input.as_bytes())
+            .map_err(crypto_error)?;
+
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_ref()),
+            self.public_key
+        ))
+    }
+}
+
+/// Encrypts a payload for a push subscription
+///
+/// # Arguments
+/// * `p256dh` - The subscription's base64url-encoded public key
+/// * `auth` - The subscription's base64url-encoded authentication secret
+/// * `plaintext` - The payload, at most [`MAX_PAYLOAD_LEN`] bytes
+///
+/// # Returns
+/// The `aes128gcm` request body
+pub fn encrypt(p256dh: &str, auth: &str, plaintext: &[u8]) -> AppResult<Vec<u8>> {
+    let ua_public = decode_key("p256dh", p256dh, PUBLIC_KEY_LEN)?;
+    let auth_secret = decode_key("auth", auth, AUTH_SECRET_LEN)?;
+
+    if plaintext.len() > MAX_PAYLOAD_LEN {
+        return Err(AppError::Validation {
+            field: "payload".to_string(),
+            reason: format!("Payload must be at most {} bytes", MAX_PAYLOAD_LEN),
+        });
+    }
+
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill(&mut salt).map_err(crypto_error)?;
+
+    let as_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng).map_err(crypto_error)?;
+    let as_public = as_private.compute_public_key().map_err(crypto_error)?;
+
+    let ecdh_secret = agreement::agree_ephemeral(
+        as_private,
+        &UnparsedPublicKey::new(&ECDH_P256, &ua_public),
+        |secret| secret.to_vec(),
+    )
+    .map_err(|_| AppError::Validation {
+        field: "p256dh".to_string(),
+        reason: "Not a valid P-256 public key".to_string(),
+    })?;
+
+    encrypt_record(
+        &ecdh_secret,
+        &auth_secret,
+        &ua_public,
+        as_public.as_ref(),
+        &salt,
+        plaintext,
+    )
+}
+
+/// Encrypts a payload as a single `aes128gcm` record
+///
+/// # Arguments
+/// * `ecdh_secret` - Shared secret of the server and subscription keys
+/// * `auth_secret` - The subscription's authentication secret
+/// * `ua_public` - The subscription's public key
+/// * `as_public` - The server's ephemeral public key
+/// * `salt` - Random salt
+/// * `plaintext` - The payload
+fn encrypt_record(
+    ecdh_secret: &[u8],
+    auth_secret: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+    plaintext: &[u8],
+) -> AppResult<Vec<u8>> {
+    let ikm = hkdf_sha256(
+        auth_secret,
+        ecdh_secret,
+        &[b"WebPush: info\0", ua_public, as_public],
+        32,
+    )?;
+    let cek = hkdf_sha256(salt, &ikm, &[b"Content-Encoding: aes128gcm\0"], 16)?;
+    let nonce = hkdf_sha256(salt, &ikm, &[b"Content-Encoding: nonce\0"], 12)?;
+
+    let key = UnboundKey::new(&AES_128_GCM, &cek)
+        .map(LessSafeKey::new)
+        .map_err(crypto_error)?;
+
+    // A single record is the last one, marked by the 0x02 padding delimiter
+    let mut record = plaintext.to_vec();
+    record.push(2);
+    key.seal_in_place_append_tag(
+        Nonce::try_assume_unique_for_key(&nonce).map_err(crypto_error)?,
+        Aad::empty(),
+        &mut record,
+    )
+    .map_err(crypto_error)?;
+
+    let mut body = Vec::with_capacity(HEADER_LEN + record.len());
+    body.extend_from_slice(salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(as_public);
+    body.extend_from_slice(&record);
+    Ok(body)
+}
+
+/// Output length for HKDF expansion
+struct OkmLen(usize);
+
+impl hkdf::KeyType for OkmLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// Derives key material with HKDF-SHA256
+fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[&[u8]], len: usize) -> AppResult<Vec<u8>> {
+    let prk = hkdf::Salt::new(HKDF_SHA256, salt).extract(ikm);
+    let okm = prk.expand(info, OkmLen(len)).map_err(crypto_error)?;
+    let mut out = vec![0u8; len];
+    okm.fill(&mut out).map_err(crypto_error)?;
+    Ok(out)
+}
+
+/// Decodes a base64url subscription key and checks its length
+fn decode_key(field: &str, value: &str, len: usize) -> AppResult<Vec<u8>> {
+    match URL_SAFE_NO_PAD.decode(value.trim_end_matches('=')) {
+        Ok(bytes) if bytes.len() == len => Ok(bytes),
+        _ => Err(AppError::Validation {
+            field: field.to_string(),
+            reason: format!("Must be base64url encoding {} bytes", len),
+        }),
+    }
+}
+
+fn crypto_error(_: ring::error::Unspecified) -> AppError {
+    AppError::Internal {
+        source: anyhow::anyhow!("Web Push encryption or signing failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED, UnparsedPublicKey as SignaturePublicKey};
+
+    fn b64(value: &str) -> Vec<u8> {
+        URL_SAFE_NO_PAD.decode(value).unwrap()
+    }
+
+    fn config(keys: &VapidKeys) -> WebPushConfig {
+        WebPushConfig {
+            public_key: keys.public_key.clone(),
+            private_key: keys.private_key.clone(),
+            subject: "mailto:ops@example.com".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Decrypts a single-record body with the subscription's key
+    fn decrypt(
+        ua_private: EphemeralPrivateKey,
+        ua_public: &[u8],
+        auth: &[u8],
+        body: &[u8],
+    ) -> Vec<u8> {
+        let (header, ciphertext) = body.split_at(HEADER_LEN);
+        let salt = &header[..SALT_LEN];
+        let as_public = &header[SALT_LEN + 5..];
+
+        let ecdh_secret = agreement::agree_ephemeral(
+            ua_private,
+            &UnparsedPublicKey::new(&ECDH_P256, as_public),
+            |secret| secret.to_vec(),
+        )
+        .unwrap();
+        let ikm = hkdf_sha256(
+            auth,
+            &ecdh_secret,
+            &[b"WebPush: info\0", ua_public, as_public],
+            32,
+        )
+        .unwrap();
+        let cek = hkdf_sha256(salt, &ikm, &[b"Content-Encoding: aes128gcm\0"], 16).unwrap();
+        let nonce = hkdf_sha256(salt, &ikm, &[b"Content-Encoding: nonce\0"], 12).unwrap();
+
+        let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &cek).unwrap());
+        let mut in_out = ciphertext.to_vec();
+        let record = key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(&nonce).unwrap(),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .unwrap();
+        assert_eq!(record.last(), Some(&2));
+        record[..record.len() - 1].to_vec()
+    }
+
+    /// Example from RFC 8291, Appendix A
+    #[test]
+    fn test_encrypt_record_rfc8291_vector() {
+        let body = encrypt_record(
+            &b64("kyrL1jIIOHEzg3sM2ZWRHDRB62YACZhhSlknJ672kSs"),
+            &b64("BTBZMqHH6r4Tts7J_aSIgg"),
+            &b64("BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4"),
+            &b64("BP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A8"),
+            &b64("DGv6ra1nlYgDCS1FRnbzlw"),
+            b"When I grow up, I want to be a watermelon",
+        )
+        .unwrap();
+
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(body),
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN"
+        );
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let rng = SystemRandom::new();
+        let ua_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng).unwrap();
+        let ua_public = ua_private.compute_public_key().unwrap();
+        let auth = [7u8; AUTH_SECRET_LEN];
+
+        let body = encrypt(
+            &URL_SAFE_NO_PAD.encode(ua_public.as_ref()),
+            &URL_SAFE_NO_PAD.encode(auth),
+            b"hello",
+        )
+        .unwrap();
+
+        assert_eq!(&body[SALT_LEN..SALT_LEN + 4], &RECORD_SIZE.to_be_bytes());
+        assert_eq!(
+            decrypt(ua_private, ua_public.as_ref(), &auth, &body),
+            b"hello"
+        );
+    }
+
+    #[test]
+    fn test_encrypt_rejects_bad_subscription_keys() {
+        let result = encrypt("c2hvcnQ", "BTBZMqHH6r4Tts7J_aSIgg", b"hi");
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "p256dh"));
+    }
+
+    #[test]
+    fn test_encrypt_rejects_oversized_payload() {
+        let keys = generate_vapid_keys().unwrap();
+        let payload = vec![b'a'; MAX_PAYLOAD_LEN + 1];
+        let result = encrypt(&keys.public_key, "BTBZMqHH6r4Tts7J_aSIgg", &payload);
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "payload"));
+    }
+
+    #[test]
+    fn test_no_keys_disables_vapid() {
+        assert!(
+            Vapid::from_config(&WebPushConfig::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_mismatched_keys_rejected() {
+        let mut config = config(&generate_vapid_keys().unwrap());
+        config.private_key = generate_vapid_keys().unwrap().private_key;
+        assert!(Vapid::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_authorization_token() {
+        let keys = generate_vapid_keys().unwrap();
+        let vapid = Vapid::from_config(&config(&keys)).unwrap().unwrap();
+        let now: jiff::Timestamp = "2026-01-02T03:04:05Z".parse().unwrap();
+
+        let header = vapid
+            .authorization("https://fcm.googleapis.com/fcm/send/abc", now)
+            .unwrap();
+        let (token, key) = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(key, keys.public_key);
+
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        SignaturePublicKey::new(&ECDSA_P256_SHA256_FIXED, b64(&keys.public_key))
+            .verify(signing_input.as_bytes(), &b64(signature))
+            .unwrap();
+
+        let claims: serde_json::Value =
+            serde_json::from_slice(&b64(signing_input.split('.').nth(1).unwrap())).unwrap();
+        assert_eq!(claims["aud"], "https://fcm.googleapis.com");
+        assert_eq!(claims["sub"], "mailto:ops@example.com");
+        assert_eq!(claims["exp"], now.as_second() + VAPID_TOKEN_LIFETIME_SECS);
+    }
+}